@@ -0,0 +1,581 @@
+//! `star-crank`: keeper CLI that opens the distribution day and runs every crank page
+
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use star_fee_distributor::state::{
+    DayClassTotal, InvestorRecord, Policy, Progress, RemainderPolicy, STREAMFLOW_PROGRAM_ID,
+};
+use star_fee_distributor::utils::{StreamflowSchedule, StreamflowUtils};
+use star_fee_distributor_client::accounts::{fetch_investor_record, fetch_policy, fetch_progress};
+use star_fee_distributor_client::calculator::{calculate_day_totals, InvestorInput};
+use star_fee_distributor_client::instructions::{
+    crank_distribute, init_investor_record, migrate_investor_record, refresh_locks,
+    set_lookup_table, CrankAccounts, TOKEN_2022_PROGRAM_ID,
+};
+use star_fee_distributor_client::lookup_table::{
+    compile_message, create_table, extend_table, fetch_lookup_table, missing_addresses,
+    remaining_capacity,
+};
+use star_fee_distributor_client::pda::VaultPdas;
+use star_fee_distributor_client::planner::{plan_pages, InvestorEntry, PagePlan, PlanLimits};
+
+/// Associated Token Account program ID
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Investor records created per transaction
+const RECORDS_PER_TX: usize = 8;
+
+/// Investor locks sampled per `refresh_locks` transaction
+const LOCKS_PER_TX: usize = 12;
+
+#[derive(Parser, Debug)]
+#[command(name = "star-crank", about = "Run the Star Fee Distributor daily crank")]
+struct Args {
+    /// RPC endpoint
+    #[arg(long, default_value = "http://localhost:8899")]
+    rpc_url: String,
+
+    /// Vault whose distribution is cranked
+    #[arg(long)]
+    vault: Pubkey,
+
+    /// Keypair paying for and signing the crank transactions
+    #[arg(long)]
+    keypair: String,
+
+    /// Mint vested by the investor Streamflow streams
+    #[arg(long)]
+    stream_mint: Pubkey,
+
+    /// Only include streams created by this sender
+    #[arg(long)]
+    stream_sender: Option<Pubkey>,
+
+    /// CP-AMM pool of the honorary position
+    #[arg(long)]
+    cp_amm_pool: Pubkey,
+
+    /// Honorary position (defaults to the position registered on the policy)
+    #[arg(long)]
+    honorary_position: Option<Pubkey>,
+
+    /// Creator quote ATA (defaults to the policy creator's quote ATA)
+    #[arg(long)]
+    creator_quote_ata: Option<Pubkey>,
+
+    /// Investors per crank page, ignored when the policy fixes a page size
+    #[arg(long, default_value_t = 8)]
+    page_size: usize,
+
+    /// Priority fee in micro-lamports per compute unit
+    #[arg(long, default_value_t = 0)]
+    priority_fee: u64,
+
+    /// Compute unit limit of setup transactions (crank pages use the planned estimate)
+    #[arg(long, default_value_t = 400_000)]
+    compute_unit_limit: u32,
+
+    /// Send attempts per transaction
+    #[arg(long, default_value_t = 5)]
+    max_retries: u32,
+
+    /// Label each page's payouts with a `{vault, day, page}` memo
+    #[arg(long)]
+    memo: bool,
+
+    /// Only send legacy transactions, never address lookup tables
+    #[arg(long)]
+    no_lookup_tables: bool,
+
+    /// Only sample investor locks (`refresh_locks`) and exit; schedule it between
+    /// distribution days when the policy enables TWAL weighting, and between the previous
+    /// day's close and `open_day` to snapshot locks for the next day
+    #[arg(long)]
+    refresh_locks: bool,
+
+    /// Keypair of the policy's page attester, co-signing every crank page when the vault
+    /// requires attestation
+    #[arg(long)]
+    page_attester_keypair: Option<String>,
+
+    /// Token account of the keypair holding the vault's cranker pass, when cranking under
+    /// the allowlist by pass rather than as a listed key
+    #[arg(long)]
+    cranker_pass: Option<Pubkey>,
+}
+
+/// A Streamflow stream paying the vault's investors
+struct Stream {
+    pubkey: Pubkey,
+    recipient: Pubkey,
+    investor_quote_ata: Pubkey,
+    schedule: StreamflowSchedule,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    anyhow::ensure!(args.page_size > 0, "--page-size must be positive");
+
+    let rpc = RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed());
+    let payer = read_keypair_file(&args.keypair)
+        .map_err(|err| anyhow!("failed to read keypair {}: {}", args.keypair, err))?;
+    let pdas = VaultPdas::new(&args.vault);
+
+    let policy = fetch_policy(&rpc, &args.vault).context("failed to fetch policy")?;
+    let progress_before = fetch_progress(&rpc, &args.vault).context("failed to fetch progress")?;
+
+    // SPL Token or Token-2022; investor ATAs are derived under it
+    let token_program = rpc
+        .get_account(&policy.quote_mint)
+        .context("failed to fetch quote mint")?
+        .owner;
+    let streams = fetch_streams(&rpc, &args, &policy.quote_mint, &token_program)?;
+    if streams.is_empty() {
+        bail!("no Streamflow streams found for mint {}", args.stream_mint);
+    }
+    println!("Found {} investor streams", streams.len());
+
+    ensure_investor_records(&rpc, &args, &payer, &policy.pool, &streams)?;
+
+    if args.refresh_locks {
+        anyhow::ensure!(
+            !progress_before.is_day_in_progress(),
+            "a day is in progress; locks are sampled between distribution days"
+        );
+        let stream_keys: Vec<Pubkey> = streams.iter().map(|stream| stream.pubkey).collect();
+        for chunk in stream_keys.chunks(LOCKS_PER_TX) {
+            let ix = refresh_locks(&args.vault, chunk);
+            send_with_retries(&rpc, &args, &payer, ix, args.compute_unit_limit)?;
+        }
+        println!("Refreshed {} investor locks", stream_keys.len());
+        return Ok(());
+    }
+
+    let treasury = pdas.treasury(&policy.quote_mint);
+    let creator_quote_ata = match args.creator_quote_ata {
+        Some(ata) => ata,
+        None if policy.creator != Pubkey::default() => {
+            associated_token_address(&policy.creator, &policy.quote_mint, &token_program)
+        }
+        None => bail!("policy has no creator role set; pass --creator-quote-ata"),
+    };
+
+    let honorary_position = match args.honorary_position {
+        Some(position) => position,
+        None if policy.position != Pubkey::default() => policy.position,
+        None => bail!("policy has no registered position; pass --honorary-position"),
+    };
+
+    let page_attester = if policy.page_attester == Pubkey::default() {
+        None
+    } else {
+        let path = args.page_attester_keypair.as_ref().ok_or_else(|| {
+            anyhow!(
+                "policy requires pages co-signed by {}; pass --page-attester-keypair",
+                policy.page_attester
+            )
+        })?;
+        let attester = read_keypair_file(path)
+            .map_err(|err| anyhow!("failed to read keypair {}: {}", path, err))?;
+        anyhow::ensure!(
+            attester.pubkey() == policy.page_attester,
+            "--page-attester-keypair is not the policy's page attester {}",
+            policy.page_attester
+        );
+        Some(attester)
+    };
+
+    let crank_accounts = CrankAccounts {
+        crank_caller: payer.pubkey(),
+        vault: args.vault,
+        program_treasury: treasury,
+        creator_quote_ata,
+        cp_amm_pool: args.cp_amm_pool,
+        honorary_position,
+        switchboard_function: None,
+        cranker_pass: args.cranker_pass,
+        memo: args.memo,
+        quote_mint: (policy.remainder_policy == RemainderPolicy::Burn
+            || token_program == TOKEN_2022_PROGRAM_ID)
+            .then_some(policy.quote_mint),
+        token_program,
+        insurance_fund: (policy.insurance_bps > 0).then_some(pdas.insurance(&policy.quote_mint)),
+        referrer_quote_ata: policy
+            .referral
+            .map(|referral| associated_token_address(&referral.referrer, &policy.quote_mint, &token_program)),
+        page_attester: page_attester.as_ref().map(|attester| attester.pubkey()),
+        crank_fee_vault: (policy.crank_reimbursement_lamports > 0).then_some(pdas.crank_fee_vault),
+    };
+
+    let investors: Vec<InvestorEntry> = streams
+        .iter()
+        .map(|stream| InvestorEntry {
+            stream_pubkey: stream.pubkey,
+            investor_quote_ata: stream.investor_quote_ata,
+            bonfida_destination: None,
+        })
+        .collect();
+    let limits = PlanLimits {
+        max_investors_per_page: Some(args.page_size),
+        page_size: (policy.page_size > 0).then_some(policy.page_size),
+        priority_fee: args.priority_fee > 0,
+        allow_lookup_tables: !args.no_lookup_tables,
+        ..PlanLimits::default()
+    };
+    let plan = plan_pages(&crank_accounts, &investors, &limits)?;
+    let lookup_tables = if plan.needs_lookup_tables() {
+        prepare_lookup_tables(&rpc, &args, &payer, &policy, &plan)?
+    } else {
+        Vec::new()
+    };
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let mut signatures = Vec::new();
+
+    if progress_before.is_new_day(now) {
+        let class_totals = day_class_totals(&rpc, &args, &policy, &streams, now)?;
+        let ix = plan.open_day_instruction(&payer.pubkey(), &args.vault, class_totals, now);
+        signatures.push(send_with_retries(&rpc, &args, &payer, ix, args.compute_unit_limit)?);
+        println!(
+            "Opened day with {} investors over {} pages",
+            plan.total_investors, plan.total_pages
+        );
+    } else if progress_before.is_day_complete() {
+        println!("Today's distribution is already complete");
+        print_report(&args.vault, &progress_before, &progress_before, &signatures);
+        return Ok(());
+    } else if progress_before.investor_root != plan.investor_root {
+        bail!("today's day was opened with a different investor set; wait for the next day");
+    }
+
+    let progress = fetch_progress(&rpc, &args.vault)?;
+    let mut page_sent = false;
+    for page in &plan.pages {
+        if page.page <= progress.pagination_cursor {
+            continue;
+        }
+
+        // Respect the policy's cool-down between consecutive pages
+        if page_sent && policy.page_cooldown_secs > 0 {
+            sleep(Duration::from_secs(policy.page_cooldown_secs as u64));
+        }
+        page_sent = true;
+
+        let ix = crank_distribute(&crank_accounts, page.page, &page.investors);
+        let sent = send_instructions_with_retries(
+            &rpc,
+            &args,
+            &payer,
+            page_attester.as_ref(),
+            &[ix],
+            page.compute_units,
+            &lookup_tables,
+        );
+        match sent {
+            Ok(signature) => signatures.push(signature),
+            // A timed-out confirmation may still have landed
+            Err(err) if fetch_progress(&rpc, &args.vault)?.pagination_cursor >= page.page => {
+                eprintln!("Page {} landed despite send error: {}", page.page, err);
+            }
+            Err(err) => return Err(err.context(format!("crank page {} failed", page.page))),
+        }
+        println!("Cranked page {}/{}", page.page, plan.total_pages);
+    }
+
+    let progress_after = fetch_progress(&rpc, &args.vault)?;
+    print_report(&args.vault, &progress_before, &progress_after, &signatures);
+
+    Ok(())
+}
+
+/// Enumerate the Streamflow streams of `--stream-mint`, sorted by stream pubkey as the
+/// crank requires
+fn fetch_streams(rpc: &RpcClient, args: &Args, quote_mint: &Pubkey, token_program: &Pubkey) -> Result<Vec<Stream>> {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        StreamflowUtils::MINT_OFFSET,
+        args.stream_mint.as_ref(),
+    ))];
+    if let Some(sender) = args.stream_sender {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            StreamflowUtils::SENDER_OFFSET,
+            sender.as_ref(),
+        )));
+    }
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let mut streams: Vec<Stream> = rpc
+        .get_program_accounts_with_config(&STREAMFLOW_PROGRAM_ID, config)
+        .context("failed to enumerate Streamflow streams")?
+        .into_iter()
+        .filter(|(_, account)| account.data.len() >= StreamflowUtils::CONTRACT_MIN_SIZE)
+        .map(|(pubkey, account)| {
+            let offset = StreamflowUtils::RECIPIENT_OFFSET;
+            let recipient = Pubkey::try_from(&account.data[offset..offset + 32])
+                .expect("slice is 32 bytes");
+            Stream {
+                pubkey,
+                recipient,
+                investor_quote_ata: associated_token_address(&recipient, quote_mint, token_program),
+                schedule: StreamflowUtils::read_schedule(&account.data).expect("contract size is checked"),
+            }
+        })
+        .collect();
+
+    streams.sort_by_key(|stream| stream.pubkey);
+    Ok(streams)
+}
+
+/// Create the investor record PDAs the crank requires but that do not exist yet, and
+/// grow records still in an older, shorter layout
+fn ensure_investor_records(
+    rpc: &RpcClient,
+    args: &Args,
+    payer: &Keypair,
+    cp_amm_pool: &Pubkey,
+    streams: &[Stream],
+) -> Result<()> {
+    let pdas = VaultPdas::new(&args.vault);
+    let mut missing = Vec::new();
+    let mut outdated = Vec::new();
+    for stream in streams {
+        let record = rpc
+            .get_account_with_commitment(&pdas.investor_record(&stream.pubkey), rpc.commitment())?
+            .value;
+        match record {
+            None => missing.push(stream.pubkey),
+            Some(account) if account.data.len() < InvestorRecord::SIZE => outdated.push(stream.pubkey),
+            Some(_) => {}
+        }
+    }
+
+    for chunk in missing.chunks(RECORDS_PER_TX) {
+        let ixs: Vec<Instruction> = chunk
+            .iter()
+            .map(|stream| init_investor_record(&payer.pubkey(), &args.vault, cp_amm_pool, stream))
+            .collect();
+        send_instructions_with_retries(rpc, args, payer, None, &ixs, args.compute_unit_limit, &[])?;
+    }
+
+    for chunk in outdated.chunks(RECORDS_PER_TX) {
+        let ixs: Vec<Instruction> = chunk
+            .iter()
+            .map(|stream| migrate_investor_record(&payer.pubkey(), &args.vault, stream))
+            .collect();
+        send_instructions_with_retries(rpc, args, payer, None, &ixs, args.compute_unit_limit, &[])?;
+    }
+
+    if !missing.is_empty() {
+        println!("Created {} investor records", missing.len());
+    }
+    if !outdated.is_empty() {
+        println!("Migrated {} investor records", outdated.len());
+    }
+
+    Ok(())
+}
+
+/// Per-class locked and weight totals the day's pages will weigh, read at `lock_ts`: each
+/// investor's snapshot for the day being opened, or its live lock when `refresh_locks` has
+/// not snapshotted it, time-weighted under `twal_weighting` like the crank
+fn day_class_totals(
+    rpc: &RpcClient,
+    args: &Args,
+    policy: &Policy,
+    streams: &[Stream],
+    lock_ts: i64,
+) -> Result<Vec<DayClassTotal>> {
+    let day = Progress::day_number(lock_ts);
+    let mut investors = Vec::with_capacity(streams.len());
+    for stream in streams {
+        let record = fetch_investor_record(rpc, &args.vault, &stream.pubkey)?;
+        let instant_locked = record
+            .as_ref()
+            .and_then(|record| record.snapshot_for(day))
+            .unwrap_or_else(|| stream.schedule.locked_amount(lock_ts));
+        let twal = match &record {
+            Some(record) if policy.twal_weighting => record.time_weighted_locked(lock_ts)?,
+            _ => None,
+        };
+        investors.push(InvestorInput {
+            locked_amount: twal.unwrap_or(instant_locked),
+            unlock_end_ts: stream.schedule.end_time.min(i64::MAX as u64) as i64,
+            class: record.as_ref().map_or(0, |record| record.class),
+            ..InvestorInput::default()
+        });
+    }
+    Ok(calculate_day_totals(policy, &investors, lock_ts)?)
+}
+
+fn send_with_retries(
+    rpc: &RpcClient,
+    args: &Args,
+    payer: &Keypair,
+    ix: Instruction,
+    compute_unit_limit: u32,
+) -> Result<Signature> {
+    send_instructions_with_retries(rpc, args, payer, None, &[ix], compute_unit_limit, &[])
+}
+
+/// Send `ixs` with compute budget instructions, retrying with a fresh blockhash and backoff.
+/// With `lookup_tables` the transaction is sent as v0, loading accounts from them;
+/// `co_signer` signs alongside the payer when an instruction requires it.
+fn send_instructions_with_retries(
+    rpc: &RpcClient,
+    args: &Args,
+    payer: &Keypair,
+    co_signer: Option<&Keypair>,
+    ixs: &[Instruction],
+    compute_unit_limit: u32,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<Signature> {
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        compute_unit_limit,
+    )];
+    if args.priority_fee > 0 {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee));
+    }
+    instructions.extend_from_slice(ixs);
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let blockhash = rpc.get_latest_blockhash()?;
+        let message = compile_message(&payer.pubkey(), &instructions, lookup_tables, blockhash)?;
+        let mut signers = vec![payer];
+        signers.extend(co_signer);
+        let tx = VersionedTransaction::try_new(message, &signers)?;
+
+        match rpc.send_and_confirm_transaction(&tx) {
+            Ok(signature) => return Ok(signature),
+            Err(err) if attempt < args.max_retries => {
+                eprintln!("Attempt {}/{} failed: {}", attempt, args.max_retries, err);
+                sleep(Duration::from_millis(500 * 2u64.pow(attempt - 1)));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Make every address of the plan loadable from lookup tables: extend the table
+/// registered on the policy (creating and registering one when there is none) and put
+/// what does not fit into extra tables created for this run. The keeper keypair must
+/// be the authority of the registered table.
+fn prepare_lookup_tables(
+    rpc: &RpcClient,
+    args: &Args,
+    payer: &Keypair,
+    policy: &Policy,
+    plan: &PagePlan,
+) -> Result<Vec<AddressLookupTableAccount>> {
+    let wanted = plan.lookup_tables.concat();
+
+    let registered = if policy.lookup_table == Pubkey::default() {
+        let table = create_lookup_table(rpc, args, payer)?;
+        let ix = set_lookup_table(&payer.pubkey(), &args.vault, &table);
+        match send_with_retries(rpc, args, payer, ix, args.compute_unit_limit) {
+            Ok(_) => println!("Registered lookup table {} on the vault policy", table),
+            // Registration needs the operator role; the table still serves this run
+            Err(err) => eprintln!("Could not register lookup table {}: {}", table, err),
+        }
+        table
+    } else {
+        policy.lookup_table
+    };
+
+    let mut tables = vec![fetch_lookup_table(rpc, &registered)?];
+    let mut missing = missing_addresses(&tables, &wanted);
+    while !missing.is_empty() {
+        let table = tables.last().expect("at least the registered table");
+        let take = remaining_capacity(table).min(missing.len());
+        if take == 0 {
+            let extra = create_lookup_table(rpc, args, payer)?;
+            tables.push(fetch_lookup_table(rpc, &extra)?);
+            continue;
+        }
+
+        for ix in extend_table(&table.key, &payer.pubkey(), &payer.pubkey(), &missing[..take]) {
+            send_with_retries(rpc, args, payer, ix, args.compute_unit_limit)
+                .with_context(|| format!("failed to extend lookup table {}", table.key))?;
+        }
+        println!("Added {} addresses to lookup table {}", take, table.key);
+
+        let key = table.key;
+        *tables.last_mut().expect("at least the registered table") = fetch_lookup_table(rpc, &key)?;
+        missing.drain(..take);
+    }
+
+    // Addresses appended in a slot are only loadable from the next one
+    let extended_at = rpc.get_slot()?;
+    while rpc.get_slot()? <= extended_at {
+        sleep(Duration::from_millis(400));
+    }
+
+    Ok(tables)
+}
+
+/// Create an empty lookup table owned by the keeper keypair
+fn create_lookup_table(rpc: &RpcClient, args: &Args, payer: &Keypair) -> Result<Pubkey> {
+    let recent_slot = rpc.get_slot_with_commitment(CommitmentConfig::finalized())?;
+    let (ix, table) = create_table(&payer.pubkey(), &payer.pubkey(), recent_slot);
+    send_with_retries(rpc, args, payer, ix, args.compute_unit_limit)
+        .context("failed to create lookup table")?;
+    println!("Created lookup table {}", table);
+
+    Ok(table)
+}
+
+fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+fn print_report(vault: &Pubkey, before: &Progress, after: &Progress, signatures: &[Signature]) {
+    println!();
+    println!("Distribution report for vault {}", vault);
+    println!("  Day:                     {}", after.current_day);
+    println!("  Pages:                   {}/{}", after.pagination_cursor, after.total_pages);
+    println!("  Investors processed:     {}/{}", after.investors_processed, after.total_investors);
+    println!("  Day complete:            {}", after.is_day_complete());
+    println!("  Claimed today:           {}", after.claimed_today);
+    println!("  Distributed today:       {}", after.distributed_today);
+    println!("  Carry over:              {}", after.carry_over);
+    println!(
+        "  Paid to investors (run): {}",
+        after
+            .lifetime_distributed_to_investors
+            .saturating_sub(before.lifetime_distributed_to_investors)
+    );
+    println!(
+        "  Paid to creator (run):   {}",
+        after.lifetime_paid_to_creator.saturating_sub(before.lifetime_paid_to_creator)
+    );
+    for signature in signatures {
+        println!("  Transaction:             {}", signature);
+    }
+}
@@ -0,0 +1,99 @@
+//! Fee distribution math of the Star Fee Distributor program
+//!
+//! Plain integer arithmetic with no dependencies (not even `std`): the eligible share,
+//! daily cap, payout curves, day-wide weights, dust thresholds and the carry-over and
+//! remainder books. The program wraps it in `star_fee_distributor::utils::DistributionMath`,
+//! mapping `MathError` onto `StarError`, and its invariants are property-tested here
+//! without the Solana toolchain (`cargo test -p star-distribution-core`).
+
+#![no_std]
+
+use core::fmt;
+
+mod math;
+
+pub use math::DistributionMath;
+
+/// Basis points making up a whole (100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Arithmetic failure of the distribution math
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    /// A result did not fit its integer type
+    Overflow,
+    /// Accounting would have gone negative
+    Underflow,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::Overflow => f.write_str("math overflow"),
+            MathError::Underflow => f.write_str("math underflow"),
+        }
+    }
+}
+
+pub type Result<T> = core::result::Result<T, MathError>;
+
+/// Shape of the pro-rata payout weighting (mirrors `state::PayoutCurve`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutCurve {
+    /// Weight proportional to the locked amount
+    Linear,
+    /// Weight proportional to the square root of the locked amount
+    Sqrt,
+}
+
+/// What a page does with a payout below the minimum (mirrors `state::DustPolicy`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DustPolicy {
+    /// Keep it in the investor pool as carry-over
+    Accumulate,
+    /// Release it to the day's remainder
+    Drop,
+    /// Pay it anyway, ignoring the threshold
+    Pay,
+}
+
+/// Policy knobs that turn weights into payouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayoutRules {
+    pub curve: PayoutCurve,
+    /// Payouts below this are not paid, unless `dust_policy` is `Pay`
+    pub min_payout_lamports: u64,
+    pub dust_policy: DustPolicy,
+}
+
+impl PayoutRules {
+    /// Threshold actually applied: `Pay` ignores `min_payout_lamports`
+    pub fn effective_min_payout(&self) -> u64 {
+        match self.dust_policy {
+            DustPolicy::Pay => 0,
+            DustPolicy::Accumulate | DustPolicy::Drop => self.min_payout_lamports,
+        }
+    }
+}
+
+/// Investor pool of a day, fixed by its first page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayPool {
+    /// Investors' share of the claim, before the daily cap
+    pub investor_fee: u64,
+    /// `investor_fee` after the daily cap
+    pub capped_investor_fee: u64,
+    /// Capped fee plus the carry-over the day opened with
+    pub total: u64,
+}
+
+/// Books of one distributed page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PagePayout {
+    /// Paid to the page's investors
+    pub distributed: u64,
+    /// Below-threshold dust released to the remainder (`DustPolicy::Drop`)
+    pub withheld: u64,
+    /// What is left of the day's pool after the page
+    pub carry_over: u64,
+}
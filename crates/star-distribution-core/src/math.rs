@@ -0,0 +1,230 @@
+use crate::{DayPool, DustPolicy, MathError, PagePayout, PayoutCurve, PayoutRules, Result, BPS_DENOMINATOR};
+
+/// Mathematical utilities for fee distribution calculations
+pub struct DistributionMath;
+
+impl DistributionMath {
+    /// Calculate the eligible investor share based on locked amounts
+    /// Returns basis points (0-10000) for investor fee share
+    pub fn calculate_eligible_share_bps(
+        locked_total: u64,
+        y0: u64,
+        max_investor_fee_share_bps: u16,
+    ) -> Result<u16> {
+        if y0 == 0 {
+            return Ok(0);
+        }
+
+        // f_locked(t) = locked_total(t) / Y0
+        let f_locked = (locked_total as u128)
+            .checked_mul(BPS_DENOMINATOR as u128)
+            .ok_or(MathError::Overflow)?
+            / y0 as u128;
+
+        // eligible_investor_share_bps = min(investor_fee_share_bps, floor(f_locked(t) * 10000))
+        let eligible_share = f_locked.min(max_investor_fee_share_bps as u128) as u16;
+
+        Ok(eligible_share)
+    }
+
+    /// Raise the eligible share to the policy floor while any tokens remain locked. The floor
+    /// never exceeds the fee share cap, and fully vested pages keep a zero share.
+    pub fn apply_share_floor(
+        eligible_share_bps: u16,
+        locked_total: u64,
+        min_investor_share_bps: u16,
+        max_investor_fee_share_bps: u16,
+    ) -> u16 {
+        if locked_total == 0 {
+            return eligible_share_bps;
+        }
+
+        eligible_share_bps.max(min_investor_share_bps.min(max_investor_fee_share_bps))
+    }
+
+    /// Calculate investor fee amount in quote tokens
+    pub fn calculate_investor_fee_quote(claimed_quote: u64, eligible_share_bps: u16) -> Result<u64> {
+        if eligible_share_bps == 0 {
+            return Ok(0);
+        }
+
+        Self::apply_bps(claimed_quote, eligible_share_bps as u64)
+    }
+
+    /// Apply daily cap to the distribution amount; a `daily_cap` of 0 means uncapped
+    pub fn apply_daily_cap(requested_amount: u64, daily_cap: u64, already_distributed: u64) -> Result<u64> {
+        if daily_cap == 0 {
+            return Ok(requested_amount);
+        }
+
+        // Saturates on purpose: carry-over is paid on top of the cap and counts towards
+        // `already_distributed`, so a day past its cap has no room left rather than an error
+        let remaining_cap = daily_cap.saturating_sub(already_distributed);
+
+        Ok(requested_amount.min(remaining_cap))
+    }
+
+    /// Investor pool fixed by a day's first page: the investors' share of the claim, capped
+    /// by the room left under `daily_cap`, plus the carry-over the day opened with
+    pub fn calculate_day_pool(
+        claimed_quote: u64,
+        eligible_share_bps: u16,
+        daily_cap: u64,
+        already_distributed: u64,
+        carry_in: u64,
+    ) -> Result<DayPool> {
+        let investor_fee = Self::calculate_investor_fee_quote(claimed_quote, eligible_share_bps)?;
+        let capped_investor_fee = Self::apply_daily_cap(investor_fee, daily_cap, already_distributed)?;
+        let total = capped_investor_fee.checked_add(carry_in).ok_or(MathError::Overflow)?;
+
+        Ok(DayPool { investor_fee, capped_investor_fee, total })
+    }
+
+    /// Apply a payout curve to a (weighted) locked amount. `Sqrt` returns
+    /// `floor(sqrt(locked * 2^32))`, i.e. the square root in 16.16 fixed point, so small
+    /// holders keep their relative precision.
+    pub fn apply_payout_curve(locked: u64, curve: PayoutCurve) -> u64 {
+        match curve {
+            PayoutCurve::Linear => locked,
+            PayoutCurve::Sqrt => Self::isqrt((locked as u128) << 32) as u64,
+        }
+    }
+
+    /// Sum of the curved amounts of a day's (or class's) investors, the denominator of
+    /// `calculate_investor_weight`
+    pub fn calculate_curved_total(locked: &[u64], curve: PayoutCurve) -> Result<u64> {
+        locked
+            .iter()
+            .try_fold(0u64, |acc, amount| acc.checked_add(Self::apply_payout_curve(*amount, curve)))
+            .ok_or(MathError::Overflow)
+    }
+
+    /// Calculate pro-rata weight for an investor under the payout curve. `total_curved` is
+    /// the sum of every investor's curved amount, so the weights sum to at most 10000.
+    pub fn calculate_investor_weight(investor_locked: u64, total_curved: u64, curve: PayoutCurve) -> Result<u64> {
+        if total_curved == 0 {
+            return Ok(0);
+        }
+
+        // weight_i(t) = curve(locked_i(t)) / sum_j curve(locked_j(t))
+        // Return as basis points (0-10000)
+        Self::split_pro_rata(BPS_DENOMINATOR, Self::apply_payout_curve(investor_locked, curve), total_curved)
+    }
+
+    /// Integer square root, rounded down
+    fn isqrt(value: u128) -> u128 {
+        if value < 2 {
+            return value;
+        }
+
+        // Newton's method from an initial guess at or above the root converges downwards
+        let mut x = 1u128 << ((128 - value.leading_zeros()).div_ceil(2));
+        loop {
+            let next = (x + value / x) / 2;
+            if next >= x {
+                return x;
+            }
+            x = next;
+        }
+    }
+
+    /// Scale a locked amount by a lock-duration multiplier (basis points, 10000 = 1x)
+    pub fn calculate_weighted_locked(locked_amount: u64, multiplier_bps: u16) -> Result<u64> {
+        Self::apply_bps(locked_amount, multiplier_bps as u64)
+    }
+
+    /// Calculate individual investor payout; payouts below `min_payout_lamports` are 0
+    pub fn calculate_investor_payout(
+        total_investor_fee_quote: u64,
+        investor_weight_bps: u64,
+        min_payout_lamports: u64,
+    ) -> Result<u64> {
+        let payout = Self::apply_bps(total_investor_fee_quote, investor_weight_bps)?;
+
+        if payout < min_payout_lamports {
+            Ok(0)
+        } else {
+            Ok(payout)
+        }
+    }
+
+    /// Pay a page of investors out of the day's pool. Each investor gets their day-wide
+    /// weight (`weighted_locked[i]` against the day's `total_curved`, committed at day open)
+    /// of `day_pool`, written to `payouts[i]`; `pool_remaining` is what earlier pages left of
+    /// it. `payouts` must be as long as `weighted_locked`.
+    pub fn distribute_page(
+        day_pool: u64,
+        pool_remaining: u64,
+        weighted_locked: &[u64],
+        total_curved: u64,
+        rules: PayoutRules,
+        payouts: &mut [u64],
+    ) -> Result<PagePayout> {
+        let min_payout_lamports = rules.effective_min_payout();
+        let mut distributed = 0u64;
+        let mut withheld = 0u64;
+
+        for (weighted, payout) in weighted_locked.iter().zip(payouts.iter_mut()) {
+            let weight_bps = Self::calculate_investor_weight(*weighted, total_curved, rules.curve)?;
+            *payout = Self::calculate_investor_payout(day_pool, weight_bps, min_payout_lamports)?;
+
+            // Dropped dust leaves the investor pool for the remainder
+            if *payout == 0 && rules.dust_policy == DustPolicy::Drop {
+                let dust = Self::calculate_investor_payout(day_pool, weight_bps, 0)?;
+                withheld = withheld.checked_add(dust).ok_or(MathError::Overflow)?;
+            }
+
+            distributed = distributed.checked_add(*payout).ok_or(MathError::Overflow)?;
+        }
+
+        let carry_over = Self::calculate_page_carry_over(pool_remaining, distributed, withheld)?;
+        Ok(PagePayout { distributed, withheld, carry_over })
+    }
+
+    /// Share of `amount` proportional to `part / total`, rounded down (0 when `total` is 0)
+    pub fn split_pro_rata(amount: u64, part: u64, total: u64) -> Result<u64> {
+        if total == 0 {
+            return Ok(0);
+        }
+
+        let share = (amount as u128)
+            .checked_mul(part as u128)
+            .ok_or(MathError::Overflow)?
+            / total as u128;
+
+        u64::try_from(share).map_err(|_| MathError::Overflow)
+    }
+
+    /// Carry-over after a page: the investor pool (capped fee plus carry-in) minus what was
+    /// paid and what was withheld for the creator, i.e. rounding and below-threshold dust.
+    /// Paying out more than the pool fails with `Underflow`.
+    pub fn calculate_page_carry_over(total_to_distribute: u64, distributed: u64, withheld: u64) -> Result<u64> {
+        total_to_distribute
+            .checked_sub(distributed)
+            .and_then(|remaining| remaining.checked_sub(withheld))
+            .ok_or(MathError::Underflow)
+    }
+
+    /// Creator remainder at day close. The day's treasury inflow (claims plus the carry-over
+    /// it opened with) leaves as investor payouts, the remainder, or carry-over kept for
+    /// the next day, so `claimed + carry_in = distributed + remainder + carry_out`. Payouts
+    /// and carry-over beyond the inflow fail with `Underflow`.
+    pub fn calculate_day_remainder(
+        claimed_today: u64,
+        carry_over_at_open: u64,
+        distributed_today: u64,
+        carry_over: u64,
+    ) -> Result<u64> {
+        claimed_today
+            .checked_add(carry_over_at_open)
+            .ok_or(MathError::Overflow)?
+            .checked_sub(distributed_today)
+            .and_then(|remaining| remaining.checked_sub(carry_over))
+            .ok_or(MathError::Underflow)
+    }
+
+    /// `amount * bps / 10000`, rounded down
+    fn apply_bps(amount: u64, bps: u64) -> Result<u64> {
+        Self::split_pro_rata(amount, bps, BPS_DENOMINATOR)
+    }
+}
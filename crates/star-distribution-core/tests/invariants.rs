@@ -0,0 +1,186 @@
+//! Property tests of the distribution pipeline (eligible share, daily cap, day-wide weights,
+//! dust policy, carry-over and remainder) over whole days split into pages, the way the
+//! crank runs them.
+
+use proptest::prelude::*;
+use star_distribution_core::{DistributionMath, DustPolicy, PayoutCurve, PayoutRules};
+
+#[derive(Debug, Clone)]
+struct DayParams {
+    claimed: u64,
+    carry_in: u64,
+    y0: u64,
+    fee_share_bps: u16,
+    daily_cap: u64,
+    rules: PayoutRules,
+}
+
+#[derive(Debug)]
+struct Day {
+    payouts: Vec<u64>,
+    distributed: u64,
+    carry_over: u64,
+    remainder: u64,
+}
+
+/// Run one day over `locked`, split into pages of `page_size`. The weight denominator is
+/// the day's curved total, committed before the first page the way `open_day` does.
+fn run_day(params: &DayParams, locked: &[u64], page_size: usize) -> Day {
+    let day_locked: u64 = locked.iter().sum();
+    let total_curved = DistributionMath::calculate_curved_total(locked, params.rules.curve).unwrap();
+    let eligible_share_bps =
+        DistributionMath::calculate_eligible_share_bps(day_locked, params.y0, params.fee_share_bps).unwrap();
+    let pool = DistributionMath::calculate_day_pool(
+        params.claimed,
+        eligible_share_bps,
+        params.daily_cap,
+        0,
+        params.carry_in,
+    )
+    .unwrap();
+
+    let mut payouts = vec![0u64; locked.len()];
+    let mut distributed = 0u64;
+    let mut carry_over = pool.total;
+    for (page, page_payouts) in locked.chunks(page_size).zip(payouts.chunks_mut(page_size)) {
+        let page_payout =
+            DistributionMath::distribute_page(pool.total, carry_over, page, total_curved, params.rules, page_payouts)
+                .unwrap();
+        distributed += page_payout.distributed;
+        carry_over = page_payout.carry_over;
+    }
+
+    let remainder =
+        DistributionMath::calculate_day_remainder(params.claimed, params.carry_in, distributed, carry_over).unwrap();
+
+    Day { payouts, distributed, carry_over, remainder }
+}
+
+fn curve() -> impl Strategy<Value = PayoutCurve> {
+    prop_oneof![Just(PayoutCurve::Linear), Just(PayoutCurve::Sqrt)]
+}
+
+fn dust_policy() -> impl Strategy<Value = DustPolicy> {
+    prop_oneof![Just(DustPolicy::Accumulate), Just(DustPolicy::Drop), Just(DustPolicy::Pay)]
+}
+
+prop_compose! {
+    fn day_params()(
+        claimed in 0u64..1_000_000_000_000_000,
+        carry_in in 0u64..1_000_000_000_000,
+        y0 in 1u64..1_000_000_000_000_000,
+        fee_share_bps in 0u16..=10_000,
+        daily_cap in prop_oneof![Just(0u64), 1u64..1_000_000_000_000_000],
+        curve in curve(),
+        min_payout_lamports in 0u64..1_000_000_000,
+        dust_policy in dust_policy(),
+    ) -> DayParams {
+        DayParams {
+            claimed,
+            carry_in,
+            y0,
+            fee_share_bps,
+            daily_cap,
+            rules: PayoutRules { curve, min_payout_lamports, dust_policy },
+        }
+    }
+}
+
+fn locked_amounts() -> impl Strategy<Value = Vec<u64>> {
+    prop::collection::vec(0u64..1_000_000_000_000, 1..40)
+}
+
+proptest! {
+    #[test]
+    fn payouts_never_exceed_the_claim(
+        params in day_params(),
+        locked in locked_amounts(),
+        page_size in 1usize..12,
+    ) {
+        let params = DayParams { carry_in: 0, ..params };
+        let day = run_day(&params, &locked, page_size);
+
+        prop_assert_eq!(day.payouts.iter().sum::<u64>(), day.distributed);
+        prop_assert!(day.distributed <= params.claimed);
+        if params.daily_cap != 0 {
+            prop_assert!(day.distributed <= params.daily_cap);
+        }
+    }
+
+    #[test]
+    fn day_conserves_its_inflow(
+        params in day_params(),
+        locked in locked_amounts(),
+        page_size in 1usize..12,
+    ) {
+        let day = run_day(&params, &locked, page_size);
+
+        prop_assert_eq!(
+            day.distributed as u128 + day.carry_over as u128 + day.remainder as u128,
+            params.claimed as u128 + params.carry_in as u128
+        );
+    }
+
+    #[test]
+    fn payouts_do_not_depend_on_the_page_split(
+        params in day_params(),
+        locked in locked_amounts(),
+        page_size in 1usize..12,
+    ) {
+        let single_page = run_day(&params, &locked, locked.len());
+        let paged = run_day(&params, &locked, page_size);
+        prop_assert_eq!(paged.payouts, single_page.payouts);
+        prop_assert_eq!(paged.remainder, single_page.remainder);
+    }
+
+    #[test]
+    fn payout_does_not_change_when_investors_move_between_pages(
+        params in day_params(),
+        (locked, order) in locked_amounts().prop_flat_map(|locked| {
+            let order = Just((0..locked.len()).collect::<Vec<_>>()).prop_shuffle();
+            (Just(locked), order)
+        }),
+        page_size in 1usize..12,
+        moved_page_size in 1usize..12,
+    ) {
+        let moved: Vec<u64> = order.iter().map(|investor| locked[*investor]).collect();
+
+        let before = run_day(&params, &locked, page_size);
+        let after = run_day(&params, &moved, moved_page_size);
+        for (position, investor) in order.iter().enumerate() {
+            prop_assert_eq!(after.payouts[position], before.payouts[*investor]);
+        }
+        prop_assert_eq!(after.remainder, before.remainder);
+    }
+
+    #[test]
+    fn payout_is_monotonic_in_the_locked_amount(
+        params in day_params(),
+        locked in locked_amounts(),
+        index in any::<prop::sample::Index>(),
+        extra in 1u64..1_000_000_000_000,
+    ) {
+        let investor = index.index(locked.len());
+        let mut more_locked = locked.clone();
+        more_locked[investor] += extra;
+
+        let before = run_day(&params, &locked, locked.len());
+        let after = run_day(&params, &more_locked, more_locked.len());
+        prop_assert!(after.payouts[investor] >= before.payouts[investor]);
+    }
+
+    #[test]
+    fn eligible_share_is_monotonic_in_the_locked_total(
+        locked_total in any::<u64>(),
+        extra in any::<u64>(),
+        y0 in 1u64..u64::MAX,
+        fee_share_bps in 0u16..=10_000,
+    ) {
+        let before = DistributionMath::calculate_eligible_share_bps(locked_total, y0, fee_share_bps).unwrap();
+        let after =
+            DistributionMath::calculate_eligible_share_bps(locked_total.saturating_add(extra), y0, fee_share_bps)
+                .unwrap();
+        prop_assert!(before <= after);
+        prop_assert!(after <= fee_share_bps);
+    }
+}
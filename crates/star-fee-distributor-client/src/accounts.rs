@@ -0,0 +1,99 @@
+//! Account fetch and deserialize helpers
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use solana_client::rpc_client::RpcClient;
+use star_fee_distributor::state::{
+    Blacklist, CrankerAllowlist, GlobalConfig, InvestorRecord, InvestorRegistry,
+    InvestorRegistryChunk, Policy, PolicyTemplate, Progress, VaultMetadata,
+};
+
+use crate::error::{ClientError, Result};
+use crate::pda::{derive_global_config_pda, derive_policy_template_pda, VaultPdas};
+
+/// Deserialize a program-owned Anchor account, checking owner and discriminator
+pub fn deserialize_account<T: AccountDeserialize>(
+    address: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+) -> Result<T> {
+    if *owner != star_fee_distributor::ID {
+        return Err(ClientError::InvalidOwner(*address));
+    }
+    let mut data = data;
+    T::try_deserialize(&mut data).map_err(|err| ClientError::Deserialize(*address, err))
+}
+
+/// Fetch and deserialize a program-owned account
+pub fn fetch_account<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    deserialize_account(address, &account.owner, &account.data)
+}
+
+/// Fetch an optional PDA (blacklist, cranker allowlist, global config, registry, metadata),
+/// `None` when uninitialized
+pub fn fetch_optional_account<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<T>> {
+    match fetch_account(rpc, address) {
+        Ok(account) => Ok(Some(account)),
+        Err(ClientError::AccountNotFound(_)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn fetch_policy(rpc: &RpcClient, vault: &Pubkey) -> Result<Policy> {
+    fetch_account(rpc, &VaultPdas::new(vault).policy)
+}
+
+pub fn fetch_progress(rpc: &RpcClient, vault: &Pubkey) -> Result<Progress> {
+    fetch_account(rpc, &VaultPdas::new(vault).progress)
+}
+
+pub fn fetch_investor_record(
+    rpc: &RpcClient,
+    vault: &Pubkey,
+    stream_pubkey: &Pubkey,
+) -> Result<Option<InvestorRecord>> {
+    fetch_optional_account(rpc, &VaultPdas::new(vault).investor_record(stream_pubkey))
+}
+
+pub fn fetch_blacklist(rpc: &RpcClient, vault: &Pubkey) -> Result<Option<Blacklist>> {
+    fetch_optional_account(rpc, &VaultPdas::new(vault).blacklist)
+}
+
+pub fn fetch_cranker_allowlist(rpc: &RpcClient, vault: &Pubkey) -> Result<Option<CrankerAllowlist>> {
+    fetch_optional_account(rpc, &VaultPdas::new(vault).cranker_allowlist)
+}
+
+pub fn fetch_global_config(rpc: &RpcClient) -> Result<Option<GlobalConfig>> {
+    fetch_optional_account(rpc, &derive_global_config_pda().0)
+}
+
+pub fn fetch_policy_template(rpc: &RpcClient, template_id: u32) -> Result<PolicyTemplate> {
+    fetch_account(rpc, &derive_policy_template_pda(template_id).0)
+}
+
+pub fn fetch_vault_metadata(rpc: &RpcClient, vault: &Pubkey) -> Result<Option<VaultMetadata>> {
+    fetch_optional_account(rpc, &VaultPdas::new(vault).vault_metadata)
+}
+
+pub fn fetch_investor_registry(rpc: &RpcClient, vault: &Pubkey) -> Result<Option<InvestorRegistry>> {
+    fetch_optional_account(rpc, &VaultPdas::new(vault).investor_registry)
+}
+
+/// Fetch every chunk of the vault's investor registry, in index order
+pub fn fetch_registry_chunks(rpc: &RpcClient, vault: &Pubkey) -> Result<Vec<InvestorRegistryChunk>> {
+    let pdas = VaultPdas::new(vault);
+    let chunk_count = match fetch_investor_registry(rpc, vault)? {
+        Some(registry) => registry.chunk_count,
+        None => return Ok(Vec::new()),
+    };
+    (0..chunk_count)
+        .map(|chunk_index| fetch_account(rpc, &pdas.registry_chunk(chunk_index)))
+        .collect()
+}
@@ -0,0 +1,69 @@
+//! Decoding of versioned instruction arguments
+//!
+//! `init_policy` and `crank_distribute` take their parameters as versioned structs whose
+//! first byte selects the layout of the rest. Indexers and keepers replaying transactions
+//! decode them here; a version newer than this client fails with `UnsupportedArgsVersion`
+//! instead of being misread.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use star_fee_distributor::instruction::{CrankDistribute, InitPolicy};
+use star_fee_distributor::state::{CrankArgsV1, InitializeArgsV1};
+
+use crate::error::{ClientError, Result};
+
+/// Arguments of an `init_policy` instruction, by version
+#[derive(Debug, Clone)]
+pub enum InitializeArgs {
+    V1(InitializeArgsV1),
+}
+
+/// Arguments of a `crank_distribute` instruction, by version
+#[derive(Debug, Clone)]
+pub enum CrankArgs {
+    V1(CrankArgsV1),
+}
+
+impl CrankArgs {
+    /// Page the instruction distributes, whatever its version
+    pub fn page(&self) -> u64 {
+        match self {
+            CrankArgs::V1(args) => args.page,
+        }
+    }
+}
+
+/// Decode the arguments of `init_policy` instruction data
+pub fn decode_initialize_args(data: &[u8]) -> Result<InitializeArgs> {
+    let (version, mut payload) = versioned_payload(data, &InitPolicy::DISCRIMINATOR, "init_policy")?;
+    match version {
+        InitializeArgsV1::VERSION => InitializeArgsV1::deserialize(&mut payload)
+            .map(InitializeArgs::V1)
+            .map_err(|error| ClientError::DecodeArgs("init_policy", error)),
+        _ => Err(ClientError::UnsupportedArgsVersion(version)),
+    }
+}
+
+/// Decode the arguments of `crank_distribute` instruction data
+pub fn decode_crank_args(data: &[u8]) -> Result<CrankArgs> {
+    let (version, mut payload) = versioned_payload(data, &CrankDistribute::DISCRIMINATOR, "crank_distribute")?;
+    match version {
+        CrankArgsV1::VERSION => CrankArgsV1::deserialize(&mut payload)
+            .map(CrankArgs::V1)
+            .map_err(|error| ClientError::DecodeArgs("crank_distribute", error)),
+        _ => Err(ClientError::UnsupportedArgsVersion(version)),
+    }
+}
+
+/// Check the instruction discriminator and return the argument version with the
+/// arguments' bytes (version byte included)
+fn versioned_payload<'a>(data: &'a [u8], discriminator: &[u8], name: &'static str) -> Result<(u8, &'a [u8])> {
+    if data.len() < 8 || data[..8] != *discriminator {
+        return Err(ClientError::UnexpectedInstruction(name));
+    }
+
+    let payload = &data[8..];
+    let version = *payload
+        .first()
+        .ok_or_else(|| ClientError::DecodeArgs(name, std::io::ErrorKind::UnexpectedEof.into()))?;
+    Ok((version, payload))
+}
@@ -0,0 +1,326 @@
+//! Off-chain distribution calculator
+//!
+//! Replays the crank's page and day-close math with the program's own `DistributionMath`,
+//! so keepers can compute the exact payouts of a page before sending it and assert the
+//! balances afterwards. Any difference means the off-chain inputs (locks, classes,
+//! exclusions) or the program itself drifted, and should stop the keeper.
+
+use star_fee_distributor::errors::StarError;
+use star_fee_distributor::state::{DayClassTotal, Policy, Progress};
+pub use star_fee_distributor::utils::DistributionMath;
+
+use crate::error::{ClientError, Result};
+
+/// An investor of a page as the crank sees it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InvestorInput {
+    /// Locked amount the crank weighs: the lock at the day's lock timestamp, or the
+    /// time-weighted average under `twal_weighting`. Zero for skipped streams.
+    pub locked_amount: u64,
+    /// Timestamp at which the lock fully vests, for the lock-duration multiplier
+    pub unlock_end_ts: i64,
+    /// Investor class from the investor record
+    pub class: u8,
+    /// The stream was cancelled or closed and carries no lock
+    pub skipped: bool,
+    /// Blacklisted or opted out: keeps its weight, but its share is withheld for the creator
+    pub excluded: bool,
+    /// The payout ATA is frozen: its share is routed like dust, per the dust policy
+    pub frozen: bool,
+}
+
+/// Expected outcome of one crank page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageCalculation {
+    /// Locked amount per investor class, booked against the class totals committed at open
+    pub class_locked: Vec<u64>,
+    /// Curved weight per investor class, booked against the class totals committed at open
+    pub class_weights: Vec<u64>,
+    /// Amount moved to the insurance fund
+    pub insured: u64,
+    /// Day claim (plus harvested fees) left after insurance, added to `claimed_today`;
+    /// zero past the first page
+    pub claimed: u64,
+    /// Sum of the classes' eligible shares, as reported by `InvestorPayoutPage`
+    pub eligible_share_bps: u16,
+    pub investor_fee_quote: u64,
+    pub capped_investor_fee: u64,
+    /// What is left of the day's pool before the page: on the first page the capped
+    /// investor fee plus the carry-over, later the pool's remaining `carry_over`
+    pub total_to_distribute: u64,
+    /// The day's pool per investor class, fixed by the first page
+    pub class_pools: Vec<u64>,
+    /// Transfer per investor, in page order (zero for skipped, excluded, frozen and dust
+    /// payouts)
+    pub payouts: Vec<u64>,
+    pub distributed: u64,
+    /// Shares of excluded investors, plus dust and frozen shares under `DustPolicy::Drop`,
+    /// left for the creator
+    pub withheld: u64,
+    pub carry_over: u64,
+}
+
+/// Expected outcome of the day close run by the final page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayCloseCalculation {
+    /// Everything the day brought in that investors neither received nor carry over
+    pub remainder: u64,
+    pub referral_fee: u64,
+    /// Remainder left for the creator (or burned) after the referral fee
+    pub creator_remainder: u64,
+}
+
+/// Compute the page `page` of the current day with `claimed_quote` claimed from the
+/// position (by the first page only; later pages ignore it), given the policy and
+/// progress as they are before the page lands
+pub fn calculate_page(
+    policy: &Policy,
+    progress: &Progress,
+    page: u64,
+    claimed_quote: u64,
+    investors: &[InvestorInput],
+    current_timestamp: i64,
+) -> Result<PageCalculation> {
+    // The first page applies a queued parameter change before anything else
+    let mut policy = policy.clone();
+    if page == 1 {
+        policy.apply_pending_update(current_timestamp);
+    }
+    let policy = &policy;
+
+    if policy.compound_investor_share {
+        return Err(star_error(StarError::CompoundingUnsupported));
+    }
+    if investors.is_empty() {
+        return Err(star_error(StarError::NoLockedInvestors));
+    }
+
+    let total_locked = investors
+        .iter()
+        .try_fold(0u64, |acc, investor| checked_add(acc, investor.locked_amount))?;
+    if total_locked == 0 {
+        return Err(star_error(StarError::NoLockedInvestors));
+    }
+
+    // Investors are weighed as of the lock timestamp committed at day open
+    let (class_locked, class_curved_total) = class_totals(policy, investors, progress.day_lock_ts)?;
+    let weighted_locked = weighted_locked(policy, investors, progress.day_lock_ts)?;
+    let class_count = class_locked.len();
+    for class in 0..class_count {
+        if checked_add(progress.day_class_locked_processed[class], class_locked[class])? > progress.day_class_locked[class]
+            || checked_add(progress.day_class_weights_processed[class], class_curved_total[class])?
+                > progress.day_class_weights[class]
+        {
+            return Err(star_error(StarError::DayLockedTotalExceeded));
+        }
+    }
+
+    // Only the first page claims and fixes the day's pool; later pages pay out of what is
+    // left of it in `carry_over`
+    let (insured, claimed, eligible_share_bps, investor_fee_quote, capped_investor_fee, total_to_distribute, class_pools) = if page == 1 {
+        let day_claimed = checked_add(claimed_quote, progress.harvested_pending)?;
+        let insured = DistributionMath::calculate_investor_fee_quote(day_claimed, policy.insurance_bps)?;
+        let claimed = day_claimed - insured;
+
+        let mut eligible_share_bps = 0u16;
+        let mut class_investor_fees = Vec::with_capacity(class_count);
+        for class in 0..class_count {
+            let (class_y0, class_fee_share_bps) = policy.investor_class_terms(class, current_timestamp);
+            let class_day_locked = progress.day_class_locked[class];
+            let class_eligible_bps = DistributionMath::apply_share_floor(
+                DistributionMath::calculate_eligible_share_bps(class_day_locked, class_y0, class_fee_share_bps)?,
+                class_day_locked,
+                policy.min_investor_share_bps,
+                class_fee_share_bps,
+            );
+            eligible_share_bps = eligible_share_bps
+                .checked_add(class_eligible_bps)
+                .ok_or_else(|| star_error(StarError::MathOverflow))?;
+            class_investor_fees.push(DistributionMath::calculate_investor_fee_quote(claimed, class_eligible_bps)?);
+        }
+
+        let investor_fee_quote = class_investor_fees.iter().try_fold(0u64, |acc, fee| checked_add(acc, *fee))?;
+        let capped_investor_fee = DistributionMath::apply_daily_cap(
+            investor_fee_quote,
+            policy.effective_daily_cap(current_timestamp),
+            progress.distributed_today,
+        )?;
+        let total_to_distribute = checked_add(capped_investor_fee, progress.carry_over)?;
+
+        let day_class_weights = &progress.day_class_weights[..class_count];
+        let (class_split_weights, class_split_total) = if investor_fee_quote > 0 {
+            (&class_investor_fees[..], investor_fee_quote)
+        } else {
+            let total_curved = day_class_weights.iter().try_fold(0u64, |acc, curved| checked_add(acc, *curved))?;
+            (day_class_weights, total_curved)
+        };
+        let class_pools = class_split_weights
+            .iter()
+            .map(|weight| DistributionMath::split_pro_rata(total_to_distribute, *weight, class_split_total))
+            .collect::<anchor_lang::Result<Vec<u64>>>()?;
+
+        (insured, claimed, eligible_share_bps, investor_fee_quote, capped_investor_fee, total_to_distribute, class_pools)
+    } else {
+        let class_pools = progress.day_class_pools[..class_count].to_vec();
+        (0, 0, progress.day_eligible_share_bps, 0, 0, progress.carry_over, class_pools)
+    };
+
+    let mut payouts = vec![0u64; investors.len()];
+    let mut distributed = 0u64;
+    let mut withheld = 0u64;
+
+    for (i, investor) in investors.iter().enumerate() {
+        if investor.skipped || investor.locked_amount == 0 {
+            continue;
+        }
+
+        let class = investor.class as usize;
+        let weight_bps = DistributionMath::calculate_investor_weight(
+            weighted_locked[i],
+            progress.day_class_weights[class],
+            policy.payout_curve,
+        )?;
+
+        if investor.excluded {
+            withheld = checked_add(
+                withheld,
+                DistributionMath::calculate_investor_payout(class_pools[class], weight_bps, 0)?,
+            )?;
+            continue;
+        }
+
+        // Unpaid shares are routed by the dust policy; the calculator does not track the
+        // investors' accrued dust
+        let mut accrued_dust = 0u64;
+        if investor.frozen {
+            let share = DistributionMath::calculate_investor_payout(class_pools[class], weight_bps, 0)?;
+            policy.dust_policy.route_unpaid_share(share, &mut withheld, &mut accrued_dust)?;
+            continue;
+        }
+
+        payouts[i] = DistributionMath::calculate_investor_payout(
+            class_pools[class],
+            weight_bps,
+            policy.effective_min_payout(),
+        )?;
+        if payouts[i] == 0 {
+            let dust = DistributionMath::calculate_investor_payout(class_pools[class], weight_bps, 0)?;
+            policy.dust_policy.route_unpaid_share(dust, &mut withheld, &mut accrued_dust)?;
+        }
+        distributed = checked_add(distributed, payouts[i])?;
+    }
+
+    let carry_over = DistributionMath::calculate_page_carry_over(total_to_distribute, distributed, withheld)?;
+
+    Ok(PageCalculation {
+        class_locked,
+        class_weights: class_curved_total,
+        insured,
+        claimed,
+        eligible_share_bps,
+        investor_fee_quote,
+        capped_investor_fee,
+        total_to_distribute,
+        class_pools,
+        payouts,
+        distributed,
+        withheld,
+        carry_over,
+    })
+}
+
+impl PageCalculation {
+    /// Record the page in `progress` the way the crank does, so the next page (or the
+    /// day close) can be calculated before this one lands
+    pub fn apply(&self, progress: &mut Progress, page: u64, investors_processed: u64) -> Result<()> {
+        if page == 1 {
+            progress.harvested_pending = 0;
+            progress.record_claim(self.claimed)?;
+            progress.fix_day_pool(self.total_to_distribute, &self.class_pools, self.eligible_share_bps)?;
+        }
+        progress.record_page_totals(&self.class_locked, &self.class_weights)?;
+        progress.record_page_payouts(self.distributed, self.withheld)?;
+        progress.pagination_cursor = page;
+        progress.investors_processed = checked_add(progress.investors_processed, investors_processed)?;
+        Ok(())
+    }
+}
+
+/// Per-class locked and weight totals of the day's whole investor set, read at `lock_ts`,
+/// to commit with `open_day`
+pub fn calculate_day_totals(policy: &Policy, investors: &[InvestorInput], lock_ts: i64) -> Result<Vec<DayClassTotal>> {
+    let (class_locked, class_weights) = class_totals(policy, investors, lock_ts)?;
+    Ok(class_locked
+        .into_iter()
+        .zip(class_weights)
+        .map(|(locked, weight)| DayClassTotal { locked, weight })
+        .collect())
+}
+
+/// Compute the day close from the progress after the final page. The final page fails
+/// unless the day's pages reached the class totals committed at open.
+pub fn calculate_day_close(policy: &Policy, progress: &Progress) -> Result<DayCloseCalculation> {
+    progress.check_day_totals_reached()?;
+
+    let remainder = DistributionMath::calculate_day_remainder(
+        progress.claimed_today,
+        progress.carry_over_at_open,
+        progress.distributed_today,
+        progress.carry_over,
+    )?;
+
+    let referral_fee = match policy.referral {
+        Some(referral) => DistributionMath::calculate_investor_fee_quote(progress.claimed_today, referral.referral_bps)?
+            .min(remainder),
+        None => 0,
+    };
+
+    Ok(DayCloseCalculation {
+        remainder,
+        referral_fee,
+        creator_remainder: remainder - referral_fee,
+    })
+}
+
+/// Locked amounts scaled by the lock-duration multiplier as of `lock_ts`
+fn weighted_locked(policy: &Policy, investors: &[InvestorInput], lock_ts: i64) -> Result<Vec<u64>> {
+    Ok(investors
+        .iter()
+        .map(|investor| {
+            let remaining_secs = investor.unlock_end_ts.saturating_sub(lock_ts).max(0);
+            DistributionMath::calculate_weighted_locked(
+                investor.locked_amount,
+                policy.duration_multiplier_bps(remaining_secs),
+            )
+        })
+        .collect::<anchor_lang::Result<Vec<u64>>>()?)
+}
+
+/// Locked amounts and curved weights of `investors`, summed per investor class
+fn class_totals(policy: &Policy, investors: &[InvestorInput], lock_ts: i64) -> Result<(Vec<u64>, Vec<u64>)> {
+    let weighted_locked = weighted_locked(policy, investors, lock_ts)?;
+    let class_count = policy.investor_class_count();
+    let mut class_locked = vec![0u64; class_count];
+    let mut class_curved_total = vec![0u64; class_count];
+    for (investor, weighted) in investors.iter().zip(&weighted_locked) {
+        let class = investor.class as usize;
+        if class >= class_count {
+            return Err(star_error(StarError::InvalidInvestorClass));
+        }
+        class_locked[class] = checked_add(class_locked[class], investor.locked_amount)?;
+        class_curved_total[class] = checked_add(
+            class_curved_total[class],
+            DistributionMath::apply_payout_curve(*weighted, policy.payout_curve),
+        )?;
+    }
+    Ok((class_locked, class_curved_total))
+}
+
+/// The error the crank fails the page with
+fn star_error(error: StarError) -> ClientError {
+    ClientError::Math(error.into())
+}
+
+fn checked_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or_else(|| star_error(StarError::MathOverflow))
+}
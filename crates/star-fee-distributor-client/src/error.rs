@@ -0,0 +1,49 @@
+use anchor_lang::prelude::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("RPC error: {0}")]
+    Rpc(#[from] solana_client::client_error::ClientError),
+
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error("Account {0} is not owned by the Star Fee Distributor program")]
+    InvalidOwner(Pubkey),
+
+    #[error("Failed to deserialize account {0}: {1}")]
+    Deserialize(Pubkey, anchor_lang::error::Error),
+
+    #[error("Investor set is empty")]
+    EmptyInvestorSet,
+
+    #[error("Stream {0} appears more than once in the investor set")]
+    DuplicateInvestor(Pubkey),
+
+    #[error("A page holding only stream {0} exceeds the transaction limits")]
+    InvestorDoesNotFit(Pubkey),
+
+    #[error("Page {0} at the policy page size exceeds the transaction limits")]
+    PageDoesNotFit(u64),
+
+    #[error("Failed to compile transaction: {0}")]
+    Compile(String),
+
+    #[error("Account {0} is not a valid address lookup table")]
+    InvalidLookupTable(Pubkey),
+
+    #[error("Instruction data is not a {0} instruction")]
+    UnexpectedInstruction(&'static str),
+
+    #[error("Instruction argument version {0} is not supported by this client")]
+    UnsupportedArgsVersion(u8),
+
+    #[error("Failed to decode {0} arguments: {1}")]
+    DecodeArgs(&'static str, std::io::Error),
+
+    #[error("Distribution calculation failed: {0}")]
+    Math(#[from] anchor_lang::error::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
@@ -0,0 +1,255 @@
+//! Event decoding for `emit!` (program logs) and `emit_cpi!` (self-CPI instruction data)
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiInnerInstructions, UiInstruction, UiLoadedAddresses,
+    UiTransactionEncoding, UiTransactionStatusMeta,
+};
+use star_fee_distributor::events::*;
+
+use crate::error::Result;
+
+/// Log prefix Anchor uses for `emit!` payloads
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+macro_rules! star_events {
+    ($($name:ident),* $(,)?) => {
+        /// Any event emitted by the program
+        pub enum StarEvent {
+            $($name($name),)*
+        }
+
+        impl StarEvent {
+            /// Decode an event from its discriminator-prefixed payload
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                if data.len() < 8 {
+                    return None;
+                }
+                let (discriminator, mut payload) = data.split_at(8);
+                $(
+                    if discriminator == $name::DISCRIMINATOR {
+                        return $name::deserialize(&mut payload).ok().map(StarEvent::$name);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+star_events!(
+    HonoraryPositionInitialized,
+    PolicyInitialized,
+    DayOpened,
+    QuoteFeesClaimed,
+    InvestorPayoutPage,
+    CreatorPayoutDayClosed,
+    DistributionAborted,
+    InvestorPayout,
+    DailyCapApplied,
+    InvestorSkipped,
+    RecoveryProposed,
+    RecoveryExecuted,
+    RecoveryCancelled,
+    PolicyUpdateQueued,
+    PolicyUpdateApplied,
+    RoleGranted,
+    RoleRevoked,
+    StaleVaultWithdrawn,
+    BlacklistUpdated,
+    InvestorOptOutUpdated,
+    InvestorShareCompounded,
+    CompoundingModeSet,
+    CrankerAllowlistUpdated,
+    AccountsMigrated,
+    LookupTableSet,
+    CrankWindowSet,
+    PageCooldownSet,
+    RemainderPolicySet,
+    RemainderDonated,
+    Y0Updated,
+    InvestorClassesSet,
+    InvestorClassSet,
+    TwalWeightingSet,
+    LocksRefreshed,
+    PayoutCurveSet,
+    MinInvestorShareSet,
+    FeesHarvested,
+    ReconciliationMismatch,
+    ReconciliationAbortSet,
+    InsuranceFunded,
+    InsuranceBpsSet,
+    ReferralFeePaid,
+    PageAttesterSet,
+    FreezeAuthorityRequirementSet,
+    TransferHookAllowlistUpdated,
+    PositionAdopted,
+    PositionMigrationQueued,
+    PositionMigrationCancelled,
+    PositionMigrated,
+    DustPolicySet,
+    PageSizeSet,
+    InvestorRegistered,
+    InvestorDeregistered,
+    RegistryModeSet,
+    CrankerPassSet,
+    CrankFeeVaultFunded,
+    CrankReimbursementSet,
+    CrankReimbursed,
+    DayForceClosed,
+    DaySkipped,
+    PolicyTemplateCreated,
+    PolicyTemplateApplied,
+    VaultMetadataSet,
+    LocksSnapshotted,
+    LockSnapshotRequirementSet,
+);
+
+/// Where in a transaction an event was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSource {
+    /// `emit!`: a `Program data:` log line of the program
+    Log,
+    /// `emit_cpi!`: a self-CPI inner instruction of the program
+    Cpi,
+}
+
+/// An event together with the transaction it was emitted in
+pub struct DecodedEvent {
+    pub slot: u64,
+    pub signature: Signature,
+    pub block_time: Option<i64>,
+    pub source: EventSource,
+    pub event: StarEvent,
+}
+
+/// Decode the `emit!` events found in a transaction's log messages
+pub fn decode_logs<S: AsRef<str>>(logs: &[S]) -> Vec<StarEvent> {
+    logs.iter()
+        .filter_map(|log| log.as_ref().strip_prefix(PROGRAM_DATA_PREFIX))
+        .filter_map(|encoded| STANDARD.decode(encoded).ok())
+        .filter_map(|data| StarEvent::decode(&data))
+        .collect()
+}
+
+/// Decode an `emit_cpi!` event from the data of a self-CPI inner instruction
+/// (`EVENT_IX_TAG_LE` followed by the event payload)
+pub fn decode_cpi_event(instruction_data: &[u8]) -> Option<StarEvent> {
+    instruction_data
+        .strip_prefix(&anchor_lang::event::EVENT_IX_TAG_LE[..])
+        .and_then(StarEvent::decode)
+}
+
+/// Decode the `emit!` events logged by this program only. The invocation stack is tracked
+/// from the runtime's `invoke`/`success`/`failed` lines, so `Program data:` lines written by
+/// other programs in the same transaction are skipped.
+pub fn decode_program_logs<S: AsRef<str>>(logs: &[S]) -> Vec<StarEvent> {
+    let program_id = star_fee_distributor::ID.to_string();
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(encoded) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if invocations.last() == Some(&program_id.as_str()) {
+                events.extend(STANDARD.decode(encoded).ok().and_then(|data| StarEvent::decode(&data)));
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(program), Some("invoke")) => invocations.push(program),
+                (Some(_), Some("success" | "failed:")) => {
+                    invocations.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
+
+/// Decode every event of a fetched transaction with its slot/signature context: first the
+/// `emit!` events from the program's log lines, then the `emit_cpi!` events from its self-CPI
+/// inner instructions. The transaction must be fetched with a binary encoding
+/// (see `fetch_transaction_events`); JSON-parsed transactions decode to no events.
+pub fn decode_transaction(transaction: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<DecodedEvent> {
+    let (Some(decoded), Some(meta)) = (
+        transaction.transaction.transaction.decode(),
+        transaction.transaction.meta.as_ref(),
+    ) else {
+        return Vec::new();
+    };
+
+    let signature = decoded.signatures.first().copied().unwrap_or_default();
+    let with_context = |source, event| DecodedEvent {
+        slot: transaction.slot,
+        signature,
+        block_time: transaction.block_time,
+        source,
+        event,
+    };
+
+    let logs: Option<&Vec<String>> = meta.log_messages.as_ref().into();
+    let mut events: Vec<DecodedEvent> = logs
+        .map(|logs| decode_program_logs(logs.as_slice()))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|event| with_context(EventSource::Log, event))
+        .collect();
+
+    let account_keys = transaction_account_keys(&decoded, meta);
+    let inner_instructions: Option<&Vec<UiInnerInstructions>> = meta.inner_instructions.as_ref().into();
+    for instruction in inner_instructions.into_iter().flatten().flat_map(|inner| &inner.instructions) {
+        let UiInstruction::Compiled(compiled) = instruction else {
+            continue;
+        };
+        if account_keys.get(compiled.program_id_index as usize) != Some(&star_fee_distributor::ID) {
+            continue;
+        }
+        let Ok(data) = bs58::decode(&compiled.data).into_vec() else {
+            continue;
+        };
+        if let Some(event) = decode_cpi_event(&data) {
+            events.push(with_context(EventSource::Cpi, event));
+        }
+    }
+
+    events
+}
+
+/// Fetch a confirmed transaction and decode its events
+pub fn fetch_transaction_events(rpc: &RpcClient, signature: &Signature) -> Result<Vec<DecodedEvent>> {
+    let transaction = rpc.get_transaction_with_config(
+        signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+
+    Ok(decode_transaction(&transaction))
+}
+
+/// Static account keys followed by the addresses loaded from lookup tables
+/// (writable, then readonly), matching the runtime's account indexing
+fn transaction_account_keys(transaction: &VersionedTransaction, meta: &UiTransactionStatusMeta) -> Vec<Pubkey> {
+    let mut keys = transaction.message.static_account_keys().to_vec();
+    let loaded: Option<&UiLoadedAddresses> = meta.loaded_addresses.as_ref().into();
+    if let Some(loaded) = loaded {
+        keys.extend(
+            loaded.writable.iter()
+                .chain(&loaded.readonly)
+                .map(|address| address.parse().unwrap_or_default()),
+        );
+    }
+    keys
+}
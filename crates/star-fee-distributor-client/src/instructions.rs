@@ -0,0 +1,482 @@
+//! Typed instruction builders
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use star_fee_distributor::state::{
+    CrankArgsV1, DayClassTotal, DurationMultiplier, FeeShareDecay, InitializeArgsV1, InvestorAccount, PolicyEpoch, Referral,
+    VaultInitParams, CP_AMM_PROGRAM_ID, STREAMFLOW_PROGRAM_ID,
+};
+
+use crate::pda::{derive_event_authority_pda, derive_global_config_pda, VaultPdas};
+
+/// SPL Token program ID
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    anchor_lang::prelude::pubkey!("TokenkegQfeZyiNwAjsQTTQwGXLS3n4yjAWc7zDJN6Vcd");
+
+/// Token-2022 program ID
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    anchor_lang::prelude::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// SPL Memo program ID
+pub const MEMO_PROGRAM_ID: Pubkey =
+    anchor_lang::prelude::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Arguments of `init_policy`
+#[derive(Debug, Clone, Default)]
+pub struct InitializeParams {
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub fee_share_decay: Option<FeeShareDecay>,
+    pub duration_multipliers: Vec<DurationMultiplier>,
+    pub epochs: Vec<PolicyEpoch>,
+    pub referral: Option<Referral>,
+}
+
+/// Accounts of `init_policy` and `init_position` that are not PDAs
+#[derive(Debug, Clone, Copy)]
+pub struct InitializeAccounts {
+    pub payer: Pubkey,
+    pub authority: Pubkey,
+    pub vault: Pubkey,
+    pub cp_amm_pool: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_mint: Pubkey,
+    /// Token program owning the quote mint (SPL Token or Token-2022)
+    pub quote_token_program: Pubkey,
+}
+
+pub fn init_policy(accounts: &InitializeAccounts, params: InitializeParams) -> Instruction {
+    let pdas = VaultPdas::new(&accounts.vault);
+
+    let metas = star_fee_distributor::accounts::InitPolicy {
+        payer: accounts.payer,
+        authority: accounts.authority,
+        vault: accounts.vault,
+        cp_amm_pool: accounts.cp_amm_pool,
+        quote_mint: accounts.quote_mint,
+        base_mint: accounts.base_mint,
+        policy: pdas.policy,
+        progress: pdas.progress,
+        position_owner_pda: pdas.position_owner,
+        program_treasury: pdas.treasury(&accounts.quote_mint),
+        global_config: derive_global_config_pda().0,
+        system_program: system_program::ID,
+        token_program: accounts.quote_token_program,
+    }
+    .to_account_metas(None);
+
+    let data = star_fee_distributor::instruction::InitPolicy {
+        args: InitializeArgsV1::new(VaultInitParams {
+            investor_fee_share_bps: params.investor_fee_share_bps,
+            daily_cap: params.daily_cap,
+            min_payout_lamports: params.min_payout_lamports,
+            y0: params.y0,
+            fee_share_decay: params.fee_share_decay,
+            duration_multipliers: params.duration_multipliers,
+            epochs: params.epochs,
+            referral: params.referral,
+        }),
+    }
+    .data();
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data,
+    }
+}
+
+/// `position` is the CP-AMM position account, opened over `[tick_lower, tick_upper]`
+/// (entirely above the pool price), and `position_nft_account` the position owner PDA's
+/// account of its NFT; the authority must sign
+pub fn init_position(
+    accounts: &InitializeAccounts,
+    position: &Pubkey,
+    position_nft_account: &Pubkey,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Instruction {
+    let pdas = VaultPdas::new(&accounts.vault);
+
+    let metas = star_fee_distributor::accounts::InitPosition {
+        authority: accounts.authority,
+        payer: accounts.payer,
+        vault: accounts.vault,
+        policy: pdas.policy,
+        cp_amm_pool: accounts.cp_amm_pool,
+        quote_mint: accounts.quote_mint,
+        base_mint: accounts.base_mint,
+        position_owner_pda: pdas.position_owner,
+        position: *position,
+        position_nft_account: *position_nft_account,
+        cp_amm_program: CP_AMM_PROGRAM_ID,
+        system_program: system_program::ID,
+        token_program: TOKEN_PROGRAM_ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data: star_fee_distributor::instruction::InitPosition {
+            tick_lower,
+            tick_upper,
+        }
+        .data(),
+    }
+}
+
+/// Existing position handed over by `adopt_position`; DAMM v2 position NFTs, and so both
+/// NFT token accounts, are Token-2022
+#[derive(Debug, Clone, Copy)]
+pub struct AdoptPositionAccounts {
+    /// Current holder of the position NFT; must sign
+    pub position_holder: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    /// Holder's token account holding the position NFT
+    pub holder_nft_account: Pubkey,
+    /// Position owner PDA's token account receiving the position NFT
+    pub custody_nft_account: Pubkey,
+}
+
+/// Adopt an existing CP-AMM position instead of `init_position`; the authority and the
+/// position holder must sign (`accounts.payer` is unused)
+pub fn adopt_position(accounts: &InitializeAccounts, adopted: &AdoptPositionAccounts) -> Instruction {
+    let pdas = VaultPdas::new(&accounts.vault);
+
+    let metas = star_fee_distributor::accounts::AdoptPosition {
+        authority: accounts.authority,
+        position_holder: adopted.position_holder,
+        vault: accounts.vault,
+        policy: pdas.policy,
+        cp_amm_pool: accounts.cp_amm_pool,
+        quote_mint: accounts.quote_mint,
+        base_mint: accounts.base_mint,
+        position_owner_pda: pdas.position_owner,
+        position: adopted.position,
+        position_nft_mint: adopted.position_nft_mint,
+        holder_nft_account: adopted.holder_nft_account,
+        custody_nft_account: adopted.custody_nft_account,
+        token_program: TOKEN_2022_PROGRAM_ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data: star_fee_distributor::instruction::AdoptPosition {}.data(),
+    }
+}
+
+/// Open the next distribution day; `caller` must be the policy authority or operator
+pub fn open_day(
+    caller: &Pubkey,
+    vault: &Pubkey,
+    investor_root: [u8; 32],
+    total_investors: u64,
+    total_pages: u64,
+    class_totals: Vec<DayClassTotal>,
+    lock_ts: i64,
+) -> Instruction {
+    let pdas = VaultPdas::new(vault);
+
+    let metas = star_fee_distributor::accounts::OpenDay {
+        caller: *caller,
+        vault: *vault,
+        policy: pdas.policy,
+        progress: pdas.progress,
+    }
+    .to_account_metas(None);
+
+    let data = star_fee_distributor::instruction::OpenDay {
+        investor_root,
+        total_investors,
+        total_pages,
+        class_totals,
+        lock_ts,
+    }
+    .data();
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data,
+    }
+}
+
+/// Create the record of `stream`, which must vest token A of the policy's `cp_amm_pool`
+pub fn init_investor_record(payer: &Pubkey, vault: &Pubkey, cp_amm_pool: &Pubkey, stream: &Pubkey) -> Instruction {
+    let pdas = VaultPdas::new(vault);
+
+    let metas = star_fee_distributor::accounts::InitInvestorRecord {
+        payer: *payer,
+        vault: *vault,
+        policy: pdas.policy,
+        cp_amm_pool: *cp_amm_pool,
+        stream: *stream,
+        investor_record: pdas.investor_record(stream),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data: star_fee_distributor::instruction::InitInvestorRecord {}.data(),
+    }
+}
+
+/// Grow an investor record created before the current layout; anyone may pay
+pub fn migrate_investor_record(payer: &Pubkey, vault: &Pubkey, stream: &Pubkey) -> Instruction {
+    let pdas = VaultPdas::new(vault);
+
+    let metas = star_fee_distributor::accounts::MigrateInvestorRecord {
+        payer: *payer,
+        vault: *vault,
+        stream: *stream,
+        investor_record: pdas.investor_record(stream),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data: star_fee_distributor::instruction::MigrateInvestorRecord {}.data(),
+    }
+}
+
+/// Sample the current locks of `streams` for time-weighted weighting and, between days,
+/// snapshot them for the next day; anyone may send it
+pub fn refresh_locks(vault: &Pubkey, streams: &[Pubkey]) -> Instruction {
+    let pdas = VaultPdas::new(vault);
+
+    let mut metas = star_fee_distributor::accounts::RefreshLocks {
+        vault: *vault,
+        policy: pdas.policy,
+        progress: pdas.progress,
+    }
+    .to_account_metas(None);
+    for stream in streams {
+        metas.push(AccountMeta::new_readonly(*stream, false));
+        metas.push(AccountMeta::new(pdas.investor_record(stream), false));
+    }
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data: star_fee_distributor::instruction::RefreshLocks {}.data(),
+    }
+}
+
+/// Claim the position's quote fees into the treasury between distribution days; anyone may
+/// send it. `quote_token_program` owns the quote mint.
+pub fn harvest_fees(
+    caller: &Pubkey,
+    vault: &Pubkey,
+    quote_mint: &Pubkey,
+    quote_token_program: &Pubkey,
+    cp_amm_pool: &Pubkey,
+) -> Instruction {
+    let pdas = VaultPdas::new(vault);
+
+    let metas = star_fee_distributor::accounts::HarvestFees {
+        caller: *caller,
+        vault: *vault,
+        policy: pdas.policy,
+        progress: pdas.progress,
+        position_owner_pda: pdas.position_owner,
+        program_treasury: pdas.treasury(quote_mint),
+        quote_mint: (*quote_token_program == TOKEN_2022_PROGRAM_ID).then_some(*quote_mint),
+        cp_amm_program: CP_AMM_PROGRAM_ID,
+        cp_amm_pool: *cp_amm_pool,
+        token_program: *quote_token_program,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data: star_fee_distributor::instruction::HarvestFees {}.data(),
+    }
+}
+
+/// Register `lookup_table` on the vault's policy; the operator must sign
+pub fn set_lookup_table(operator: &Pubkey, vault: &Pubkey, lookup_table: &Pubkey) -> Instruction {
+    let metas = star_fee_distributor::accounts::SetLookupTable {
+        operator: *operator,
+        vault: *vault,
+        policy: VaultPdas::new(vault).policy,
+    }
+    .to_account_metas(None);
+
+    let data = star_fee_distributor::instruction::SetLookupTable {
+        lookup_table: *lookup_table,
+    }
+    .data();
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data,
+    }
+}
+
+/// Accounts of `crank_distribute` that are not PDAs
+#[derive(Debug, Clone, Copy)]
+pub struct CrankAccounts {
+    pub crank_caller: Pubkey,
+    pub vault: Pubkey,
+    pub program_treasury: Pubkey,
+    pub creator_quote_ata: Pubkey,
+    pub cp_amm_pool: Pubkey,
+    /// Honorary position registered on the policy
+    pub honorary_position: Pubkey,
+    /// Switchboard function, when cranking as its enclave signer
+    pub switchboard_function: Option<Pubkey>,
+    /// Caller's token account holding the vault's cranker pass
+    pub cranker_pass: Option<Pubkey>,
+    /// Label the page's payouts with a `{vault, day, page}` memo
+    pub memo: bool,
+    /// Quote mint, required on the final page when the policy burns the remainder, and on
+    /// every page of a Token-2022 treasury
+    pub quote_mint: Option<Pubkey>,
+    /// Token program owning the quote mint (SPL Token or Token-2022)
+    pub token_program: Pubkey,
+    /// Insurance fund, required while the policy sets `insurance_bps`
+    pub insurance_fund: Option<Pubkey>,
+    /// Referrer's quote ATA, required on the final page while the policy has a referral
+    pub referrer_quote_ata: Option<Pubkey>,
+    /// Second keeper co-signing the page, required while the policy sets `page_attester`
+    pub page_attester: Option<Pubkey>,
+    /// Crank fee vault, required while the policy reimburses crank callers
+    pub crank_fee_vault: Option<Pubkey>,
+}
+
+/// One investor of a crank page
+#[derive(Debug, Clone)]
+pub struct PageInvestor {
+    pub account: InvestorAccount,
+    /// Merkle proof against the root committed by `open_day`
+    pub proof: Vec<[u8; 32]>,
+    /// Bonfida schedules only: the destination token account of the schedule
+    pub bonfida_destination: Option<Pubkey>,
+}
+
+/// Remaining accounts of a crank page: (vesting account, investor quote ATA,
+/// investor record) per investor, plus the Bonfida destination when present. These
+/// identify the page's investors; the instruction data only carries their proofs.
+pub fn crank_remaining_accounts(vault: &Pubkey, investors: &[PageInvestor]) -> Vec<AccountMeta> {
+    let pdas = VaultPdas::new(vault);
+    let mut metas = Vec::with_capacity(investors.len() * 3);
+
+    for investor in investors {
+        metas.push(AccountMeta::new_readonly(investor.account.stream_pubkey, false));
+        metas.push(AccountMeta::new(investor.account.investor_quote_ata, false));
+        metas.push(AccountMeta::new(
+            pdas.investor_record(&investor.account.stream_pubkey),
+            false,
+        ));
+        if let Some(destination) = investor.bonfida_destination {
+            metas.push(AccountMeta::new_readonly(destination, false));
+        }
+    }
+
+    metas
+}
+
+/// Named accounts shared by `crank_distribute` and `crank_from_registry`
+fn crank_account_metas(accounts: &CrankAccounts) -> Vec<AccountMeta> {
+    let pdas = VaultPdas::new(&accounts.vault);
+
+    star_fee_distributor::accounts::CrankDistribute {
+        crank_caller: accounts.crank_caller,
+        vault: accounts.vault,
+        position_owner_pda: pdas.position_owner,
+        program_treasury: accounts.program_treasury,
+        creator_quote_ata: accounts.creator_quote_ata,
+        policy: pdas.policy,
+        progress: pdas.progress,
+        blacklist: pdas.blacklist,
+        cranker_allowlist: pdas.cranker_allowlist,
+        switchboard_function: accounts.switchboard_function,
+        cranker_pass: accounts.cranker_pass,
+        memo_program: accounts.memo.then_some(MEMO_PROGRAM_ID),
+        quote_mint: accounts.quote_mint,
+        insurance_fund: accounts.insurance_fund,
+        referrer_quote_ata: accounts.referrer_quote_ata,
+        page_attester: accounts.page_attester,
+        crank_fee_vault: accounts.crank_fee_vault,
+        cp_amm_program: CP_AMM_PROGRAM_ID,
+        cp_amm_pool: accounts.cp_amm_pool,
+        honorary_position: accounts.honorary_position,
+        streamflow_program: STREAMFLOW_PROGRAM_ID,
+        token_program: accounts.token_program,
+        system_program: system_program::ID,
+        event_authority: derive_event_authority_pda().0,
+        program: star_fee_distributor::ID,
+    }
+    .to_account_metas(None)
+}
+
+pub fn crank_distribute(
+    accounts: &CrankAccounts,
+    page: u64,
+    investors: &[PageInvestor],
+) -> Instruction {
+    let mut metas = crank_account_metas(accounts);
+    metas.extend(crank_remaining_accounts(&accounts.vault, investors));
+
+    let data = star_fee_distributor::instruction::CrankDistribute {
+        args: CrankArgsV1::new(page, investors.iter().map(|investor| investor.proof.clone()).collect()),
+    }
+    .data();
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data,
+    }
+}
+
+/// `crank_from_registry` for chunk `chunk_index`. `investors` are the chunk's entries
+/// sorted by stream pubkey; their proofs are ignored.
+pub fn crank_from_registry(
+    accounts: &CrankAccounts,
+    chunk_index: u32,
+    investors: &[PageInvestor],
+) -> Instruction {
+    let pdas = VaultPdas::new(&accounts.vault);
+
+    let mut metas = crank_account_metas(accounts);
+    metas.push(AccountMeta::new_readonly(pdas.investor_registry, false));
+    metas.push(AccountMeta::new_readonly(pdas.registry_chunk(chunk_index), false));
+    metas.extend(crank_remaining_accounts(&accounts.vault, investors));
+
+    let data = star_fee_distributor::instruction::CrankFromRegistry { chunk_index }.data();
+
+    Instruction {
+        program_id: star_fee_distributor::ID,
+        accounts: metas,
+        data,
+    }
+}
+
+/// `crank_distribute` for a quote mint with a transfer hook: `hook_accounts` (the hook
+/// program, its extra account metas PDA and the accounts those metas list) follow the
+/// investor accounts, and `accounts.quote_mint` must be set
+pub fn crank_distribute_with_transfer_hook(
+    accounts: &CrankAccounts,
+    page: u64,
+    investors: &[PageInvestor],
+    hook_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut instruction = crank_distribute(accounts, page, investors);
+    instruction.accounts.extend_from_slice(hook_accounts);
+    instruction
+}
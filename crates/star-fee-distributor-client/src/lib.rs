@@ -0,0 +1,19 @@
+//! Rust client for the Star Fee Distributor program
+//!
+//! Typed instruction builders, PDA helpers, account fetching and event decoding,
+//! so keepers and integrators do not hand-roll account metas.
+
+pub mod accounts;
+pub mod args;
+pub mod calculator;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod lookup_table;
+pub mod merkle;
+pub mod pda;
+pub mod planner;
+
+pub use error::ClientError;
+pub use star_fee_distributor::ID as PROGRAM_ID;
+pub use star_fee_distributor::state::{InvestorAccount, Policy, Progress};
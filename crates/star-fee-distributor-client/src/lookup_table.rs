@@ -0,0 +1,111 @@
+//! Address lookup table management
+//!
+//! Large crank pages only fit in v0 transactions that load the vault's static
+//! accounts and the investors' accounts from address lookup tables. These helpers
+//! collect the addresses a day needs, create and extend tables to hold them, and
+//! compile v0 messages against the fetched tables.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::address_lookup_table::instruction::{create_lookup_table, extend_lookup_table};
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, Message, VersionedMessage};
+
+use crate::error::{ClientError, Result};
+use crate::instructions::{crank_distribute, CrankAccounts, PageInvestor};
+use crate::planner::LOOKUP_TABLE_MAX_ADDRESSES;
+
+/// Addresses appended per `extend_lookup_table` instruction, keeping the
+/// transaction under the size limit
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 30;
+
+/// Every non-signer account of a day's crank transactions except the invoked program:
+/// the vault's static accounts first, then each investor's vesting account, quote ATA,
+/// investor record (and Bonfida destination), without duplicates
+pub fn crank_lookup_addresses(accounts: &CrankAccounts, investors: &[PageInvestor]) -> Vec<Pubkey> {
+    let mut addresses: Vec<Pubkey> = Vec::new();
+
+    for meta in crank_distribute(accounts, 1, investors).accounts {
+        if !meta.is_signer
+            && meta.pubkey != star_fee_distributor::ID
+            && !addresses.contains(&meta.pubkey)
+        {
+            addresses.push(meta.pubkey);
+        }
+    }
+
+    addresses
+}
+
+/// `create_lookup_table` instruction and the new table's address; `recent_slot` must
+/// be a recent finalized slot
+pub fn create_table(authority: &Pubkey, payer: &Pubkey, recent_slot: u64) -> (Instruction, Pubkey) {
+    create_lookup_table(*authority, *payer, recent_slot)
+}
+
+/// `extend_lookup_table` instructions appending `addresses` to `table`, one per
+/// `MAX_ADDRESSES_PER_EXTEND` addresses; send each in its own transaction
+pub fn extend_table(
+    table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| extend_lookup_table(*table, *authority, Some(*payer), chunk.to_vec()))
+        .collect()
+}
+
+/// Addresses of `wanted` not yet held by any of `tables`, in order
+pub fn missing_addresses(tables: &[AddressLookupTableAccount], wanted: &[Pubkey]) -> Vec<Pubkey> {
+    wanted
+        .iter()
+        .filter(|address| !tables.iter().any(|table| table.addresses.contains(address)))
+        .copied()
+        .collect()
+}
+
+/// Free slots left in `table`
+pub fn remaining_capacity(table: &AddressLookupTableAccount) -> usize {
+    LOOKUP_TABLE_MAX_ADDRESSES.saturating_sub(table.addresses.len())
+}
+
+/// Fetch and decode a lookup table
+pub fn fetch_lookup_table(rpc: &RpcClient, address: &Pubkey) -> Result<AddressLookupTableAccount> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|_| ClientError::InvalidLookupTable(*address))?;
+
+    Ok(AddressLookupTableAccount {
+        key: *address,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// Compile `instructions` into a v0 message loading accounts from `tables`, or a
+/// legacy message when `tables` is empty
+pub fn compile_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedMessage> {
+    if tables.is_empty() {
+        return Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(payer),
+            &blockhash,
+        )));
+    }
+
+    let message = v0::Message::try_compile(payer, instructions, tables, blockhash)
+        .map_err(|err| ClientError::Compile(err.to_string()))?;
+    Ok(VersionedMessage::V0(message))
+}
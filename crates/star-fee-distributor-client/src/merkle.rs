@@ -0,0 +1,61 @@
+//! Merkle tree over a day's (stream, investor quote ATA) set, as committed by `open_day`
+
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::utils::MerkleUtils;
+
+/// Tree built bottom-up with sorted-pair hashing; an unpaired node is promoted as is
+pub struct InvestorMerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl InvestorMerkleTree {
+    /// Build the tree over `(stream_pubkey, investor_quote_ata)` entries in crank order
+    pub fn new(entries: &[(Pubkey, Pubkey)]) -> Self {
+        let leaves: Vec<[u8; 32]> = entries
+            .iter()
+            .map(|(stream, ata)| MerkleUtils::investor_leaf(stream, ata))
+            .collect();
+
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => MerkleUtils::hash_pair(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    /// Root to commit with `open_day` (all zeroes for an empty set)
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or([0u8; 32])
+    }
+
+    /// Proof for the entry at `index`
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        let mut index = index;
+
+        for level in &self.levels[..self.levels.len().saturating_sub(1)] {
+            let sibling = index ^ 1;
+            if let Some(node) = level.get(sibling) {
+                proof.push(*node);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+}
@@ -0,0 +1,71 @@
+//! PDA derivation helpers, mirroring `star_fee_distributor::state`
+
+use anchor_lang::prelude::Pubkey;
+
+pub use star_fee_distributor::state::{
+    derive_blacklist_pda, derive_crank_fee_vault_pda, derive_cranker_allowlist_pda,
+    derive_global_config_pda, derive_insurance_pda, derive_investor_fee_position_owner_pda,
+    derive_investor_record_pda, derive_investor_registry_pda, derive_policy_pda,
+    derive_policy_template_pda, derive_progress_pda, derive_recovery_pda, derive_registered_investor_pda,
+    derive_registry_chunk_pda, derive_treasury_pda,
+    derive_vault_metadata_pda,
+};
+
+/// Seed of Anchor's `#[event_cpi]` event authority PDA
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+pub fn derive_event_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &star_fee_distributor::ID)
+}
+
+/// All per-vault PDAs, derived once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VaultPdas {
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub progress: Pubkey,
+    pub position_owner: Pubkey,
+    pub blacklist: Pubkey,
+    pub cranker_allowlist: Pubkey,
+    pub recovery: Pubkey,
+    pub investor_registry: Pubkey,
+    pub crank_fee_vault: Pubkey,
+    pub vault_metadata: Pubkey,
+}
+
+impl VaultPdas {
+    pub fn new(vault: &Pubkey) -> Self {
+        Self {
+            vault: *vault,
+            policy: derive_policy_pda(vault).0,
+            progress: derive_progress_pda(vault).0,
+            position_owner: derive_investor_fee_position_owner_pda(vault).0,
+            blacklist: derive_blacklist_pda(vault).0,
+            cranker_allowlist: derive_cranker_allowlist_pda(vault).0,
+            recovery: derive_recovery_pda(vault).0,
+            investor_registry: derive_investor_registry_pda(vault).0,
+            crank_fee_vault: derive_crank_fee_vault_pda(vault).0,
+            vault_metadata: derive_vault_metadata_pda(vault).0,
+        }
+    }
+
+    pub fn treasury(&self, quote_mint: &Pubkey) -> Pubkey {
+        derive_treasury_pda(&self.vault, quote_mint).0
+    }
+
+    pub fn insurance(&self, quote_mint: &Pubkey) -> Pubkey {
+        derive_insurance_pda(&self.vault, quote_mint).0
+    }
+
+    pub fn investor_record(&self, stream_pubkey: &Pubkey) -> Pubkey {
+        derive_investor_record_pda(&self.vault, stream_pubkey).0
+    }
+
+    pub fn registry_chunk(&self, chunk_index: u32) -> Pubkey {
+        derive_registry_chunk_pda(&self.vault, chunk_index).0
+    }
+
+    pub fn registered_investor(&self, stream_pubkey: &Pubkey) -> Pubkey {
+        derive_registered_investor_pda(&self.vault, stream_pubkey).0
+    }
+}
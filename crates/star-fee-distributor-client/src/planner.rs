@@ -0,0 +1,368 @@
+//! Off-chain pagination planner
+//!
+//! Splits a day's investor set into crank pages that fit the transaction size and
+//! compute limits, in the exact order and shape `crank_distribute` expects: streams
+//! strictly increasing across the day, one Merkle proof per investor against the
+//! committed root, pages numbered from 1, and the day closed by page `total_pages`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, Message, VersionedMessage};
+use star_fee_distributor::state::{DayClassTotal, InvestorAccount, MAX_INVESTORS_PER_PAGE};
+use star_fee_distributor::utils::PaginationUtils;
+
+use crate::error::{ClientError, Result};
+use crate::instructions::{crank_distribute, open_day, CrankAccounts, PageInvestor};
+use crate::lookup_table::crank_lookup_addresses;
+use crate::merkle::InvestorMerkleTree;
+
+/// Maximum serialized transaction size (`PACKET_DATA_SIZE`)
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Maximum compute units a transaction may request
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Maximum addresses held by one address lookup table
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+
+/// An investor of the day's set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvestorEntry {
+    pub stream_pubkey: Pubkey,
+    pub investor_quote_ata: Pubkey,
+    /// Bonfida schedules only: the destination token account of the schedule
+    pub bonfida_destination: Option<Pubkey>,
+}
+
+/// Limits and cost model used to size pages
+#[derive(Debug, Clone, Copy)]
+pub struct PlanLimits {
+    pub max_transaction_size: usize,
+    pub max_compute_units: u32,
+    /// Compute units of a page independent of its investors (claim, checks, page event)
+    pub base_compute_units: u32,
+    /// Compute units per investor (vesting read, record update, transfer CPI)
+    pub compute_units_per_investor: u32,
+    /// Compute units per Merkle proof node hashed on-chain
+    pub compute_units_per_proof_node: u32,
+    /// Hard cap on investors per page, clamped to the on-chain `MAX_INVESTORS_PER_PAGE`
+    pub max_investors_per_page: Option<usize>,
+    /// The policy's fixed `page_size`, when set: pages are cut to exactly that size
+    /// instead of greedily, and a page that does not fit fails the plan
+    pub page_size: Option<u16>,
+    /// Whether crank transactions carry a `set_compute_unit_price` instruction
+    pub priority_fee: bool,
+    /// Whether v0 transactions with address lookup tables may be used
+    pub allow_lookup_tables: bool,
+}
+
+impl Default for PlanLimits {
+    fn default() -> Self {
+        Self {
+            max_transaction_size: MAX_TRANSACTION_SIZE,
+            max_compute_units: MAX_COMPUTE_UNITS,
+            base_compute_units: 60_000,
+            compute_units_per_investor: 25_000,
+            compute_units_per_proof_node: 300,
+            max_investors_per_page: None,
+            page_size: None,
+            priority_fee: true,
+            allow_lookup_tables: true,
+        }
+    }
+}
+
+/// One planned crank page
+#[derive(Debug, Clone)]
+pub struct PlannedPage {
+    /// 1-based page number passed to `crank_distribute`
+    pub page: u64,
+    pub investors: Vec<PageInvestor>,
+    /// Whether this page closes the day (`page == total_pages`)
+    pub is_final_page: bool,
+    /// Remaining accounts passed with the page
+    pub remaining_accounts: usize,
+    /// Serialized transaction size, compute budget instructions included
+    pub transaction_size: usize,
+    /// Estimated compute units, suitable for `set_compute_unit_limit`
+    pub compute_units: u32,
+}
+
+/// The day's page plan
+#[derive(Debug, Clone)]
+pub struct PagePlan {
+    /// Root to commit with `open_day`
+    pub investor_root: [u8; 32],
+    pub total_investors: u64,
+    pub total_pages: u64,
+    pub pages: Vec<PlannedPage>,
+    /// Address lookup table contents, one entry per table; empty for legacy transactions
+    pub lookup_tables: Vec<Vec<Pubkey>>,
+}
+
+impl PagePlan {
+    pub fn needs_lookup_tables(&self) -> bool {
+        !self.lookup_tables.is_empty()
+    }
+
+    /// `open_day` instruction committing this plan and the day's per-class locked and
+    /// weight totals (see `calculator::calculate_day_totals`), read at `lock_ts`
+    pub fn open_day_instruction(
+        &self,
+        caller: &Pubkey,
+        vault: &Pubkey,
+        class_totals: Vec<DayClassTotal>,
+        lock_ts: i64,
+    ) -> Instruction {
+        open_day(caller, vault, self.investor_root, self.total_investors, self.total_pages, class_totals, lock_ts)
+    }
+
+    /// `crank_distribute` instructions of every page, in order
+    pub fn crank_instructions(&self, accounts: &CrankAccounts) -> Vec<Instruction> {
+        self.pages
+            .iter()
+            .map(|page| crank_distribute(accounts, page.page, &page.investors))
+            .collect()
+    }
+}
+
+/// Plan the day's pages for `investors` (in any order)
+pub fn plan_pages(
+    accounts: &CrankAccounts,
+    investors: &[InvestorEntry],
+    limits: &PlanLimits,
+) -> Result<PagePlan> {
+    if investors.is_empty() {
+        return Err(ClientError::EmptyInvestorSet);
+    }
+
+    // The crank requires strictly increasing stream pubkeys across the whole day
+    let mut ordered = investors.to_vec();
+    ordered.sort_by_key(|investor| investor.stream_pubkey);
+    if let Some(pair) = ordered
+        .windows(2)
+        .find(|pair| pair[0].stream_pubkey == pair[1].stream_pubkey)
+    {
+        return Err(ClientError::DuplicateInvestor(pair[0].stream_pubkey));
+    }
+
+    let leaves: Vec<(Pubkey, Pubkey)> = ordered
+        .iter()
+        .map(|investor| (investor.stream_pubkey, investor.investor_quote_ata))
+        .collect();
+    let tree = InvestorMerkleTree::new(&leaves);
+    let page_investors: Vec<PageInvestor> = ordered
+        .iter()
+        .enumerate()
+        .map(|(index, investor)| PageInvestor {
+            account: InvestorAccount {
+                stream_pubkey: investor.stream_pubkey,
+                investor_quote_ata: investor.investor_quote_ata,
+                // Locked amounts and weights are recomputed on-chain
+                locked_amount: 0,
+                weight: 0,
+            },
+            proof: tree.proof(index),
+            bonfida_destination: investor.bonfida_destination,
+        })
+        .collect();
+
+    let legacy = partition(accounts, &page_investors, limits, &[]);
+    let (pages, lookup_tables) = if limits.allow_lookup_tables {
+        let lookup_tables = lookup_table_addresses(accounts, &page_investors);
+        match (legacy, partition(accounts, &page_investors, limits, &lookup_tables)) {
+            (Ok(legacy), Ok(with_tables)) if with_tables.len() < legacy.len() => {
+                (with_tables, lookup_tables)
+            }
+            (Ok(legacy), _) => (legacy, Vec::new()),
+            (Err(_), with_tables) => (with_tables?, lookup_tables),
+        }
+    } else {
+        (legacy?, Vec::new())
+    };
+
+    let total_pages = pages.len() as u64;
+    let pages = pages
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut page)| {
+            page.page = index as u64 + 1;
+            page.is_final_page = page.page == total_pages;
+            page
+        })
+        .collect();
+
+    Ok(PagePlan {
+        investor_root: tree.root(),
+        total_investors: ordered.len() as u64,
+        total_pages,
+        pages,
+        lookup_tables,
+    })
+}
+
+/// Greedily fill each page with the next investors while the page still fits; for a
+/// fixed order this yields the fewest pages
+fn partition(
+    accounts: &CrankAccounts,
+    investors: &[PageInvestor],
+    limits: &PlanLimits,
+    lookup_tables: &[Vec<Pubkey>],
+) -> Result<Vec<PlannedPage>> {
+    if let Some(page_size) = limits.page_size.filter(|size| *size > 0) {
+        return partition_fixed(accounts, investors, limits, lookup_tables, page_size as u64);
+    }
+
+    let max_per_page = limits
+        .max_investors_per_page
+        .unwrap_or(MAX_INVESTORS_PER_PAGE)
+        .clamp(1, MAX_INVESTORS_PER_PAGE);
+    let mut pages = Vec::new();
+    let mut start = 0;
+
+    while start < investors.len() {
+        let mut page = size_page(accounts, &investors[start..start + 1], limits, lookup_tables)?
+            .ok_or(ClientError::InvestorDoesNotFit(investors[start].account.stream_pubkey))?;
+        let mut end = start + 1;
+
+        while end < investors.len() && end - start < max_per_page {
+            match size_page(accounts, &investors[start..end + 1], limits, lookup_tables)? {
+                Some(larger) => {
+                    page = larger;
+                    end += 1;
+                }
+                None => break,
+            }
+        }
+
+        pages.push(page);
+        start = end;
+    }
+
+    Ok(pages)
+}
+
+/// Cut the pages the crank expects under a fixed policy page size, with the same page
+/// math as the program
+fn partition_fixed(
+    accounts: &CrankAccounts,
+    investors: &[PageInvestor],
+    limits: &PlanLimits,
+    lookup_tables: &[Vec<Pubkey>],
+    page_size: u64,
+) -> Result<Vec<PlannedPage>> {
+    let total = investors.len() as u64;
+    let mut pages = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let (start, _) = PaginationUtils::get_page_bounds(page, page_size);
+        let end = start + PaginationUtils::get_page_size(page, page_size, total);
+        let planned = size_page(accounts, &investors[start as usize..end as usize], limits, lookup_tables)?
+            .ok_or(ClientError::PageDoesNotFit(page))?;
+        pages.push(planned);
+
+        if PaginationUtils::is_last_page(page, page_size, total) {
+            return Ok(pages);
+        }
+        page += 1;
+    }
+}
+
+/// Size a candidate page, `None` when it exceeds a limit
+fn size_page(
+    accounts: &CrankAccounts,
+    investors: &[PageInvestor],
+    limits: &PlanLimits,
+    lookup_tables: &[Vec<Pubkey>],
+) -> Result<Option<PlannedPage>> {
+    let compute_units = investors.iter().fold(limits.base_compute_units, |units, investor| {
+        let proof_units = limits
+            .compute_units_per_proof_node
+            .saturating_mul(investor.proof.len() as u32);
+        units
+            .saturating_add(limits.compute_units_per_investor)
+            .saturating_add(proof_units)
+    });
+    if compute_units > limits.max_compute_units {
+        return Ok(None);
+    }
+
+    // Page number and final flag do not change the size; they are assigned afterwards
+    let crank_ix = crank_distribute(accounts, 1, investors);
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(compute_units)];
+    if limits.priority_fee {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(0));
+    }
+    instructions.push(crank_ix);
+
+    let transaction_size = transaction_size(&accounts.crank_caller, &instructions, lookup_tables)?;
+    if transaction_size > limits.max_transaction_size {
+        return Ok(None);
+    }
+
+    Ok(Some(PlannedPage {
+        page: 0,
+        investors: investors.to_vec(),
+        is_final_page: false,
+        remaining_accounts: investors
+            .iter()
+            .map(|investor| 3 + usize::from(investor.bonfida_destination.is_some()))
+            .sum(),
+        transaction_size,
+        compute_units,
+    }))
+}
+
+/// Exact wire size of a transaction signed by its required signers
+fn transaction_size(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[Vec<Pubkey>],
+) -> Result<usize> {
+    let (message, num_signatures) = if lookup_tables.is_empty() {
+        let message = Message::new(instructions, Some(payer));
+        let num_signatures = message.header.num_required_signatures;
+        (VersionedMessage::Legacy(message), num_signatures)
+    } else {
+        // Table keys do not affect the size; placeholders stand in for the real tables
+        let tables: Vec<AddressLookupTableAccount> = lookup_tables
+            .iter()
+            .enumerate()
+            .map(|(index, addresses)| AddressLookupTableAccount {
+                key: Pubkey::new_from_array([index as u8 + 1; 32]),
+                addresses: addresses.clone(),
+            })
+            .collect();
+        let message = v0::Message::try_compile(payer, instructions, &tables, Hash::default())
+            .map_err(|err| ClientError::Compile(err.to_string()))?;
+        let num_signatures = message.header.num_required_signatures;
+        (VersionedMessage::V0(message), num_signatures)
+    };
+
+    let message_size = bincode::serialized_size(&message)
+        .map_err(|err| ClientError::Compile(err.to_string()))? as usize;
+    let signatures_size = short_vec_len(num_signatures as usize) + 64 * num_signatures as usize;
+
+    Ok(signatures_size + message_size)
+}
+
+/// Addresses to load through lookup tables, split into tables of 256
+fn lookup_table_addresses(accounts: &CrankAccounts, investors: &[PageInvestor]) -> Vec<Vec<Pubkey>> {
+    crank_lookup_addresses(accounts, investors)
+        .chunks(LOOKUP_TABLE_MAX_ADDRESSES)
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
+
+/// Length of a compact-u16 prefix
+fn short_vec_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
@@ -0,0 +1,805 @@
+//! Event types, mirroring `star_fee_distributor::events`
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::pubkey::Pubkey;
+use crate::state::{
+    CrankWindow, DayClassTotal, DustPolicy, InvestorClass, PayoutCurve, RemainderPolicy, Role,
+    SkipRouting,
+};
+use crate::Discriminator;
+
+/// Emitted when an honorary LP position is successfully initialized
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct HonoraryPositionInitialized {
+    pub position: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool: Pubkey,
+    pub vault: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub timestamp: i64,
+    pub base_mint: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub treasury: Pubkey,
+}
+
+/// Emitted when a vault's policy, progress and treasury are initialized
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyInitialized {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a distribution day is opened with its committed investor set
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DayOpened {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub investor_root: [u8; 32],
+    pub total_investors: u64,
+    pub total_pages: u64,
+    pub timestamp: i64,
+    pub total_locked: u64,
+    pub class_totals: Vec<DayClassTotal>,
+    pub lock_ts: i64,
+}
+
+/// Emitted when quote fees are claimed from the honorary position
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QuoteFeesClaimed {
+    pub amount: u64,
+    pub position: Pubkey,
+    pub day: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted for each page of investor payouts during distribution
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorPayoutPage {
+    pub day: i64,
+    pub page: u64,
+    pub distributed: u64,
+    pub carry_over: u64,
+    pub investors_processed: u64,
+    pub locked_total: u64,
+    pub eligible_share_bps: u16,
+    pub timestamp: i64,
+    pub page_hash: [u8; 32],
+}
+
+/// Emitted when the final page of a day's distribution is completed
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorPayoutDayClosed {
+    pub day: i64,
+    pub remainder: u64,
+    pub total_distributed_to_investors: u64,
+    pub total_claimed: u64,
+    pub creator: Pubkey,
+    /// Policy parameters in effect for the day
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub timestamp: i64,
+    /// Remainder burned instead of paid under `RemainderPolicy::Burn`
+    pub burned: u64,
+}
+
+/// Emitted when distribution fails due to base fee detection
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DistributionAborted {
+    pub reason: String,
+    pub day: i64,
+    pub base_fee_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an investor receives a payout
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorPayout {
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
+    pub weight: u64,
+    pub day: i64,
+    pub page: u64,
+    pub timestamp: i64,
+    pub payout_id: [u8; 32],
+    /// Amount the investor received after any Token-2022 transfer fee; `amount` is gross
+    pub net_amount: u64,
+}
+
+/// Emitted when daily cap is applied to limit payouts
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DailyCapApplied {
+    pub day: i64,
+    pub requested_payout: u64,
+    pub capped_payout: u64,
+    pub cap_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Reason an investor was skipped during distribution
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The vesting stream was cancelled by its sender
+    StreamCancelled,
+    /// The vesting stream account was closed
+    StreamClosed,
+    /// Nothing is locked in the investor's stream
+    ZeroLocked,
+    /// The computed payout fell below `min_payout_lamports`
+    BelowMinPayout,
+    /// The beneficiary wallet is on the vault blacklist
+    Blacklisted,
+    /// The stream recipient opted out of fee income
+    OptedOut,
+    /// The investor's quote token account is frozen by the mint's freeze authority
+    AccountFrozen,
+}
+
+/// Emitted when an investor is skipped instead of paid
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorSkipped {
+    pub investor: Pubkey,
+    pub stream: Pubkey,
+    pub reason: SkipReason,
+    pub day: i64,
+    pub page: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the policy authority proposes a timelocked treasury recovery
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryProposed {
+    pub vault: Pubkey,
+    pub proposer: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+    pub source: Pubkey,
+}
+
+/// Emitted when a treasury recovery is executed after its timelock
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryExecuted {
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending treasury recovery is cancelled
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryCancelled {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the policy authority queues a parameter change
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyUpdateQueued {
+    pub vault: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the crank applies a queued parameter change
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyUpdateApplied {
+    pub vault: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin grants a role
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RoleGranted {
+    pub vault: Pubkey,
+    pub role: Role,
+    pub account: Pubkey,
+    pub previous: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin revokes a role
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RoleRevoked {
+    pub vault: Pubkey,
+    pub role: Role,
+    pub account: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator sweeps the treasury of a stale vault
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StaleVaultWithdrawn {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub last_activity_ts: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the policy authority adds or removes a blacklisted wallet
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BlacklistUpdated {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+    pub blacklisted: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a stream recipient opts out of or back into fee income
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorOptOutUpdated {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub opted_out: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a page's investor share is re-deposited as pool liquidity
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorShareCompounded {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub page: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin toggles auto-compounding
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompoundingModeSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the operator adds or removes an allowed cranker
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrankerAllowlistUpdated {
+    pub vault: Pubkey,
+    pub cranker: Pubkey,
+    pub allowed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's accounts are upgraded to the current layouts
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AccountsMigrated {
+    pub vault: Pubkey,
+    pub policy_from_version: u8,
+    pub progress_from_version: u8,
+    pub policy_version: u8,
+    pub progress_version: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the operator registers the vault's address lookup table
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LookupTableSet {
+    pub vault: Pubkey,
+    pub lookup_table: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets or clears the vault's crank window
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrankWindowSet {
+    pub vault: Pubkey,
+    pub crank_window: Option<CrankWindow>,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the minimum delay between crank pages
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PageCooldownSet {
+    pub vault: Pubkey,
+    pub page_cooldown_secs: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator changes what day close does with the remainder
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RemainderPolicySet {
+    pub vault: Pubkey,
+    pub remainder_policy: RemainderPolicy,
+    pub timestamp: i64,
+}
+
+/// Emitted when a day's remainder is rolled into the next day's investor pool
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RemainderDonated {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub amount: u64,
+    pub carry_over: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority corrects a misconfigured `y0`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Y0Updated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_y0: u64,
+    pub new_y0: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority replaces the policy's investor classes
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorClassesSet {
+    pub vault: Pubkey,
+    pub investor_classes: Vec<InvestorClass>,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority assigns an investor to a class
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorClassSet {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub class: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin switches between instant and time-weighted locked weighting
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TwalWeightingSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when investor locks are sampled for time-weighted weighting
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LocksRefreshed {
+    pub vault: Pubkey,
+    pub investors: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin changes the payout curve
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PayoutCurveSet {
+    pub vault: Pubkey,
+    pub payout_curve: PayoutCurve,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the guaranteed minimum investor share
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MinInvestorShareSet {
+    pub vault: Pubkey,
+    pub min_investor_share_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when quote fees are harvested into the treasury between distribution days
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeesHarvested {
+    pub vault: Pubkey,
+    pub caller: Pubkey,
+    pub amount: u64,
+    pub harvested_pending: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted at day close when the treasury holds less than the day's books require
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReconciliationMismatch {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub expected_balance: u64,
+    pub treasury_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets whether a reconciliation mismatch aborts day close
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReconciliationAbortSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a crank page moves part of its claim to the insurance fund
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InsuranceFunded {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub page: u64,
+    pub amount: u64,
+    pub lifetime_insured: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the insurance fund share of claimed fees
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InsuranceBpsSet {
+    pub vault: Pubkey,
+    pub insurance_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when day close pays the referrer its share of the day's claim
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReferralFeePaid {
+    pub vault: Pubkey,
+    pub referrer: Pubkey,
+    pub day: i64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the keeper that must co-sign crank pages
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PageAttesterSet {
+    pub vault: Pubkey,
+    pub page_attester: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin toggles the no-freeze-authority requirement for the quote mint
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FreezeAuthorityRequirementSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a transfer hook program is added to or removed from the global allowlist
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransferHookAllowlistUpdated {
+    pub hook_program: Pubkey,
+    pub allowed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when an existing CP-AMM position is adopted as the vault's honorary position
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PositionAdopted {
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub pool: Pubkey,
+    pub previous_holder: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a move of the honorary position to a new pool is queued
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PositionMigrationQueued {
+    pub vault: Pubkey,
+    pub new_pool: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued position migration is cancelled
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PositionMigrationCancelled {
+    pub vault: Pubkey,
+    pub new_pool: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the honorary position moves to a new pool
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PositionMigrated {
+    pub vault: Pubkey,
+    pub old_pool: Pubkey,
+    pub old_position: Pubkey,
+    pub new_pool: Pubkey,
+    pub new_position: Pubkey,
+    pub fees_claimed: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the handling of below-threshold payouts
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DustPolicySet {
+    pub vault: Pubkey,
+    pub dust_policy: DustPolicy,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the fixed crank page size
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PageSizeSet {
+    pub vault: Pubkey,
+    pub page_size: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when an investor is added to the on-chain registry
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorRegistered {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub investor_quote_ata: Pubkey,
+    pub chunk_index: u32,
+    pub registered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an investor is removed from the on-chain registry
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorDeregistered {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub chunk_index: u32,
+    pub deregistered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin switches the vault to or from registry-driven distribution
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RegistryModeSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator sets the cranker pass mint
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrankerPassSet {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator funds the crank fee vault
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrankFeeVaultFunded {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator sets the per-page crank reimbursement
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrankReimbursementSet {
+    pub vault: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a crank page reimburses its caller from the crank fee vault
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrankReimbursed {
+    pub vault: Pubkey,
+    pub cranker: Pubkey,
+    pub day: i64,
+    pub page: u64,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator or authority closes a day before its final committed page
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DayForceClosed {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub pages_processed: u64,
+    pub total_pages: u64,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority skips a distribution day
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DaySkipped {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub routing: SkipRouting,
+    pub amount: u64,
+    pub carry_over: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the global config admin creates a policy template
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyTemplateCreated {
+    pub template: Pubkey,
+    pub template_id: u32,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault is initialized from a policy template
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyTemplateApplied {
+    pub vault: Pubkey,
+    pub template: Pubkey,
+    pub template_id: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's metadata is created or replaced
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VaultMetadataSet {
+    pub vault: Pubkey,
+    pub name: String,
+    pub project_uri: String,
+    pub logo_uri: String,
+    pub creator_contact_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when refresh_locks caches investor locks for the next distribution day
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LocksSnapshotted {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub investors: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin toggles the lock snapshot requirement for crank pages
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LockSnapshotRequirementSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+impl_discriminator!(HonoraryPositionInitialized, [7, 212, 240, 190, 102, 41, 75, 41]);
+impl_discriminator!(PolicyInitialized, [102, 184, 59, 178, 235, 69, 251, 181]);
+impl_discriminator!(DayOpened, [61, 42, 29, 30, 72, 243, 186, 236]);
+impl_discriminator!(QuoteFeesClaimed, [127, 57, 20, 176, 215, 35, 90, 88]);
+impl_discriminator!(InvestorPayoutPage, [42, 53, 234, 101, 225, 240, 203, 121]);
+impl_discriminator!(CreatorPayoutDayClosed, [142, 22, 53, 87, 241, 61, 177, 45]);
+impl_discriminator!(DistributionAborted, [250, 124, 188, 164, 75, 28, 35, 127]);
+impl_discriminator!(InvestorPayout, [112, 200, 52, 36, 85, 197, 39, 136]);
+impl_discriminator!(DailyCapApplied, [142, 217, 74, 102, 184, 171, 50, 221]);
+impl_discriminator!(InvestorSkipped, [209, 176, 74, 139, 245, 244, 123, 221]);
+impl_discriminator!(RecoveryProposed, [144, 19, 211, 226, 22, 231, 82, 41]);
+impl_discriminator!(RecoveryExecuted, [161, 218, 6, 191, 85, 217, 12, 144]);
+impl_discriminator!(RecoveryCancelled, [191, 25, 236, 86, 25, 77, 117, 96]);
+impl_discriminator!(PolicyUpdateQueued, [183, 30, 78, 217, 3, 221, 214, 28]);
+impl_discriminator!(PolicyUpdateApplied, [101, 205, 254, 93, 38, 184, 214, 20]);
+impl_discriminator!(RoleGranted, [220, 183, 89, 228, 143, 63, 246, 58]);
+impl_discriminator!(RoleRevoked, [167, 183, 52, 229, 126, 206, 62, 61]);
+impl_discriminator!(StaleVaultWithdrawn, [94, 119, 27, 171, 12, 245, 234, 141]);
+impl_discriminator!(BlacklistUpdated, [246, 166, 44, 25, 56, 182, 121, 74]);
+impl_discriminator!(InvestorOptOutUpdated, [110, 61, 155, 89, 191, 44, 53, 150]);
+impl_discriminator!(InvestorShareCompounded, [191, 215, 164, 11, 229, 63, 40, 177]);
+impl_discriminator!(CompoundingModeSet, [7, 92, 29, 186, 1, 207, 86, 105]);
+impl_discriminator!(CrankerAllowlistUpdated, [160, 249, 176, 180, 241, 230, 243, 146]);
+impl_discriminator!(AccountsMigrated, [173, 233, 46, 214, 67, 130, 155, 176]);
+impl_discriminator!(LookupTableSet, [37, 0, 74, 11, 76, 159, 230, 206]);
+impl_discriminator!(CrankWindowSet, [20, 125, 105, 75, 206, 186, 180, 3]);
+impl_discriminator!(PageCooldownSet, [127, 69, 179, 135, 111, 95, 93, 90]);
+impl_discriminator!(RemainderPolicySet, [173, 59, 60, 16, 93, 57, 15, 236]);
+impl_discriminator!(RemainderDonated, [87, 141, 236, 27, 109, 41, 45, 77]);
+impl_discriminator!(Y0Updated, [117, 56, 135, 124, 250, 229, 176, 17]);
+impl_discriminator!(InvestorClassesSet, [29, 61, 173, 221, 132, 205, 166, 226]);
+impl_discriminator!(InvestorClassSet, [136, 223, 8, 67, 171, 249, 245, 67]);
+impl_discriminator!(TwalWeightingSet, [250, 189, 115, 101, 205, 30, 23, 93]);
+impl_discriminator!(LocksRefreshed, [75, 74, 131, 82, 107, 107, 193, 53]);
+impl_discriminator!(PayoutCurveSet, [167, 213, 10, 155, 80, 71, 84, 221]);
+impl_discriminator!(MinInvestorShareSet, [169, 84, 168, 45, 100, 14, 83, 95]);
+impl_discriminator!(FeesHarvested, [30, 236, 182, 190, 77, 254, 76, 10]);
+impl_discriminator!(ReconciliationMismatch, [152, 182, 81, 212, 194, 173, 248, 90]);
+impl_discriminator!(ReconciliationAbortSet, [173, 228, 24, 125, 179, 234, 110, 94]);
+impl_discriminator!(InsuranceFunded, [158, 72, 23, 80, 138, 56, 33, 206]);
+impl_discriminator!(InsuranceBpsSet, [53, 172, 157, 168, 107, 234, 19, 93]);
+impl_discriminator!(ReferralFeePaid, [202, 116, 225, 250, 115, 147, 10, 18]);
+impl_discriminator!(PageAttesterSet, [73, 125, 217, 127, 145, 176, 31, 111]);
+impl_discriminator!(FreezeAuthorityRequirementSet, [83, 88, 115, 44, 37, 205, 88, 169]);
+impl_discriminator!(TransferHookAllowlistUpdated, [213, 8, 74, 20, 16, 248, 177, 103]);
+impl_discriminator!(PositionAdopted, [175, 46, 200, 80, 86, 180, 140, 118]);
+impl_discriminator!(PositionMigrationQueued, [123, 70, 70, 62, 109, 218, 149, 111]);
+impl_discriminator!(PositionMigrationCancelled, [20, 189, 61, 30, 2, 177, 39, 76]);
+impl_discriminator!(PositionMigrated, [20, 48, 135, 253, 211, 168, 242, 198]);
+impl_discriminator!(DustPolicySet, [229, 33, 253, 177, 36, 43, 109, 154]);
+impl_discriminator!(PageSizeSet, [4, 188, 191, 240, 165, 123, 217, 224]);
+impl_discriminator!(InvestorRegistered, [184, 3, 160, 94, 174, 76, 24, 185]);
+impl_discriminator!(InvestorDeregistered, [227, 121, 33, 151, 104, 187, 63, 63]);
+impl_discriminator!(RegistryModeSet, [86, 210, 188, 218, 149, 251, 103, 243]);
+impl_discriminator!(CrankerPassSet, [222, 23, 181, 7, 214, 138, 111, 77]);
+impl_discriminator!(CrankFeeVaultFunded, [132, 195, 157, 31, 13, 44, 123, 72]);
+impl_discriminator!(CrankReimbursementSet, [223, 133, 209, 205, 226, 148, 99, 169]);
+impl_discriminator!(CrankReimbursed, [238, 206, 107, 93, 71, 254, 79, 42]);
+impl_discriminator!(DayForceClosed, [61, 178, 84, 109, 250, 73, 217, 13]);
+impl_discriminator!(DaySkipped, [169, 37, 165, 185, 79, 243, 191, 169]);
+impl_discriminator!(PolicyTemplateCreated, [74, 202, 59, 33, 248, 12, 79, 230]);
+impl_discriminator!(PolicyTemplateApplied, [175, 46, 198, 187, 35, 87, 165, 68]);
+impl_discriminator!(VaultMetadataSet, [191, 199, 173, 149, 246, 237, 254, 28]);
+impl_discriminator!(LocksSnapshotted, [19, 53, 17, 131, 54, 209, 10, 211]);
+impl_discriminator!(LockSnapshotRequirementSet, [119, 225, 161, 129, 196, 195, 228, 32]);
+
+decode_events!(
+    HonoraryPositionInitialized,
+    PolicyInitialized,
+    DayOpened,
+    QuoteFeesClaimed,
+    InvestorPayoutPage,
+    CreatorPayoutDayClosed,
+    DistributionAborted,
+    InvestorPayout,
+    DailyCapApplied,
+    InvestorSkipped,
+    RecoveryProposed,
+    RecoveryExecuted,
+    RecoveryCancelled,
+    PolicyUpdateQueued,
+    PolicyUpdateApplied,
+    RoleGranted,
+    RoleRevoked,
+    StaleVaultWithdrawn,
+    BlacklistUpdated,
+    InvestorOptOutUpdated,
+    InvestorShareCompounded,
+    CompoundingModeSet,
+    CrankerAllowlistUpdated,
+    AccountsMigrated,
+    LookupTableSet,
+    CrankWindowSet,
+    PageCooldownSet,
+    RemainderPolicySet,
+    RemainderDonated,
+    Y0Updated,
+    InvestorClassesSet,
+    InvestorClassSet,
+    TwalWeightingSet,
+    LocksRefreshed,
+    PayoutCurveSet,
+    MinInvestorShareSet,
+    FeesHarvested,
+    ReconciliationMismatch,
+    ReconciliationAbortSet,
+    InsuranceFunded,
+    InsuranceBpsSet,
+    ReferralFeePaid,
+    PageAttesterSet,
+    FreezeAuthorityRequirementSet,
+    TransferHookAllowlistUpdated,
+    PositionAdopted,
+    PositionMigrationQueued,
+    PositionMigrationCancelled,
+    PositionMigrated,
+    DustPolicySet,
+    PageSizeSet,
+    InvestorRegistered,
+    InvestorDeregistered,
+    RegistryModeSet,
+    CrankerPassSet,
+    CrankFeeVaultFunded,
+    CrankReimbursementSet,
+    CrankReimbursed,
+    DayForceClosed,
+    DaySkipped,
+    PolicyTemplateCreated,
+    PolicyTemplateApplied,
+    VaultMetadataSet,
+    LocksSnapshotted,
+    LockSnapshotRequirementSet,
+);
@@ -0,0 +1,101 @@
+//! Account and event types of the Star Fee Distributor program
+//!
+//! `no_std` (with `alloc`) and free of the Anchor/solana-program dependency tree, so
+//! frontends can decode `Policy`, `Progress`, `InvestorRecord` and events in wasm.
+//! Layouts mirror `star_fee_distributor::state` and `star_fee_distributor::events`
+//! and must be updated alongside them.
+
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use borsh::BorshDeserialize;
+
+/// 8-byte Anchor discriminator prefixing an account or event
+pub trait Discriminator {
+    const DISCRIMINATOR: [u8; 8];
+}
+
+macro_rules! impl_discriminator {
+    ($name:ident, $discriminator:expr) => {
+        impl Discriminator for $name {
+            const DISCRIMINATOR: [u8; 8] = $discriminator;
+        }
+    };
+}
+
+macro_rules! decode_events {
+    ($($name:ident),* $(,)?) => {
+        /// Any event emitted by the program
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum StarEvent {
+            $($name($name),)*
+        }
+
+        impl StarEvent {
+            /// Decode an event from its discriminator-prefixed payload
+            /// (the base64-decoded `Program data:` log, or `emit_cpi!` data after its tag)
+            pub fn decode(data: &[u8]) -> Result<Self, crate::DecodeError> {
+                let (discriminator, _) = crate::split_discriminator(data)?;
+                $(
+                    if discriminator == $name::DISCRIMINATOR {
+                        return crate::decode::<$name>(data).map(StarEvent::$name);
+                    }
+                )*
+                Err(crate::DecodeError::UnknownDiscriminator)
+            }
+        }
+    };
+}
+
+pub mod events;
+pub mod pubkey;
+pub mod state;
+
+pub use events::StarEvent;
+pub use pubkey::Pubkey;
+pub use state::{InvestorRecord, Policy, Progress};
+
+/// Failure decoding account or event data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// Data shorter than the discriminator
+    TooShort,
+    /// Discriminator does not match the expected type
+    UnknownDiscriminator,
+    /// Borsh payload is malformed
+    InvalidData,
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::TooShort => f.write_str("data shorter than the discriminator"),
+            DecodeError::UnknownDiscriminator => f.write_str("unknown discriminator"),
+            DecodeError::InvalidData => f.write_str("invalid data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+fn split_discriminator(data: &[u8]) -> Result<(&[u8], &[u8]), DecodeError> {
+    if data.len() < 8 {
+        return Err(DecodeError::TooShort);
+    }
+    Ok(data.split_at(8))
+}
+
+/// Decode account data or an event payload of type `T`, checking its discriminator.
+/// Trailing bytes (account space reserved for growing vectors) are ignored.
+pub fn decode<T: Discriminator + BorshDeserialize>(data: &[u8]) -> Result<T, DecodeError> {
+    let (discriminator, mut payload) = split_discriminator(data)?;
+    if discriminator != T::DISCRIMINATOR {
+        return Err(DecodeError::UnknownDiscriminator);
+    }
+    T::deserialize(&mut payload).map_err(|_| DecodeError::InvalidData)
+}
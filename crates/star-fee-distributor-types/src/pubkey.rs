@@ -0,0 +1,51 @@
+use alloc::string::String;
+use core::fmt;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// 32-byte account address, layout-compatible with `solana_program::pubkey::Pubkey`
+#[derive(
+    BorshSerialize, BorshDeserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    pub const fn new_from_array(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn to_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.0).into_string()
+    }
+
+    /// Parse a base58 address
+    pub fn from_base58(address: &str) -> Option<Self> {
+        let mut bytes = [0u8; 32];
+        match bs58::decode(address).onto(&mut bytes) {
+            Ok(32) => Some(Self(bytes)),
+            _ => None,
+        }
+    }
+}
+
+impl AsRef<[u8]> for Pubkey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_base58())
+    }
+}
+
+impl fmt::Debug for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_base58())
+    }
+}
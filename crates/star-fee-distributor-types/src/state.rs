@@ -0,0 +1,555 @@
+//! Account types, mirroring `star_fee_distributor::state`
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::pubkey::Pubkey;
+use crate::Discriminator;
+
+/// Policy configuration for fee distribution
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Policy {
+    /// Maximum basis points (0-10000) for investor fee share
+    pub investor_fee_share_bps: u16,
+    /// Daily maximum investor payout in lamports; 0 means uncapped
+    pub daily_cap: u64,
+    /// Minimum payout threshold in lamports (dust filter, see `dust_policy`); 0 pays
+    /// every nonzero share
+    pub min_payout_lamports: u64,
+    /// Total investor allocation minted at TGE (Y0)
+    pub y0: u64,
+    /// Quote mint for this vault
+    pub quote_mint: Pubkey,
+    /// Vault this policy belongs to
+    pub vault: Pubkey,
+    /// Timestamp when policy was created
+    pub created_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Optional schedule overriding `investor_fee_share_bps` with a time-decaying share
+    pub fee_share_decay: Option<FeeShareDecay>,
+    /// Weight multipliers by remaining lock time, ascending by `min_remaining_secs`
+    pub duration_multipliers: Vec<DurationMultiplier>,
+    /// Pre-committed parameter epochs, ascending by `start_ts`
+    pub epochs: Vec<PolicyEpoch>,
+    /// Admin for privileged instructions (keypair or multisig vault PDA)
+    pub authority: Pubkey,
+    /// Delay before a queued parameter change takes effect
+    pub update_delay_secs: i64,
+    /// Parameter change queued by `update_policy`, applied by the crank once effective
+    pub pending_update: Option<PendingPolicyUpdate>,
+    /// Operator role (pausing, cranker allowlist); `Pubkey::default()` when revoked
+    pub operator: Pubkey,
+    /// Creator role (remainder destination owner); `Pubkey::default()` leaves it unrestricted
+    pub creator: Pubkey,
+    /// Days without a crank after which the creator may sweep the treasury
+    pub stale_after_days: u64,
+    /// Re-deposit the investor share as pool liquidity instead of paying investors; no longer
+    /// supported, and the crank refuses vaults that still have it set
+    pub compound_investor_share: bool,
+    /// Formerly the treasury lending market and the automation thread; zeroed by
+    /// `migrate_accounts`
+    pub _reserved: [u8; 64],
+    /// Layout version, upgraded by `migrate_accounts`
+    pub version: u8,
+    /// Address lookup table holding the vault's crank accounts; `Pubkey::default()` when none
+    pub lookup_table: Pubkey,
+    /// Daily UTC window crank pages must land in; `None` allows cranking at any time
+    pub crank_window: Option<CrankWindow>,
+    /// Minimum seconds between consecutive crank pages of a day; 0 disables the cool-down
+    pub page_cooldown_secs: u32,
+    /// What day close does with the remainder
+    pub remainder_policy: RemainderPolicy,
+    /// Whether the one-time `update_y0` correction has been used
+    pub y0_corrected: bool,
+    /// Investor classes with their own Y0 and fee share; empty treats every investor as
+    /// one class under `y0` and the effective fee share
+    pub investor_classes: Vec<InvestorClass>,
+    /// Weigh investors by their time-weighted average locked amount since their last page,
+    /// sampled by `refresh_locks`, instead of the instant snapshot
+    pub twal_weighting: bool,
+    /// Curve applied to each investor's (weighted) locked amount before pro-rata weighting
+    pub payout_curve: PayoutCurve,
+    /// Minimum eligible investor share while any tokens remain locked (capped by the fee
+    /// share in effect); 0 disables the floor
+    pub min_investor_share_bps: u16,
+    /// Fail the final crank page instead of only emitting `ReconciliationMismatch` when the
+    /// treasury holds less than the day's books say it should
+    pub abort_on_reconciliation_mismatch: bool,
+    /// Share of each page's claimed fees moved to the insurance fund before the
+    /// investor/creator split; 0 disables the deduction
+    pub insurance_bps: u16,
+    /// Integrating platform paid a share of each day's claim at day close, out of the
+    /// creator remainder; `None` when the vault has no referrer
+    pub referral: Option<Referral>,
+    /// Second keeper that must co-sign every crank page, attesting to its investor data;
+    /// `Pubkey::default()` lets any permitted cranker submit pages alone
+    pub page_attester: Pubkey,
+    /// Refuse to open the honorary position when the quote mint has a freeze authority,
+    /// so no investor ATA can be frozen mid-distribution
+    pub require_no_freeze_authority: bool,
+    /// Honorary CP-AMM position, registered by `init_position` or `adopt_position`;
+    /// `Pubkey::default()` until then
+    pub position: Pubkey,
+    /// CP-AMM pool of the honorary position, recorded with it; the crank and harvests
+    /// must use this pool once set
+    pub pool: Pubkey,
+    /// Queued move of the honorary position to an upgraded pool
+    pub pending_position_migration: Option<PendingPositionMigration>,
+    /// Tick range of the honorary position given to `init_position`, entirely above the
+    /// pool price so it only accrues quote fees; both zero when not configured
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    /// Handling of payouts below `min_payout_lamports`
+    pub dust_policy: DustPolicy,
+    /// Investors per crank page; every page but the final one must hold exactly this
+    /// many. 0 leaves page sizes to the keeper (up to `MAX_INVESTORS_PER_PAGE`)
+    pub page_size: u16,
+    /// Distribute from the on-chain investor registry with `crank_from_registry`;
+    /// `crank_distribute` is rejected while set
+    pub registry_mode: bool,
+    /// Mint of the transferable cranker pass; `Pubkey::default()` when unset
+    pub cranker_pass_mint: Pubkey,
+    /// Lamports reimbursed to the caller of every crank page; 0 disables
+    pub crank_reimbursement_lamports: u64,
+    /// Crank pages only accept investors whose lock was snapshotted for the current day
+    pub require_lock_snapshot: bool,
+}
+
+impl_discriminator!(Policy, [222, 135, 7, 163, 235, 177, 33, 68]);
+
+/// Daily distribution progress tracking (zero-copy on-chain; `repr(C)` without padding
+/// matches the Borsh encoding)
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// Unix timestamp of last distribution day
+    pub last_distribution_ts: i64,
+    /// Total amount distributed today (in lamports)
+    pub distributed_today: u64,
+    /// Investor dust carried to the next page or day, held in the treasury
+    pub carry_over: u64,
+    /// Current pagination cursor for investor accounts
+    pub pagination_cursor: u64,
+    /// Current day being processed
+    pub current_day: i64,
+    /// Total amount claimed today from honorary position
+    pub claimed_today: u64,
+    /// Total investors in the day's set, committed at day open
+    pub total_investors: u64,
+    /// Total pages expected for the day, committed at day open
+    pub total_pages: u64,
+    /// Investors processed so far today
+    pub investors_processed: u64,
+    /// Total quote fees claimed over the vault's lifetime
+    pub lifetime_claimed: u64,
+    /// Total paid to investors over the vault's lifetime
+    pub lifetime_distributed_to_investors: u64,
+    /// Total remainder paid to the creator over the vault's lifetime
+    pub lifetime_paid_to_creator: u64,
+    /// Number of distribution days closed
+    pub days_processed: u64,
+    /// Timestamp of the last crank page processed
+    pub last_crank_ts: i64,
+    /// Investor share re-deposited as liquidity over the vault's lifetime
+    pub lifetime_compounded: u64,
+    /// Formerly the treasury lending books; zeroed by `migrate_accounts`
+    pub _reserved: [u64; 2],
+    /// Last stream pubkey processed today (stream pubkeys must strictly increase)
+    pub last_stream_pubkey: Pubkey,
+    /// Merkle root of the day's (stream, investor quote ATA) set, committed at day open
+    pub investor_root: [u8; 32],
+    /// Vault this progress belongs to
+    pub vault: Pubkey,
+    /// Whether distribution is complete for current day (0 or 1)
+    pub day_complete: u8,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Layout version, upgraded by `migrate_accounts`
+    pub version: u8,
+    /// Keeps the `repr(C)` layout free of implicit padding
+    pub _padding: [u8; 5],
+    /// Timestamp of the day's last crank page, for the page cool-down; 0 before the first page
+    pub last_page_ts: i64,
+    /// Phase of the distribution day: 0 idle, 1 claimed, 2 distributing, 3 closed
+    pub phase: u8,
+    /// Keeps the appended fields 8-byte aligned
+    pub _phase_padding: [u8; 7],
+    /// `carry_over` when the day was opened, reconciled against the day's flows at close
+    pub carry_over_at_open: u64,
+    /// Quote fees claimed by `harvest_fees` between days, folded into the next day's claim
+    pub harvested_pending: u64,
+    /// Quote fees moved to the insurance fund over the vault's lifetime
+    pub lifetime_insured: u64,
+    /// Total locked amount of the day's investor set, committed at day open
+    pub day_locked_total: u64,
+    /// Locked amount weighed by the day's pages so far
+    pub day_locked_processed: u64,
+    /// The day's investor pool per investor class, fixed by the first page's claim
+    pub day_class_pools: [u64; 4],
+    /// Eligible investor share of the day's claim, summed over classes
+    pub day_eligible_share_bps: u16,
+    pub _day_padding: [u8; 6],
+    /// Timestamp the day's locks are read at, committed at day open
+    pub day_lock_ts: i64,
+    /// Locked amount of each investor class, committed at day open
+    pub day_class_locked: [u64; 4],
+    /// Curved, multiplier-weighted total of each investor class, committed at day open
+    pub day_class_weights: [u64; 4],
+    /// Locked amount of each class weighed by the day's pages so far
+    pub day_class_locked_processed: [u64; 4],
+    /// Curved weight of each class weighed by the day's pages so far
+    pub day_class_weights_processed: [u64; 4],
+}
+
+impl_discriminator!(Progress, [125, 4, 195, 102, 134, 179, 253, 6]);
+
+/// Per-investor lifetime payout record, updated on every crank page
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorRecord {
+    /// Vault this record belongs to
+    pub vault: Pubkey,
+    /// Vesting stream this record tracks
+    pub stream_pubkey: Pubkey,
+    /// Cumulative quote tokens received
+    pub cumulative_received: u64,
+    /// Day of the last payout
+    pub last_payout_day: i64,
+    /// Payouts withheld for falling below the dust threshold
+    pub accrued_dust: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Recipient refused fee income; the crank withholds their share for the creator
+    pub opted_out: bool,
+    /// Index into `policy.investor_classes` (0 when the policy has no classes)
+    pub class: u8,
+    /// Start of the current time-weighted locked period (0 before the first sample)
+    pub twal_period_start: i64,
+    /// Timestamp of the latest lock sample
+    pub twal_last_ts: i64,
+    /// Locked amount at the latest sample, held until the next one
+    pub twal_last_locked: u64,
+    /// Sum of locked amount × seconds over the period up to `twal_last_ts`
+    pub twal_accumulated: u128,
+    /// Day of the cached lock snapshot (0 before the first snapshot)
+    pub snapshot_day: i64,
+    /// Locked amount cached by `refresh_locks`, used by that day's crank page
+    pub snapshot_locked: u64,
+    /// Timestamp the snapshot was taken at
+    pub snapshot_ts: i64,
+}
+
+impl_discriminator!(InvestorRecord, [170, 144, 39, 68, 178, 31, 194, 117]);
+
+/// Per-vault list of investor wallets excluded from distribution
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Blacklist {
+    /// Vault this blacklist belongs to
+    pub vault: Pubkey,
+    /// Excluded beneficiary wallets
+    pub wallets: Vec<Pubkey>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(Blacklist, [131, 9, 212, 250, 58, 186, 247, 3]);
+
+/// Per-vault header of the on-chain investor registry
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorRegistry {
+    /// Vault this registry belongs to
+    pub vault: Pubkey,
+    /// Number of chunks created so far
+    pub chunk_count: u32,
+    /// Investors registered across all chunks
+    pub total_investors: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(InvestorRegistry, [205, 137, 9, 185, 171, 210, 247, 123]);
+
+/// One chunk of the investor registry
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorRegistryChunk {
+    /// Vault this chunk belongs to
+    pub vault: Pubkey,
+    /// Index of the chunk within the registry
+    pub index: u32,
+    /// Registered investors
+    pub entries: Vec<RegistryEntry>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(InvestorRegistryChunk, [161, 25, 43, 35, 227, 133, 216, 15]);
+
+/// A registered investor: the vesting account and the quote ATA its payouts go to
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegistryEntry {
+    pub stream_pubkey: Pubkey,
+    pub investor_quote_ata: Pubkey,
+}
+
+/// Marks a stream as registered and records its chunk
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RegisteredInvestor {
+    /// Vault the stream is registered with
+    pub vault: Pubkey,
+    /// Vesting account registered
+    pub stream_pubkey: Pubkey,
+    /// Chunk holding the entry
+    pub chunk_index: u32,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(RegisteredInvestor, [38, 128, 176, 178, 218, 214, 198, 178]);
+
+/// Per-vault allowlist of identities permitted to run the crank
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrankerAllowlist {
+    /// Vault this allowlist belongs to
+    pub vault: Pubkey,
+    /// Whether the crank is restricted to the allowlist
+    pub enabled: bool,
+    /// Allowed cranker keys
+    pub crankers: Vec<Pubkey>,
+    /// Switchboard function whose enclave signer is an accepted cranker; `Pubkey::default()` when unset
+    pub switchboard_function: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(CrankerAllowlist, [205, 177, 145, 198, 129, 197, 19, 135]);
+
+/// Program-wide configuration shared by every vault
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GlobalConfig {
+    /// Manages the configuration; set to the program's upgrade authority at creation
+    pub admin: Pubkey,
+    /// Transfer hook programs accepted on Token-2022 quote mints
+    pub allowed_transfer_hooks: Vec<Pubkey>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(GlobalConfig, [149, 8, 156, 202, 160, 252, 176, 217]);
+
+/// Distribution parameters of one vault, as passed to `batch_initialize` and stored on
+/// policy templates
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VaultInitParams {
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub fee_share_decay: Option<FeeShareDecay>,
+    pub duration_multipliers: Vec<DurationMultiplier>,
+    pub epochs: Vec<PolicyEpoch>,
+    pub referral: Option<Referral>,
+}
+
+/// Reusable distribution parameters, created by the global config admin and cloned into
+/// new vaults by `initialize_from_template`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyTemplate {
+    /// Identifier the template PDA is derived from
+    pub template_id: u32,
+    /// Parameters every vault created from the template starts with
+    pub params: VaultInitParams,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(PolicyTemplate, [244, 51, 184, 166, 117, 30, 3, 126]);
+
+/// Optional human-readable description of a vault, for explorers and dashboards
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VaultMetadata {
+    /// Vault described
+    pub vault: Pubkey,
+    /// Display name, at most `MAX_VAULT_NAME_LEN` bytes
+    pub name: String,
+    /// Project website or metadata JSON, at most `MAX_VAULT_URI_LEN` bytes
+    pub project_uri: String,
+    /// Logo image, at most `MAX_VAULT_URI_LEN` bytes
+    pub logo_uri: String,
+    /// Hash of the creator's off-chain contact details; all zero when unset
+    pub creator_contact_hash: [u8; 32],
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl_discriminator!(VaultMetadata, [248, 177, 244, 93, 67, 19, 117, 57]);
+
+/// Pending timelocked recovery of treasury funds, one per vault
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryProposal {
+    /// Vault the recovery targets
+    pub vault: Pubkey,
+    /// Authority that proposed the recovery
+    pub proposer: Pubkey,
+    /// Account that funded the proposal rent and receives it back on close
+    pub payer: Pubkey,
+    /// Token account receiving the recovered funds
+    pub destination: Pubkey,
+    /// Amount of quote tokens to recover
+    pub amount: u64,
+    /// Timestamp of the proposal
+    pub proposed_at: i64,
+    /// Earliest timestamp the recovery can be executed
+    pub executable_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Token account the funds are recovered from: the treasury or the insurance fund
+    pub source: Pubkey,
+}
+
+impl_discriminator!(RecoveryProposal, [87, 221, 41, 148, 57, 150, 240, 111]);
+
+/// Roles that can be granted on a policy
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Parameter changes, recovery, and role management (stored as `authority`)
+    Admin,
+    /// Operational controls such as pausing and the cranker allowlist
+    Operator,
+    /// Owner of the remainder destination
+    Creator,
+}
+
+/// Destination of the day-close remainder
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemainderPolicy {
+    /// Transfer it to the creator's quote ATA
+    Creator,
+    /// Roll it into the next day's investor pool via the carry-over
+    DonateToInvestors,
+    /// Burn it from the treasury (for vaults whose quote token is the project's own)
+    Burn,
+}
+
+/// Where `skip_day` sends the fees of a skipped day
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipRouting {
+    /// Claim nothing; the fees stay in the position (and any harvested fees stay pending)
+    /// for the next day's first page
+    Hold,
+    /// Claim the fees and transfer them, with any harvested fees, to the creator's quote ATA
+    Creator,
+    /// Claim the fees and roll them, with any harvested fees, into the next day's
+    /// investor pool via the carry-over
+    RollOver,
+}
+
+/// What the crank does with an investor payout below `min_payout_lamports`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DustPolicy {
+    /// Keep it in the investor pool as carry-over, recorded as the investor's `accrued_dust`
+    Accumulate,
+    /// Release it to the day's remainder
+    Drop,
+    /// Pay it anyway, ignoring the threshold
+    Pay,
+}
+
+/// Shape of the pro-rata payout weighting
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayoutCurve {
+    /// Weight proportional to the locked amount
+    Linear,
+    /// Weight proportional to the square root of the locked amount (quadratic-funding
+    /// style), favoring smaller holders
+    Sqrt,
+}
+
+/// Linear schedule stepping the investor fee share from `start_bps` to `end_bps`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeShareDecay {
+    /// Investor share at (and before) `start_ts`
+    pub start_bps: u16,
+    /// Investor share once `duration` seconds have elapsed
+    pub end_bps: u16,
+    /// Unix timestamp the decay starts at
+    pub start_ts: i64,
+    /// Decay duration in seconds
+    pub duration: i64,
+}
+
+/// Investor class (e.g. seed, strategic) earning its own slice of the claimed fees
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvestorClass {
+    /// Total allocation of the class minted at TGE
+    pub y0: u64,
+    /// Maximum basis points of claimed fees paid to the class
+    pub investor_fee_share_bps: u16,
+}
+
+/// Locked amount and curved weight of one investor class, committed by `open_day`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayClassTotal {
+    /// Sum of the class's locked amounts at the day's lock timestamp
+    pub locked: u64,
+    /// Sum of the class's curved, multiplier-weighted locked amounts at that timestamp
+    pub weight: u64,
+}
+
+/// Referral fee owed to the platform that integrated the vault
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Referral {
+    /// Wallet whose quote ATA receives the fee
+    pub referrer: Pubkey,
+    /// Share of the day's claimed fees, paid out of the creator remainder
+    pub referral_bps: u16,
+}
+
+/// Time-of-day window (seconds after 00:00 UTC) in which crank pages are accepted.
+/// A window with `end_secs < start_secs` wraps past midnight.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrankWindow {
+    /// Start of the window, inclusive
+    pub start_secs: u32,
+    /// End of the window, exclusive
+    pub end_secs: u32,
+}
+
+/// Weight multiplier applied to investors whose lock runs for at least `min_remaining_secs`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DurationMultiplier {
+    /// Minimum remaining vesting time (seconds) for this tier to apply
+    pub min_remaining_secs: i64,
+    /// Multiplier on the locked amount in basis points (10000 = 1x)
+    pub multiplier_bps: u16,
+}
+
+/// Queued move of the honorary position to a new pool
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingPositionMigration {
+    /// Pool the position moves to
+    pub new_pool: Pubkey,
+    /// Timestamp from which `migrate_position` may run
+    pub executable_at: i64,
+}
+
+/// Parameters taking effect from `start_ts` until the next epoch starts
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolicyEpoch {
+    /// Unix timestamp the epoch takes effect at
+    pub start_ts: i64,
+    /// Maximum investor fee share during the epoch
+    pub investor_fee_share_bps: u16,
+    /// Daily cap during the epoch (0 = uncapped)
+    pub daily_cap: u64,
+}
+
+/// Queued change to the sensitive policy parameters
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingPolicyUpdate {
+    /// New maximum investor fee share
+    pub investor_fee_share_bps: u16,
+    /// New daily cap (0 = uncapped)
+    pub daily_cap: u64,
+    /// Timestamp from which the change may be applied
+    pub effective_at: i64,
+}
@@ -4,7 +4,7 @@ import { Program } from "@coral-xyz/anchor";
 // Type definitions for the program (will be generated after build)
 interface StarFeeDistributor {
   methods: {
-    initializeHonoraryPosition(
+    initPolicy(
       investorFeeShareBps: anchor.BN,
       dailyCap: anchor.BN,
       minPayoutLamports: anchor.BN,
@@ -34,7 +34,7 @@ async function main() {
     // Deploy the program
     console.log("Deploying program...");
     const deployTx = await program.methods
-      .initializeHonoraryPosition(
+      .initPolicy(
         new anchor.BN(5000), // 50% investor fee share
         new anchor.BN(1000000000), // 1B daily cap
         new anchor.BN(1000), // 1000 minimum payout
@@ -0,0 +1,224 @@
+//! Local test double for Meteora DAMM v2, used by `mock-integrations` builds of the
+//! distributor.
+//!
+//! A pool is a quote token account at `["pool", quote_mint]` that is its own authority.
+//! Fees accrue by depositing quote tokens into it (`accrue_fees`), and a claim sweeps its
+//! whole balance to the destination. The mock only produces quote fees and does not track
+//! positions beyond requiring the owner's signature. Quote mints may be SPL Token or
+//! Token-2022; Token-2022 transfers go through `transfer_checked`, so a transfer-fee mint
+//! withholds its fee on the way like on mainnet.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token::{self, Transfer};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{self, InitializeAccount3, Mint, TokenAccount, TokenInterface, TransferChecked};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+
+declare_id!("MockCpAmm1111111111111111111111111111111111");
+
+/// Size of a token account for `mint`, with the account extensions a Token-2022 mint requires
+fn token_account_size(mint: &AccountInfo) -> Result<usize> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let extensions = ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    Ok(ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&extensions)?)
+}
+
+/// Move `amount` quote tokens, through `transfer_checked` when the quote mint is supplied
+fn transfer_quote<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    quote_mint: Option<&InterfaceAccount<'info, Mint>>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    match quote_mint {
+        Some(quote_mint) => token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked { from, mint: quote_mint.to_account_info(), to, authority },
+                signer_seeds,
+            ),
+            amount,
+            quote_mint.decimals,
+        ),
+        None => token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer { from, to, authority },
+                signer_seeds,
+            ),
+            amount,
+        ),
+    }
+}
+
+#[program]
+pub mod mock_cp_amm {
+    use super::*;
+
+    /// Create the pool (fee) token account for `quote_mint`
+    pub fn init_pool(ctx: Context<InitPool>) -> Result<()> {
+        let quote_mint = ctx.accounts.quote_mint.key();
+        let pool_seeds: &[&[u8]] = &[b"pool", quote_mint.as_ref(), &[ctx.bumps.pool]];
+        let pool_size = token_account_size(&ctx.accounts.quote_mint.to_account_info())?;
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+                &[pool_seeds],
+            ),
+            Rent::get()?.minimum_balance(pool_size),
+            pool_size as u64,
+            &ctx.accounts.token_program.key(),
+        )?;
+
+        token_interface::initialize_account3(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            InitializeAccount3 {
+                account: ctx.accounts.pool.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+        ))
+    }
+
+    /// Open a position; the mock keeps no position state
+    pub fn create_position(ctx: Context<CreatePosition>) -> Result<()> {
+        msg!(
+            "Mock position opened in pool {} for owner {}",
+            ctx.accounts.pool.key(),
+            ctx.accounts.position_owner.key()
+        );
+
+        Ok(())
+    }
+
+    /// Close a position; the mock keeps no position state
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        msg!(
+            "Mock position closed in pool {} for owner {}",
+            ctx.accounts.pool.key(),
+            ctx.accounts.position_owner.key()
+        );
+
+        Ok(())
+    }
+
+    /// Simulate trading fees by depositing `amount` quote tokens into the pool
+    pub fn accrue_fees(ctx: Context<AccrueFees>, amount: u64) -> Result<()> {
+        transfer_quote(
+            &ctx.accounts.token_program,
+            ctx.accounts.funder_quote_ata.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.funder.to_account_info(),
+            ctx.accounts.quote_mint.as_ref(),
+            &[],
+            amount,
+        )
+    }
+
+    /// Sweep every accrued fee to `destination` and return the claimed amounts
+    pub fn claim_position_fee(ctx: Context<ClaimPositionFee>) -> Result<ClaimedFees> {
+        let quote_amount = ctx.accounts.pool.amount;
+        let quote_mint = ctx.accounts.pool.mint;
+
+        if quote_amount > 0 {
+            transfer_quote(
+                &ctx.accounts.token_program,
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.quote_mint.as_ref(),
+                &[&[b"pool", quote_mint.as_ref(), &[ctx.bumps.pool]]],
+                quote_amount,
+            )?;
+        }
+
+        Ok(ClaimedFees {
+            base_amount: 0,
+            quote_amount,
+        })
+    }
+}
+
+/// Fees paid out by `claim_position_fee`, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimedFees {
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitPool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(owner = token_program.key())]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Created here as a token account that is its own authority
+    #[account(mut, seeds = [b"pool", quote_mint.key().as_ref()], bump)]
+    pub pool: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePosition<'info> {
+    #[account(seeds = [b"pool", pool.mint.as_ref()], bump)]
+    pub pool: InterfaceAccount<'info, TokenAccount>,
+
+    pub position_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(seeds = [b"pool", pool.mint.as_ref()], bump)]
+    pub pool: InterfaceAccount<'info, TokenAccount>,
+
+    pub position_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueFees<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(mut, token::mint = pool.mint, token::authority = funder)]
+    pub funder_quote_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"pool", pool.mint.as_ref()], bump)]
+    pub pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for a Token-2022 quote mint
+    #[account(address = pool.mint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPositionFee<'info> {
+    #[account(mut, seeds = [b"pool", pool.mint.as_ref()], bump)]
+    pub pool: InterfaceAccount<'info, TokenAccount>,
+
+    pub position_owner: Signer<'info>,
+
+    #[account(mut, token::mint = pool.mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for a Token-2022 quote mint
+    #[account(address = pool.mint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
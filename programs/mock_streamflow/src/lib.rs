@@ -0,0 +1,128 @@
+//! Local test double for the Streamflow vesting program, used by `mock-integrations`
+//! builds of the distributor.
+//!
+//! Contracts are written with Streamflow's byte layout for every field the distributor
+//! reads and vest linearly from `start_time` to `end_time`. No tokens are escrowed.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+
+declare_id!("MockStream111111111111111111111111111111111");
+
+/// Size of a Streamflow `Contract` account
+pub const CONTRACT_SIZE: usize = 1104;
+
+/// Byte offsets of the `Contract` fields written by the mock
+pub const CREATED_AT_OFFSET: usize = 9;
+pub const CANCELED_AT_OFFSET: usize = 25;
+pub const END_TIME_OFFSET: usize = 33;
+pub const SENDER_OFFSET: usize = 49;
+pub const RECIPIENT_OFFSET: usize = 113;
+pub const MINT_OFFSET: usize = 177;
+pub const START_TIME_OFFSET: usize = 409;
+pub const NET_AMOUNT_DEPOSITED_OFFSET: usize = 417;
+pub const CLOSED_OFFSET: usize = 667;
+
+#[program]
+pub mod mock_streamflow {
+    use super::*;
+
+    /// Create a stream vesting `net_amount_deposited` linearly over `[start_time, end_time]`
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        start_time: u64,
+        end_time: u64,
+        net_amount_deposited: u64,
+    ) -> Result<()> {
+        require!(start_time < end_time, MockStreamflowError::InvalidSchedule);
+
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.sender.to_account_info(),
+                    to: ctx.accounts.contract.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(CONTRACT_SIZE),
+            CONTRACT_SIZE as u64,
+            &crate::ID,
+        )?;
+
+        let mut data = ctx.accounts.contract.try_borrow_mut_data()?;
+        write_u64(&mut data, CREATED_AT_OFFSET, Clock::get()?.unix_timestamp as u64);
+        write_u64(&mut data, END_TIME_OFFSET, end_time);
+        write_pubkey(&mut data, SENDER_OFFSET, &ctx.accounts.sender.key());
+        write_pubkey(&mut data, RECIPIENT_OFFSET, &ctx.accounts.recipient.key());
+        write_pubkey(&mut data, MINT_OFFSET, &ctx.accounts.mint.key());
+        write_u64(&mut data, START_TIME_OFFSET, start_time);
+        write_u64(&mut data, NET_AMOUNT_DEPOSITED_OFFSET, net_amount_deposited);
+
+        Ok(())
+    }
+
+    /// Cancel the stream at the current time
+    pub fn cancel_stream(ctx: Context<UpdateStream>) -> Result<()> {
+        let mut data = ctx.accounts.contract.try_borrow_mut_data()?;
+        write_u64(&mut data, CANCELED_AT_OFFSET, Clock::get()?.unix_timestamp as u64);
+
+        Ok(())
+    }
+
+    /// Mark the stream closed
+    pub fn close_stream(ctx: Context<UpdateStream>) -> Result<()> {
+        let mut data = ctx.accounts.contract.try_borrow_mut_data()?;
+        data[CLOSED_OFFSET] = 1;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Recipient wallet recorded in the contract
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Token mint recorded in the contract
+    pub mint: UncheckedAccount<'info>,
+
+    /// Fresh keypair the contract is created at
+    #[account(mut)]
+    pub contract: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateStream<'info> {
+    pub sender: Signer<'info>,
+
+    /// CHECK: Mock contract created by this program for `sender`
+    #[account(
+        mut,
+        owner = crate::ID,
+        constraint = contract.data_len() == CONTRACT_SIZE
+            && contract.try_borrow_data()?[SENDER_OFFSET..SENDER_OFFSET + 32] == sender.key().to_bytes()
+            @ MockStreamflowError::InvalidSender
+    )]
+    pub contract: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum MockStreamflowError {
+    #[msg("The stream must end after it starts.")]
+    InvalidSchedule,
+    #[msg("Only the stream's sender may update it.")]
+    InvalidSender,
+}
+
+fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+    data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_pubkey(data: &mut [u8], offset: usize, key: &Pubkey) {
+    data[offset..offset + 32].copy_from_slice(key.as_ref());
+}
@@ -79,4 +79,270 @@ pub enum StarError {
     
     #[msg("Distribution is already complete for this day.")]
     DistributionAlreadyComplete,
+    
+    #[msg("Investor ATA owner does not match the vesting stream recipient.")]
+    InvestorRecipientMismatch,
+    
+    #[msg("Duplicate investor: the stream appears twice in a row in the day's pages.")]
+    DuplicateInvestor,
+    
+    #[msg("Investor set Merkle root must be non-zero.")]
+    InvalidInvestorRoot,
+    
+    #[msg("Investor is not part of the investor set committed for this day.")]
+    InvalidMerkleProof,
+    
+    #[msg("No distribution day is open. Call open_day first.")]
+    DayNotOpen,
+    
+    #[msg("Expected investor and page counts must be non-zero, with no more pages than investors.")]
+    InvalidInvestorCount,
+    
+    #[msg("Pages must be processed in order, up to the total committed at day open.")]
+    PageOutOfOrder,
+    
+    #[msg("Processed investor count does not match the total committed at day open.")]
+    InvestorCountMismatch,
+    
+    #[msg("Investor record is missing or does not belong to this vault and stream.")]
+    InvalidInvestorRecord,
+    
+    #[msg("Fee share decay schedule must have bps within 0-10000 and a positive duration.")]
+    InvalidFeeShareDecay,
+    
+    #[msg("Duration multipliers must be positive, strictly ascending by remaining lock time, and within the tier limit.")]
+    InvalidDurationMultipliers,
+    
+    #[msg("Policy epochs must be strictly ascending by start time, within the epoch limit, have valid bps and a positive daily cap, and cannot be combined with a fee share decay.")]
+    InvalidPolicyEpochs,
+    
+    #[msg("Signer is not the policy authority.")]
+    Unauthorized,
+    
+    #[msg("Recovery timelock has not elapsed yet.")]
+    RecoveryTimelockActive,
+    
+    #[msg("Recovery amount must be positive and not exceed the treasury balance.")]
+    InvalidRecoveryAmount,
+    
+    #[msg("Recovery destination does not match the proposal.")]
+    RecoveryDestinationMismatch,
+    
+    #[msg("The admin role cannot be revoked; grant it to a new account instead.")]
+    CannotRevokeAdmin,
+    
+    #[msg("Vault has not been inactive long enough for a stale withdrawal.")]
+    VaultNotStale,
+    
+    #[msg("Stale vault threshold must be at least one day.")]
+    InvalidStaleThreshold,
+    
+    #[msg("Blacklist has reached its maximum number of entries.")]
+    BlacklistFull,
+    
+    #[msg("Wallet is not on the blacklist.")]
+    NotBlacklisted,
+    
+    // The three lending errors outlived treasury lending; they stay so later codes keep their numbers
+    #[msg("No lending market is configured for this vault.")]
+    LendingNotConfigured,
+    
+    #[msg("Lending market account does not match the policy.")]
+    InvalidLendingMarket,
+    
+    #[msg("Treasury funds are lent out; pass the lending market so the crank can withdraw them.")]
+    LendingFundsOutstanding,
+    
+    // Likewise for the automation thread errors, which outlived `bootstrap_crank_thread`
+    #[msg("Automation thread program ID does not match the known thread program.")]
+    InvalidThreadProgram,
+    
+    #[msg("Automation thread schedule must be a non-empty cron expression.")]
+    InvalidThreadSchedule,
+    
+    #[msg("Crank caller is not on the cranker allowlist.")]
+    CrankerNotAllowed,
+    
+    #[msg("Cranker allowlist has reached its maximum number of entries.")]
+    CrankerAllowlistFull,
+    
+    #[msg("Switchboard function account does not match the allowlist or is not owned by Switchboard.")]
+    InvalidSwitchboardFunction,
+    
+    #[msg("Account version is newer than this program supports.")]
+    UnsupportedAccountVersion,
+    
+    #[msg("Batch accounts do not match the vault parameters.")]
+    InvalidBatchAccounts,
+    
+    #[msg("Honorary position is not owned by the investor fee position owner PDA.")]
+    InvalidPositionOwner,
+    
+    #[msg("CP-AMM program ID does not match the known CP-AMM program.")]
+    InvalidCpAmmProgram,
+    
+    #[msg("Page has more investors than MAX_INVESTORS_PER_PAGE.")]
+    PageTooLarge,
+    
+    #[msg("Crank window bounds must be distinct seconds within a day.")]
+    InvalidCrankWindow,
+    
+    #[msg("Crank pages are only accepted inside the policy's crank window.")]
+    OutsideCrankWindow,
+    
+    #[msg("Minimum delay since the previous crank page has not elapsed.")]
+    PageCooldownActive,
+    
+    #[msg("Distribution day is not in a phase that allows this step.")]
+    InvalidPhaseTransition,
+    
+    #[msg("Quote mint account is required to burn the remainder or price Token-2022 transfer fees.")]
+    MissingQuoteMint,
+    
+    #[msg("y0 can only be corrected once, within the correction window after policy creation.")]
+    Y0CorrectionUnavailable,
+    
+    #[msg("Investor classes are invalid: too many, zero y0, or fee shares above 10000 bps.")]
+    InvalidInvestorClasses,
+    
+    #[msg("Investor class index is not configured on the policy.")]
+    InvalidInvestorClass,
+    
+    #[msg("Cannot change this while a distribution day is in progress.")]
+    DayInProgress,
+    
+    #[msg("Time-weighted locked weighting is not enabled for this vault.")]
+    TwalWeightingDisabled,
+    
+    #[msg("Clock is earlier than the recorded distribution state or would not advance the day number.")]
+    ClockRollback,
+    
+    #[msg("Treasury balance does not reconcile with the day's claims and payouts.")]
+    TreasuryReconciliationFailed,
+    
+    #[msg("Insurance fund account required while insurance_bps is set.")]
+    InsuranceFundRequired,
+    
+    #[msg("Invalid referral: referrer must be set and referral_bps at most 10000.")]
+    InvalidReferral,
+    
+    #[msg("Referrer quote ATA required while the policy has a referral.")]
+    ReferrerAtaRequired,
+    
+    #[msg("Referrer quote ATA must be the referrer's quote mint account.")]
+    InvalidReferrerAta,
+    
+    #[msg("Page must be co-signed by the configured page attester.")]
+    PageAttestationRequired,
+    
+    #[msg("Treasury token account must not have a delegate.")]
+    TreasuryDelegateSet,
+    
+    #[msg("Treasury close authority must be unset or the position owner PDA.")]
+    InvalidTreasuryCloseAuthority,
+    
+    #[msg("Quote mint carries a Token-2022 permanent delegate, which is not allowed.")]
+    UnsupportedMintExtension,
+    
+    #[msg("Quote mint must not have a freeze authority while the policy requires it.")]
+    QuoteMintFreezeAuthoritySet,
+    
+    #[msg("Transfer hook allowlist is full.")]
+    TransferHookAllowlistFull,
+    
+    #[msg("Quote mint's transfer hook program is not on the global allowlist.")]
+    TransferHookNotAllowed,
+    
+    #[msg("The vault already has an honorary position.")]
+    PositionAlreadyRegistered,
+    
+    #[msg("Position NFT must be a single zero-decimal token held by the position holder.")]
+    InvalidPositionNft,
+    
+    #[msg("CP-AMM pool does not match the vault's registered pool.")]
+    InvalidCpAmmPool,
+    
+    #[msg("Position does not match the vault's registered honorary position.")]
+    InvalidHonoraryPosition,
+    
+    #[msg("No position migration is queued.")]
+    NoPendingPositionMigration,
+    
+    #[msg("Position migration timelock has not elapsed.")]
+    PositionMigrationTimelockActive,
+    
+    #[msg("Position tick range must satisfy MIN_TICK <= tick_lower < tick_upper <= MAX_TICK.")]
+    InvalidTickRange,
+    
+    #[msg("Page does not match the policy page size.")]
+    InvalidPageSize,
+    
+    #[msg("Investors out of order: stream pubkeys must strictly increase across the day's pages.")]
+    InvestorsOutOfOrder,
+    
+    #[msg("Registry chunk is full.")]
+    RegistryChunkFull,
+    
+    #[msg("Registry chunks still have room; register into an existing chunk.")]
+    RegistryHasRoom,
+    
+    #[msg("Registry chunk does not hold the investor.")]
+    InvalidRegistryChunk,
+    
+    #[msg("Vault distributes from its investor registry; use crank_from_registry.")]
+    RegistryModeEnabled,
+    
+    #[msg("Vault is not in registry mode.")]
+    RegistryModeDisabled,
+    
+    #[msg("Day was not opened with the investor registry's chunk count and investor total.")]
+    RegistryMismatch,
+    
+    #[msg("Crank fee vault is required while crank reimbursement is enabled.")]
+    CrankFeeVaultRequired,
+    
+    #[msg("Vault metadata name or URI exceeds its maximum length.")]
+    VaultMetadataTooLong,
+    
+    #[msg("Investor locks can only be sampled between distribution days, or snapshotted once the next day has begun.")]
+    LockSnapshotClosed,
+    
+    #[msg("The investor's lock was not snapshotted for the current day.")]
+    LockSnapshotMissing,
+    
+    #[msg("The day's pages weigh more locked tokens or class weight than were committed at day open.")]
+    DayLockedTotalExceeded,
+    
+    #[msg("Unsupported instruction argument version.")]
+    UnsupportedArgsVersion,
+    
+    #[msg("Math underflow: an amount would fall below zero, so the distribution books do not add up.")]
+    MathUnderflow,
+
+    #[msg("Auto-compounding is not supported: the honorary position cannot take liquidity.")]
+    CompoundingUnsupported,
+
+    #[msg("The day's pages did not weigh the class totals committed at open.")]
+    DayTotalsMismatch,
+
+    #[msg("Lock timestamp must fall in the day being opened, no later than now.")]
+    InvalidLockTimestamp,
+
+    #[msg("Switchboard function has no attested enclave signer.")]
+    EnclaveSignerUnset,
+
+    #[msg("Vesting account does not lock the vault's base mint.")]
+    StreamMintMismatch,
+
+    #[msg("Position migration is not supported by this build.")]
+    PositionMigrationUnsupported,
+}
+
+impl From<star_distribution_core::MathError> for StarError {
+    fn from(error: star_distribution_core::MathError) -> Self {
+        match error {
+            star_distribution_core::MathError::Overflow => StarError::MathOverflow,
+            star_distribution_core::MathError::Underflow => StarError::MathUnderflow,
+        }
+    }
 }
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{
+    CrankWindow, DayClassTotal, DustPolicy, InvestorClass, PayoutCurve, RemainderPolicy, Role,
+    SkipRouting,
+};
+
 /// Emitted when an honorary LP position is successfully initialized
 #[event]
 pub struct HonoraryPositionInitialized {
@@ -12,6 +17,39 @@ pub struct HonoraryPositionInitialized {
     pub min_payout_lamports: u64,
     pub y0: u64,
     pub timestamp: i64,
+    pub base_mint: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub treasury: Pubkey,
+}
+
+/// Emitted when a vault's policy, progress and treasury are initialized
+#[event]
+pub struct PolicyInitialized {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a distribution day is opened with its committed investor set
+#[event]
+pub struct DayOpened {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub investor_root: [u8; 32],
+    pub total_investors: u64,
+    pub total_pages: u64,
+    pub timestamp: i64,
+    pub total_locked: u64,
+    pub class_totals: Vec<DayClassTotal>,
+    pub lock_ts: i64,
 }
 
 /// Emitted when quote fees are claimed from the honorary position
@@ -34,6 +72,7 @@ pub struct InvestorPayoutPage {
     pub locked_total: u64,
     pub eligible_share_bps: u16,
     pub timestamp: i64,
+    pub page_hash: [u8; 32],
 }
 
 /// Emitted when the final page of a day's distribution is completed
@@ -44,7 +83,14 @@ pub struct CreatorPayoutDayClosed {
     pub total_distributed_to_investors: u64,
     pub total_claimed: u64,
     pub creator: Pubkey,
+    /// Policy parameters in effect for the day
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
     pub timestamp: i64,
+    /// Remainder burned instead of paid under `RemainderPolicy::Burn`
+    pub burned: u64,
 }
 
 /// Emitted when distribution fails due to base fee detection
@@ -66,6 +112,9 @@ pub struct InvestorPayout {
     pub day: i64,
     pub page: u64,
     pub timestamp: i64,
+    pub payout_id: [u8; 32],
+    /// Amount the investor received after any Token-2022 transfer fee; `amount` is gross
+    pub net_amount: u64,
 }
 
 /// Emitted when daily cap is applied to limit payouts
@@ -77,3 +126,540 @@ pub struct DailyCapApplied {
     pub cap_amount: u64,
     pub timestamp: i64,
 }
+
+/// Reason an investor was skipped during distribution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The vesting stream was cancelled by its sender
+    StreamCancelled,
+    /// The vesting stream account was closed
+    StreamClosed,
+    /// Nothing is locked in the investor's stream
+    ZeroLocked,
+    /// The computed payout fell below `min_payout_lamports`
+    BelowMinPayout,
+    /// The beneficiary wallet is on the vault blacklist
+    Blacklisted,
+    /// The stream recipient opted out of fee income
+    OptedOut,
+    /// The investor's quote token account is frozen by the mint's freeze authority
+    AccountFrozen,
+}
+
+/// Emitted when an investor is skipped instead of paid
+#[event]
+pub struct InvestorSkipped {
+    pub investor: Pubkey,
+    pub stream: Pubkey,
+    pub reason: SkipReason,
+    pub day: i64,
+    pub page: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the policy authority proposes a timelocked treasury recovery
+#[event]
+pub struct RecoveryProposed {
+    pub vault: Pubkey,
+    pub proposer: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+    pub source: Pubkey,
+}
+
+/// Emitted when a treasury recovery is executed after its timelock
+#[event]
+pub struct RecoveryExecuted {
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending treasury recovery is cancelled
+#[event]
+pub struct RecoveryCancelled {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the policy authority queues a parameter change
+#[event]
+pub struct PolicyUpdateQueued {
+    pub vault: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the crank applies a queued parameter change
+#[event]
+pub struct PolicyUpdateApplied {
+    pub vault: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin grants a role
+#[event]
+pub struct RoleGranted {
+    pub vault: Pubkey,
+    pub role: Role,
+    pub account: Pubkey,
+    pub previous: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin revokes a role
+#[event]
+pub struct RoleRevoked {
+    pub vault: Pubkey,
+    pub role: Role,
+    pub account: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator sweeps the treasury of a stale vault
+#[event]
+pub struct StaleVaultWithdrawn {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub last_activity_ts: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the policy authority adds or removes a blacklisted wallet
+#[event]
+pub struct BlacklistUpdated {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+    pub blacklisted: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a stream recipient opts out of or back into fee income
+#[event]
+pub struct InvestorOptOutUpdated {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub opted_out: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a page's investor share is re-deposited as pool liquidity
+#[event]
+pub struct InvestorShareCompounded {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub page: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin toggles auto-compounding
+#[event]
+pub struct CompoundingModeSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the operator adds or removes an allowed cranker
+#[event]
+pub struct CrankerAllowlistUpdated {
+    pub vault: Pubkey,
+    pub cranker: Pubkey,
+    pub allowed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's accounts are upgraded to the current layouts
+#[event]
+pub struct AccountsMigrated {
+    pub vault: Pubkey,
+    pub policy_from_version: u8,
+    pub progress_from_version: u8,
+    pub policy_version: u8,
+    pub progress_version: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the operator registers the vault's address lookup table
+#[event]
+pub struct LookupTableSet {
+    pub vault: Pubkey,
+    pub lookup_table: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets or clears the vault's crank window
+#[event]
+pub struct CrankWindowSet {
+    pub vault: Pubkey,
+    pub crank_window: Option<CrankWindow>,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the minimum delay between crank pages
+#[event]
+pub struct PageCooldownSet {
+    pub vault: Pubkey,
+    pub page_cooldown_secs: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator changes what day close does with the remainder
+#[event]
+pub struct RemainderPolicySet {
+    pub vault: Pubkey,
+    pub remainder_policy: RemainderPolicy,
+    pub timestamp: i64,
+}
+
+/// Emitted when a day's remainder is rolled into the next day's investor pool
+#[event]
+pub struct RemainderDonated {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub amount: u64,
+    pub carry_over: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority corrects a misconfigured `y0`
+#[event]
+pub struct Y0Updated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_y0: u64,
+    pub new_y0: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority replaces the policy's investor classes
+#[event]
+pub struct InvestorClassesSet {
+    pub vault: Pubkey,
+    pub investor_classes: Vec<InvestorClass>,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority assigns an investor to a class
+#[event]
+pub struct InvestorClassSet {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub class: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin switches between instant and time-weighted locked weighting
+#[event]
+pub struct TwalWeightingSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when investor locks are sampled for time-weighted weighting
+#[event]
+pub struct LocksRefreshed {
+    pub vault: Pubkey,
+    pub investors: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin changes the payout curve
+#[event]
+pub struct PayoutCurveSet {
+    pub vault: Pubkey,
+    pub payout_curve: PayoutCurve,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the guaranteed minimum investor share
+#[event]
+pub struct MinInvestorShareSet {
+    pub vault: Pubkey,
+    pub min_investor_share_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when quote fees are harvested into the treasury between distribution days
+#[event]
+pub struct FeesHarvested {
+    pub vault: Pubkey,
+    pub caller: Pubkey,
+    pub amount: u64,
+    pub harvested_pending: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted at day close when the treasury holds less than the day's books require
+#[event]
+pub struct ReconciliationMismatch {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub expected_balance: u64,
+    pub treasury_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets whether a reconciliation mismatch aborts day close
+#[event]
+pub struct ReconciliationAbortSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a crank page moves part of its claim to the insurance fund
+#[event]
+pub struct InsuranceFunded {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub page: u64,
+    pub amount: u64,
+    pub lifetime_insured: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the insurance fund share of claimed fees
+#[event]
+pub struct InsuranceBpsSet {
+    pub vault: Pubkey,
+    pub insurance_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when day close pays the referrer its share of the day's claim
+#[event]
+pub struct ReferralFeePaid {
+    pub vault: Pubkey,
+    pub referrer: Pubkey,
+    pub day: i64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the keeper that must co-sign crank pages
+#[event]
+pub struct PageAttesterSet {
+    pub vault: Pubkey,
+    pub page_attester: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin toggles the no-freeze-authority requirement for the quote mint
+#[event]
+pub struct FreezeAuthorityRequirementSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a transfer hook program is added to or removed from the global allowlist
+#[event]
+pub struct TransferHookAllowlistUpdated {
+    pub hook_program: Pubkey,
+    pub allowed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when an existing CP-AMM position is adopted as the vault's honorary position
+#[event]
+pub struct PositionAdopted {
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub pool: Pubkey,
+    pub previous_holder: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a move of the honorary position to a new pool is queued
+#[event]
+pub struct PositionMigrationQueued {
+    pub vault: Pubkey,
+    pub new_pool: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued position migration is cancelled
+#[event]
+pub struct PositionMigrationCancelled {
+    pub vault: Pubkey,
+    pub new_pool: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the honorary position moves to a new pool
+#[event]
+pub struct PositionMigrated {
+    pub vault: Pubkey,
+    pub old_pool: Pubkey,
+    pub old_position: Pubkey,
+    pub new_pool: Pubkey,
+    pub new_position: Pubkey,
+    pub fees_claimed: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the handling of below-threshold payouts
+#[event]
+pub struct DustPolicySet {
+    pub vault: Pubkey,
+    pub dust_policy: DustPolicy,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the fixed crank page size
+#[event]
+pub struct PageSizeSet {
+    pub vault: Pubkey,
+    pub page_size: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when an investor is added to the on-chain registry
+#[event]
+pub struct InvestorRegistered {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub investor_quote_ata: Pubkey,
+    pub chunk_index: u32,
+    pub registered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an investor is removed from the on-chain registry
+#[event]
+pub struct InvestorDeregistered {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub chunk_index: u32,
+    pub deregistered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin switches the vault to or from registry-driven distribution
+#[event]
+pub struct RegistryModeSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator sets the cranker pass mint
+#[event]
+pub struct CrankerPassSet {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator funds the crank fee vault
+#[event]
+pub struct CrankFeeVaultFunded {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator sets the per-page crank reimbursement
+#[event]
+pub struct CrankReimbursementSet {
+    pub vault: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a crank page reimburses its caller from the crank fee vault
+#[event]
+pub struct CrankReimbursed {
+    pub vault: Pubkey,
+    pub cranker: Pubkey,
+    pub day: i64,
+    pub page: u64,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator or authority closes a day before its final committed page
+#[event]
+pub struct DayForceClosed {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub pages_processed: u64,
+    pub total_pages: u64,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority skips a distribution day
+#[event]
+pub struct DaySkipped {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub routing: SkipRouting,
+    pub amount: u64,
+    pub carry_over: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the global config admin creates a policy template
+#[event]
+pub struct PolicyTemplateCreated {
+    pub template: Pubkey,
+    pub template_id: u32,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault is initialized from a policy template
+#[event]
+pub struct PolicyTemplateApplied {
+    pub vault: Pubkey,
+    pub template: Pubkey,
+    pub template_id: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's metadata is created or replaced
+#[event]
+pub struct VaultMetadataSet {
+    pub vault: Pubkey,
+    pub name: String,
+    pub project_uri: String,
+    pub logo_uri: String,
+    pub creator_contact_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when refresh_locks caches investor locks for the next distribution day
+#[event]
+pub struct LocksSnapshotted {
+    pub vault: Pubkey,
+    pub day: i64,
+    pub investors: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin toggles the lock snapshot requirement for crank pages
+#[event]
+pub struct LockSnapshotRequirementSet {
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
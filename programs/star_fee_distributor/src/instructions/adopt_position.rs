@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::StarError;
+use crate::events::PositionAdopted;
+use crate::state::{Policy, CP_AMM_PROGRAM_ID};
+use crate::utils::{CpAmmUtils, PoolConfig, ValidationUtils};
+
+#[derive(Accounts)]
+pub struct AdoptPosition<'info> {
+    /// Policy authority
+    pub authority: Signer<'info>,
+
+    /// Current holder of the position NFT, handing over custody
+    pub position_holder: Signer<'info>,
+
+    /// The vault the position is adopted into
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA the position is registered on
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// CP-AMM pool the position belongs to
+    /// CHECK: Read through the CP-AMM adapter and validated for quote-only fee accrual
+    pub cp_amm_pool: AccountInfo<'info>,
+
+    /// Quote mint recorded on the policy
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// Base mint (first token in the pool)
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Honorary LP position owner PDA, the new custodian of the position NFT
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Existing CP-AMM position
+    /// CHECK: Owned by the CP-AMM program; pool and custody verified after the transfer
+    #[account(owner = CP_AMM_PROGRAM_ID @ StarError::InvalidCpAmmProgram)]
+    pub position: AccountInfo<'info>,
+
+    /// Mint of the position NFT (Token-2022 for DAMM v2 positions); must be the
+    /// position's own, checked against the holder's account before the transfer
+    #[account(
+        mint::token_program = token_program,
+        constraint = position_nft_mint.decimals == 0 && position_nft_mint.supply == 1
+            @ StarError::InvalidPositionNft
+    )]
+    pub position_nft_mint: InterfaceAccount<'info, Mint>,
+
+    /// Holder's token account currently holding the position NFT
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = position_holder,
+        token::token_program = token_program,
+        constraint = holder_nft_account.amount == 1 @ StarError::InvalidPositionNft
+    )]
+    pub holder_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Position owner PDA's token account receiving the position NFT
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = position_owner_pda,
+        token::token_program = token_program
+    )]
+    pub custody_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program of the position NFT (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Take custody of an existing CP-AMM position and register it as the vault's honorary
+/// position, in place of `init_position`. Fees it accrued before adoption are claimed and
+/// distributed by the next crank like any other. Policy authority only.
+pub fn handler(ctx: Context<AdoptPosition>) -> Result<()> {
+    let policy = &ctx.accounts.policy;
+    let vault_key = ctx.accounts.vault.key();
+
+    require!(policy.position == Pubkey::default(), StarError::PositionAlreadyRegistered);
+
+    // The adopted position must meet the same quote-only rules as a fresh one, over the
+    // range it actually provides liquidity in
+    let pool = CpAmmUtils::load_pool(&ctx.accounts.cp_amm_pool)?;
+    let (tick_lower, tick_upper) = CpAmmUtils::position_tick_range(&pool)?;
+    let mut pool_config = PoolConfig {
+        token_a: ctx.accounts.base_mint.key(),
+        token_b: ctx.accounts.quote_mint.key(),
+        pool_id: ctx.accounts.cp_amm_pool.key(),
+        tick_lower,
+        tick_upper,
+    };
+
+    ValidationUtils::validate_quote_only_pool(&pool_config, &policy.quote_mint)?;
+    ValidationUtils::validate_quote_mint_freeze_authority(&ctx.accounts.quote_mint, policy)?;
+
+    // Only a range above the pool price is recorded; it is what lets the crank accept a
+    // pool collecting fees in both tokens, or liquidity in the position
+    if ValidationUtils::validate_quote_only_range(&pool_config, pool.sqrt_price).is_err() {
+        pool_config.tick_lower = 0;
+        pool_config.tick_upper = 0;
+    }
+
+    // The holder's NFT must be the position's own, before custody moves
+    CpAmmUtils::verify_position_custody(
+        &ctx.accounts.position,
+        &ctx.accounts.cp_amm_pool.key(),
+        &ctx.accounts.holder_nft_account.to_account_info(),
+        &ctx.accounts.position_holder.key(),
+    )?;
+
+    let transfer_ix = TransferChecked {
+        from: ctx.accounts.holder_nft_account.to_account_info(),
+        mint: ctx.accounts.position_nft_mint.to_account_info(),
+        to: ctx.accounts.custody_nft_account.to_account_info(),
+        authority: ctx.accounts.position_holder.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_ix),
+        1,
+        ctx.accounts.position_nft_mint.decimals,
+    )?;
+
+    CpAmmUtils::verify_position_custody(
+        &ctx.accounts.position,
+        &ctx.accounts.cp_amm_pool.key(),
+        &ctx.accounts.custody_nft_account.to_account_info(),
+        &ctx.accounts.position_owner_pda.key(),
+    )?;
+
+    // An adopted position may have been someone's LP position; any liquidity it holds
+    // must sit in a quote-only range
+    ValidationUtils::validate_fee_only_position(&ctx.accounts.position, &pool_config, pool.sqrt_price)?;
+
+    let position = ctx.accounts.position.key();
+    let policy = &mut ctx.accounts.policy;
+    policy.position = position;
+    policy.pool = ctx.accounts.cp_amm_pool.key();
+    policy.tick_lower = pool_config.tick_lower;
+    policy.tick_upper = pool_config.tick_upper;
+    // The crank re-validates the live pool against the recorded range on every page
+    ValidationUtils::validate_pool_state(&pool, policy)?;
+
+    emit!(PositionAdopted {
+        vault: vault_key,
+        position,
+        pool: ctx.accounts.cp_amm_pool.key(),
+        previous_holder: ctx.accounts.position_holder.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Position {} adopted for vault: {}, pool: {}",
+        position,
+        vault_key,
+        ctx.accounts.cp_amm_pool.key()
+    );
+
+    Ok(())
+}
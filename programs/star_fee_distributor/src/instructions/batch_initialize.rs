@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::PolicyInitialized;
+use crate::instructions::initialize::build_policy;
+use crate::state::{
+    derive_investor_fee_position_owner_pda, derive_policy_pda, derive_progress_pda,
+    derive_treasury_pda, GlobalConfig, Policy, Progress, VaultInitParams, POLICY_SEED,
+    PROGRESS_SEED, TREASURY_SEED, VAULT_SEED,
+};
+use crate::utils::{PoolConfig, TokenTransferUtils, ValidationUtils};
+
+/// Remaining accounts per vault: vault, CP-AMM pool, quote mint, base mint, policy PDA,
+/// progress PDA, treasury PDA
+pub const BATCH_ACCOUNTS_PER_VAULT: usize = 7;
+
+#[derive(Accounts)]
+pub struct BatchInitialize<'info> {
+    /// Funds every vault's accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Policy authority of every vault; may be a keypair or a multisig vault PDA
+    /// CHECK: Any pubkey, stored on the policies and checked by privileged instructions
+    pub authority: UncheckedAccount<'info>,
+
+    /// Global config holding the allowed transfer hook programs; may not exist yet
+    /// CHECK: PDA seeds checked; read with `GlobalConfig::load`
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Token program owning every vault's quote mint (SPL Token or Token-2022), for the
+    /// treasury accounts
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Create Policy, Progress and the treasury token account for several vaults at once.
+/// `params[i]` configures the vault of the i-th group of `BATCH_ACCOUNTS_PER_VAULT`
+/// remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchInitialize<'info>>,
+    params: Vec<VaultInitParams>,
+) -> Result<()> {
+    require!(
+        !params.is_empty() && ctx.remaining_accounts.len() == params.len() * BATCH_ACCOUNTS_PER_VAULT,
+        StarError::InvalidBatchAccounts
+    );
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let global_config = GlobalConfig::load(&ctx.accounts.global_config)?;
+
+    for (vault_params, group) in params
+        .into_iter()
+        .zip(ctx.remaining_accounts.chunks(BATCH_ACCOUNTS_PER_VAULT))
+    {
+        let [vault, cp_amm_pool, quote_mint_info, base_mint_info, policy_info, progress_info, treasury_info] =
+            group
+        else {
+            return err!(StarError::InvalidBatchAccounts);
+        };
+
+        let quote_mint = InterfaceAccount::<Mint>::try_from(quote_mint_info)?;
+        let base_mint = InterfaceAccount::<Mint>::try_from(base_mint_info)?;
+        require_keys_eq!(
+            *quote_mint_info.owner,
+            ctx.accounts.token_program.key(),
+            StarError::InvalidQuoteMint
+        );
+
+        // Validate pool configuration for quote-only fee accrual
+        let pool_config = PoolConfig {
+            token_a: base_mint.key(),
+            token_b: quote_mint.key(),
+            pool_id: cp_amm_pool.key(),
+            tick_lower: 0, // Would be provided in real implementation
+            tick_upper: 0, // Would be provided in real implementation
+        };
+        ValidationUtils::validate_quote_only_pool(&pool_config, &quote_mint.key())?;
+        ValidationUtils::validate_quote_mint_extensions(quote_mint_info, global_config.as_ref())?;
+
+        let vault_key = vault.key();
+        let (policy_pda, policy_bump) = derive_policy_pda(&vault_key);
+        let (progress_pda, progress_bump) = derive_progress_pda(&vault_key);
+        let (treasury_pda, treasury_bump) = derive_treasury_pda(&vault_key, &quote_mint.key());
+        let (position_owner_pda, _) = derive_investor_fee_position_owner_pda(&vault_key);
+        require_keys_eq!(policy_info.key(), policy_pda, StarError::InvalidBatchAccounts);
+        require_keys_eq!(progress_info.key(), progress_pda, StarError::InvalidBatchAccounts);
+        require_keys_eq!(treasury_info.key(), treasury_pda, StarError::InvalidBatchAccounts);
+
+        let policy = build_policy(
+            vault_params,
+            quote_mint.key(),
+            vault_key,
+            policy_bump,
+            ctx.accounts.authority.key(),
+        )?;
+
+        // Policy
+        create_pda_account(
+            &ctx.accounts.payer,
+            policy_info,
+            Policy::SIZE,
+            &crate::ID,
+            &[VAULT_SEED, vault_key.as_ref(), POLICY_SEED, &[policy_bump]],
+            &ctx.accounts.system_program,
+        )?;
+        policy.try_serialize(&mut &mut policy_info.try_borrow_mut_data()?[..])?;
+
+        // Progress (zero-copy: discriminator followed by the raw struct)
+        create_pda_account(
+            &ctx.accounts.payer,
+            progress_info,
+            Progress::SIZE,
+            &crate::ID,
+            &[VAULT_SEED, vault_key.as_ref(), PROGRESS_SEED, &[progress_bump]],
+            &ctx.accounts.system_program,
+        )?;
+        {
+            let loader = AccountLoader::<Progress>::try_from_unchecked(&crate::ID, progress_info)?;
+            *loader.load_init()? = Progress::new(vault_key, progress_bump);
+        }
+        progress_info.try_borrow_mut_data()?[..8].copy_from_slice(&Progress::DISCRIMINATOR);
+
+        // Treasury token account owned by the position owner PDA
+        create_pda_account(
+            &ctx.accounts.payer,
+            treasury_info,
+            TokenTransferUtils::token_account_len(quote_mint_info)?,
+            &ctx.accounts.token_program.key(),
+            &[
+                VAULT_SEED,
+                vault_key.as_ref(),
+                TREASURY_SEED,
+                quote_mint.key().as_ref(),
+                &[treasury_bump],
+            ],
+            &ctx.accounts.system_program,
+        )?;
+        invoke(
+            &spl_token_2022::instruction::initialize_account3(
+                &ctx.accounts.token_program.key(),
+                &treasury_pda,
+                &quote_mint.key(),
+                &position_owner_pda,
+            )?,
+            &[
+                treasury_info.clone(),
+                quote_mint_info.clone(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+        let treasury = InterfaceAccount::<TokenAccount>::try_from(treasury_info)?;
+        ValidationUtils::validate_treasury_account(&treasury, &position_owner_pda)?;
+
+        emit!(PolicyInitialized {
+            vault: vault_key,
+            authority: ctx.accounts.authority.key(),
+            quote_mint: quote_mint.key(),
+            pool: cp_amm_pool.key(),
+            investor_fee_share_bps: policy.investor_fee_share_bps,
+            daily_cap: policy.daily_cap,
+            min_payout_lamports: policy.min_payout_lamports,
+            y0: policy.y0,
+            timestamp: current_timestamp,
+        });
+
+        msg!(
+            "Batch initialized vault: {}, quote_mint: {}, pool: {}",
+            vault_key,
+            quote_mint.key(),
+            cp_amm_pool.key()
+        );
+    }
+
+    Ok(())
+}
+
+/// Create a PDA owned by `owner`. Tolerates accounts pre-funded by a third party,
+/// which would make a plain `create_account` fail.
+fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    require!(account.data_is_empty(), StarError::InvalidBatchAccounts);
+
+    let rent = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            owner,
+        );
+    }
+
+    if current_lamports < rent {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                SystemTransfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent - current_lamports,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        owner,
+    )
+}
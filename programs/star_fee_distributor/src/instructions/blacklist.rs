@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::BlacklistUpdated;
+use crate::state::{Blacklist, Policy, MAX_BLACKLIST_ENTRIES};
+
+#[derive(Accounts)]
+pub struct InitBlacklist<'info> {
+    /// Policy authority
+    pub authority: Signer<'info>,
+
+    /// Funds the blacklist rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault the blacklist belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Blacklist PDA
+    #[account(
+        init,
+        payer = payer,
+        space = Blacklist::SIZE,
+        seeds = [b"vault", vault.key().as_ref(), b"blacklist"],
+        bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBlacklist<'info> {
+    /// Policy authority
+    pub authority: Signer<'info>,
+
+    /// The vault the blacklist belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Blacklist PDA
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"blacklist"],
+        bump = blacklist.bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+}
+
+pub fn init_handler(ctx: Context<InitBlacklist>) -> Result<()> {
+    let blacklist = &mut ctx.accounts.blacklist;
+    blacklist.set_inner(Blacklist::new(ctx.accounts.vault.key(), ctx.bumps.blacklist));
+
+    msg!("Blacklist initialized for vault: {}", ctx.accounts.vault.key());
+
+    Ok(())
+}
+
+/// Exclude `wallet` from distribution; its share flows to the creator remainder
+pub fn add_handler(ctx: Context<UpdateBlacklist>, wallet: Pubkey) -> Result<()> {
+    let blacklist = &mut ctx.accounts.blacklist;
+
+    if !blacklist.contains(&wallet) {
+        require!(blacklist.wallets.len() < MAX_BLACKLIST_ENTRIES, StarError::BlacklistFull);
+        blacklist.wallets.push(wallet);
+    }
+
+    emit!(BlacklistUpdated {
+        vault: ctx.accounts.vault.key(),
+        wallet,
+        blacklisted: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Wallet {} blacklisted for vault: {}", wallet, ctx.accounts.vault.key());
+
+    Ok(())
+}
+
+/// Restore `wallet` to distribution from the next page on
+pub fn remove_handler(ctx: Context<UpdateBlacklist>, wallet: Pubkey) -> Result<()> {
+    let blacklist = &mut ctx.accounts.blacklist;
+
+    let index = blacklist
+        .wallets
+        .iter()
+        .position(|entry| *entry == wallet)
+        .ok_or(StarError::NotBlacklisted)?;
+    blacklist.wallets.swap_remove(index);
+
+    emit!(BlacklistUpdated {
+        vault: ctx.accounts.vault.key(),
+        wallet,
+        blacklisted: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Wallet {} removed from blacklist for vault: {}", wallet, ctx.accounts.vault.key());
+
+    Ok(())
+}
@@ -1,17 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::memo::{self, BuildMemo, Memo};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
 
 use crate::errors::StarError;
-use crate::events::{QuoteFeesClaimed, InvestorPayoutPage, CreatorPayoutDayClosed, InvestorPayout, DailyCapApplied};
-use crate::state::{Policy, Progress, InvestorAccount, derive_policy_pda, derive_progress_pda, derive_investor_fee_position_owner_pda};
+use crate::events::{QuoteFeesClaimed, InvestorPayoutPage, CreatorPayoutDayClosed, InvestorPayout, DailyCapApplied, InvestorSkipped, SkipReason, PolicyUpdateApplied, RemainderDonated, ReconciliationMismatch, InsuranceFunded, ReferralFeePaid, CrankReimbursed};
+use crate::state::{Blacklist, CrankArgsV1, CrankPageResult, CrankerAllowlist, DayPhase, Policy, RemainderPolicy, Progress, InvestorAccount, InvestorRecord, InvestorRegistry, InvestorRegistryChunk, MAX_INVESTORS_PER_PAGE, derive_investor_registry_pda, derive_registry_chunk_pda};
 use crate::utils::{
-    DistributionMath, PaginationUtils, ValidationUtils, TokenTransferUtils, 
-    StreamflowUtils, ClaimResult, PoolConfig
+    DistributionMath, PaginationUtils, ValidationUtils, TokenTransferUtils, MerkleUtils,
+    VestingUtils, VestingLock, VestingRecipient, ClaimResult, PoolConfig, CpAmmUtils,
+    CheckedTransferAccounts,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CrankDistribute<'info> {
-    /// Anyone can call this crank (permissionless)
+    /// Anyone can call this crank (permissionless unless the cranker allowlist is enabled)
     #[account(mut)]
     pub crank_caller: Signer<'info>,
 
@@ -27,13 +32,24 @@ pub struct CrankDistribute<'info> {
     )]
     pub position_owner_pda: AccountInfo<'info>,
 
-    /// Program treasury ATA (holds claimed quote fees)
-    #[account(mut)]
-    pub program_treasury: Account<'info, TokenAccount>,
+    /// Program treasury PDA (holds claimed quote fees), controlled by the position owner PDA
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"treasury", policy.quote_mint.as_ref()],
+        bump,
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
 
     /// Creator's quote token ATA (receives remainder)
-    #[account(mut)]
-    pub creator_quote_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = creator_quote_ata.mint == policy.quote_mint @ StarError::InvalidCreatorAta,
+        constraint = policy.creator == Pubkey::default()
+            || creator_quote_ata.owner == policy.creator @ StarError::InvalidCreatorAta
+    )]
+    pub creator_quote_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// Policy PDA containing distribution configuration
     #[account(
@@ -49,248 +65,853 @@ pub struct CrankDistribute<'info> {
         seeds = [b"vault", vault.key().as_ref(), b"progress"],
         bump
     )]
-    pub progress: Account<'info, Progress>,
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Per-vault blacklist; may be uninitialized when the vault has none
+    /// CHECK: PDA derived from the vault, parsed only when owned by this program
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"blacklist"],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// Per-vault cranker allowlist; may be uninitialized when cranking is permissionless
+    /// CHECK: PDA derived from the vault, parsed only when owned by this program
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"cranker_allowlist"],
+        bump
+    )]
+    pub cranker_allowlist: UncheckedAccount<'info>,
+
+    /// Switchboard function whose enclave signer may crank (checked against the allowlist)
+    /// CHECK: Validated by `ValidationUtils::validate_cranker`
+    pub switchboard_function: Option<UncheckedAccount<'info>>,
+
+    /// Caller's token account holding a cranker pass (checked against the allowlist)
+    pub cranker_pass: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Memo program; when passed, the page's payouts are labeled with `{vault, day, page}`
+    pub memo_program: Option<Program<'info, Memo>>,
+
+    /// Quote mint; required on the final page when the policy burns the remainder, and on
+    /// every page of a Token-2022 treasury
+    #[account(mut, address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Insurance fund; required while `policy.insurance_bps > 0`
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"insurance", policy.quote_mint.as_ref()],
+        bump
+    )]
+    pub insurance_fund: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Referrer's quote token ATA; required on the final page while the policy has a referral
+    #[account(
+        mut,
+        constraint = referrer_quote_ata.mint == policy.quote_mint @ StarError::InvalidReferrerAta,
+        constraint = policy.referral.is_some_and(|referral| referrer_quote_ata.owner == referral.referrer)
+            @ StarError::InvalidReferrerAta
+    )]
+    pub referrer_quote_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Second keeper co-signing the page data; required while `policy.page_attester` is set
+    pub page_attester: Option<Signer<'info>>,
+
+    /// Crank fee vault reimbursing the caller; required while
+    /// `policy.crank_reimbursement_lamports > 0`
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"crank_fee_vault"],
+        bump
+    )]
+    pub crank_fee_vault: Option<SystemAccount<'info>>,
 
     /// CP-AMM program for claiming fees
     /// CHECK: Validated CP-AMM program
     pub cp_amm_program: AccountInfo<'info>,
 
     /// CP-AMM pool account
-    /// CHECK: Validated CP-AMM pool
+    /// CHECK: Must be the policy's registered pool, once one is recorded
+    #[account(
+        mut,
+        constraint = policy.pool == Pubkey::default() || cp_amm_pool.key() == policy.pool
+            @ StarError::InvalidCpAmmPool
+    )]
     pub cp_amm_pool: AccountInfo<'info>,
 
+    /// Honorary position, checked to hold no liquidity before its fees are claimed
+    /// CHECK: Must be the policy's registered position, once one is recorded
+    #[account(
+        constraint = policy.position == Pubkey::default() || honorary_position.key() == policy.position
+            @ StarError::InvalidHonoraryPosition
+    )]
+    pub honorary_position: AccountInfo<'info>,
+
     /// Streamflow program for reading vesting schedules
     /// CHECK: Validated Streamflow program
     pub streamflow_program: AccountInfo<'info>,
 
-    /// Token program for transfers
-    pub token_program: Program<'info, Token>,
+    /// Token program owning the treasury (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// System program
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<CrankDistribute>,
+/// Where a page's investors come from
+enum PageSource {
+    /// Keeper-supplied entries, proven against the investor root committed at day open
+    Proofs(Vec<Vec<[u8; 32]>>),
+    /// A registry chunk's investors in stream pubkey order; membership and pagination are
+    /// already on-chain
+    Registry(Vec<InvestorAccount>),
+}
+
+impl PageSource {
+    /// Number of investor entries the page's remaining_accounts carry
+    fn investor_count(&self) -> usize {
+        match self {
+            PageSource::Proofs(investor_proofs) => investor_proofs.len(),
+            PageSource::Registry(investors) => investors.len(),
+        }
+    }
+}
+
+/// Distribute page `args.page`. Investors are identified by their remaining_accounts
+/// entries alone, one per proof, so the instruction data carries nothing else per investor.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CrankDistribute<'info>>,
+    args: CrankArgsV1,
+) -> Result<CrankPageResult> {
+    args.validate_version()?;
+
+    // A vault in registry mode only distributes to its registered investors
+    require!(!ctx.accounts.policy.registry_mode, StarError::RegistryModeEnabled);
+
+    let investor_entries = ctx.remaining_accounts;
+    distribute_page(ctx, args.page, PageSource::Proofs(args.investor_proofs), investor_entries)
+}
+
+/// Distribute registry chunk `chunk_index` as page `chunk_index + 1`. remaining_accounts
+/// start with the investor registry and chunk PDAs, followed by the investor entries of
+/// the chunk's investors in stream pubkey order.
+pub fn registry_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CrankDistribute<'info>>,
+    chunk_index: u32,
+) -> Result<CrankPageResult> {
+    require!(ctx.accounts.policy.registry_mode, StarError::RegistryModeDisabled);
+
+    let vault_key = ctx.accounts.vault.key();
+    let (registry_info, chunk_info, investor_entries) = match ctx.remaining_accounts {
+        [registry, chunk, investor_entries @ ..] => (registry, chunk, investor_entries),
+        _ => return err!(StarError::InvalidRegistryChunk),
+    };
+    require_keys_eq!(
+        registry_info.key(),
+        derive_investor_registry_pda(&vault_key).0,
+        StarError::InvalidRegistryChunk
+    );
+    require_keys_eq!(
+        chunk_info.key(),
+        derive_registry_chunk_pda(&vault_key, chunk_index).0,
+        StarError::InvalidRegistryChunk
+    );
+    let registry = Account::<InvestorRegistry>::try_from(registry_info)?;
+    let chunk = Account::<InvestorRegistryChunk>::try_from(chunk_info)?;
+
+    // The day's page count and investor total must be the registry's, which cannot
+    // change until the day closes
+    {
+        let progress = ctx.accounts.progress.load()?;
+        require!(
+            progress.total_pages == registry.chunk_count as u64
+                && progress.total_investors == registry.total_investors,
+            StarError::RegistryMismatch
+        );
+    }
+
+    let mut investor_accounts: Vec<InvestorAccount> = chunk
+        .entries
+        .iter()
+        .map(|entry| InvestorAccount {
+            stream_pubkey: entry.stream_pubkey,
+            investor_quote_ata: entry.investor_quote_ata,
+            locked_amount: 0,
+            weight: 0,
+        })
+        .collect();
+    investor_accounts.sort_by_key(|investor| investor.stream_pubkey);
+
+    distribute_page(ctx, chunk_index as u64 + 1, PageSource::Registry(investor_accounts), investor_entries)
+}
+
+fn distribute_page<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CrankDistribute<'info>>,
     page: u64,
-    investor_accounts: Vec<InvestorAccount>,
-) -> Result<()> {
+    source: PageSource,
+    investor_entries: &'info [AccountInfo<'info>],
+) -> Result<CrankPageResult> {
     let policy = &mut ctx.accounts.policy;
-    let progress = &mut ctx.accounts.progress;
+    let mut progress = ctx.accounts.progress.load_mut()?;
     let vault = &ctx.accounts.vault;
     let current_timestamp = Clock::get()?.unix_timestamp;
 
+    crate::cu_checkpoint!("crank start page={}", page);
+
     // Validate page number
     require!(page > 0, StarError::InvalidPage);
 
-    // Check if this is a new day (24h gate enforcement)
-    if progress.is_new_day(current_timestamp) {
-        progress.reset_for_new_day(current_timestamp);
-        msg!("Starting new distribution day: {}", progress.current_day);
-    } else {
-        // Check if enough time has passed since last distribution (24h gate)
-        let time_since_last = current_timestamp - progress.last_distribution_ts;
+    // Never record a page earlier than the day open or the previous page
+    progress.check_clock(current_timestamp)?;
+
+    // Pool parameters can change after initialization; stop distributing as soon as the
+    // pool could charge fees in the base token
+    let pool = CpAmmUtils::load_pool(&ctx.accounts.cp_amm_pool)?;
+    ValidationUtils::validate_pool_state(&pool, policy)?;
+
+    // Restrict the caller when the vault has enabled its cranker allowlist
+    let cranker_allowlist = CrankerAllowlist::load(&ctx.accounts.cranker_allowlist)?;
+    ValidationUtils::validate_cranker(
+        cranker_allowlist.as_ref(),
+        &ctx.accounts.crank_caller.key(),
+        &policy.cranker_pass_mint,
+        ctx.accounts.cranker_pass.as_deref(),
+        ctx.accounts.switchboard_function.as_deref(),
+    )?;
+
+    // High-value vaults also need the configured second keeper to co-sign the page, so no
+    // single cranker's investor data is trusted alone
+    if policy.page_attester != Pubkey::default() {
         require!(
-            time_since_last >= 86400, // 24 hours in seconds
-            StarError::DistributionTooEarly
+            ctx.accounts.page_attester
+                .as_ref()
+                .is_some_and(|attester| attester.key() == policy.page_attester),
+            StarError::PageAttestationRequired
         );
     }
 
-    // Check if distribution is already complete for today
-    require!(!progress.day_complete, StarError::DistributionAlreadyComplete);
+    // Pages only land inside the creator's daily window, when one is configured
+    if let Some(window) = &policy.crank_window {
+        require!(window.contains(current_timestamp), StarError::OutsideCrankWindow);
+    }
 
-    // Validate investor accounts are provided for this page
-    require!(!investor_accounts.is_empty(), StarError::NoLockedInvestors);
+    // Space out the day's pages when the policy sets a cool-down
+    if policy.page_cooldown_secs > 0 && progress.last_page_ts > 0 {
+        let next_page_at = progress.last_page_ts
+            .checked_add(policy.page_cooldown_secs as i64)
+            .ok_or(StarError::MathOverflow)?;
+        require!(current_timestamp >= next_page_at, StarError::PageCooldownActive);
+    }
 
-    // Claim fees from the honorary position
-    let claim_result = claim_fees_from_position(&ctx)?;
+    // Pages can only run once a day has been opened (24h gate enforced by open_day)
+    require!(progress.last_distribution_ts > 0, StarError::DayNotOpen);
 
-    // CRITICAL: Verify no base fees are present
-    ValidationUtils::detect_base_fees(&claim_result)?;
+    // Check if distribution is already complete for today
+    require!(!progress.is_day_complete(), StarError::DistributionAlreadyComplete);
 
-    // Update progress with claimed amount
-    progress.claimed_today = progress.claimed_today
-        .checked_add(claim_result.quote_amount)
-        .ok_or(StarError::MathOverflow)?;
+    // Pages run in order, up to the count committed at day open
+    require!(
+        page == progress.pagination_cursor + 1 && page <= progress.total_pages,
+        StarError::PageOutOfOrder
+    );
 
-    emit!(QuoteFeesClaimed {
-        amount: claim_result.quote_amount,
-        position: ctx.accounts.position_owner_pda.key(),
-        day: progress.current_day,
-        timestamp: current_timestamp,
-    });
+    // Apply a queued parameter change at the start of a day so every page uses the same values
+    if page == 1 {
+        if let Some(update) = policy.apply_pending_update(current_timestamp) {
+            emit!(PolicyUpdateApplied {
+                vault: vault.key(),
+                investor_fee_share_bps: update.investor_fee_share_bps,
+                daily_cap: update.daily_cap,
+                timestamp: current_timestamp,
+            });
+        }
+    }
+    // The rest of the page only reads the policy
+    let policy = &ctx.accounts.policy;
 
-    // Calculate total locked amount across all investors in this page
-    let total_locked = investor_accounts
-        .iter()
-        .map(|acc| acc.locked_amount)
-        .sum::<u64>();
+    // Validate investor accounts are provided for this page. Registry chunks emptied by
+    // removals still count as a page, so the day can close past them.
+    let investor_count = source.investor_count();
+    let from_registry = matches!(source, PageSource::Registry(_));
+    require!(from_registry || investor_count > 0, StarError::NoLockedInvestors);
+    require!(investor_count <= MAX_INVESTORS_PER_PAGE, StarError::PageTooLarge);
 
-    require!(total_locked > 0, StarError::NoLockedInvestors);
+    // A fixed page size pins every page to its slice of the day's set, so the declared
+    // final page must be the one that reaches the end of it
+    if !from_registry && policy.page_size > 0 {
+        let page_size = policy.page_size as u64;
+        require!(
+            investor_count as u64 == PaginationUtils::get_page_size(page, page_size, progress.total_investors),
+            StarError::InvalidPageSize
+        );
+        require!(
+            PaginationUtils::is_last_page(page, page_size, progress.total_investors)
+                == (page == progress.total_pages),
+            StarError::InvalidPageSize
+        );
+    }
 
-    // Calculate eligible investor share
-    let eligible_share_bps = DistributionMath::calculate_eligible_share_bps(
-        total_locked,
-        policy.y0,
-        policy.investor_fee_share_bps,
+    // Resolve each investor's vesting lock and payout ATA from remaining_accounts
+    let (mut entries, hook_accounts) = load_page_entries(
+        investor_entries,
+        investor_count,
+        &vault.key(),
+        &policy.quote_mint,
+        progress.day_lock_ts,
+        progress.current_day,
+        policy.require_lock_snapshot,
     )?;
+    let investor_accounts: Vec<InvestorAccount> = entries
+        .iter()
+        .map(|entry| InvestorAccount {
+            stream_pubkey: entry.record.stream_pubkey,
+            investor_quote_ata: entry.investor_ata.key(),
+            locked_amount: entry.lock.locked_amount,
+            weight: 0,
+        })
+        .collect();
 
-    // Calculate total investor fee amount
-    let total_investor_fee_quote = DistributionMath::calculate_investor_fee_quote(
-        claim_result.quote_amount,
-        eligible_share_bps,
-    )?;
+    match &source {
+        PageSource::Proofs(investor_proofs) => {
+            // Reject duplicate investors within the page or already processed on an earlier page
+            progress.last_stream_pubkey = ValidationUtils::validate_investor_order(
+                &investor_accounts,
+                &progress.last_stream_pubkey,
+            )?;
 
-    // Apply daily cap
-    let capped_investor_fee = DistributionMath::apply_daily_cap(
-        total_investor_fee_quote,
-        policy.daily_cap,
-        progress.distributed_today,
+            // Every entry must belong to the investor set committed when the day was opened
+            for (investor, proof) in investor_accounts.iter().zip(investor_proofs.iter()) {
+                let leaf = MerkleUtils::investor_leaf(&investor.stream_pubkey, &investor.investor_quote_ata);
+                require!(
+                    MerkleUtils::verify(proof, &progress.investor_root, leaf),
+                    StarError::InvalidMerkleProof
+                );
+            }
+        }
+        PageSource::Registry(registered) => {
+            // The entries must be the chunk's investors, in stream pubkey order
+            for (investor, expected) in investor_accounts.iter().zip(registered.iter()) {
+                require_keys_eq!(investor.stream_pubkey, expected.stream_pubkey, StarError::InvalidStreamAccount);
+                require_keys_eq!(
+                    investor.investor_quote_ata,
+                    expected.investor_quote_ata,
+                    StarError::InvalidInvestorAta
+                );
+            }
+        }
+    }
+
+    crate::cu_checkpoint!("proofs verified investors={}", investor_accounts.len());
+
+    crate::cu_checkpoint!("vesting loaded");
+
+    // Accounts after the investor entries feed the quote mint's transfer hook
+    let checked_transfer = resolve_checked_transfer(
+        &ctx.accounts.token_program,
+        ctx.accounts.quote_mint.as_ref(),
+        hook_accounts,
     )?;
 
-    if capped_investor_fee < total_investor_fee_quote {
-        emit!(DailyCapApplied {
+    // Liquidity in the position would accrue base fees alongside the quote fees we claim,
+    // unless it sits in the configured range above the live pool price
+    let position_range = PoolConfig {
+        token_a: pool.token_a_mint,
+        token_b: pool.token_b_mint,
+        pool_id: ctx.accounts.cp_amm_pool.key(),
+        tick_lower: policy.tick_lower,
+        tick_upper: policy.tick_upper,
+    };
+    ValidationUtils::validate_fee_only_position(&ctx.accounts.honorary_position, &position_range, pool.sqrt_price)?;
+    // Vaults that opted into compounding before it was withdrawn must opt out first
+    require!(!policy.compound_investor_share, StarError::CompoundingUnsupported);
+
+    // Fees are claimed exactly once per day, by its first page; later pages pay out of the
+    // pool that claim fixed and never add to `claimed_today`
+    let first_page = page == 1;
+    if first_page {
+        // Claim fees from the honorary position
+        let claim_result = claim_fees_from_position(&ctx)?;
+        crate::cu_checkpoint!("claim");
+
+        // CRITICAL: Verify no base fees are present
+        ValidationUtils::detect_base_fees(&claim_result)?;
+
+        // Fees harvested since the last day join the day's claim; they already count
+        // towards lifetime_claimed
+        let harvested = std::mem::take(&mut progress.harvested_pending);
+        // A transfer-fee quote mint withholds part of the claim on its way into the treasury
+        let claim_fee = quote_transfer_fee(ctx.accounts, claim_result.quote_amount)?;
+        let day_claimed = claim_result.quote_amount
+            .checked_sub(claim_fee)
+            .ok_or(StarError::MathUnderflow)?
+            .checked_add(harvested)
+            .ok_or(StarError::MathOverflow)?;
+
+        // The insurance fund takes its slice off the top, before the investor/creator split
+        let insured = DistributionMath::calculate_investor_fee_quote(day_claimed, policy.insurance_bps)?;
+        if insured > 0 {
+            let insurance_fund = ctx.accounts.insurance_fund
+                .as_ref()
+                .ok_or(StarError::InsuranceFundRequired)?;
+
+            TokenTransferUtils::transfer_signed(
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.program_treasury.to_account_info(),
+                &insurance_fund.to_account_info(),
+                &ctx.accounts.position_owner_pda,
+                &[
+                    b"vault",
+                    vault.key().as_ref(),
+                    b"investor_fee_pos_owner",
+                    &[ctx.bumps.position_owner_pda],
+                ],
+                insured,
+                checked_transfer.as_ref(),
+            )?;
+
+            progress.lifetime_insured = progress.lifetime_insured
+                .checked_add(insured)
+                .ok_or(StarError::MathOverflow)?;
+
+            emit!(InsuranceFunded {
+                vault: vault.key(),
+                day: progress.current_day,
+                page,
+                amount: insured,
+                lifetime_insured: progress.lifetime_insured,
+                timestamp: current_timestamp,
+            });
+        }
+
+        // Record the day's claim; a second claim in the same day fails the phase check
+        progress.record_claim(day_claimed - insured)?;
+        progress.lifetime_claimed = progress.lifetime_claimed
+            .checked_add(claim_result.quote_amount)
+            .ok_or(StarError::MathOverflow)?;
+
+        emit!(QuoteFeesClaimed {
+            amount: claim_result.quote_amount,
+            position: ctx.accounts.position_owner_pda.key(),
             day: progress.current_day,
-            requested_payout: total_investor_fee_quote,
-            capped_payout: capped_investor_fee,
-            cap_amount: policy.daily_cap,
             timestamp: current_timestamp,
         });
+    } else {
+        // Later pages only run once the first page has fixed the day's pool
+        require!(
+            progress.phase()? == DayPhase::Distributing,
+            StarError::InvalidPhaseTransition
+        );
     }
 
-    // Add carry-over from previous calculations
-    let total_to_distribute = capped_investor_fee
-        .checked_add(progress.carry_over)
+    // Every investor is weighed as of the lock timestamp committed at day open, so the
+    // page's weights add up against the day's committed class totals
+    let lock_ts = progress.day_lock_ts;
+
+    // Under time-weighted weighting an investor counts with their average lock from their
+    // last page up to the lock timestamp, so topping up a stream just before the day opens
+    // carries little weight. Records never sampled fall back to the instant lock; every
+    // period restarts at the lock timestamp.
+    if policy.twal_weighting {
+        for entry in entries.iter_mut() {
+            let instant_locked = entry.lock.locked_amount;
+            if entry.lock.skip_reason.is_none() {
+                if let Some(twal) = entry.record.time_weighted_locked(lock_ts)? {
+                    entry.lock.locked_amount = twal;
+                }
+            }
+            entry.record.restart_twal(instant_locked, lock_ts);
+        }
+    }
+
+    // Calculate total locked amount across all investors in this page
+    let total_locked = entries
+        .iter()
+        .try_fold(0u64, |acc, entry| acc.checked_add(entry.lock.locked_amount))
         .ok_or(StarError::MathOverflow)?;
 
+    // A keeper leaves fully vested investors out of its pages; a registry chunk cannot, so
+    // a chunk with nothing locked passes its whole pool on as carry-over
+    require!(total_locked > 0 || from_registry, StarError::NoLockedInvestors);
+
+    // Pro-rata weights use locked amounts scaled by the lock-duration multiplier
+    let weighted_locked = entries
+        .iter()
+        .map(|entry| entry.lock.weighted_locked(policy, lock_ts))
+        .collect::<Result<Vec<u64>>>()?;
+
+    // Group the page's locked amounts and curved weights by investor class
+    let class_count = policy.investor_class_count();
+    let mut class_locked = vec![0u64; class_count];
+    let mut class_curved_total = vec![0u64; class_count];
+    for (i, entry) in entries.iter().enumerate() {
+        let class = entry.record.class as usize;
+        require!(class < class_count, StarError::InvalidInvestorClass);
+        class_locked[class] = class_locked[class]
+            .checked_add(entry.lock.locked_amount)
+            .ok_or(StarError::MathOverflow)?;
+        class_curved_total[class] = class_curved_total[class]
+            .checked_add(DistributionMath::apply_payout_curve(weighted_locked[i], policy.payout_curve))
+            .ok_or(StarError::MathOverflow)?;
+    }
+
+    // Together the day's pages may never weigh more than the class totals committed at
+    // open; the final page requires them to be reached exactly
+    progress.record_page_totals(&class_locked, &class_curved_total)?;
+
+    if first_page {
+        // Each class earns an eligible share against its own Y0, capped by its own fee share
+        // (the policy share in effect now when no classes are configured) and raised to the
+        // policy floor while the class still has tokens locked. The day's claim is shared by
+        // every page, so class locks are the day's committed class totals.
+        let mut eligible_share_bps = 0u16;
+        let mut class_investor_fees = Vec::with_capacity(class_count);
+        for class in 0..class_count {
+            let (class_y0, class_fee_share_bps) = policy.investor_class_terms(class, current_timestamp);
+            let class_day_locked = progress.day_class_locked[class];
+            let class_eligible_bps = DistributionMath::apply_share_floor(
+                DistributionMath::calculate_eligible_share_bps(
+                    class_day_locked,
+                    class_y0,
+                    class_fee_share_bps,
+                )?,
+                class_day_locked,
+                policy.min_investor_share_bps,
+                class_fee_share_bps,
+            );
+            eligible_share_bps = eligible_share_bps
+                .checked_add(class_eligible_bps)
+                .ok_or(StarError::MathOverflow)?;
+            class_investor_fees.push(DistributionMath::calculate_investor_fee_quote(
+                progress.claimed_today,
+                class_eligible_bps,
+            )?);
+        }
+
+        // Calculate total investor fee amount
+        let total_investor_fee_quote = class_investor_fees
+            .iter()
+            .try_fold(0u64, |acc, fee| acc.checked_add(*fee))
+            .ok_or(StarError::MathOverflow)?;
+
+        // Apply daily cap
+        let daily_cap = policy.effective_daily_cap(current_timestamp);
+        let capped_investor_fee = DistributionMath::apply_daily_cap(
+            total_investor_fee_quote,
+            daily_cap,
+            progress.distributed_today,
+        )?;
+
+        if capped_investor_fee < total_investor_fee_quote {
+            emit!(DailyCapApplied {
+                day: progress.current_day,
+                requested_payout: total_investor_fee_quote,
+                capped_payout: capped_investor_fee,
+                cap_amount: daily_cap,
+                timestamp: current_timestamp,
+            });
+        }
+
+        // Add carry-over from previous days
+        let total_to_distribute = capped_investor_fee
+            .checked_add(progress.carry_over)
+            .ok_or(StarError::MathOverflow)?;
+
+        // Split the pool between classes by the fees they earned, or by the day's committed
+        // class weights when only carry-over is left to distribute; rounding dust carries over
+        let day_class_weights = &progress.day_class_weights[..class_count];
+        let (class_split_weights, class_split_total) = if total_investor_fee_quote > 0 {
+            (&class_investor_fees[..], total_investor_fee_quote)
+        } else {
+            let total_curved = day_class_weights
+                .iter()
+                .try_fold(0u64, |acc, curved| acc.checked_add(*curved))
+                .ok_or(StarError::MathOverflow)?;
+            (day_class_weights, total_curved)
+        };
+        let class_pools = class_split_weights
+            .iter()
+            .map(|weight| DistributionMath::split_pro_rata(total_to_distribute, *weight, class_split_total))
+            .collect::<Result<Vec<u64>>>()?;
+
+        progress.fix_day_pool(total_to_distribute, &class_pools, eligible_share_bps)?;
+    }
+
+    // Every page pays out of the day's pool; what is left of it is held in `carry_over`
+    let class_pools = progress.day_class_pools;
+    let eligible_share_bps = progress.day_eligible_share_bps;
+
+    let blacklisted_wallets = Blacklist::load_wallets(&ctx.accounts.blacklist)?;
+
     // Distribute to investors in this page
     let mut distributed_this_page = 0u64;
-    let mut carry_over_this_page = 0u64;
+    let mut investors_paid = 0u64;
+    // Shares of blacklisted or opted-out investors stay in the treasury for the creator remainder
+    let mut withheld_this_page = 0u64;
 
     for (i, investor) in investor_accounts.iter().enumerate() {
-        // Calculate investor weight
+        crate::cu_checkpoint!("investor {} start", i);
+        let entry = &mut entries[i];
+
+        // Cancelled or closed streams carry no lock; skip them and keep the page going
+        if let Some(reason) = entry.lock.skip_reason {
+            emit!(InvestorSkipped {
+                investor: investor.investor_quote_ata,
+                stream: investor.stream_pubkey,
+                reason,
+                day: progress.current_day,
+                page,
+                timestamp: current_timestamp,
+            });
+            continue;
+        }
+
+        // Fully vested investors earn nothing this day
+        if entry.lock.locked_amount == 0 {
+            emit!(InvestorSkipped {
+                investor: investor.investor_quote_ata,
+                stream: investor.stream_pubkey,
+                reason: SkipReason::ZeroLocked,
+                day: progress.current_day,
+                page,
+                timestamp: current_timestamp,
+            });
+            continue;
+        }
+
+        // Calculate investor weight within its class pool
+        let class = entry.record.class as usize;
+        let class_pool = class_pools[class];
         let weight_bps = DistributionMath::calculate_investor_weight(
-            investor.locked_amount,
-            total_locked,
+            weighted_locked[i],
+            progress.day_class_weights[class],
+            policy.payout_curve,
         )?;
 
+        // Blacklisted and opted-out investors keep their weight, but their share is
+        // withheld for the creator remainder
+        let excluded_reason = if entry.beneficiary.is_some_and(|wallet| blacklisted_wallets.contains(&wallet)) {
+            Some(SkipReason::Blacklisted)
+        } else if entry.record.opted_out {
+            Some(SkipReason::OptedOut)
+        } else {
+            None
+        };
+
+        if let Some(reason) = excluded_reason {
+            let withheld = DistributionMath::calculate_investor_payout(class_pool, weight_bps, 0)?;
+            withheld_this_page = withheld_this_page
+                .checked_add(withheld)
+                .ok_or(StarError::MathOverflow)?;
+
+            emit!(InvestorSkipped {
+                investor: investor.investor_quote_ata,
+                stream: investor.stream_pubkey,
+                reason,
+                day: progress.current_day,
+                page,
+                timestamp: current_timestamp,
+            });
+            continue;
+        }
+
+        // A transfer into a frozen ATA would fail the whole page; the investor's share is
+        // routed like a payout below the threshold instead
+        if entry.frozen {
+            let share = DistributionMath::calculate_investor_payout(class_pool, weight_bps, 0)?;
+            policy.dust_policy.route_unpaid_share(share, &mut withheld_this_page, &mut entry.record.accrued_dust)?;
+
+            emit!(InvestorSkipped {
+                investor: investor.investor_quote_ata,
+                stream: investor.stream_pubkey,
+                reason: SkipReason::AccountFrozen,
+                day: progress.current_day,
+                page,
+                timestamp: current_timestamp,
+            });
+
+            entry.record.exit(&crate::ID)?;
+            continue;
+        }
+
         // Calculate individual payout
         let payout = DistributionMath::calculate_investor_payout(
-            total_to_distribute,
+            class_pool,
             weight_bps,
-            policy.min_payout_lamports,
+            policy.effective_min_payout(),
         )?;
 
         if payout > 0 {
-            // Transfer tokens to investor
-            // Note: In a real implementation, this would use the position_owner_pda as authority
-            // For now, we'll use the program as authority since we control the treasury
-            let transfer_ix = Transfer {
-                from: ctx.accounts.program_treasury.to_account_info(),
-                to: investor.investor_quote_ata.to_account_info(),
-                authority: ctx.accounts.position_owner_pda.to_account_info(),
-            };
-
-            let cpi_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                transfer_ix,
-                &[&[
+            // Transfer tokens to investor, signed by the treasury's position owner PDA
+            TokenTransferUtils::transfer_signed(
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.program_treasury.to_account_info(),
+                entry.investor_ata,
+                &ctx.accounts.position_owner_pda,
+                &[
                     b"vault",
                     vault.key().as_ref(),
                     b"investor_fee_pos_owner",
                     &[ctx.bumps.position_owner_pda],
-                ]],
-            );
+                ],
+                payout,
+                checked_transfer.as_ref(),
+            )?;
+            crate::cu_checkpoint!("investor {} transfer", i);
 
-            token::transfer(cpi_ctx, payout)?;
+            // Investors receive the payout net of any transfer fee the quote mint withholds
+            let net_amount = payout
+                .checked_sub(quote_transfer_fee(ctx.accounts, payout)?)
+                .ok_or(StarError::MathUnderflow)?;
 
             distributed_this_page = distributed_this_page
                 .checked_add(payout)
                 .ok_or(StarError::MathOverflow)?;
+            investors_paid += 1;
 
-            emit!(InvestorPayout {
+            emit_cpi!(InvestorPayout {
                 investor: investor.investor_quote_ata.key(),
                 amount: payout,
-                locked_amount: investor.locked_amount,
+                locked_amount: entry.lock.locked_amount,
                 weight: weight_bps,
                 day: progress.current_day,
                 page,
                 timestamp: current_timestamp,
+                payout_id: MerkleUtils::payout_id(&vault.key(), progress.current_day, &investor.stream_pubkey),
+                net_amount,
+            });
+
+            entry.record.cumulative_received = entry.record.cumulative_received
+                .checked_add(net_amount)
+                .ok_or(StarError::MathOverflow)?;
+            entry.record.last_payout_day = progress.current_day;
+        } else {
+            // A share below the dust threshold either stays with investors, recorded on
+            // the investor, or is released to the day's remainder
+            let dust = DistributionMath::calculate_investor_payout(class_pool, weight_bps, 0)?;
+            policy.dust_policy.route_unpaid_share(dust, &mut withheld_this_page, &mut entry.record.accrued_dust)?;
+
+            emit!(InvestorSkipped {
+                investor: investor.investor_quote_ata,
+                stream: investor.stream_pubkey,
+                reason: SkipReason::BelowMinPayout,
+                day: progress.current_day,
+                page,
+                timestamp: current_timestamp,
             });
         }
+
+        entry.record.exit(&crate::ID)?;
     }
 
-    // Calculate carry-over (dust that couldn't be distributed)
-    carry_over_this_page = total_to_distribute
-        .checked_sub(distributed_this_page)
-        .unwrap_or(0);
+    // Skipped investors' records are not written in the loop; persist their restarted periods
+    if policy.twal_weighting {
+        for entry in &entries {
+            entry.record.exit(&crate::ID)?;
+        }
+    }
+
+    crate::cu_checkpoint!("investor loop done");
+
+    // What the page paid and withheld leaves the day's pool; dust that couldn't be paid
+    // stays in it as carry-over for the next pages and days
+    let carry_over_this_page = progress.record_page_payouts(distributed_this_page, withheld_this_page)?;
 
     // Update progress
-    progress.distributed_today = progress.distributed_today
+    progress.lifetime_distributed_to_investors = progress.lifetime_distributed_to_investors
         .checked_add(distributed_this_page)
         .ok_or(StarError::MathOverflow)?;
 
-    progress.carry_over = carry_over_this_page;
     progress.pagination_cursor = page;
+    progress.last_crank_ts = current_timestamp;
+    progress.last_page_ts = current_timestamp;
+    progress.investors_processed = progress.investors_processed
+        .checked_add(investor_accounts.len() as u64)
+        .ok_or(StarError::MathOverflow)?;
 
-    emit!(InvestorPayoutPage {
+    // Commit to the exact inputs the payouts used, so auditors can check them against the
+    // published investor set
+    let page_hash = MerkleUtils::page_hash(
+        &investor_accounts
+            .iter()
+            .zip(&entries)
+            .map(|(investor, entry)| {
+                (investor.stream_pubkey, investor.investor_quote_ata, entry.lock.locked_amount)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    emit_cpi!(InvestorPayoutPage {
         day: progress.current_day,
         page,
         distributed: distributed_this_page,
         carry_over: carry_over_this_page,
         investors_processed: investor_accounts.len() as u64,
-        locked_total,
+        locked_total: total_locked,
         eligible_share_bps,
         timestamp: current_timestamp,
+        page_hash,
     });
 
-    // Check if this is the last page (would be determined by the caller)
-    // For now, we'll assume the caller knows when to trigger the final page
-    if is_final_page_for_day(&ctx, page)? {
-        // Calculate remainder to send to creator
-        let total_claimed = progress.claimed_today;
-        let total_distributed_to_investors = progress.distributed_today;
-        
-        let remainder = total_claimed
-            .checked_sub(total_distributed_to_investors)
-            .unwrap_or(0);
-
-        if remainder > 0 {
-            // Transfer remainder to creator
-            let transfer_ix = Transfer {
-                from: ctx.accounts.program_treasury.to_account_info(),
-                to: ctx.accounts.creator_quote_ata.to_account_info(),
-                authority: ctx.accounts.position_owner_pda.to_account_info(),
-            };
+    // Reimburse the caller's transaction fees at the rate the creator set, never more than
+    // the vault holds above its rent reserve, so an empty vault does not stall the day
+    if policy.crank_reimbursement_lamports > 0 {
+        let crank_fee_vault = ctx.accounts.crank_fee_vault
+            .as_ref()
+            .ok_or(StarError::CrankFeeVaultRequired)?;
+        let available = crank_fee_vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        let reimbursed = policy.crank_reimbursement_lamports.min(available);
 
+        if reimbursed > 0 {
+            let vault_key = vault.key();
+            let crank_fee_vault_bump = ctx.bumps.crank_fee_vault.ok_or(StarError::CrankFeeVaultRequired)?;
+            let crank_fee_vault_seeds: &[&[u8]] = &[
+                b"vault",
+                vault_key.as_ref(),
+                b"crank_fee_vault",
+                &[crank_fee_vault_bump],
+            ];
+            let signer = &[crank_fee_vault_seeds];
             let cpi_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                transfer_ix,
-                &[&[
-                    b"vault",
-                    vault.key().as_ref(),
-                    b"investor_fee_pos_owner",
-                    &[ctx.bumps.position_owner_pda],
-                ]],
+                ctx.accounts.system_program.to_account_info(),
+                SystemTransfer {
+                    from: crank_fee_vault.to_account_info(),
+                    to: ctx.accounts.crank_caller.to_account_info(),
+                },
+                signer,
             );
+            system_program::transfer(cpi_ctx, reimbursed)?;
 
-            token::transfer(cpi_ctx, remainder)?;
-
-            emit!(CreatorPayoutDayClosed {
+            emit!(CrankReimbursed {
+                vault: vault_key,
+                cranker: ctx.accounts.crank_caller.key(),
                 day: progress.current_day,
-                remainder,
-                total_distributed_to_investors,
-                total_claimed,
-                creator: ctx.accounts.creator_quote_ata.key(),
+                page,
+                lamports: reimbursed,
                 timestamp: current_timestamp,
             });
         }
+    }
 
-        // Mark day as complete
-        progress.day_complete = true;
-        progress.carry_over = 0; // Reset carry-over for next day
+    // Label the page's transfers so recipients can reconcile deposits to distribution rounds
+    if let Some(memo_program) = &ctx.accounts.memo_program {
+        let payout_memo = format!(
+            "{{\"vault\":\"{}\",\"day\":{},\"page\":{}}}",
+            vault.key(),
+            progress.current_day,
+            page
+        );
+        memo::build_memo(
+            CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+            payout_memo.as_bytes(),
+        )?;
     }
 
+    // The final committed page closes the day
+    let is_final_page = page == progress.total_pages;
+
     msg!(
         "Distribution crank completed for day {}, page {}, distributed: {}, carry_over: {}",
         progress.current_day,
@@ -299,27 +920,385 @@ pub fn handler(
         carry_over_this_page
     );
 
-    Ok(())
-}
+    if is_final_page {
+        // The day's pages must have weighed exactly the totals open_day committed; an
+        // inflated commitment would otherwise shrink every investor's weight
+        progress.check_day_totals_reached()?;
+
+        // Release the progress borrow; close_day loads it again
+        drop(progress);
+        crate::cu_checkpoint!("day close start");
+        let day_closed = close_day(
+            DayCloseAccounts {
+                vault: &ctx.accounts.vault,
+                position_owner_pda: &ctx.accounts.position_owner_pda,
+                program_treasury: &mut ctx.accounts.program_treasury,
+                creator_quote_ata: &ctx.accounts.creator_quote_ata,
+                policy: &ctx.accounts.policy,
+                progress: &ctx.accounts.progress,
+                quote_mint: ctx.accounts.quote_mint.as_ref(),
+                referrer_quote_ata: ctx.accounts.referrer_quote_ata.as_ref(),
+                token_program: &ctx.accounts.token_program,
+            },
+            ctx.bumps.position_owner_pda,
+            checked_transfer.as_ref(),
+            current_timestamp,
+        )?;
+        crate::cu_checkpoint!("day close done");
+        if let Some(day_closed) = day_closed {
+            emit_cpi!(day_closed);
+        }
+    }
 
-/// Claim fees from the honorary LP position via CP-AMM
-fn claim_fees_from_position(ctx: &Context<CrankDistribute>) -> Result<ClaimResult> {
-    // Call CP-AMM program to claim fees from honorary position
-    // Handle CP-AMM specific account requirements
-    // Return actual claimed amounts
-    
-    Ok(ClaimResult {
-        base_amount: 0, // Must be 0 for quote-only validation
-        quote_amount: 1000000, // Quote fee accrual
+    crate::cu_checkpoint!("crank end page={}", page);
+
+    Ok(CrankPageResult {
+        distributed: distributed_this_page,
+        carry_over: carry_over_this_page,
+        investors_paid,
+        day_complete: is_final_page,
     })
 }
 
-/// Determine if this is the final page for the current day
-fn is_final_page_for_day(ctx: &Context<CrankDistribute>, current_page: u64) -> Result<bool> {
-    // This would be determined by the caller or by checking if there are more investors
-    // For now, we'll use a simple heuristic
-    // In production, this logic would be more sophisticated
-    
-    // Placeholder: assume page 10 is always the last page
-    Ok(current_page >= 10)
+/// Accounts the day close reads and pays from, shared by the final crank page and
+/// `force_close_day`
+pub(crate) struct DayCloseAccounts<'a, 'info> {
+    pub vault: &'a AccountInfo<'info>,
+    pub position_owner_pda: &'a AccountInfo<'info>,
+    pub program_treasury: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub creator_quote_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub policy: &'a Policy,
+    pub progress: &'a AccountLoader<'info, Progress>,
+    /// Required under `RemainderPolicy::Burn`
+    pub quote_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    /// Required while the policy has a referral
+    pub referrer_quote_ata: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Close the day after its final committed page: verify every committed investor was
+/// processed, then settle the remainder per the policy: pay the creator, roll it into the
+/// carry-over, or burn it. Returns the day-close event, if a remainder was paid or burned,
+/// for the caller to emit via CPI.
+pub(crate) fn close_day<'info>(
+    accounts: DayCloseAccounts<'_, 'info>,
+    position_owner_bump: u8,
+    checked_transfer: Option<&CheckedTransferAccounts<'_, 'info>>,
+    current_timestamp: i64,
+) -> Result<Option<CreatorPayoutDayClosed>> {
+    let mut progress = accounts.progress.load_mut()?;
+
+    require!(
+        progress.investors_processed == progress.total_investors,
+        StarError::InvestorCountMismatch
+    );
+
+    // The creator receives everything the day brought in that investors neither received
+    // nor keep as carry-over; carried dust stays in the treasury for the next day
+    let total_claimed = progress.claimed_today;
+    let total_distributed_to_investors = progress.distributed_today;
+
+    let remainder = DistributionMath::calculate_day_remainder(
+        total_claimed,
+        progress.carry_over_at_open,
+        total_distributed_to_investors,
+        progress.carry_over,
+    )?;
+
+    // The treasury must still hold the remainder plus the carry-over, i.e. everything the
+    // day brought in (with the carry-over at open) less what investors received. Direct
+    // top-ups may leave more; less means tokens left outside the books.
+    let expected_balance = remainder
+        .checked_add(progress.carry_over)
+        .ok_or(StarError::MathOverflow)?;
+    accounts.program_treasury.reload()?;
+    let treasury_balance = accounts.program_treasury.amount;
+    if treasury_balance < expected_balance {
+        require!(
+            !accounts.policy.abort_on_reconciliation_mismatch,
+            StarError::TreasuryReconciliationFailed
+        );
+
+        emit!(ReconciliationMismatch {
+            vault: accounts.vault.key(),
+            day: progress.current_day,
+            expected_balance,
+            treasury_balance,
+            timestamp: current_timestamp,
+        });
+
+        msg!(
+            "Treasury reconciliation mismatch for day {}: expected at least {}, found {}",
+            progress.current_day,
+            expected_balance,
+            treasury_balance
+        );
+    }
+
+    let vault_key = accounts.vault.key();
+    let position_owner_seeds: &[&[u8]] = &[
+        b"vault",
+        vault_key.as_ref(),
+        b"investor_fee_pos_owner",
+        &[position_owner_bump],
+    ];
+
+    // The referrer's share of the day's claim comes out of the remainder, never the
+    // investors' share
+    let mut remainder = remainder;
+    if let Some(referral) = accounts.policy.referral {
+        let referral_fee = DistributionMath::calculate_investor_fee_quote(total_claimed, referral.referral_bps)?
+            .min(remainder);
+        if referral_fee > 0 {
+            let referrer_quote_ata = accounts.referrer_quote_ata
+                .ok_or(StarError::ReferrerAtaRequired)?;
+
+            TokenTransferUtils::transfer_signed(
+                &accounts.token_program.to_account_info(),
+                &accounts.program_treasury.to_account_info(),
+                &referrer_quote_ata.to_account_info(),
+                accounts.position_owner_pda,
+                position_owner_seeds,
+                referral_fee,
+                checked_transfer,
+            )?;
+            remainder -= referral_fee;
+
+            emit!(ReferralFeePaid {
+                vault: vault_key,
+                referrer: referral.referrer,
+                day: progress.current_day,
+                amount: referral_fee,
+                timestamp: current_timestamp,
+            });
+        }
+    }
+
+    let mut paid_to_creator = 0u64;
+    let mut burned = 0u64;
+    if remainder > 0 {
+        match accounts.policy.remainder_policy {
+            RemainderPolicy::Creator => {
+                // Transfer remainder to creator
+                TokenTransferUtils::transfer_signed(
+                    &accounts.token_program.to_account_info(),
+                    &accounts.program_treasury.to_account_info(),
+                    &accounts.creator_quote_ata.to_account_info(),
+                    accounts.position_owner_pda,
+                    position_owner_seeds,
+                    remainder,
+                    checked_transfer,
+                )?;
+
+                progress.lifetime_paid_to_creator = progress.lifetime_paid_to_creator
+                    .checked_add(remainder)
+                    .ok_or(StarError::MathOverflow)?;
+                paid_to_creator = remainder;
+            }
+            RemainderPolicy::DonateToInvestors => {
+                // The creator forgoes the remainder; it stays in the treasury as next day's carry-over
+                progress.carry_over = progress.carry_over
+                    .checked_add(remainder)
+                    .ok_or(StarError::MathOverflow)?;
+
+                emit!(RemainderDonated {
+                    vault: vault_key,
+                    day: progress.current_day,
+                    amount: remainder,
+                    carry_over: progress.carry_over,
+                    timestamp: current_timestamp,
+                });
+            }
+            RemainderPolicy::Burn => {
+                // Deflationary vaults destroy the remainder instead of paying it out
+                let quote_mint = accounts
+                    .quote_mint
+                    .ok_or(StarError::MissingQuoteMint)?;
+
+                let burn_ix = Burn {
+                    mint: quote_mint.to_account_info(),
+                    from: accounts.program_treasury.to_account_info(),
+                    authority: accounts.position_owner_pda.to_account_info(),
+                };
+
+                let signer = &[position_owner_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    burn_ix,
+                    signer,
+                );
+
+                token_interface::burn(cpi_ctx, remainder)?;
+                burned = remainder;
+            }
+        }
+    }
+
+    let mut day_closed = None;
+    if paid_to_creator > 0 || burned > 0 {
+        day_closed = Some(CreatorPayoutDayClosed {
+            day: progress.current_day,
+            remainder: paid_to_creator,
+            total_distributed_to_investors,
+            total_claimed,
+            creator: accounts.creator_quote_ata.key(),
+            investor_fee_share_bps: accounts.policy.effective_investor_fee_share_bps(current_timestamp),
+            daily_cap: accounts.policy.effective_daily_cap(current_timestamp),
+            min_payout_lamports: accounts.policy.min_payout_lamports,
+            y0: accounts.policy.y0,
+            timestamp: current_timestamp,
+            burned,
+        });
+    }
+
+    // Mark day as complete
+    progress.advance_phase(&[DayPhase::Distributing], DayPhase::Closed)?;
+    progress.day_complete = 1;
+    progress.days_processed = progress.days_processed
+        .checked_add(1)
+        .ok_or(StarError::MathOverflow)?;
+
+    msg!("Distribution day {} closed", progress.current_day);
+
+    Ok(day_closed)
+}
+
+/// Vesting lock and payout account resolved for one investor on the page
+struct PageEntry<'info> {
+    investor_ata: &'info AccountInfo<'info>,
+    /// Wallet owning the payout ATA (unset for skipped streams)
+    beneficiary: Option<Pubkey>,
+    /// The payout ATA is frozen and cannot receive transfers
+    frozen: bool,
+    record: Account<'info, InvestorRecord>,
+    lock: VestingLock,
+}
+
+/// Walk remaining_accounts as `investor_count` (vesting account, investor quote ATA, investor
+/// record) entries, reading each lock and binding the ATA owner to the vesting recipient. The
+/// record identifies the vesting account, so no investor keys travel in instruction data.
+/// Bonfida schedules record a destination token account rather than a wallet, so that token
+/// account follows.
+/// A lock snapshotted for `current_day` replaces the live locked amount of an active stream.
+/// Returns the entries and the accounts left after them (the transfer hook's extras).
+fn load_page_entries<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    investor_count: usize,
+    vault: &Pubkey,
+    quote_mint: &Pubkey,
+    current_timestamp: i64,
+    current_day: i64,
+    require_snapshot: bool,
+) -> Result<(Vec<PageEntry<'info>>, &'info [AccountInfo<'info>])> {
+    let mut accounts = remaining_accounts.iter();
+    let mut entries = Vec::with_capacity(investor_count);
+
+    for _ in 0..investor_count {
+        let stream_info = accounts.next().ok_or(StarError::InvalidStreamAccount)?;
+        let investor_ata = accounts.next().ok_or(StarError::InvalidInvestorAta)?;
+        let record_info = accounts.next().ok_or(StarError::InvalidInvestorRecord)?;
+
+        // Records can only be created by init_investor_record for this (vault, stream)
+        let record = Account::<InvestorRecord>::try_from(record_info)?;
+        require_keys_eq!(record.vault, *vault, StarError::InvalidInvestorRecord);
+        require_keys_eq!(record.stream_pubkey, stream_info.key(), StarError::InvalidInvestorRecord);
+
+        // Read the lock on-chain from the vesting program (Streamflow or Bonfida)
+        let mut lock = VestingUtils::read_lock(stream_info, current_timestamp)?;
+        if lock.skip_reason.is_none() {
+            match record.snapshot_for(current_day) {
+                Some(locked_amount) => lock.locked_amount = locked_amount,
+                None => require!(!require_snapshot, StarError::LockSnapshotMissing),
+            }
+        }
+
+        // Payouts may only land in a quote-mint account owned by the stream's recipient
+        let mut beneficiary = None;
+        let mut frozen = false;
+        if let Some(recipient) = lock.recipient {
+            let expected_owner = match recipient {
+                VestingRecipient::Wallet(wallet) => wallet,
+                VestingRecipient::TokenAccount(destination) => {
+                    let destination_info = accounts.next().ok_or(StarError::InvalidStreamAccount)?;
+                    require_keys_eq!(destination_info.key(), destination, StarError::InvalidStreamAccount);
+                    InterfaceAccount::<TokenAccount>::try_from(destination_info)?.owner
+                }
+            };
+
+            let ata = InterfaceAccount::<TokenAccount>::try_from(investor_ata)?;
+            require_keys_eq!(ata.mint, *quote_mint, StarError::InvalidInvestorAta);
+            require_keys_eq!(ata.owner, expected_owner, StarError::InvestorRecipientMismatch);
+            beneficiary = Some(expected_owner);
+            frozen = ata.is_frozen();
+        }
+
+        entries.push(PageEntry { investor_ata, beneficiary, frozen, record, lock });
+    }
+
+    Ok((entries, accounts.as_slice()))
+}
+
+/// Quote mint and extra accounts for transfers out of a Token-2022 treasury, which all go
+/// through `transfer_checked`; classic SPL Token transfers need neither. Only a quote mint
+/// with a transfer hook takes extra accounts, so every other supplied account must have
+/// been consumed by an investor entry.
+pub(crate) fn resolve_checked_transfer<'a, 'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    quote_mint: Option<&InterfaceAccount<'info, Mint>>,
+    extra_accounts: &'a [AccountInfo<'info>],
+) -> Result<Option<CheckedTransferAccounts<'a, 'info>>> {
+    if token_program.key() != spl_token_2022::ID {
+        require!(extra_accounts.is_empty(), StarError::InvalidStreamAccount);
+        return Ok(None);
+    }
+
+    let quote_mint = quote_mint.ok_or(StarError::MissingQuoteMint)?;
+    let mint_info = quote_mint.to_account_info();
+    if TokenTransferUtils::transfer_hook_program(&mint_info)?.is_none() {
+        require!(extra_accounts.is_empty(), StarError::InvalidStreamAccount);
+    }
+
+    Ok(Some(CheckedTransferAccounts {
+        mint: mint_info,
+        decimals: quote_mint.decimals,
+        extra_accounts,
+    }))
+}
+
+/// Transfer fee withheld from a quote transfer of `amount`. A Token-2022 treasury needs the
+/// optional `quote_mint` account to price it; classic SPL Token transfers carry none.
+fn quote_transfer_fee(accounts: &CrankDistribute, amount: u64) -> Result<u64> {
+    match accounts.quote_mint.as_ref() {
+        Some(quote_mint) => TokenTransferUtils::transfer_fee(&quote_mint.to_account_info(), amount),
+        None => {
+            require!(
+                *accounts.program_treasury.to_account_info().owner != spl_token_2022::ID,
+                StarError::MissingQuoteMint
+            );
+            Ok(0)
+        }
+    }
+}
+
+/// Claim fees from the honorary LP position via CP-AMM into the treasury
+fn claim_fees_from_position<'info>(
+    ctx: &Context<'_, '_, '_, 'info, CrankDistribute<'info>>,
+) -> Result<ClaimResult> {
+    let vault_key = ctx.accounts.vault.key();
+    let quote_mint = ctx.accounts.quote_mint.as_ref().map(|mint| mint.to_account_info());
+
+    CpAmmUtils::claim_position_fees(
+        &ctx.accounts.cp_amm_program,
+        &ctx.accounts.cp_amm_pool,
+        &ctx.accounts.position_owner_pda,
+        &[
+            b"vault",
+            vault_key.as_ref(),
+            b"investor_fee_pos_owner",
+            &[ctx.bumps.position_owner_pda],
+        ],
+        &ctx.accounts.program_treasury.to_account_info(),
+        quote_mint.as_ref(),
+        &ctx.accounts.token_program.to_account_info(),
+    )
 }
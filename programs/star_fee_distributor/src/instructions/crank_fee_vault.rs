@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+
+use crate::errors::StarError;
+use crate::events::{CrankFeeVaultFunded, CrankReimbursementSet};
+use crate::state::{Policy, Role};
+
+#[derive(Accounts)]
+pub struct FundCrankFeeVault<'info> {
+    /// Holder of the creator role, funding the vault
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// The vault whose crankers are reimbursed
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the creator role
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Creator, &creator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Crank fee vault PDA, a system account holding the reimbursement lamports
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"crank_fee_vault"],
+        bump
+    )]
+    pub crank_fee_vault: SystemAccount<'info>,
+
+    /// System program for the transfer
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCrankReimbursement<'info> {
+    /// Holder of the creator role
+    pub creator: Signer<'info>,
+
+    /// The vault whose crank reimbursement is set
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the creator role and reimbursement amount
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Creator, &creator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+}
+
+/// Move `amount` lamports from the creator into the crank fee vault. The first deposit
+/// must cover the rent-exempt minimum of an empty account, which is never paid out.
+pub fn fund_handler(ctx: Context<FundCrankFeeVault>, amount: u64) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        SystemTransfer {
+            from: ctx.accounts.creator.to_account_info(),
+            to: ctx.accounts.crank_fee_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_ctx, amount)?;
+
+    let balance = ctx.accounts.crank_fee_vault.lamports();
+
+    emit!(CrankFeeVaultFunded {
+        vault: ctx.accounts.vault.key(),
+        amount,
+        balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Crank fee vault funded with {} lamports for vault: {}, balance: {}",
+        amount,
+        ctx.accounts.vault.key(),
+        balance
+    );
+
+    Ok(())
+}
+
+/// Set the lamports paid from the crank fee vault to the caller of every crank page
+/// (0 disables reimbursement)
+pub fn set_reimbursement_handler(ctx: Context<SetCrankReimbursement>, lamports: u64) -> Result<()> {
+    ctx.accounts.policy.crank_reimbursement_lamports = lamports;
+
+    emit!(CrankReimbursementSet {
+        vault: ctx.accounts.vault.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Crank reimbursement set to {} lamports per page for vault: {}",
+        lamports,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::{CrankerAllowlistUpdated, CrankerPassSet};
+use crate::state::{CrankerAllowlist, Policy, Role, MAX_ALLOWED_CRANKERS};
+
+#[derive(Accounts)]
+pub struct InitCrankerAllowlist<'info> {
+    /// Holder of the operator role
+    pub operator: Signer<'info>,
+
+    /// Funds the allowlist rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault the allowlist belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the operator role
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Operator, &operator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Cranker allowlist PDA
+    #[account(
+        init,
+        payer = payer,
+        space = CrankerAllowlist::SIZE,
+        seeds = [b"vault", vault.key().as_ref(), b"cranker_allowlist"],
+        bump
+    )]
+    pub cranker_allowlist: Account<'info, CrankerAllowlist>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCrankerAllowlist<'info> {
+    /// Holder of the operator role
+    pub operator: Signer<'info>,
+
+    /// The vault the allowlist belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the operator role
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Operator, &operator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Cranker allowlist PDA
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"cranker_allowlist"],
+        bump = cranker_allowlist.bump
+    )]
+    pub cranker_allowlist: Account<'info, CrankerAllowlist>,
+}
+
+#[derive(Accounts)]
+pub struct SetCrankerPass<'info> {
+    /// Holder of the creator role
+    pub creator: Signer<'info>,
+
+    /// The vault whose cranker pass is set
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the creator role and cranker pass mint
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Creator, &creator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+}
+
+pub fn init_handler(ctx: Context<InitCrankerAllowlist>) -> Result<()> {
+    let cranker_allowlist = &mut ctx.accounts.cranker_allowlist;
+    cranker_allowlist.set_inner(CrankerAllowlist::new(ctx.accounts.vault.key(), ctx.bumps.cranker_allowlist));
+
+    msg!("Cranker allowlist initialized for vault: {}", ctx.accounts.vault.key());
+
+    Ok(())
+}
+
+/// Add or remove a cranker key
+pub fn set_cranker_handler(
+    ctx: Context<UpdateCrankerAllowlist>,
+    cranker: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    let cranker_allowlist = &mut ctx.accounts.cranker_allowlist;
+    let index = cranker_allowlist.crankers.iter().position(|entry| *entry == cranker);
+
+    match (allowed, index) {
+        (true, None) => {
+            require!(
+                cranker_allowlist.crankers.len() < MAX_ALLOWED_CRANKERS,
+                StarError::CrankerAllowlistFull
+            );
+            cranker_allowlist.crankers.push(cranker);
+        }
+        (false, Some(index)) => {
+            cranker_allowlist.crankers.swap_remove(index);
+        }
+        _ => {}
+    }
+
+    emit!(CrankerAllowlistUpdated {
+        vault: ctx.accounts.vault.key(),
+        cranker,
+        allowed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Cranker {} allowed: {} for vault: {}", cranker, allowed, ctx.accounts.vault.key());
+
+    Ok(())
+}
+
+/// Enable or disable the allowlist and set the Switchboard function whose attested
+/// enclave signer is accepted as a cranker (`Pubkey::default()` to accept none)
+pub fn configure_handler(
+    ctx: Context<UpdateCrankerAllowlist>,
+    enabled: bool,
+    switchboard_function: Pubkey,
+) -> Result<()> {
+    let cranker_allowlist = &mut ctx.accounts.cranker_allowlist;
+    cranker_allowlist.enabled = enabled;
+    cranker_allowlist.switchboard_function = switchboard_function;
+
+    msg!(
+        "Cranker allowlist enabled: {}, switchboard function: {} for vault: {}",
+        enabled,
+        switchboard_function,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Set the mint of the cranker pass (`Pubkey::default()` to revoke every pass). While
+/// the allowlist is enabled, whoever currently holds a token of this mint may crank, so
+/// cranking rights move with the token.
+pub fn set_cranker_pass_handler(ctx: Context<SetCrankerPass>, mint: Pubkey) -> Result<()> {
+    ctx.accounts.policy.cranker_pass_mint = mint;
+
+    emit!(CrankerPassSet {
+        vault: ctx.accounts.vault.key(),
+        mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Cranker pass mint set to {} for vault: {}", mint, ctx.accounts.vault.key());
+
+    Ok(())
+}
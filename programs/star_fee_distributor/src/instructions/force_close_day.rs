@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::DayForceClosed;
+use crate::instructions::crank::{close_day, resolve_checked_transfer, DayCloseAccounts};
+use crate::state::{DayPhase, Policy, Progress, Role};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ForceCloseDay<'info> {
+    /// Policy authority or holder of the creator role
+    #[account(
+        constraint = policy.has_role(Role::Admin, &signer.key())
+            || policy.has_role(Role::Creator, &signer.key()) @ StarError::Unauthorized
+    )]
+    pub signer: Signer<'info>,
+
+    /// The vault whose day is closed
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Honorary LP position owner PDA (treasury authority)
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Program treasury PDA holding the day's remainder
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"treasury", policy.quote_mint.as_ref()],
+        bump,
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Creator's quote token ATA (receives remainder)
+    #[account(
+        mut,
+        constraint = creator_quote_ata.mint == policy.quote_mint @ StarError::InvalidCreatorAta,
+        constraint = policy.creator == Pubkey::default()
+            || creator_quote_ata.owner == policy.creator @ StarError::InvalidCreatorAta
+    )]
+    pub creator_quote_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Policy PDA containing distribution configuration
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA tracking daily distribution state
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Quote mint; required under `RemainderPolicy::Burn` and for a Token-2022 treasury
+    #[account(mut, address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Referrer's quote token ATA; required while the policy has a referral
+    #[account(
+        mut,
+        constraint = referrer_quote_ata.mint == policy.quote_mint @ StarError::InvalidReferrerAta,
+        constraint = policy.referral.is_some_and(|referral| referrer_quote_ata.owner == referral.referrer)
+            @ StarError::InvalidReferrerAta
+    )]
+    pub referrer_quote_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program owning the treasury (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Close the day before its final committed page once every committed investor has been
+/// processed, e.g. when the keeper committed more pages than the investor set needed.
+/// Settles the remainder exactly like the final crank page; remaining_accounts carry the
+/// quote mint's transfer hook accounts, if any. Unlike the final page it does not require
+/// the class totals committed at open to be reached, so a stream cancelled after the day
+/// opened cannot wedge it; the share nobody was weighed for stays in `carry_over` for the
+/// next day's investors rather than reaching the creator.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ForceCloseDay<'info>>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    let (day, pages_processed, total_pages) = {
+        let progress = ctx.accounts.progress.load()?;
+        // Only between pages: a page that claimed but never distributed would be lost.
+        // `close_day` then checks that every committed investor was processed.
+        require!(
+            progress.phase()? == DayPhase::Distributing,
+            StarError::InvalidPhaseTransition
+        );
+        (progress.current_day, progress.pagination_cursor, progress.total_pages)
+    };
+
+    let checked_transfer = resolve_checked_transfer(
+        &ctx.accounts.token_program,
+        ctx.accounts.quote_mint.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+    let day_closed = close_day(
+        DayCloseAccounts {
+            vault: &ctx.accounts.vault,
+            position_owner_pda: &ctx.accounts.position_owner_pda,
+            program_treasury: &mut ctx.accounts.program_treasury,
+            creator_quote_ata: &ctx.accounts.creator_quote_ata,
+            policy: &ctx.accounts.policy,
+            progress: &ctx.accounts.progress,
+            quote_mint: ctx.accounts.quote_mint.as_ref(),
+            referrer_quote_ata: ctx.accounts.referrer_quote_ata.as_ref(),
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.bumps.position_owner_pda,
+        checked_transfer.as_ref(),
+        current_timestamp,
+    )?;
+    if let Some(day_closed) = day_closed {
+        emit_cpi!(day_closed);
+    }
+
+    emit!(DayForceClosed {
+        vault: ctx.accounts.vault.key(),
+        day,
+        pages_processed,
+        total_pages,
+        closed_by: ctx.accounts.signer.key(),
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Day {} force-closed after page {} of {} for vault: {}",
+        day,
+        pages_processed,
+        total_pages,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
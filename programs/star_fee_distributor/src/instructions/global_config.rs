@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::TransferHookAllowlistUpdated;
+use crate::program::StarFeeDistributor;
+use crate::state::{GlobalConfig, MAX_ALLOWED_TRANSFER_HOOKS};
+
+#[derive(Accounts)]
+pub struct InitGlobalConfig<'info> {
+    /// Program upgrade authority, which becomes the config admin
+    pub admin: Signer<'info>,
+
+    /// Funds the config rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// This program, to locate its program data account
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ StarError::Unauthorized)]
+    pub program: Program<'info, StarFeeDistributor>,
+
+    /// Program data account holding the upgrade authority
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ StarError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// Global config PDA
+    #[account(
+        init,
+        payer = payer,
+        space = GlobalConfig::SIZE,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    /// Config admin
+    pub admin: Signer<'info>,
+
+    /// Global config PDA
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ StarError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn init_handler(ctx: Context<InitGlobalConfig>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.set_inner(GlobalConfig::new(ctx.accounts.admin.key(), ctx.bumps.global_config));
+
+    msg!("Global config initialized with admin: {}", ctx.accounts.admin.key());
+
+    Ok(())
+}
+
+/// Add or remove a transfer hook program accepted on Token-2022 quote mints
+pub fn set_transfer_hook_handler(
+    ctx: Context<UpdateGlobalConfig>,
+    hook_program: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    let index = global_config.allowed_transfer_hooks.iter().position(|entry| *entry == hook_program);
+
+    match (allowed, index) {
+        (true, None) => {
+            require!(
+                global_config.allowed_transfer_hooks.len() < MAX_ALLOWED_TRANSFER_HOOKS,
+                StarError::TransferHookAllowlistFull
+            );
+            global_config.allowed_transfer_hooks.push(hook_program);
+        }
+        (false, Some(index)) => {
+            global_config.allowed_transfer_hooks.swap_remove(index);
+        }
+        _ => {}
+    }
+
+    emit!(TransferHookAllowlistUpdated {
+        hook_program,
+        allowed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Transfer hook {} allowed: {}", hook_program, allowed);
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::FeesHarvested;
+use crate::state::{Policy, Progress};
+use crate::utils::{CpAmmUtils, ValidationUtils};
+
+#[derive(Accounts)]
+pub struct HarvestFees<'info> {
+    /// Anyone may harvest; the fees only ever move into the vault's treasury
+    pub caller: Signer<'info>,
+
+    /// The vault whose position is harvested
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the quote mint
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA accumulating the harvested amount
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Honorary LP position owner PDA, signing the claim
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Program treasury PDA receiving the claimed quote fees
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"treasury", policy.quote_mint.as_ref()],
+        bump,
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint; required for a Token-2022 treasury
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// CP-AMM program for claiming fees
+    /// CHECK: Validated CP-AMM program
+    pub cp_amm_program: AccountInfo<'info>,
+
+    /// CP-AMM pool account
+    /// CHECK: Must be the policy's registered pool, once one is recorded
+    #[account(
+        mut,
+        constraint = policy.pool == Pubkey::default() || cp_amm_pool.key() == policy.pool
+            @ StarError::InvalidCpAmmPool
+    )]
+    pub cp_amm_pool: AccountInfo<'info>,
+
+    /// Token program owning the treasury (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim the position's quote fees into the treasury between distribution days, so
+/// accrued fees are not exposed to pool-side risk until the next crank. The amount is
+/// held in `progress.harvested_pending` and distributed with the next day's first page.
+pub fn handler(ctx: Context<HarvestFees>) -> Result<()> {
+    let mut progress = ctx.accounts.progress.load_mut()?;
+    require!(!progress.is_day_in_progress(), StarError::DayInProgress);
+
+    let vault_key = ctx.accounts.vault.key();
+    let position_owner_seeds: &[&[u8]] = &[
+        b"vault",
+        vault_key.as_ref(),
+        b"investor_fee_pos_owner",
+        &[ctx.bumps.position_owner_pda],
+    ];
+
+    let balance_before = ctx.accounts.program_treasury.amount;
+    let quote_mint = ctx.accounts.quote_mint.as_ref().map(|mint| mint.to_account_info());
+    let claim_result = CpAmmUtils::claim_position_fees(
+        &ctx.accounts.cp_amm_program,
+        &ctx.accounts.cp_amm_pool,
+        &ctx.accounts.position_owner_pda,
+        position_owner_seeds,
+        &ctx.accounts.program_treasury.to_account_info(),
+        quote_mint.as_ref(),
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    // CRITICAL: Verify no base fees are present
+    ValidationUtils::detect_base_fees(&claim_result)?;
+
+    // Book what actually arrived in the treasury: a transfer-fee quote mint withholds part
+    // of the claim, and the day's first page adds the pending amount without deducting it
+    ctx.accounts.program_treasury.reload()?;
+    let amount = ctx.accounts.program_treasury.amount
+        .checked_sub(balance_before)
+        .ok_or(StarError::MathUnderflow)?;
+    progress.harvested_pending = progress.harvested_pending
+        .checked_add(amount)
+        .ok_or(StarError::MathOverflow)?;
+    progress.lifetime_claimed = progress.lifetime_claimed
+        .checked_add(amount)
+        .ok_or(StarError::MathOverflow)?;
+
+    emit!(FeesHarvested {
+        vault: vault_key,
+        caller: ctx.accounts.caller.key(),
+        amount,
+        harvested_pending: progress.harvested_pending,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Harvested {} quote fees for vault: {}, pending for next day: {}",
+        amount,
+        vault_key,
+        progress.harvested_pending
+    );
+
+    Ok(())
+}
@@ -1,16 +1,23 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::StarError;
-use crate::events::HonoraryPositionInitialized;
-use crate::state::{Policy, Progress, derive_policy_pda, derive_progress_pda, derive_investor_fee_position_owner_pda, derive_treasury_pda};
-use crate::utils::{ValidationUtils, PoolConfig, TokenTransferUtils};
+use crate::events::{HonoraryPositionInitialized, PolicyInitialized};
+use crate::state::{
+    derive_treasury_pda, GlobalConfig, InitializeArgsV1, Policy, Progress, VaultInitParams, CP_AMM_PROGRAM_ID,
+};
+use crate::utils::{CpAmmUtils, PoolConfig, ValidationUtils};
 
 #[derive(Accounts)]
-pub struct InitializeHonoraryPosition<'info> {
+pub struct InitPolicy<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    /// Policy authority; may be a keypair or a multisig vault PDA (e.g. Squads)
+    /// CHECK: Any pubkey, stored on the policy and checked by privileged instructions
+    pub authority: UncheckedAccount<'info>,
+
     /// The vault this honorary position belongs to
     /// CHECK: Validated to ensure it's a legitimate vault
     pub vault: AccountInfo<'info>,
@@ -22,15 +29,11 @@ pub struct InitializeHonoraryPosition<'info> {
 
     /// Quote mint (must be the second token in the pool)
     #[account()]
-    pub quote_mint: Account<'info, Mint>,
+    pub quote_mint: InterfaceAccount<'info, Mint>,
 
     /// Base mint (first token in the pool)
     #[account()]
-    pub base_mint: Account<'info, Mint>,
-
-    /// CP-AMM program
-    /// CHECK: Validated CP-AMM program ID
-    pub cp_amm_program: AccountInfo<'info>,
+    pub base_mint: InterfaceAccount<'info, Mint>,
 
     /// Policy PDA for storing distribution configuration
     #[account(
@@ -50,99 +53,284 @@ pub struct InitializeHonoraryPosition<'info> {
         seeds = [b"vault", vault.key().as_ref(), b"progress"],
         bump
     )]
-    pub progress: Account<'info, Progress>,
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Honorary LP position owner PDA (treasury authority)
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Program treasury PDA token account for holding claimed quote fees
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault", vault.key().as_ref(), b"treasury", quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = position_owner_pda,
+        token::token_program = token_program
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Global config holding the allowed transfer hook programs; may not exist yet
+    /// CHECK: PDA seeds checked; read with `GlobalConfig::load`
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Token program owning the quote mint (SPL Token or Token-2022), for the treasury account
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitPosition<'info> {
+    /// Policy authority
+    pub authority: Signer<'info>,
 
-    /// Program treasury ATA for holding claimed quote fees
-    /// CHECK: Will be created if it doesn't exist
+    /// Funds the position accounts
     #[account(mut)]
-    pub program_treasury: Account<'info, TokenAccount>,
+    pub payer: Signer<'info>,
+
+    /// The vault this honorary position belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA created by `init_policy`, registering the position
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// CP-AMM pool the position is opened in
+    /// CHECK: Validated to ensure quote-only fee accrual
+    #[account(mut)]
+    pub cp_amm_pool: AccountInfo<'info>,
+
+    /// Quote mint recorded on the policy
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// Base mint (first token in the pool)
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Honorary LP position owner PDA, custodian of the position NFT
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Honorary position created by the CP-AMM program
+    /// CHECK: Initialized by the CP-AMM program and verified after creation
+    #[account(mut)]
+    pub position: AccountInfo<'info>,
+
+    /// Token account holding the position NFT, owned by the position owner PDA
+    /// CHECK: Verified against the position's NFT mint after creation
+    pub position_nft_account: AccountInfo<'info>,
+
+    /// CP-AMM program
+    /// CHECK: Validated against the known CP-AMM program ID
+    #[account(address = CP_AMM_PROGRAM_ID @ StarError::InvalidCpAmmProgram)]
+    pub cp_amm_program: AccountInfo<'info>,
 
     /// System program for account creation
     pub system_program: Program<'info, System>,
 
-    /// Token program for token account operations
+    /// Token program for the position NFT
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(
-    ctx: Context<InitializeHonoraryPosition>,
-    investor_fee_share_bps: u16,
-    daily_cap: u64,
-    min_payout_lamports: u64,
-    y0: u64,
-) -> Result<()> {
-    let policy = &mut ctx.accounts.policy;
-    let progress = &mut ctx.accounts.progress;
-    let vault = &ctx.accounts.vault;
-    let quote_mint = &ctx.accounts.quote_mint;
+/// Create the vault's Policy, Progress and treasury PDAs from the distribution parameters.
+/// The honorary position is created separately by `init_position`.
+pub fn policy_handler(ctx: Context<InitPolicy>, args: InitializeArgsV1) -> Result<()> {
+    args.validate_version()?;
+    init_vault_policy(ctx.accounts, &ctx.bumps, args.params)
+}
 
-    // Validate input parameters
-    require!(investor_fee_share_bps <= 10000, StarError::InvalidFeeShareBps);
-    require!(daily_cap > 0, StarError::InvalidDailyCap);
-    require!(min_payout_lamports > 0, StarError::InvalidMinPayout);
-    require!(y0 > 0, StarError::InvalidY0);
+/// Validate the pool and quote mint, then initialize the vault's Policy and Progress
+/// from `params`; shared by `init_policy` and `initialize_from_template`
+pub(crate) fn init_vault_policy(
+    accounts: &mut InitPolicy,
+    bumps: &InitPolicyBumps,
+    params: VaultInitParams,
+) -> Result<()> {
+    let vault = &accounts.vault;
+    let quote_mint = &accounts.quote_mint;
 
     // Validate pool configuration for quote-only fee accrual
     let pool_config = PoolConfig {
-        token_a: ctx.accounts.base_mint.key(),
-        token_b: ctx.accounts.quote_mint.key(),
-        pool_id: ctx.accounts.cp_amm_pool.key(),
+        token_a: accounts.base_mint.key(),
+        token_b: accounts.quote_mint.key(),
+        pool_id: accounts.cp_amm_pool.key(),
         tick_lower: 0, // Would be provided in real implementation
         tick_upper: 0, // Would be provided in real implementation
     };
 
     ValidationUtils::validate_quote_only_pool(&pool_config, &quote_mint.key())?;
 
-    // Initialize policy
-    let policy_bump = ctx.bumps.policy;
-    *policy = Policy::new(
-        investor_fee_share_bps,
-        daily_cap,
-        min_payout_lamports,
-        y0,
+    // Only the position owner PDA may ever move the treasury's funds
+    let global_config = GlobalConfig::load(&accounts.global_config)?;
+    ValidationUtils::validate_quote_mint_extensions(&quote_mint.to_account_info(), global_config.as_ref())?;
+    ValidationUtils::validate_treasury_account(
+        &accounts.program_treasury,
+        &accounts.position_owner_pda.key(),
+    )?;
+
+    // Initialize and validate policy
+    let policy = build_policy(
+        params,
         quote_mint.key(),
         vault.key(),
-        policy_bump,
-    );
-
-    // Validate policy
-    policy.validate()?;
+        bumps.policy,
+        accounts.authority.key(),
+    )?;
 
     // Initialize progress
-    let progress_bump = ctx.bumps.progress;
-    *progress = Progress::new(vault.key(), progress_bump);
+    *accounts.progress.load_init()? = Progress::new(vault.key(), bumps.progress);
 
-    // Verify program treasury is owned by the correct mint
-    require!(
-        ctx.accounts.program_treasury.mint == quote_mint.key(),
-        StarError::InvalidQuoteMint
+    emit!(PolicyInitialized {
+        vault: vault.key(),
+        authority: accounts.authority.key(),
+        quote_mint: quote_mint.key(),
+        pool: accounts.cp_amm_pool.key(),
+        investor_fee_share_bps: policy.investor_fee_share_bps,
+        daily_cap: policy.daily_cap,
+        min_payout_lamports: policy.min_payout_lamports,
+        y0: policy.y0,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Policy initialized for vault: {}, quote_mint: {}, pool: {}",
+        vault.key(),
+        quote_mint.key(),
+        accounts.cp_amm_pool.key()
     );
 
-    // Create honorary LP position via CP-AMM
-    // Transfer ownership to our PDA
-    // Verify the position configuration
+    accounts.policy.set_inner(policy);
+
+    Ok(())
+}
+
+/// Create the honorary CP-AMM position owned by the position owner PDA over
+/// `[tick_lower, tick_upper]` and verify its custody; policy authority only
+pub fn position_handler(ctx: Context<InitPosition>, tick_lower: i32, tick_upper: i32) -> Result<()> {
+    let policy = &ctx.accounts.policy;
+    let vault_key = ctx.accounts.vault.key();
 
-    // For now, we'll emit the event with the expected position key
-    let (position_owner_pda, _) = derive_investor_fee_position_owner_pda(vault);
+    require!(policy.position == Pubkey::default(), StarError::PositionAlreadyRegistered);
+
+    // Re-check the pool against the policy's quote mint before opening the position
+    let pool_config = PoolConfig {
+        token_a: ctx.accounts.base_mint.key(),
+        token_b: ctx.accounts.quote_mint.key(),
+        pool_id: ctx.accounts.cp_amm_pool.key(),
+        tick_lower,
+        tick_upper,
+    };
+
+    ValidationUtils::validate_quote_only_pool(&pool_config, &policy.quote_mint)?;
+    let pool = CpAmmUtils::load_pool(&ctx.accounts.cp_amm_pool)?;
+    ValidationUtils::validate_quote_only_range(&pool_config, pool.sqrt_price)?;
+    ValidationUtils::validate_quote_mint_freeze_authority(&ctx.accounts.quote_mint, policy)?;
+
+    CpAmmUtils::create_position(
+        &ctx.accounts.cp_amm_program,
+        &ctx.accounts.cp_amm_pool,
+        &ctx.accounts.position,
+        &ctx.accounts.position_owner_pda,
+        &[
+            b"vault",
+            vault_key.as_ref(),
+            b"investor_fee_pos_owner",
+            &[ctx.bumps.position_owner_pda],
+        ],
+        &ctx.accounts.payer,
+        tick_lower,
+        tick_upper,
+    )?;
+
+    CpAmmUtils::verify_position_custody(
+        &ctx.accounts.position,
+        &ctx.accounts.cp_amm_pool.key(),
+        &ctx.accounts.position_nft_account,
+        &ctx.accounts.position_owner_pda.key(),
+    )?;
+
+    // Any liquidity in the honorary position must stay in its quote-only range
+    ValidationUtils::validate_fee_only_position(&ctx.accounts.position, &pool_config, pool.sqrt_price)?;
+
+    let position_nft_mint = CpAmmUtils::position_nft_mint(&ctx.accounts.position)?;
+
+    let policy = &mut ctx.accounts.policy;
+    policy.position = ctx.accounts.position.key();
+    policy.pool = ctx.accounts.cp_amm_pool.key();
+    policy.tick_lower = tick_lower;
+    policy.tick_upper = tick_upper;
 
     emit!(HonoraryPositionInitialized {
-        position: position_owner_pda,
-        quote_mint: quote_mint.key(),
+        position: ctx.accounts.position.key(),
+        quote_mint: policy.quote_mint,
         pool: ctx.accounts.cp_amm_pool.key(),
-        vault: vault.key(),
-        investor_fee_share_bps,
-        daily_cap,
-        min_payout_lamports,
-        y0,
+        vault: vault_key,
+        investor_fee_share_bps: policy.investor_fee_share_bps,
+        daily_cap: policy.daily_cap,
+        min_payout_lamports: policy.min_payout_lamports,
+        y0: policy.y0,
         timestamp: Clock::get()?.unix_timestamp,
+        base_mint: ctx.accounts.base_mint.key(),
+        token_a_mint: pool.token_a_mint,
+        token_b_mint: pool.token_b_mint,
+        position_nft_mint,
+        treasury: derive_treasury_pda(&vault_key, &policy.quote_mint).0,
     });
 
     msg!(
-        "Honorary position initialized for vault: {}, quote_mint: {}, pool: {}",
-        vault.key(),
-        quote_mint.key(),
+        "Honorary position {} initialized for vault: {}, pool: {}",
+        ctx.accounts.position.key(),
+        vault_key,
         ctx.accounts.cp_amm_pool.key()
     );
 
     Ok(())
 }
+
+/// Validate a vault's initialization parameters and build its policy
+pub(crate) fn build_policy(
+    params: VaultInitParams,
+    quote_mint: Pubkey,
+    vault: Pubkey,
+    bump: u8,
+    authority: Pubkey,
+) -> Result<Policy> {
+    require!(params.investor_fee_share_bps <= 10000, StarError::InvalidFeeShareBps);
+    require!(params.y0 > 0, StarError::InvalidY0);
+
+    let mut policy = Policy::new(
+        params.investor_fee_share_bps,
+        params.daily_cap,
+        params.min_payout_lamports,
+        params.y0,
+        quote_mint,
+        vault,
+        bump,
+        params.fee_share_decay,
+        params.duration_multipliers,
+        params.epochs,
+        authority,
+    );
+    policy.referral = params.referral;
+    policy.validate()?;
+
+    Ok(policy)
+}
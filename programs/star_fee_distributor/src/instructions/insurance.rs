@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::InsuranceBpsSet;
+use crate::state::Policy;
+
+#[derive(Accounts)]
+pub struct InitInsuranceFund<'info> {
+    /// Policy authority
+    pub authority: Signer<'info>,
+
+    /// Funds the insurance fund rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault the insurance fund belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority and quote mint
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Honorary LP position owner PDA, authority of the insurance fund
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Quote mint of the vault
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// Insurance fund token account
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault", vault.key().as_ref(), b"insurance", quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = position_owner_pda,
+        token::token_program = token_program
+    )]
+    pub insurance_fund: InterfaceAccount<'info, TokenAccount>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Token program owning the quote mint (SPL Token or Token-2022), for the insurance fund
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceBps<'info> {
+    /// Current admin (policy authority)
+    pub authority: Signer<'info>,
+
+    /// The vault whose insurance share is set
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the insurance share
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Insurance fund token account; must be initialized before a share is set
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"insurance", policy.quote_mint.as_ref()],
+        bump
+    )]
+    pub insurance_fund: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn init_handler(ctx: Context<InitInsuranceFund>) -> Result<()> {
+    msg!(
+        "Insurance fund {} initialized for vault: {}",
+        ctx.accounts.insurance_fund.key(),
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Move `insurance_bps` of every page's claimed fees to the insurance fund before the
+/// investor/creator split (0 disables). The fund is only withdrawable through the
+/// timelocked recovery flow.
+pub fn set_bps_handler(ctx: Context<SetInsuranceBps>, insurance_bps: u16) -> Result<()> {
+    require!(insurance_bps <= 10000, StarError::InvalidFeeShareBps);
+
+    ctx.accounts.policy.insurance_bps = insurance_bps;
+
+    emit!(InsuranceBpsSet {
+        vault: ctx.accounts.vault.key(),
+        insurance_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Insurance share set to {} bps for vault: {}",
+        insurance_bps,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::{InvestorClassSet, InvestorClassesSet};
+use crate::state::{InvestorClass, InvestorRecord, Policy, Progress};
+
+#[derive(Accounts)]
+pub struct SetInvestorClasses<'info> {
+    /// Current admin (policy authority)
+    pub authority: Signer<'info>,
+
+    /// The vault whose classes are configured
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the investor classes
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA; classes only change between distribution days
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+}
+
+#[derive(Accounts)]
+pub struct SetInvestorClass<'info> {
+    /// Current admin (policy authority)
+    pub authority: Signer<'info>,
+
+    /// The vault the record belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the investor classes
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA; classes only change between distribution days
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Vesting account the record tracks
+    /// CHECK: Used for PDA derivation only
+    pub stream: AccountInfo<'info>,
+
+    /// Investor record PDA holding the class
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_record", stream.key().as_ref()],
+        bump = investor_record.bump
+    )]
+    pub investor_record: Account<'info, InvestorRecord>,
+}
+
+/// Replace the policy's investor classes. Records keep their class index, so shrinking
+/// the list requires reassigning records of removed classes before the next crank.
+pub fn set_classes_handler(ctx: Context<SetInvestorClasses>, investor_classes: Vec<InvestorClass>) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+    Policy::validate_investor_classes(&investor_classes)?;
+
+    ctx.accounts.policy.investor_classes = investor_classes.clone();
+
+    emit!(InvestorClassesSet {
+        vault: ctx.accounts.vault.key(),
+        investor_classes: investor_classes.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Investor classes set to {:?} for vault: {}",
+        investor_classes,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Assign the investor tracked by the record to `class`
+pub fn set_class_handler(ctx: Context<SetInvestorClass>, class: u8) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+    require!(
+        (class as usize) < ctx.accounts.policy.investor_class_count(),
+        StarError::InvalidInvestorClass
+    );
+
+    ctx.accounts.investor_record.class = class;
+
+    emit!(InvestorClassSet {
+        vault: ctx.accounts.vault.key(),
+        stream: ctx.accounts.stream.key(),
+        class,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Investor class set to {} for vault: {}, stream: {}",
+        class,
+        ctx.accounts.vault.key(),
+        ctx.accounts.stream.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::StarError;
+use crate::events::InvestorOptOutUpdated;
+use crate::state::{InvestorRecord, Policy};
+use crate::utils::{CpAmmUtils, ValidationUtils, VestingUtils};
+
+#[derive(Accounts)]
+pub struct InitInvestorRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault this record belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA (ensures the vault is initialized)
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// CP-AMM pool of the honorary position, whose token A is the vault's base mint
+    /// CHECK: Must be the policy's registered pool; read through the CP-AMM adapter
+    #[account(address = policy.pool @ StarError::InvalidCpAmmPool)]
+    pub cp_amm_pool: AccountInfo<'info>,
+
+    /// Vesting account the record tracks
+    /// CHECK: Must lock the vault's base mint; read through the vesting adapter
+    pub stream: AccountInfo<'info>,
+
+    /// Investor record PDA
+    #[account(
+        init,
+        payer = payer,
+        space = InvestorRecord::SIZE,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_record", stream.key().as_ref()],
+        bump
+    )]
+    pub investor_record: Account<'info, InvestorRecord>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOptOut<'info> {
+    /// Stream recipient (Streamflow recipient wallet or Bonfida destination owner)
+    pub recipient: Signer<'info>,
+
+    /// The vault the record belongs to
+    /// CHECK: Validated through the investor record PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Vesting account the record tracks
+    /// CHECK: Read through the vesting adapter to resolve its recipient
+    pub stream: AccountInfo<'info>,
+
+    /// Bonfida destination token account (required for Bonfida schedules only)
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Investor record PDA holding the opt-out flag
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_record", stream.key().as_ref()],
+        bump = investor_record.bump
+    )]
+    pub investor_record: Account<'info, InvestorRecord>,
+}
+
+pub fn handler(ctx: Context<InitInvestorRecord>) -> Result<()> {
+    // The crank only reads streams with a record, so only streams vesting the vault's
+    // base token can share in its fees
+    let pool = CpAmmUtils::load_pool(&ctx.accounts.cp_amm_pool)?;
+    ValidationUtils::validate_stream_mint(&ctx.accounts.stream, &pool)?;
+
+    let investor_record = &mut ctx.accounts.investor_record;
+    investor_record.set_inner(InvestorRecord::new(
+        ctx.accounts.vault.key(),
+        ctx.accounts.stream.key(),
+        ctx.bumps.investor_record,
+    ));
+
+    msg!(
+        "Investor record initialized for vault: {}, stream: {}",
+        ctx.accounts.vault.key(),
+        ctx.accounts.stream.key()
+    );
+
+    Ok(())
+}
+
+/// Record whether the stream recipient refuses fee income
+pub fn set_opt_out_handler(ctx: Context<SetOptOut>, opted_out: bool) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let lock = VestingUtils::read_lock(&ctx.accounts.stream, current_timestamp)?;
+
+    // Only the stream's recipient may change the choice
+    let recipient = VestingUtils::resolve_recipient(&lock, ctx.accounts.destination_token_account.as_ref())?;
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        recipient,
+        StarError::InvestorRecipientMismatch
+    );
+
+    ctx.accounts.investor_record.opted_out = opted_out;
+
+    emit!(InvestorOptOutUpdated {
+        vault: ctx.accounts.vault.key(),
+        stream: ctx.accounts.stream.key(),
+        recipient,
+        opted_out,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Investor opt-out set to {} for vault: {}, stream: {}",
+        opted_out,
+        ctx.accounts.vault.key(),
+        ctx.accounts.stream.key()
+    );
+
+    Ok(())
+}
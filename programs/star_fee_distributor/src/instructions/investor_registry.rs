@@ -0,0 +1,363 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::StarError;
+use crate::events::{InvestorDeregistered, InvestorRegistered};
+use crate::state::{
+    InvestorRegistry, InvestorRegistryChunk, Policy, Progress, RegisteredInvestor, RegistryEntry,
+    MAX_REGISTRY_CHUNK_ENTRIES,
+};
+use crate::utils::VestingUtils;
+
+#[derive(Accounts)]
+pub struct InitInvestorRegistry<'info> {
+    /// Policy authority
+    pub authority: Signer<'info>,
+
+    /// Funds the registry rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault the registry belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Investor registry PDA
+    #[account(
+        init,
+        payer = payer,
+        space = InvestorRegistry::SIZE,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_registry"],
+        bump
+    )]
+    pub investor_registry: Account<'info, InvestorRegistry>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddRegistryChunk<'info> {
+    /// Funds the chunk rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault the registry belongs to
+    /// CHECK: Validated through the registry PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Investor registry PDA
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_registry"],
+        bump = investor_registry.bump
+    )]
+    pub investor_registry: Account<'info, InvestorRegistry>,
+
+    /// Next registry chunk PDA
+    #[account(
+        init,
+        payer = payer,
+        space = InvestorRegistryChunk::SIZE,
+        seeds = [
+            b"vault",
+            vault.key().as_ref(),
+            b"registry_chunk",
+            &investor_registry.chunk_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub registry_chunk: Account<'info, InvestorRegistryChunk>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chunk_index: u32)]
+pub struct RegisterInvestor<'info> {
+    /// Policy authority or the stream's recipient
+    pub signer: Signer<'info>,
+
+    /// Funds the registration marker rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault the registry belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority and quote mint
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA, to keep the registry fixed while a day is in progress
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Investor registry PDA
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_registry"],
+        bump = investor_registry.bump
+    )]
+    pub investor_registry: Account<'info, InvestorRegistry>,
+
+    /// Registry chunk receiving the entry
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"registry_chunk", &chunk_index.to_le_bytes()],
+        bump = registry_chunk.bump
+    )]
+    pub registry_chunk: Account<'info, InvestorRegistryChunk>,
+
+    /// Vesting account registered
+    /// CHECK: Read through the vesting adapter to resolve its recipient
+    pub stream: AccountInfo<'info>,
+
+    /// Bonfida destination token account (required for Bonfida schedules only)
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Quote ATA the investor's payouts go to
+    #[account(constraint = investor_quote_ata.mint == policy.quote_mint @ StarError::InvalidInvestorAta)]
+    pub investor_quote_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Registration marker PDA, so a stream is registered at most once
+    #[account(
+        init,
+        payer = payer,
+        space = RegisteredInvestor::SIZE,
+        seeds = [b"vault", vault.key().as_ref(), b"registered_investor", stream.key().as_ref()],
+        bump
+    )]
+    pub registered_investor: Account<'info, RegisteredInvestor>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterInvestor<'info> {
+    /// Policy authority or the stream's recipient; receives the marker's rent
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The vault the registry belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA, to keep the registry fixed while a day is in progress
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Investor registry PDA
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_registry"],
+        bump = investor_registry.bump
+    )]
+    pub investor_registry: Account<'info, InvestorRegistry>,
+
+    /// Vesting account deregistered
+    /// CHECK: Read through the vesting adapter to resolve its recipient
+    pub stream: AccountInfo<'info>,
+
+    /// Bonfida destination token account (required for Bonfida schedules only)
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Registration marker PDA, closed to the signer
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"vault", vault.key().as_ref(), b"registered_investor", stream.key().as_ref()],
+        bump = registered_investor.bump
+    )]
+    pub registered_investor: Account<'info, RegisteredInvestor>,
+
+    /// Registry chunk holding the entry
+    #[account(
+        mut,
+        seeds = [
+            b"vault",
+            vault.key().as_ref(),
+            b"registry_chunk",
+            &registered_investor.chunk_index.to_le_bytes()
+        ],
+        bump = registry_chunk.bump
+    )]
+    pub registry_chunk: Account<'info, InvestorRegistryChunk>,
+}
+
+pub fn init_handler(ctx: Context<InitInvestorRegistry>) -> Result<()> {
+    let investor_registry = &mut ctx.accounts.investor_registry;
+    investor_registry.set_inner(InvestorRegistry::new(ctx.accounts.vault.key(), ctx.bumps.investor_registry));
+
+    msg!("Investor registry initialized for vault: {}", ctx.accounts.vault.key());
+
+    Ok(())
+}
+
+/// Create the next registry chunk. Permissionless, but only once every existing chunk
+/// is full, so chunks cannot be created empty ahead of need.
+pub fn add_chunk_handler(ctx: Context<AddRegistryChunk>) -> Result<()> {
+    let investor_registry = &mut ctx.accounts.investor_registry;
+    require!(investor_registry.is_full(), StarError::RegistryHasRoom);
+
+    let index = investor_registry.chunk_count;
+    ctx.accounts.registry_chunk.set_inner(InvestorRegistryChunk::new(
+        ctx.accounts.vault.key(),
+        index,
+        ctx.bumps.registry_chunk,
+    ));
+    investor_registry.chunk_count = index.checked_add(1).ok_or(StarError::MathOverflow)?;
+
+    msg!("Registry chunk {} created for vault: {}", index, ctx.accounts.vault.key());
+
+    Ok(())
+}
+
+/// Add a stream and its payout ATA to the registry chunk `chunk_index`. The ATA must be
+/// a quote-mint account owned by the stream's recipient. Policy authority or the
+/// recipient only, and only between distribution days.
+pub fn register_handler(ctx: Context<RegisterInvestor>, chunk_index: u32) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let recipient = stream_recipient(
+        &ctx.accounts.stream,
+        ctx.accounts.destination_token_account.as_ref(),
+        current_timestamp,
+    )?;
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == ctx.accounts.policy.authority || signer == recipient,
+        StarError::Unauthorized
+    );
+    require_keys_eq!(
+        ctx.accounts.investor_quote_ata.owner,
+        recipient,
+        StarError::InvestorRecipientMismatch
+    );
+
+    let registry_chunk = &mut ctx.accounts.registry_chunk;
+    require!(registry_chunk.entries.len() < MAX_REGISTRY_CHUNK_ENTRIES, StarError::RegistryChunkFull);
+    registry_chunk.entries.push(RegistryEntry {
+        stream_pubkey: ctx.accounts.stream.key(),
+        investor_quote_ata: ctx.accounts.investor_quote_ata.key(),
+    });
+
+    let investor_registry = &mut ctx.accounts.investor_registry;
+    investor_registry.total_investors = investor_registry.total_investors
+        .checked_add(1)
+        .ok_or(StarError::MathOverflow)?;
+
+    ctx.accounts.registered_investor.set_inner(RegisteredInvestor {
+        vault: ctx.accounts.vault.key(),
+        stream_pubkey: ctx.accounts.stream.key(),
+        chunk_index,
+        bump: ctx.bumps.registered_investor,
+    });
+
+    emit!(InvestorRegistered {
+        vault: ctx.accounts.vault.key(),
+        stream: ctx.accounts.stream.key(),
+        investor_quote_ata: ctx.accounts.investor_quote_ata.key(),
+        chunk_index,
+        registered_by: signer,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Investor registered for vault: {}, stream: {}, chunk: {}",
+        ctx.accounts.vault.key(),
+        ctx.accounts.stream.key(),
+        chunk_index
+    );
+
+    Ok(())
+}
+
+/// Remove a stream from the registry, freeing its chunk slot for the next registration.
+/// Policy authority or the recipient only, and only between distribution days.
+pub fn deregister_handler(ctx: Context<DeregisterInvestor>) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let signer = ctx.accounts.signer.key();
+    // The authority can drop streams that no longer resolve to a recipient
+    if signer != ctx.accounts.policy.authority {
+        let recipient = stream_recipient(
+            &ctx.accounts.stream,
+            ctx.accounts.destination_token_account.as_ref(),
+            current_timestamp,
+        )?;
+        require_keys_eq!(signer, recipient, StarError::Unauthorized);
+    }
+
+    let stream_key = ctx.accounts.stream.key();
+    let registry_chunk = &mut ctx.accounts.registry_chunk;
+    let index = registry_chunk
+        .entries
+        .iter()
+        .position(|entry| entry.stream_pubkey == stream_key)
+        .ok_or(StarError::InvalidRegistryChunk)?;
+    registry_chunk.entries.swap_remove(index);
+
+    let investor_registry = &mut ctx.accounts.investor_registry;
+    investor_registry.total_investors = investor_registry.total_investors
+        .checked_sub(1)
+        .ok_or(StarError::MathUnderflow)?;
+
+    let chunk_index = ctx.accounts.registered_investor.chunk_index;
+
+    emit!(InvestorDeregistered {
+        vault: ctx.accounts.vault.key(),
+        stream: stream_key,
+        chunk_index,
+        deregistered_by: signer,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Investor deregistered for vault: {}, stream: {}, chunk: {}",
+        ctx.accounts.vault.key(),
+        stream_key,
+        chunk_index
+    );
+
+    Ok(())
+}
+
+/// Wallet the stream pays out to; cancelled or closed streams have none
+fn stream_recipient(
+    stream: &AccountInfo,
+    destination_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    current_timestamp: i64,
+) -> Result<Pubkey> {
+    let lock = VestingUtils::read_lock(stream, current_timestamp)?;
+    VestingUtils::resolve_recipient(&lock, destination_token_account)
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::LookupTableSet;
+use crate::state::{Policy, Role};
+
+#[derive(Accounts)]
+pub struct SetLookupTable<'info> {
+    /// Holder of the operator role
+    pub operator: Signer<'info>,
+
+    /// The vault whose lookup table is registered
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA recording the lookup table
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Operator, &operator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+}
+
+/// Register the address lookup table keepers load the vault's crank accounts from;
+/// `Pubkey::default()` clears it
+pub fn handler(ctx: Context<SetLookupTable>, lookup_table: Pubkey) -> Result<()> {
+    ctx.accounts.policy.lookup_table = lookup_table;
+
+    emit!(LookupTableSet {
+        vault: ctx.accounts.vault.key(),
+        lookup_table,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Lookup table set to {} for vault: {}",
+        lookup_table,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+
+use crate::errors::StarError;
+use crate::events::AccountsMigrated;
+use crate::state::{
+    DayPhase, InvestorRecord, Policy, Progress, CURRENT_POLICY_VERSION, CURRENT_PROGRESS_VERSION,
+    DEFAULT_POLICY_UPDATE_DELAY_SECS, DEFAULT_STALE_AFTER_DAYS, MAX_INVESTOR_CLASSES,
+};
+
+#[derive(Accounts)]
+pub struct MigrateAccounts<'info> {
+    /// Funds the rent of grown accounts (anyone may migrate)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault whose accounts are migrated
+    /// CHECK: Used for PDA derivation only
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA, possibly in an older layout that no longer deserializes
+    /// CHECK: Owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// Progress PDA, possibly in the pre-zero-copy Borsh layout (same discriminator;
+    /// only loaded once grown to the zero-copy size)
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// System program for rent top-ups
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateInvestorRecord<'info> {
+    /// Funds the rent of the grown record (anyone may migrate)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault the record belongs to
+    /// CHECK: Used for PDA derivation only
+    pub vault: AccountInfo<'info>,
+
+    /// Vesting account the record tracks
+    /// CHECK: Used for PDA derivation only
+    pub stream: AccountInfo<'info>,
+
+    /// Investor record PDA, possibly in an older, shorter layout that no longer deserializes
+    /// CHECK: Owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"investor_record", stream.key().as_ref()],
+        bump
+    )]
+    pub investor_record: UncheckedAccount<'info>,
+
+    /// System program for rent top-ups
+    pub system_program: Program<'info, System>,
+}
+
+/// `Progress` as stored before it became zero-copy (Borsh, declaration order)
+#[derive(AnchorDeserialize)]
+struct LegacyProgress {
+    last_distribution_ts: i64,
+    distributed_today: u64,
+    carry_over: u64,
+    pagination_cursor: u64,
+    current_day: i64,
+    claimed_today: u64,
+    day_complete: bool,
+    last_stream_pubkey: Pubkey,
+    investor_root: [u8; 32],
+    total_investors: u64,
+    total_pages: u64,
+    investors_processed: u64,
+    vault: Pubkey,
+    bump: u8,
+    lifetime_claimed: u64,
+    lifetime_distributed_to_investors: u64,
+    lifetime_paid_to_creator: u64,
+    days_processed: u64,
+    last_crank_ts: i64,
+    lifetime_compounded: u64,
+    /// The treasury lending books; read past, never carried over
+    _reserved: [u64; 2],
+}
+
+impl LegacyProgress {
+    /// Serialized length without the discriminator; older accounts hold a prefix of it
+    const LEN: usize = 6 * 8 + 1 + 32 + 32 + 3 * 8 + 32 + 1 + 8 * 8;
+}
+
+/// Upgrade the vault's Policy and Progress to the current layouts: grow them to the
+/// current size, then run every migration step from their stored version
+pub fn handler(ctx: Context<MigrateAccounts>) -> Result<()> {
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let policy_info = ctx.accounts.policy.to_account_info();
+    check_discriminator(&policy_info, &Policy::DISCRIMINATOR)?;
+    let policy_from_version = migrate_policy(&policy_info, &payer, &system_program)?;
+
+    let progress_from_version = migrate_progress(&ctx.accounts.progress, &payer, &system_program)?;
+
+    emit!(AccountsMigrated {
+        vault: ctx.accounts.vault.key(),
+        policy_from_version,
+        progress_from_version,
+        policy_version: CURRENT_POLICY_VERSION,
+        progress_version: CURRENT_PROGRESS_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Migrated accounts for vault: {}, policy v{} -> v{}, progress v{} -> v{}",
+        ctx.accounts.vault.key(),
+        policy_from_version,
+        CURRENT_POLICY_VERSION,
+        progress_from_version,
+        CURRENT_PROGRESS_VERSION
+    );
+
+    Ok(())
+}
+
+/// Grow an investor record to the current layout. Fields are only appended and default
+/// to zero (`class` 0), so the zero-filled realloc is the whole migration.
+pub fn investor_record_handler(ctx: Context<MigrateInvestorRecord>) -> Result<()> {
+    let info = ctx.accounts.investor_record.to_account_info();
+    check_discriminator(&info, &InvestorRecord::DISCRIMINATOR)?;
+
+    grow_account(
+        &info,
+        InvestorRecord::SIZE,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Migrated investor record for vault: {}, stream: {}",
+        ctx.accounts.vault.key(),
+        ctx.accounts.stream.key()
+    );
+
+    Ok(())
+}
+
+/// Returns the version the policy was stored at
+fn migrate_policy<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8> {
+    // Fields are only ever appended, so growing with zeroes yields a readable policy
+    // whose new fields hold zero until the steps below set their defaults
+    grow_account(info, Policy::SIZE, payer, system_program)?;
+
+    let mut policy = Policy::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let from_version = policy.version;
+    require!(from_version <= CURRENT_POLICY_VERSION, StarError::UnsupportedAccountVersion);
+    if from_version == CURRENT_POLICY_VERSION {
+        return Ok(from_version);
+    }
+
+    if from_version < 1 {
+        // v1: policies created before versioning may predate these defaults
+        if policy.update_delay_secs == 0 {
+            policy.update_delay_secs = DEFAULT_POLICY_UPDATE_DELAY_SECS;
+        }
+        if policy.stale_after_days == 0 {
+            policy.stale_after_days = DEFAULT_STALE_AFTER_DAYS;
+        }
+    }
+    // v2 only appended fields that default to zero; the realloc above covers it
+    if from_version < 3 {
+        // v3: treasury lending was removed; clear its market so the bytes can be reused
+        policy._reserved[..32].fill(0);
+    }
+    if from_version < 4 {
+        // v4: automation threads were removed; clear the recorded thread likewise
+        policy._reserved[32..].fill(0);
+    }
+
+    policy.version = CURRENT_POLICY_VERSION;
+    policy.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    Ok(from_version)
+}
+
+/// Returns the version the progress was stored at
+fn migrate_progress<'info>(
+    loader: &AccountLoader<'info, Progress>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8> {
+    let info = loader.to_account_info();
+
+    // Zero-copy accounts are at least the first zero-copy size; anything shorter is the
+    // Borsh layout. Newer zero-copy fields are appended and grow in zero-filled.
+    let legacy = if info.data_len() < Progress::FIRST_ZERO_COPY_SIZE {
+        let mut data = info.try_borrow_data()?[8..].to_vec();
+        data.resize(LegacyProgress::LEN, 0);
+        Some(LegacyProgress::deserialize(&mut &data[..])?)
+    } else {
+        None
+    };
+
+    grow_account(&info, Progress::SIZE, payer, system_program)?;
+
+    let mut progress = loader.load_mut()?;
+    let from_version = match legacy {
+        Some(legacy) => {
+            *progress = Progress {
+                last_distribution_ts: legacy.last_distribution_ts,
+                distributed_today: legacy.distributed_today,
+                carry_over: legacy.carry_over,
+                pagination_cursor: legacy.pagination_cursor,
+                current_day: legacy.current_day,
+                claimed_today: legacy.claimed_today,
+                total_investors: legacy.total_investors,
+                total_pages: legacy.total_pages,
+                investors_processed: legacy.investors_processed,
+                lifetime_claimed: legacy.lifetime_claimed,
+                lifetime_distributed_to_investors: legacy.lifetime_distributed_to_investors,
+                lifetime_paid_to_creator: legacy.lifetime_paid_to_creator,
+                days_processed: legacy.days_processed,
+                last_crank_ts: legacy.last_crank_ts,
+                lifetime_compounded: legacy.lifetime_compounded,
+                _reserved: [0u64; 2],
+                last_stream_pubkey: legacy.last_stream_pubkey,
+                investor_root: legacy.investor_root,
+                vault: legacy.vault,
+                day_complete: legacy.day_complete as u8,
+                bump: legacy.bump,
+                version: 0,
+                _padding: [0u8; 5],
+                last_page_ts: 0,
+                phase: DayPhase::Idle as u8,
+                _phase_padding: [0u8; 7],
+                carry_over_at_open: 0,
+                harvested_pending: 0,
+                lifetime_insured: 0,
+                day_locked_total: 0,
+                day_locked_processed: 0,
+                day_class_pools: [0u64; MAX_INVESTOR_CLASSES],
+                day_eligible_share_bps: 0,
+                _day_padding: [0u8; 6],
+                day_lock_ts: 0,
+                day_class_locked: [0u64; MAX_INVESTOR_CLASSES],
+                day_class_weights: [0u64; MAX_INVESTOR_CLASSES],
+                day_class_locked_processed: [0u64; MAX_INVESTOR_CLASSES],
+                day_class_weights_processed: [0u64; MAX_INVESTOR_CLASSES],
+            };
+            0
+        }
+        None => progress.version,
+    };
+    require!(from_version <= CURRENT_PROGRESS_VERSION, StarError::UnsupportedAccountVersion);
+    if from_version == CURRENT_PROGRESS_VERSION {
+        return Ok(from_version);
+    }
+
+    if from_version < 2 && progress.phase()? == DayPhase::Idle {
+        // v2: the phase grew in as `Idle`; derive it from the day's state instead, so a
+        // day left mid-way is not claimed again and can be finished or force-closed
+        progress.phase = progress.phase_from_day_state() as u8;
+    }
+    if from_version < 3 {
+        // v3: treasury lending was removed; clear its books so the bytes can be reused
+        progress._reserved = [0u64; 2];
+    }
+    if from_version < 4 && progress.is_day_in_progress() {
+        // v4: pages divide by class totals committed at day open, which a day opened
+        // earlier lacks, so its remaining pages cannot run. An unclaimed day is closed as
+        // is (its fees wait in the position for the next day); a day mid-distribution is cut
+        // at the investors already paid, so `force_close_day` settles it and the unpaid
+        // pool carries over to the next day's investors.
+        if progress.phase()? == DayPhase::Idle {
+            progress.phase = DayPhase::Closed as u8;
+            progress.day_complete = 1;
+        } else {
+            progress.total_investors = progress.investors_processed;
+            progress.total_pages = progress.pagination_cursor;
+        }
+    }
+
+    progress.version = CURRENT_PROGRESS_VERSION;
+
+    Ok(from_version)
+}
+
+/// Check owner and discriminator of an account that may not deserialize yet
+fn check_discriminator(info: &AccountInfo, discriminator: &[u8]) -> Result<()> {
+    require_keys_eq!(*info.owner, crate::ID, StarError::InvalidOwner);
+    let data = info.try_borrow_data()?;
+    if data.len() < 8 || data[..8] != *discriminator {
+        return err!(ErrorCode::AccountDiscriminatorMismatch);
+    }
+    Ok(())
+}
+
+/// Reallocate `info` to `new_len` (zero-filled), topping up rent from `payer`
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if info.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(new_len);
+    // Saturates on purpose: an account already holding the new rent needs no top-up
+    let missing = required.saturating_sub(info.lamports());
+    if missing > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.clone(),
+            SystemTransfer {
+                from: payer.clone(),
+                to: info.clone(),
+            },
+        );
+        system_program::transfer(cpi_ctx, missing)?;
+    }
+
+    info.realloc(new_len, true)?;
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::{PositionMigrated, PositionMigrationCancelled, PositionMigrationQueued};
+use crate::instructions::update_policy::UpdatePolicy;
+use crate::state::{
+    PendingPositionMigration, Policy, Progress, CP_AMM_PROGRAM_ID, POSITION_MIGRATION_SUPPORTED,
+    POSITION_MIGRATION_TIMELOCK_SECS,
+};
+use crate::utils::{CpAmmUtils, PoolConfig, ValidationUtils};
+
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    /// Policy authority executing the migration
+    pub authority: Signer<'info>,
+
+    /// Funds the new position accounts and receives the old position's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault whose position migrates
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the queued migration and the position references
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA accumulating the fees claimed from the old position
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Honorary LP position owner PDA, signing for both positions
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Program treasury PDA receiving the old position's outstanding fees
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"treasury", policy.quote_mint.as_ref()],
+        bump,
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool the position currently sits in
+    /// CHECK: Must be the pool registered on the policy
+    #[account(mut, address = policy.pool @ StarError::InvalidCpAmmPool)]
+    pub old_pool: AccountInfo<'info>,
+
+    /// Current honorary position, closed by the migration
+    /// CHECK: Must be the position registered on the policy
+    #[account(mut, address = policy.position @ StarError::InvalidHonoraryPosition)]
+    pub old_position: AccountInfo<'info>,
+
+    /// Pool the position moves to, as queued
+    /// CHECK: Checked against the queued migration and validated for quote-only fee accrual
+    #[account(mut)]
+    pub new_pool: AccountInfo<'info>,
+
+    /// New honorary position created by the CP-AMM program
+    /// CHECK: Initialized by the CP-AMM program and verified after creation
+    #[account(mut)]
+    pub new_position: AccountInfo<'info>,
+
+    /// Token account holding the new position's NFT, owned by the position owner PDA
+    /// CHECK: Verified against the new position's NFT mint after creation
+    pub new_position_nft_account: AccountInfo<'info>,
+
+    /// Quote mint recorded on the policy
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// Base mint (first token in the new pool)
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// CP-AMM program
+    /// CHECK: Validated against the known CP-AMM program ID
+    #[account(address = CP_AMM_PROGRAM_ID @ StarError::InvalidCpAmmProgram)]
+    pub cp_amm_program: AccountInfo<'info>,
+
+    /// Token program for the fee claim and the position NFT
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a move of the honorary position to `new_pool`, executable after
+/// `POSITION_MIGRATION_TIMELOCK_SECS`. Queuing again replaces the pending migration and
+/// restarts the timelock.
+pub fn propose_handler(ctx: Context<UpdatePolicy>, new_pool: Pubkey) -> Result<()> {
+    // Never queue a migration this build cannot execute
+    require!(POSITION_MIGRATION_SUPPORTED, StarError::PositionMigrationUnsupported);
+
+    let policy = &mut ctx.accounts.policy;
+    require!(policy.position != Pubkey::default(), StarError::InvalidHonoraryPosition);
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let executable_at = current_timestamp
+        .checked_add(POSITION_MIGRATION_TIMELOCK_SECS)
+        .ok_or(StarError::MathOverflow)?;
+
+    policy.pending_position_migration = Some(PendingPositionMigration {
+        new_pool,
+        executable_at,
+    });
+
+    emit!(PositionMigrationQueued {
+        vault: ctx.accounts.vault.key(),
+        new_pool,
+        executable_at,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Position migration to pool {} queued for vault: {}, executable at: {}",
+        new_pool,
+        ctx.accounts.vault.key(),
+        executable_at
+    );
+
+    Ok(())
+}
+
+/// Drop the queued position migration
+pub fn cancel_handler(ctx: Context<UpdatePolicy>) -> Result<()> {
+    let migration = ctx.accounts.policy.pending_position_migration
+        .take()
+        .ok_or(StarError::NoPendingPositionMigration)?;
+
+    emit!(PositionMigrationCancelled {
+        vault: ctx.accounts.vault.key(),
+        new_pool: migration.new_pool,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Position migration to pool {} cancelled for vault: {}",
+        migration.new_pool,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Move the honorary position to the queued pool once the timelock has elapsed: claim the
+/// old position's outstanding fees, close it, open an equivalent position in the new pool
+/// and record the new pool and position on the policy. Only between distribution days.
+pub fn handler(ctx: Context<MigratePosition>) -> Result<()> {
+    // Without the CP-AMM close and create CPIs the new position would be recorded unchecked
+    require!(POSITION_MIGRATION_SUPPORTED, StarError::PositionMigrationUnsupported);
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let migration = ctx.accounts.policy.pending_position_migration
+        .ok_or(StarError::NoPendingPositionMigration)?;
+
+    require!(current_timestamp >= migration.executable_at, StarError::PositionMigrationTimelockActive);
+    require_keys_eq!(ctx.accounts.new_pool.key(), migration.new_pool, StarError::InvalidCpAmmPool);
+
+    let mut progress = ctx.accounts.progress.load_mut()?;
+    require!(!progress.is_day_in_progress(), StarError::DayInProgress);
+
+    // The new pool must meet the quote-only rules before anything moves, and the new
+    // position keeps the configured range, which must still sit above the new pool's price
+    let policy = &ctx.accounts.policy;
+    let pool_config = PoolConfig {
+        token_a: ctx.accounts.base_mint.key(),
+        token_b: ctx.accounts.quote_mint.key(),
+        pool_id: ctx.accounts.new_pool.key(),
+        tick_lower: policy.tick_lower,
+        tick_upper: policy.tick_upper,
+    };
+
+    ValidationUtils::validate_quote_only_pool(&pool_config, &policy.quote_mint)?;
+    if policy.tick_lower != policy.tick_upper {
+        ValidationUtils::validate_quote_only_range(
+            &pool_config,
+            CpAmmUtils::pool_sqrt_price(&ctx.accounts.new_pool)?,
+        )?;
+    }
+
+    let vault_key = ctx.accounts.vault.key();
+    let position_owner_seeds: &[&[u8]] = &[
+        b"vault",
+        vault_key.as_ref(),
+        b"investor_fee_pos_owner",
+        &[ctx.bumps.position_owner_pda],
+    ];
+
+    // Outstanding fees join the next day's first page, like a harvest
+    let claim_result = CpAmmUtils::claim_position_fees(
+        &ctx.accounts.cp_amm_program,
+        &ctx.accounts.old_pool,
+        &ctx.accounts.position_owner_pda,
+        position_owner_seeds,
+        &ctx.accounts.program_treasury.to_account_info(),
+        Some(&ctx.accounts.quote_mint.to_account_info()),
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    ValidationUtils::detect_base_fees(&claim_result)?;
+
+    let fees_claimed = claim_result.quote_amount;
+    progress.harvested_pending = progress.harvested_pending
+        .checked_add(fees_claimed)
+        .ok_or(StarError::MathOverflow)?;
+    progress.lifetime_claimed = progress.lifetime_claimed
+        .checked_add(fees_claimed)
+        .ok_or(StarError::MathOverflow)?;
+
+    CpAmmUtils::close_position(
+        &ctx.accounts.cp_amm_program,
+        &ctx.accounts.old_pool,
+        &ctx.accounts.old_position,
+        &ctx.accounts.position_owner_pda,
+        position_owner_seeds,
+        &ctx.accounts.payer,
+    )?;
+
+    CpAmmUtils::create_position(
+        &ctx.accounts.cp_amm_program,
+        &ctx.accounts.new_pool,
+        &ctx.accounts.new_position,
+        &ctx.accounts.position_owner_pda,
+        position_owner_seeds,
+        &ctx.accounts.payer,
+        pool_config.tick_lower,
+        pool_config.tick_upper,
+    )?;
+
+    CpAmmUtils::verify_position_custody(
+        &ctx.accounts.new_position,
+        &ctx.accounts.new_pool.key(),
+        &ctx.accounts.new_position_nft_account,
+        &ctx.accounts.position_owner_pda.key(),
+    )?;
+
+    ValidationUtils::validate_fee_only_position(
+        &ctx.accounts.new_position,
+        &pool_config,
+        CpAmmUtils::pool_sqrt_price(&ctx.accounts.new_pool)?,
+    )?;
+
+    let policy = &mut ctx.accounts.policy;
+    let old_pool = policy.pool;
+    let old_position = policy.position;
+    policy.pool = migration.new_pool;
+    policy.position = ctx.accounts.new_position.key();
+    policy.pending_position_migration = None;
+
+    emit!(PositionMigrated {
+        vault: vault_key,
+        old_pool,
+        old_position,
+        new_pool: policy.pool,
+        new_position: policy.position,
+        fees_claimed,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Position migrated from pool {} to pool {} for vault: {}, fees claimed: {}",
+        old_pool,
+        policy.pool,
+        vault_key,
+        fees_claimed
+    );
+
+    Ok(())
+}
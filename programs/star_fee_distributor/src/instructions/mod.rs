@@ -1,5 +1,63 @@
+// Every module names its entry point `handler`; lib.rs calls them by module path, and the
+// globs only re-export the generated account structs Anchor's `#[program]` expects
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize;
+pub mod open_day;
 pub mod crank;
+pub mod simulate;
+pub mod preflight;
+pub mod investor_record;
+pub mod recovery;
+pub mod update_policy;
+pub mod roles;
+pub mod stale_vault;
+pub mod blacklist;
+pub mod cranker_allowlist;
+pub mod migrate;
+pub mod batch_initialize;
+pub mod lookup_table;
+pub mod remainder_policy;
+pub mod investor_class;
+pub mod refresh_locks;
+pub mod harvest;
+pub mod insurance;
+pub mod global_config;
+pub mod adopt_position;
+pub mod migrate_position;
+pub mod investor_registry;
+pub mod crank_fee_vault;
+pub mod force_close_day;
+pub mod skip_day;
+pub mod policy_template;
+pub mod vault_metadata;
 
 pub use initialize::*;
+pub use open_day::*;
 pub use crank::*;
+pub use simulate::*;
+pub use preflight::*;
+pub use investor_record::*;
+pub use recovery::*;
+pub use update_policy::*;
+pub use roles::*;
+pub use stale_vault::*;
+pub use blacklist::*;
+pub use cranker_allowlist::*;
+pub use migrate::*;
+pub use batch_initialize::*;
+pub use lookup_table::*;
+pub use remainder_policy::*;
+pub use investor_class::*;
+pub use refresh_locks::*;
+pub use harvest::*;
+pub use insurance::*;
+pub use global_config::*;
+pub use adopt_position::*;
+pub use migrate_position::*;
+pub use investor_registry::*;
+pub use crank_fee_vault::*;
+pub use force_close_day::*;
+pub use skip_day::*;
+pub use policy_template::*;
+pub use vault_metadata::*;
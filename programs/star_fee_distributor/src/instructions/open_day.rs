@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::DayOpened;
+use crate::state::{DayClassTotal, Policy, Progress, Role};
+
+#[derive(Accounts)]
+pub struct OpenDay<'info> {
+    /// Policy authority or holder of the operator role; the investor set and class
+    /// totals it commits are trusted by every page of the day
+    pub caller: Signer<'info>,
+
+    /// The vault this distribution belongs to
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the vault's roles
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Admin, &caller.key())
+            || policy.has_role(Role::Operator, &caller.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA tracking daily distribution state
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+}
+
+pub fn handler(
+    ctx: Context<OpenDay>,
+    investor_root: [u8; 32],
+    total_investors: u64,
+    total_pages: u64,
+    class_totals: Vec<DayClassTotal>,
+    lock_ts: i64,
+) -> Result<()> {
+    let mut progress = ctx.accounts.progress.load_mut()?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    // Day numbers only move forward; a closed day can never be opened again
+    progress.check_clock(current_timestamp)?;
+    require!(
+        Progress::day_number(current_timestamp) > progress.current_day,
+        StarError::ClockRollback
+    );
+
+    // 24h gate enforcement
+    require!(
+        progress.is_new_day(current_timestamp),
+        StarError::DistributionTooEarly
+    );
+
+    // The committed investor set must be non-empty
+    require!(investor_root != [0u8; 32], StarError::InvalidInvestorRoot);
+    require!(
+        total_investors > 0 && total_pages > 0 && total_pages <= total_investors,
+        StarError::InvalidInvestorCount
+    );
+
+    // Locks are read at a moment of the day being opened, never in the future, so the
+    // committed totals can be checked against the vesting accounts by every page
+    require!(
+        lock_ts <= current_timestamp
+            && Progress::day_number(lock_ts) == Progress::day_number(current_timestamp),
+        StarError::InvalidLockTimestamp
+    );
+
+    progress.reset_for_new_day(current_timestamp);
+    progress.investor_root = investor_root;
+    progress.total_investors = total_investors;
+    progress.total_pages = total_pages;
+    // Every page divides by these totals, and the final page requires them to be reached,
+    // so only the role holders gating this instruction may commit them
+    progress.commit_day_totals(&class_totals, lock_ts)?;
+    let total_locked = progress.day_locked_total;
+
+    emit!(DayOpened {
+        vault: ctx.accounts.vault.key(),
+        day: progress.current_day,
+        investor_root,
+        total_investors,
+        total_pages,
+        timestamp: current_timestamp,
+        total_locked,
+        class_totals,
+        lock_ts,
+    });
+
+    msg!(
+        "Opened distribution day {} for vault: {}",
+        progress.current_day,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
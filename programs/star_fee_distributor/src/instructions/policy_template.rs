@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::{PolicyTemplateApplied, PolicyTemplateCreated};
+use crate::instructions::initialize::*;
+use crate::state::{GlobalConfig, PolicyTemplate, VaultInitParams};
+
+#[derive(Accounts)]
+#[instruction(template_id: u32)]
+pub struct CreatePolicyTemplate<'info> {
+    /// Global config admin
+    pub admin: Signer<'info>,
+
+    /// Funds the template rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Global config PDA holding the admin
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ StarError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Policy template PDA
+    #[account(
+        init,
+        payer = payer,
+        space = PolicyTemplate::SIZE,
+        seeds = [b"policy_template".as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub policy_template: Account<'info, PolicyTemplate>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFromTemplate<'info> {
+    /// Accounts of `init_policy` for the new vault
+    pub init_policy: InitPolicy<'info>,
+
+    /// Policy template the vault's parameters are cloned from
+    #[account(
+        seeds = [b"policy_template".as_ref(), &policy_template.template_id.to_le_bytes()],
+        bump = policy_template.bump
+    )]
+    pub policy_template: Account<'info, PolicyTemplate>,
+}
+
+/// Create an immutable policy template under `template_id`; global config admin only.
+/// The parameters are validated exactly as `init_policy` would validate them.
+pub fn create_handler(
+    ctx: Context<CreatePolicyTemplate>,
+    template_id: u32,
+    params: VaultInitParams,
+) -> Result<()> {
+    build_policy(params.clone(), Pubkey::default(), Pubkey::default(), 0, Pubkey::default())?;
+
+    ctx.accounts.policy_template.set_inner(PolicyTemplate {
+        template_id,
+        params: params.clone(),
+        bump: ctx.bumps.policy_template,
+    });
+
+    emit!(PolicyTemplateCreated {
+        template: ctx.accounts.policy_template.key(),
+        template_id,
+        investor_fee_share_bps: params.investor_fee_share_bps,
+        daily_cap: params.daily_cap,
+        min_payout_lamports: params.min_payout_lamports,
+        y0: params.y0,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Policy template {} created: {}",
+        template_id,
+        ctx.accounts.policy_template.key()
+    );
+
+    Ok(())
+}
+
+/// Initialize a vault exactly like `init_policy`, with the parameters of a policy template
+pub fn initialize_handler(ctx: Context<InitializeFromTemplate>) -> Result<()> {
+    let template_key = ctx.accounts.policy_template.key();
+    let template_id = ctx.accounts.policy_template.template_id;
+    let params = ctx.accounts.policy_template.params.clone();
+
+    init_vault_policy(&mut ctx.accounts.init_policy, &ctx.bumps.init_policy, params)?;
+
+    let vault_key = ctx.accounts.init_policy.vault.key();
+
+    emit!(PolicyTemplateApplied {
+        vault: vault_key,
+        template: template_key,
+        template_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Vault {} initialized from policy template {}", vault_key, template_id);
+
+    Ok(())
+}
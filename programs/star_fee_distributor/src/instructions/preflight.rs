@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::StarError;
+use crate::state::{Policy, Progress, CP_AMM_PROGRAM_ID, STREAMFLOW_PROGRAM_ID};
+use crate::utils::{CpAmmUtils, PoolConfig, ValidationUtils};
+
+#[derive(Accounts)]
+pub struct PreflightValidate<'info> {
+    /// The vault to validate
+    /// CHECK: Validated vault
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA containing distribution configuration
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA tracking daily distribution state
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Honorary LP position owner PDA
+    /// CHECK: This PDA owns the honorary position in CP-AMM
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Token account holding the honorary position NFT
+    pub position_nft_account: Account<'info, token::TokenAccount>,
+
+    /// Program treasury ATA (holds claimed quote fees)
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint (must be the second token in the pool)
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// Base mint (first token in the pool)
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// CP-AMM pool account
+    /// CHECK: Owner validated against the CP-AMM program
+    pub cp_amm_pool: AccountInfo<'info>,
+
+    /// CP-AMM program
+    /// CHECK: Validated against the CP-AMM program ID
+    pub cp_amm_program: AccountInfo<'info>,
+
+    /// Streamflow program
+    /// CHECK: Validated against the Streamflow program ID
+    pub streamflow_program: AccountInfo<'info>,
+}
+
+/// Run every vault setup validation without mutating state
+pub fn handler(ctx: Context<PreflightValidate>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let policy = &accounts.policy;
+
+    // Integration program IDs
+    require_keys_eq!(
+        accounts.cp_amm_program.key(),
+        CP_AMM_PROGRAM_ID,
+        StarError::InvalidCpAmmConfig
+    );
+    require_keys_eq!(
+        *accounts.cp_amm_pool.owner,
+        CP_AMM_PROGRAM_ID,
+        StarError::InvalidCpAmmConfig
+    );
+    require_keys_eq!(
+        accounts.streamflow_program.key(),
+        STREAMFLOW_PROGRAM_ID,
+        StarError::InvalidStreamAccount
+    );
+
+    // Pool quote-only configuration
+    require_keys_eq!(
+        accounts.quote_mint.key(),
+        policy.quote_mint,
+        StarError::InvalidQuoteMint
+    );
+
+    let pool_config = PoolConfig {
+        token_a: accounts.base_mint.key(),
+        token_b: accounts.quote_mint.key(),
+        pool_id: accounts.cp_amm_pool.key(),
+        tick_lower: policy.tick_lower,
+        tick_upper: policy.tick_upper,
+    };
+    ValidationUtils::validate_quote_only_pool(&pool_config, &policy.quote_mint)?;
+    if policy.tick_lower != policy.tick_upper {
+        ValidationUtils::validate_quote_only_range(
+            &pool_config,
+            CpAmmUtils::pool_sqrt_price(&accounts.cp_amm_pool)?,
+        )?;
+    }
+
+    // Treasury must hold the quote mint and be controlled by the position owner PDA
+    require_keys_eq!(
+        accounts.program_treasury.mint,
+        policy.quote_mint,
+        StarError::InvalidTreasuryAta
+    );
+    require_keys_eq!(
+        accounts.program_treasury.owner,
+        accounts.position_owner_pda.key(),
+        StarError::InvalidTreasuryAta
+    );
+
+    // The position NFT must be in the custody of the position owner PDA
+    require_keys_eq!(
+        accounts.position_nft_account.owner,
+        accounts.position_owner_pda.key(),
+        StarError::InvalidOwner
+    );
+    require!(
+        accounts.position_nft_account.amount == 1,
+        StarError::InvalidOwner
+    );
+
+    // Stored state must belong to this vault
+    require_keys_eq!(policy.vault, accounts.vault.key(), StarError::NotInitialized);
+    require_keys_eq!(accounts.progress.load()?.vault, accounts.vault.key(), StarError::NotInitialized);
+
+    msg!("Preflight validation passed for vault: {}", accounts.vault.key());
+
+    Ok(())
+}
@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::{RecoveryCancelled, RecoveryExecuted, RecoveryProposed};
+use crate::instructions::crank::resolve_checked_transfer;
+use crate::state::{Policy, RecoveryProposal, RECOVERY_TIMELOCK_SECS};
+use crate::utils::TokenTransferUtils;
+
+#[derive(Accounts)]
+pub struct ProposeRecovery<'info> {
+    /// Policy authority proposing the recovery (a multisig vault PDA signs via CPI)
+    pub authority: Signer<'info>,
+
+    /// Funds the proposal rent, so the authority never needs to hold lamports
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault whose treasury is recovered
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Honorary LP position owner PDA (treasury authority)
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Token account holding the stranded funds: the program treasury or the insurance fund
+    #[account(
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account receiving the recovered funds
+    #[account(constraint = destination.mint == policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recovery proposal PDA (one pending proposal per vault)
+    #[account(
+        init,
+        payer = payer,
+        space = RecoveryProposal::SIZE,
+        seeds = [b"vault", vault.key().as_ref(), b"recovery"],
+        bump
+    )]
+    pub recovery_proposal: Account<'info, RecoveryProposal>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    /// Policy authority executing the recovery (a multisig vault PDA signs via CPI)
+    pub authority: Signer<'info>,
+
+    /// Original proposal payer, refunded the proposal rent
+    /// CHECK: Must match the payer recorded on the proposal
+    #[account(mut, address = recovery_proposal.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// The vault whose treasury is recovered
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Honorary LP position owner PDA (treasury authority)
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Token account committed in the proposal as the source of the funds
+    #[account(
+        mut,
+        address = recovery_proposal.source @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account committed in the proposal
+    #[account(mut, address = recovery_proposal.destination @ StarError::RecoveryDestinationMismatch)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pending recovery proposal, closed back to the payer on execution
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"vault", vault.key().as_ref(), b"recovery"],
+        bump = recovery_proposal.bump
+    )]
+    pub recovery_proposal: Account<'info, RecoveryProposal>,
+
+    /// Quote mint; required for a Token-2022 treasury
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token program owning the treasury (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    /// Policy authority cancelling the recovery (a multisig vault PDA signs via CPI)
+    pub authority: Signer<'info>,
+
+    /// Original proposal payer, refunded the proposal rent
+    /// CHECK: Must match the payer recorded on the proposal
+    #[account(mut, address = recovery_proposal.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// The vault the recovery targets
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Pending recovery proposal, closed back to the payer
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"vault", vault.key().as_ref(), b"recovery"],
+        bump = recovery_proposal.bump
+    )]
+    pub recovery_proposal: Account<'info, RecoveryProposal>,
+}
+
+/// Propose moving `amount` of treasury (or insurance fund) funds to `destination` after
+/// the 72h timelock
+pub fn propose_handler(ctx: Context<ProposeRecovery>, amount: u64) -> Result<()> {
+    require!(
+        amount > 0 && amount <= ctx.accounts.program_treasury.amount,
+        StarError::InvalidRecoveryAmount
+    );
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let executable_at = current_timestamp
+        .checked_add(RECOVERY_TIMELOCK_SECS)
+        .ok_or(StarError::MathOverflow)?;
+
+    let recovery_proposal = &mut ctx.accounts.recovery_proposal;
+    recovery_proposal.vault = ctx.accounts.vault.key();
+    recovery_proposal.proposer = ctx.accounts.authority.key();
+    recovery_proposal.payer = ctx.accounts.payer.key();
+    recovery_proposal.destination = ctx.accounts.destination.key();
+    recovery_proposal.amount = amount;
+    recovery_proposal.proposed_at = current_timestamp;
+    recovery_proposal.executable_at = executable_at;
+    recovery_proposal.bump = ctx.bumps.recovery_proposal;
+    recovery_proposal.source = ctx.accounts.program_treasury.key();
+
+    emit!(RecoveryProposed {
+        vault: ctx.accounts.vault.key(),
+        proposer: ctx.accounts.authority.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        executable_at,
+        timestamp: current_timestamp,
+        source: ctx.accounts.program_treasury.key(),
+    });
+
+    msg!(
+        "TREASURY RECOVERY PROPOSED for vault: {}, amount: {}, destination: {}, executable at: {}",
+        ctx.accounts.vault.key(),
+        amount,
+        ctx.accounts.destination.key(),
+        executable_at
+    );
+
+    Ok(())
+}
+
+/// Execute a proposed recovery once its timelock has elapsed. remaining_accounts carry the
+/// quote mint's transfer hook accounts, if any.
+pub fn execute_handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteRecovery<'info>>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let recovery_proposal = &ctx.accounts.recovery_proposal;
+
+    require!(
+        current_timestamp >= recovery_proposal.executable_at,
+        StarError::RecoveryTimelockActive
+    );
+
+    // Never move more than the treasury currently holds
+    let amount = recovery_proposal.amount.min(ctx.accounts.program_treasury.amount);
+    require!(amount > 0, StarError::InvalidRecoveryAmount);
+
+    let vault_key = ctx.accounts.vault.key();
+    let checked_transfer = resolve_checked_transfer(
+        &ctx.accounts.token_program,
+        ctx.accounts.quote_mint.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+    TokenTransferUtils::transfer_signed(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.program_treasury.to_account_info(),
+        &ctx.accounts.destination.to_account_info(),
+        &ctx.accounts.position_owner_pda,
+        &[
+            b"vault",
+            vault_key.as_ref(),
+            b"investor_fee_pos_owner",
+            &[ctx.bumps.position_owner_pda],
+        ],
+        amount,
+        checked_transfer.as_ref(),
+    )?;
+
+    emit!(RecoveryExecuted {
+        vault: vault_key,
+        destination: ctx.accounts.destination.key(),
+        amount,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "TREASURY RECOVERY EXECUTED for vault: {}, amount: {}, destination: {}",
+        vault_key,
+        amount,
+        ctx.accounts.destination.key()
+    );
+
+    Ok(())
+}
+
+/// Cancel a pending recovery proposal
+pub fn cancel_handler(ctx: Context<CancelRecovery>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    emit!(RecoveryCancelled {
+        vault: ctx.accounts.vault.key(),
+        amount: ctx.accounts.recovery_proposal.amount,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Treasury recovery cancelled for vault: {}",
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::{LocksRefreshed, LocksSnapshotted};
+use crate::state::{InvestorRecord, Policy, Progress};
+use crate::utils::VestingUtils;
+
+#[derive(Accounts)]
+pub struct RefreshLocks<'info> {
+    /// The vault whose investor locks are sampled
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA, for time-weighted locked weighting
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA, to date the lock snapshot
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+}
+
+/// Read the current lock of each investor. Once the next day has begun and before it is
+/// opened, the lock is cached on the record as the investor's snapshot for that day, taken
+/// at most once so every page of the day weighs the same lock. Under time-weighted
+/// weighting the lock is also sampled into the record's accumulator, at any time of day.
+/// Permissionless. Remaining accounts are (vesting account, investor record) pairs.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshLocks<'info>>) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty() && remaining_accounts.chunks_exact(2).remainder().is_empty(),
+        StarError::InvalidInvestorRecord
+    );
+
+    let vault_key = ctx.accounts.vault.key();
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let twal_weighting = ctx.accounts.policy.twal_weighting;
+
+    let progress = ctx.accounts.progress.load()?;
+    let snapshot_day = Progress::day_number(current_timestamp);
+    let snapshot_open = !progress.is_day_in_progress() && snapshot_day > progress.current_day;
+    // An open day's pages read time-weighted averages at its committed lock timestamp, so
+    // samples only land between days
+    let twal_open = twal_weighting && !progress.is_day_in_progress();
+    require!(twal_open || snapshot_open, StarError::LockSnapshotClosed);
+
+    let mut snapshotted = 0u64;
+    for pair in remaining_accounts.chunks_exact(2) {
+        let (stream, record_info) = (&pair[0], &pair[1]);
+
+        let mut record = Account::<InvestorRecord>::try_from(record_info)?;
+        require_keys_eq!(record.vault, vault_key, StarError::InvalidInvestorRecord);
+        require_keys_eq!(record.stream_pubkey, stream.key(), StarError::InvalidInvestorRecord);
+
+        // Cancelled or closed streams sample as zero locked
+        let lock = VestingUtils::read_lock(stream, current_timestamp)?;
+        if twal_open {
+            record.sample_lock(lock.locked_amount, current_timestamp)?;
+        }
+        if snapshot_open && record.snapshot_lock(snapshot_day, lock.locked_amount, current_timestamp) {
+            snapshotted += 1;
+        }
+        record.exit(&crate::ID)?;
+    }
+
+    let investors = (remaining_accounts.len() / 2) as u64;
+
+    if snapshotted > 0 {
+        emit!(LocksSnapshotted {
+            vault: vault_key,
+            day: snapshot_day,
+            investors: snapshotted,
+            timestamp: current_timestamp,
+        });
+    }
+
+    emit!(LocksRefreshed {
+        vault: vault_key,
+        investors,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Refreshed {} investor locks for vault: {}, {} snapshotted",
+        investors,
+        vault_key,
+        snapshotted
+    );
+
+    Ok(())
+}
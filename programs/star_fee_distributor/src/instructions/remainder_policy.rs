@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::RemainderPolicySet;
+use crate::state::{Policy, RemainderPolicy, Role};
+
+#[derive(Accounts)]
+pub struct SetRemainderPolicy<'info> {
+    /// Holder of the creator role
+    pub creator: Signer<'info>,
+
+    /// The vault whose remainder policy is set
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the creator role and remainder policy
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Creator, &creator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+}
+
+/// Choose what day close does with the remainder: pay the creator, donate it to the
+/// next day's investor pool, or burn it
+pub fn handler(ctx: Context<SetRemainderPolicy>, remainder_policy: RemainderPolicy) -> Result<()> {
+    ctx.accounts.policy.remainder_policy = remainder_policy;
+
+    emit!(RemainderPolicySet {
+        vault: ctx.accounts.vault.key(),
+        remainder_policy,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Remainder policy set to {:?} for vault: {}",
+        remainder_policy,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::{RoleGranted, RoleRevoked};
+use crate::state::{Policy, Role};
+
+#[derive(Accounts)]
+pub struct ManageRole<'info> {
+    /// Current admin (policy authority)
+    pub authority: Signer<'info>,
+
+    /// The vault whose roles are managed
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the roles
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+}
+
+/// Grant `role` to `account`, replacing its current holder.
+/// Granting `Admin` hands over the policy authority.
+pub fn grant_handler(ctx: Context<ManageRole>, role: Role, account: Pubkey) -> Result<()> {
+    require_keys_neq!(account, Pubkey::default(), StarError::Unauthorized);
+
+    let policy = &mut ctx.accounts.policy;
+    let previous = policy.role_holder(role);
+
+    match role {
+        Role::Admin => policy.authority = account,
+        Role::Operator => policy.operator = account,
+        Role::Creator => policy.creator = account,
+    }
+
+    emit!(RoleGranted {
+        vault: ctx.accounts.vault.key(),
+        role,
+        account,
+        previous,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Role {:?} granted to {} for vault: {}", role, account, ctx.accounts.vault.key());
+
+    Ok(())
+}
+
+/// Revoke `role` from its current holder. The admin role cannot be revoked.
+pub fn revoke_handler(ctx: Context<ManageRole>, role: Role) -> Result<()> {
+    let policy = &mut ctx.accounts.policy;
+    let account = policy.role_holder(role);
+
+    match role {
+        Role::Admin => return err!(StarError::CannotRevokeAdmin),
+        Role::Operator => policy.operator = Pubkey::default(),
+        Role::Creator => policy.creator = Pubkey::default(),
+    }
+
+    emit!(RoleRevoked {
+        vault: ctx.accounts.vault.key(),
+        role,
+        account,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Role {:?} revoked from {} for vault: {}", role, account, ctx.accounts.vault.key());
+
+    Ok(())
+}
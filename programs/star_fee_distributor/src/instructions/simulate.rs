@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::state::{DistributionSimulation, Policy, Progress, MAX_INVESTORS_PER_PAGE};
+use crate::utils::{DistributionMath, VestingUtils};
+
+#[derive(Accounts)]
+pub struct SimulateDistribution<'info> {
+    /// The vault to simulate a distribution for
+    /// CHECK: Validated vault
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA containing distribution configuration
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA tracking daily distribution state
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+}
+
+/// Preview a single page distribution without transfers or state writes. `claimed_quote`
+/// is the day's claim; once the day's first page has run, the page pays out of the pool it fixed.
+/// Remaining accounts: one vesting account per investor, in page order.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SimulateDistribution<'info>>,
+    claimed_quote: u64,
+) -> Result<DistributionSimulation> {
+    let progress = ctx.accounts.progress.load()?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    // Preview with any queued parameter change the next day's first page would apply
+    let mut policy = (*ctx.accounts.policy).clone();
+    if progress.pagination_cursor == 0 {
+        policy.apply_pending_update(current_timestamp);
+    }
+    let policy = &policy;
+
+    require!(!ctx.remaining_accounts.is_empty(), StarError::NoLockedInvestors);
+    require!(
+        ctx.remaining_accounts.len() <= MAX_INVESTORS_PER_PAGE,
+        StarError::PageTooLarge
+    );
+
+    // Until a day is open the page stands for the whole day, read now; an open day's pages
+    // are read at its committed lock timestamp
+    let day_in_progress = progress.is_day_in_progress();
+    let lock_ts = if day_in_progress { progress.day_lock_ts } else { current_timestamp };
+
+    // Read locks exactly as the crank would (skipped streams count as zero)
+    let locks = ctx
+        .remaining_accounts
+        .iter()
+        .map(|stream| VestingUtils::read_lock(stream, lock_ts))
+        .collect::<Result<Vec<_>>>()?;
+
+    let total_locked = locks
+        .iter()
+        .try_fold(0u64, |acc, lock| acc.checked_add(lock.locked_amount))
+        .ok_or(StarError::MathOverflow)?;
+    let day_locked = if day_in_progress { progress.day_locked_total } else { total_locked };
+
+    let weighted_locked = locks
+        .iter()
+        .map(|lock| lock.weighted_locked(policy, lock_ts))
+        .collect::<Result<Vec<u64>>>()?;
+    // Weigh the page against the day's committed class weights, like the crank
+    let total_curved = if day_in_progress {
+        progress.day_class_weights
+            .iter()
+            .try_fold(0u64, |acc, weight| acc.checked_add(*weight))
+            .ok_or(StarError::MathOverflow)?
+    } else {
+        DistributionMath::calculate_curved_total(&weighted_locked, policy.payout_curve)?
+    };
+
+    // Only the day's first page claims; later pages pay out of the pool it fixed, so
+    // `claimed_quote` is ignored for them
+    let first_page = !day_in_progress || progress.pagination_cursor == 0;
+    let (eligible_share_bps, insurance_amount, claimed_quote, investor_fee_quote, capped_investor_fee, pool) =
+        if first_page {
+            let max_investor_fee_share_bps = policy.effective_investor_fee_share_bps(current_timestamp);
+            let eligible_share_bps = DistributionMath::apply_share_floor(
+                DistributionMath::calculate_eligible_share_bps(
+                    day_locked,
+                    policy.y0,
+                    max_investor_fee_share_bps,
+                )?,
+                day_locked,
+                policy.min_investor_share_bps,
+                max_investor_fee_share_bps,
+            );
+
+            // The insurance fund takes its slice before the investor/creator split
+            let insurance_amount = DistributionMath::calculate_investor_fee_quote(claimed_quote, policy.insurance_bps)?;
+            let claimed_quote = claimed_quote - insurance_amount;
+
+            let day_pool = DistributionMath::calculate_day_pool(
+                claimed_quote,
+                eligible_share_bps,
+                policy.effective_daily_cap(current_timestamp),
+                progress.distributed_today,
+                progress.carry_over,
+            )?;
+
+            (
+                eligible_share_bps,
+                insurance_amount,
+                claimed_quote,
+                day_pool.investor_fee,
+                day_pool.capped_investor_fee,
+                day_pool.total,
+            )
+        } else {
+            let pool = progress.day_class_pools
+                .iter()
+                .try_fold(0u64, |acc, class_pool| acc.checked_add(*class_pool))
+                .ok_or(StarError::MathOverflow)?;
+            (progress.day_eligible_share_bps, 0, 0, 0, 0, pool)
+        };
+
+    // What is left of the day's pool
+    let total_to_distribute = if first_page { pool } else { progress.carry_over };
+
+    let (payouts, page) =
+        DistributionMath::distribute_page(pool, total_to_distribute, &weighted_locked, total_curved, policy)?;
+    let distributed = page.distributed;
+    let carry_over = page.carry_over;
+
+    let day_remainder = DistributionMath::calculate_day_remainder(
+        claimed_quote,
+        progress.carry_over,
+        distributed,
+        carry_over,
+    )?;
+    // The referrer is paid out of the remainder first
+    let referral_fee = match policy.referral {
+        Some(referral) => DistributionMath::calculate_investor_fee_quote(claimed_quote, referral.referral_bps)?
+            .min(day_remainder),
+        None => 0,
+    };
+    let creator_remainder = day_remainder - referral_fee;
+
+    let simulation = DistributionSimulation {
+        eligible_share_bps,
+        investor_fee_quote,
+        capped_investor_fee,
+        total_to_distribute,
+        payouts,
+        distributed,
+        carry_over,
+        creator_remainder,
+        insurance_amount,
+    };
+
+    msg!(
+        "Simulated distribution: eligible_share_bps: {}, distributed: {}, creator_remainder: {}",
+        simulation.eligible_share_bps,
+        simulation.distributed,
+        simulation.creator_remainder
+    );
+
+    Ok(simulation)
+}
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::DaySkipped;
+use crate::instructions::crank::resolve_checked_transfer;
+use crate::state::{DayPhase, Policy, Progress, SkipRouting};
+use crate::utils::{CpAmmUtils, TokenTransferUtils, ValidationUtils};
+
+#[derive(Accounts)]
+pub struct SkipDay<'info> {
+    /// Policy authority
+    pub authority: Signer<'info>,
+
+    /// The vault whose day is skipped
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA tracking daily distribution state
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Honorary LP position owner PDA, signing the claim and the treasury transfer
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Program treasury PDA receiving the claimed quote fees
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"treasury", policy.quote_mint.as_ref()],
+        bump,
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Creator's quote token ATA (receives the fees under `SkipRouting::Creator`)
+    #[account(
+        mut,
+        constraint = creator_quote_ata.mint == policy.quote_mint @ StarError::InvalidCreatorAta,
+        constraint = policy.creator == Pubkey::default()
+            || creator_quote_ata.owner == policy.creator @ StarError::InvalidCreatorAta
+    )]
+    pub creator_quote_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint; required for a Token-2022 treasury
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// CP-AMM program for claiming fees
+    /// CHECK: Validated CP-AMM program
+    pub cp_amm_program: AccountInfo<'info>,
+
+    /// CP-AMM pool account
+    /// CHECK: Must be the policy's registered pool, once one is recorded
+    #[account(
+        mut,
+        constraint = policy.pool == Pubkey::default() || cp_amm_pool.key() == policy.pool
+            @ StarError::InvalidCpAmmPool
+    )]
+    pub cp_amm_pool: AccountInfo<'info>,
+
+    /// Token program owning the treasury (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Skip a distribution day, e.g. while an exploit is investigated: either the day opened
+/// but not yet cranked, or, in place of `open_day`, the next day once the 24h gate has
+/// passed. The day is marked complete without paying investors, and its fees are routed
+/// per `routing`. remaining_accounts carry the quote mint's transfer hook accounts, if any.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SkipDay<'info>>,
+    routing: SkipRouting,
+) -> Result<()> {
+    let mut progress = ctx.accounts.progress.load_mut()?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    progress.check_clock(current_timestamp)?;
+    if progress.is_day_in_progress() {
+        // Once a page has claimed, the day can only be finished or force-closed
+        require!(progress.phase()? == DayPhase::Idle, StarError::InvalidPhaseTransition);
+    } else {
+        require!(
+            Progress::day_number(current_timestamp) > progress.current_day,
+            StarError::ClockRollback
+        );
+        require!(progress.is_new_day(current_timestamp), StarError::DistributionTooEarly);
+        progress.reset_for_new_day(current_timestamp);
+    }
+
+    let vault_key = ctx.accounts.vault.key();
+    let position_owner_seeds: &[&[u8]] = &[
+        b"vault",
+        vault_key.as_ref(),
+        b"investor_fee_pos_owner",
+        &[ctx.bumps.position_owner_pda],
+    ];
+
+    let mut amount = 0u64;
+    if routing != SkipRouting::Hold {
+        let balance_before = ctx.accounts.program_treasury.amount;
+        let quote_mint = ctx.accounts.quote_mint.as_ref().map(|mint| mint.to_account_info());
+        let claim_result = CpAmmUtils::claim_position_fees(
+            &ctx.accounts.cp_amm_program,
+            &ctx.accounts.cp_amm_pool,
+            &ctx.accounts.position_owner_pda,
+            position_owner_seeds,
+            &ctx.accounts.program_treasury.to_account_info(),
+            quote_mint.as_ref(),
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        // CRITICAL: Verify no base fees are present
+        ValidationUtils::detect_base_fees(&claim_result)?;
+
+        progress.lifetime_claimed = progress.lifetime_claimed
+            .checked_add(claim_result.quote_amount)
+            .ok_or(StarError::MathOverflow)?;
+
+        // Route what actually arrived (a transfer-fee quote mint withholds part of the
+        // claim), together with fees harvested since the last day
+        ctx.accounts.program_treasury.reload()?;
+        let received = ctx.accounts.program_treasury.amount
+            .checked_sub(balance_before)
+            .ok_or(StarError::MathUnderflow)?;
+        amount = received
+            .checked_add(std::mem::take(&mut progress.harvested_pending))
+            .ok_or(StarError::MathOverflow)?;
+    }
+
+    match routing {
+        SkipRouting::Hold => {}
+        SkipRouting::Creator => {
+            if amount > 0 {
+                let checked_transfer = resolve_checked_transfer(
+                    &ctx.accounts.token_program,
+                    ctx.accounts.quote_mint.as_ref(),
+                    ctx.remaining_accounts,
+                )?;
+                TokenTransferUtils::transfer_signed(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.program_treasury.to_account_info(),
+                    &ctx.accounts.creator_quote_ata.to_account_info(),
+                    &ctx.accounts.position_owner_pda,
+                    position_owner_seeds,
+                    amount,
+                    checked_transfer.as_ref(),
+                )?;
+
+                progress.lifetime_paid_to_creator = progress.lifetime_paid_to_creator
+                    .checked_add(amount)
+                    .ok_or(StarError::MathOverflow)?;
+            }
+        }
+        SkipRouting::RollOver => {
+            progress.carry_over = progress.carry_over
+                .checked_add(amount)
+                .ok_or(StarError::MathOverflow)?;
+        }
+    }
+
+    progress.advance_phase(&[DayPhase::Idle], DayPhase::Closed)?;
+    progress.day_complete = 1;
+
+    emit!(DaySkipped {
+        vault: vault_key,
+        day: progress.current_day,
+        routing,
+        amount,
+        carry_over: progress.carry_over,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Distribution day {} skipped for vault: {}, routed {} as {:?}",
+        progress.current_day,
+        vault_key,
+        amount,
+        routing
+    );
+
+    Ok(())
+}
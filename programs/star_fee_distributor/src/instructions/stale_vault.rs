@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::StarError;
+use crate::events::StaleVaultWithdrawn;
+use crate::instructions::crank::resolve_checked_transfer;
+use crate::state::{Policy, Progress, Role};
+use crate::utils::TokenTransferUtils;
+
+#[derive(Accounts)]
+pub struct StaleVaultWithdraw<'info> {
+    /// Holder of the creator role
+    pub creator: Signer<'info>,
+
+    /// The vault whose treasury is swept
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the creator role and stale threshold
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Creator, &creator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA recording the last crank activity
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+
+    /// Honorary LP position owner PDA (treasury authority)
+    /// CHECK: PDA derived from the vault
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"investor_fee_pos_owner"],
+        bump
+    )]
+    pub position_owner_pda: AccountInfo<'info>,
+
+    /// Program treasury PDA holding the accumulated fees (never the insurance fund)
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"treasury", policy.quote_mint.as_ref()],
+        bump,
+        constraint = program_treasury.owner == position_owner_pda.key() @ StarError::InvalidTreasuryAta,
+        constraint = program_treasury.mint == policy.quote_mint @ StarError::InvalidTreasuryAta
+    )]
+    pub program_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Creator's quote token ATA receiving the sweep
+    #[account(
+        mut,
+        constraint = creator_quote_ata.mint == policy.quote_mint @ StarError::InvalidCreatorAta,
+        constraint = creator_quote_ata.owner == creator.key() @ StarError::InvalidCreatorAta
+    )]
+    pub creator_quote_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint; required for a Token-2022 treasury
+    #[account(address = policy.quote_mint @ StarError::InvalidQuoteMint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token program owning the treasury (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetStaleThreshold<'info> {
+    /// Current admin (policy authority)
+    pub authority: Signer<'info>,
+
+    /// The vault whose threshold is updated
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the stale threshold
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+}
+
+/// Sweep the whole treasury to the creator once no crank has run for
+/// `policy.stale_after_days`, so fees are not locked forever when keepers disappear.
+/// remaining_accounts carry the quote mint's transfer hook accounts, if any.
+pub fn withdraw_handler<'info>(ctx: Context<'_, '_, 'info, 'info, StaleVaultWithdraw<'info>>) -> Result<()> {
+    let policy = &ctx.accounts.policy;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    // Vaults that never cranked count inactivity from policy creation
+    let last_activity_ts = ctx.accounts.progress.load()?.last_crank_ts.max(policy.created_at);
+    let stale_after_secs = (policy.stale_after_days as i64)
+        .checked_mul(86400)
+        .ok_or(StarError::MathOverflow)?;
+    let stale_at = last_activity_ts
+        .checked_add(stale_after_secs)
+        .ok_or(StarError::MathOverflow)?;
+
+    require!(current_timestamp >= stale_at, StarError::VaultNotStale);
+
+    let amount = ctx.accounts.program_treasury.amount;
+    require!(amount > 0, StarError::InsufficientQuoteFees);
+
+    let vault_key = ctx.accounts.vault.key();
+    let checked_transfer = resolve_checked_transfer(
+        &ctx.accounts.token_program,
+        ctx.accounts.quote_mint.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+    TokenTransferUtils::transfer_signed(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.program_treasury.to_account_info(),
+        &ctx.accounts.creator_quote_ata.to_account_info(),
+        &ctx.accounts.position_owner_pda,
+        &[
+            b"vault",
+            vault_key.as_ref(),
+            b"investor_fee_pos_owner",
+            &[ctx.bumps.position_owner_pda],
+        ],
+        amount,
+        checked_transfer.as_ref(),
+    )?;
+
+    // The sweep takes any carried investor dust and harvested fees with it
+    let mut progress = ctx.accounts.progress.load_mut()?;
+    progress.carry_over = 0;
+    progress.carry_over_at_open = 0;
+    progress.harvested_pending = 0;
+
+    emit!(StaleVaultWithdrawn {
+        vault: vault_key,
+        creator: ctx.accounts.creator.key(),
+        amount,
+        last_activity_ts,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "STALE VAULT WITHDRAWAL for vault: {}, amount: {}, last activity: {}",
+        vault_key,
+        amount,
+        last_activity_ts
+    );
+
+    Ok(())
+}
+
+/// Set the number of crank-less days before the creator may sweep the treasury
+pub fn set_threshold_handler(ctx: Context<SetStaleThreshold>, stale_after_days: u64) -> Result<()> {
+    require!(stale_after_days > 0, StarError::InvalidStaleThreshold);
+
+    ctx.accounts.policy.stale_after_days = stale_after_days;
+
+    msg!(
+        "Stale threshold set to {} days for vault: {}",
+        stale_after_days,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::{
+    CompoundingModeSet, CrankWindowSet, DustPolicySet, FreezeAuthorityRequirementSet, LockSnapshotRequirementSet,
+    MinInvestorShareSet, PageAttesterSet, PageCooldownSet, PageSizeSet, PayoutCurveSet, PolicyUpdateQueued,
+    ReconciliationAbortSet, RegistryModeSet, TwalWeightingSet, Y0Updated,
+};
+use crate::state::{
+    CrankWindow, DustPolicy, PayoutCurve, PendingPolicyUpdate, Policy, Progress, MAX_INVESTORS_PER_PAGE,
+    Y0_CORRECTION_WINDOW_SECS,
+};
+
+#[derive(Accounts)]
+pub struct UpdatePolicy<'info> {
+    /// Policy authority queuing the change
+    pub authority: Signer<'info>,
+
+    /// The vault whose policy is updated
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the authority and the pending change
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutCurve<'info> {
+    /// Current admin (policy authority)
+    pub authority: Signer<'info>,
+
+    /// The vault whose payout curve is set
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the payout curve
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        has_one = authority @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Progress PDA; the curve and dust policy only change between distribution days
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"progress"],
+        bump
+    )]
+    pub progress: AccountLoader<'info, Progress>,
+}
+
+/// Queue new fee share and daily cap values, effective after `policy.update_delay_secs`.
+/// Queuing again replaces any pending change and restarts the delay.
+pub fn handler(ctx: Context<UpdatePolicy>, investor_fee_share_bps: u16, daily_cap: u64) -> Result<()> {
+    require!(investor_fee_share_bps <= 10000, StarError::InvalidFeeShareBps);
+
+    let policy = &mut ctx.accounts.policy;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let effective_at = current_timestamp
+        .checked_add(policy.update_delay_secs)
+        .ok_or(StarError::MathOverflow)?;
+
+    policy.pending_update = Some(PendingPolicyUpdate {
+        investor_fee_share_bps,
+        daily_cap,
+        effective_at,
+    });
+
+    emit!(PolicyUpdateQueued {
+        vault: ctx.accounts.vault.key(),
+        investor_fee_share_bps,
+        daily_cap,
+        effective_at,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "Policy update queued for vault: {}, investor_fee_share_bps: {}, daily_cap: {}, effective at: {}",
+        ctx.accounts.vault.key(),
+        investor_fee_share_bps,
+        daily_cap,
+        effective_at
+    );
+
+    Ok(())
+}
+
+/// Opt the vault out of auto-compounding. Re-depositing the investor share would put
+/// liquidity in the fee-only honorary position, so opting in fails with
+/// `CompoundingUnsupported`; vaults that opted in earlier can still opt out.
+pub fn set_compounding_handler(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+    require!(!enabled, StarError::CompoundingUnsupported);
+    ctx.accounts.policy.compound_investor_share = enabled;
+
+    emit!(CompoundingModeSet {
+        vault: ctx.accounts.vault.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Auto-compounding set to {} for vault: {}",
+        enabled,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Weigh investors by their time-weighted average locked amount (sampled by `refresh_locks`)
+/// instead of the instant snapshot taken by the crank
+pub fn set_twal_weighting_handler(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+    ctx.accounts.policy.twal_weighting = enabled;
+
+    emit!(TwalWeightingSet {
+        vault: ctx.accounts.vault.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Time-weighted locked weighting set to {} for vault: {}",
+        enabled,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Guarantee investors at least `min_investor_share_bps` of claimed fees while any tokens
+/// remain locked, however far the locked fraction has fallen (0 disables the floor)
+pub fn set_min_investor_share_handler(ctx: Context<UpdatePolicy>, min_investor_share_bps: u16) -> Result<()> {
+    require!(min_investor_share_bps <= 10000, StarError::InvalidFeeShareBps);
+
+    ctx.accounts.policy.min_investor_share_bps = min_investor_share_bps;
+
+    emit!(MinInvestorShareSet {
+        vault: ctx.accounts.vault.key(),
+        min_investor_share_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Minimum investor share set to {} bps for vault: {}",
+        min_investor_share_bps,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Choose whether a treasury shortfall found at day close aborts the final page
+/// (`true`) or is only reported via `ReconciliationMismatch` (`false`, the default)
+pub fn set_reconciliation_abort_handler(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+    ctx.accounts.policy.abort_on_reconciliation_mismatch = enabled;
+
+    emit!(ReconciliationAbortSet {
+        vault: ctx.accounts.vault.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Abort on reconciliation mismatch set to {} for vault: {}",
+        enabled,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Require every crank page to be co-signed by `page_attester`, or lift the requirement
+/// with `Pubkey::default()`
+pub fn set_page_attester_handler(ctx: Context<UpdatePolicy>, page_attester: Pubkey) -> Result<()> {
+    ctx.accounts.policy.page_attester = page_attester;
+
+    emit!(PageAttesterSet {
+        vault: ctx.accounts.vault.key(),
+        page_attester,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Page attester set to {} for vault: {}",
+        page_attester,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Require the quote mint to have no freeze authority when the honorary position is
+/// opened; set it before `init_position`
+pub fn set_require_no_freeze_authority_handler(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+    ctx.accounts.policy.require_no_freeze_authority = enabled;
+
+    emit!(FreezeAuthorityRequirementSet {
+        vault: ctx.accounts.vault.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "No-freeze-authority requirement set to {} for vault: {}",
+        enabled,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Require every active investor's lock to come from a `refresh_locks` snapshot for the
+/// day being cranked, rather than the live vesting account
+pub fn set_require_lock_snapshot_handler(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+    ctx.accounts.policy.require_lock_snapshot = enabled;
+
+    emit!(LockSnapshotRequirementSet {
+        vault: ctx.accounts.vault.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Lock snapshot requirement set to {} for vault: {}",
+        enabled,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Restrict crank pages to a daily UTC window, or lift the restriction with `None`
+pub fn set_crank_window_handler(ctx: Context<UpdatePolicy>, crank_window: Option<CrankWindow>) -> Result<()> {
+    if let Some(window) = &crank_window {
+        window.validate()?;
+    }
+
+    ctx.accounts.policy.crank_window = crank_window;
+
+    emit!(CrankWindowSet {
+        vault: ctx.accounts.vault.key(),
+        crank_window,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Crank window set to {:?} for vault: {}",
+        crank_window,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Require at least `page_cooldown_secs` between consecutive crank pages of a day (0 disables)
+pub fn set_page_cooldown_handler(ctx: Context<UpdatePolicy>, page_cooldown_secs: u32) -> Result<()> {
+    ctx.accounts.policy.page_cooldown_secs = page_cooldown_secs;
+
+    emit!(PageCooldownSet {
+        vault: ctx.accounts.vault.key(),
+        page_cooldown_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Page cool-down set to {}s for vault: {}",
+        page_cooldown_secs,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Correct a `y0` misconfigured at TGE. Allowed once, within `Y0_CORRECTION_WINDOW_SECS`
+/// of policy creation; takes effect immediately since the old value was never right.
+pub fn update_y0_handler(ctx: Context<UpdatePolicy>, new_y0: u64) -> Result<()> {
+    require!(new_y0 > 0, StarError::InvalidY0);
+
+    let policy = &mut ctx.accounts.policy;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let window_end = policy.created_at
+        .checked_add(Y0_CORRECTION_WINDOW_SECS)
+        .ok_or(StarError::MathOverflow)?;
+    require!(
+        !policy.y0_corrected && current_timestamp <= window_end,
+        StarError::Y0CorrectionUnavailable
+    );
+
+    let old_y0 = policy.y0;
+    policy.y0 = new_y0;
+    policy.y0_corrected = true;
+
+    emit!(Y0Updated {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        old_y0,
+        new_y0,
+        timestamp: current_timestamp,
+    });
+
+    msg!(
+        "y0 corrected from {} to {} for vault: {}",
+        old_y0,
+        new_y0,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Switch the payout curve; only between distribution days so every page of a day is
+/// weighted the same way
+pub fn set_payout_curve_handler(ctx: Context<SetPayoutCurve>, payout_curve: PayoutCurve) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+
+    ctx.accounts.policy.payout_curve = payout_curve;
+
+    emit!(PayoutCurveSet {
+        vault: ctx.accounts.vault.key(),
+        payout_curve,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Payout curve set to {:?} for vault: {}",
+        payout_curve,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Choose what the crank does with payouts below `min_payout_lamports`: keep them in the
+/// investor pool, release them to the remainder, or pay them anyway. Only between
+/// distribution days, like the payout curve.
+pub fn set_dust_policy_handler(ctx: Context<SetPayoutCurve>, dust_policy: DustPolicy) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+
+    ctx.accounts.policy.dust_policy = dust_policy;
+
+    emit!(DustPolicySet {
+        vault: ctx.accounts.vault.key(),
+        dust_policy,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Dust policy set to {:?} for vault: {}",
+        dust_policy,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Fix the number of investors per crank page (0 to leave it to the keeper). Only between
+/// distribution days, since the pages of a day must all follow the same size.
+pub fn set_page_size_handler(ctx: Context<SetPayoutCurve>, page_size: u16) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+    require!(page_size as usize <= MAX_INVESTORS_PER_PAGE, StarError::PageTooLarge);
+
+    ctx.accounts.policy.page_size = page_size;
+
+    emit!(PageSizeSet {
+        vault: ctx.accounts.vault.key(),
+        page_size,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Page size set to {} for vault: {}",
+        page_size,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
+
+/// Switch between keeper-supplied pages (`crank_distribute`) and registry-driven pages
+/// (`crank_from_registry`). Only between distribution days, so a day's pages all come
+/// from the same source.
+pub fn set_registry_mode_handler(ctx: Context<SetPayoutCurve>, enabled: bool) -> Result<()> {
+    require!(!ctx.accounts.progress.load()?.is_day_in_progress(), StarError::DayInProgress);
+
+    ctx.accounts.policy.registry_mode = enabled;
+
+    emit!(RegistryModeSet {
+        vault: ctx.accounts.vault.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Registry mode set to {} for vault: {}",
+        enabled,
+        ctx.accounts.vault.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::StarError;
+use crate::events::VaultMetadataSet;
+use crate::state::{Policy, Role, VaultMetadata};
+
+#[derive(Accounts)]
+pub struct InitVaultMetadata<'info> {
+    /// Holder of the creator role
+    pub creator: Signer<'info>,
+
+    /// Funds the metadata rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The vault described
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the creator role
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Creator, &creator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Vault metadata PDA
+    #[account(
+        init,
+        payer = payer,
+        space = VaultMetadata::SIZE,
+        seeds = [b"vault", vault.key().as_ref(), b"metadata"],
+        bump
+    )]
+    pub vault_metadata: Account<'info, VaultMetadata>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultMetadata<'info> {
+    /// Holder of the creator role
+    pub creator: Signer<'info>,
+
+    /// The vault described
+    /// CHECK: Validated through the policy PDA
+    pub vault: AccountInfo<'info>,
+
+    /// Policy PDA holding the creator role
+    #[account(
+        seeds = [b"vault", vault.key().as_ref(), b"policy"],
+        bump,
+        constraint = policy.has_role(Role::Creator, &creator.key()) @ StarError::Unauthorized
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// Vault metadata PDA
+    #[account(
+        mut,
+        seeds = [b"vault", vault.key().as_ref(), b"metadata"],
+        bump = vault_metadata.bump
+    )]
+    pub vault_metadata: Account<'info, VaultMetadata>,
+}
+
+/// Create the vault's metadata account with its initial description
+pub fn init_handler(
+    ctx: Context<InitVaultMetadata>,
+    name: String,
+    project_uri: String,
+    logo_uri: String,
+    creator_contact_hash: [u8; 32],
+) -> Result<()> {
+    let vault_metadata = &mut ctx.accounts.vault_metadata;
+    vault_metadata.set_inner(VaultMetadata::new(ctx.accounts.vault.key(), ctx.bumps.vault_metadata));
+    vault_metadata.set(name, project_uri, logo_uri, creator_contact_hash)?;
+
+    emit_metadata_set(vault_metadata)
+}
+
+/// Replace the vault's description
+pub fn set_handler(
+    ctx: Context<SetVaultMetadata>,
+    name: String,
+    project_uri: String,
+    logo_uri: String,
+    creator_contact_hash: [u8; 32],
+) -> Result<()> {
+    let vault_metadata = &mut ctx.accounts.vault_metadata;
+    vault_metadata.set(name, project_uri, logo_uri, creator_contact_hash)?;
+
+    emit_metadata_set(vault_metadata)
+}
+
+fn emit_metadata_set(vault_metadata: &VaultMetadata) -> Result<()> {
+    emit!(VaultMetadataSet {
+        vault: vault_metadata.vault,
+        name: vault_metadata.name.clone(),
+        project_uri: vault_metadata.project_uri.clone(),
+        logo_uri: vault_metadata.logo_uri.clone(),
+        creator_contact_hash: vault_metadata.creator_contact_hash,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Vault metadata set for vault: {}, name: {}", vault_metadata.vault, vault_metadata.name);
+
+    Ok(())
+}
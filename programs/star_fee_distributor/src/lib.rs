@@ -7,30 +7,446 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::*;
 
+#[cfg(all(feature = "devnet", feature = "mainnet"))]
+compile_error!("the `devnet` and `mainnet` features are mutually exclusive");
+
+#[cfg(all(feature = "mainnet", feature = "mock-integrations"))]
+compile_error!("`mock-integrations` cannot be enabled for a mainnet build");
+
+// Deployed address per cluster; builds without a cluster feature target localnet
+#[cfg(feature = "mainnet")]
+declare_id!("StarFeeMainnet11111111111111111111111111111");
+
+#[cfg(feature = "devnet")]
+declare_id!("StarFeeDevnet111111111111111111111111111111");
+
+#[cfg(not(any(feature = "devnet", feature = "mainnet")))]
 declare_id!("FEEd1str1but0r1111111111111111111111111111");
 
 #[program]
 pub mod star_fee_distributor {
     use super::*;
 
-    /// Initialize an honorary DAMM v2 LP position for quote-only fee accrual
-    pub fn initialize_honorary_position(
-        ctx: Context<InitializeHonoraryPosition>,
+    /// Create a vault's Policy and Progress PDAs with its distribution configuration.
+    /// A `daily_cap` of 0 leaves investor payouts uncapped.
+    pub fn init_policy(ctx: Context<InitPolicy>, args: InitializeArgsV1) -> Result<()> {
+        instructions::initialize::policy_handler(ctx, args)
+    }
+
+    /// Create the honorary DAMM v2 LP position for quote-only fee accrual, owned by the
+    /// position owner PDA, over a tick range above the pool price; requires `init_policy` first
+    pub fn init_position(ctx: Context<InitPosition>, tick_lower: i32, tick_upper: i32) -> Result<()> {
+        instructions::initialize::position_handler(ctx, tick_lower, tick_upper)
+    }
+
+    /// Take custody of an existing CP-AMM position as the honorary position, instead of
+    /// `init_position`; policy authority and the position's current holder
+    pub fn adopt_position(ctx: Context<AdoptPosition>) -> Result<()> {
+        instructions::adopt_position::handler(ctx)
+    }
+
+    /// Queue a move of the honorary position to an upgraded pool, executable after 72h; admin
+    /// only, and only in builds that support position migration
+    pub fn propose_position_migration(ctx: Context<UpdatePolicy>, new_pool: Pubkey) -> Result<()> {
+        instructions::migrate_position::propose_handler(ctx, new_pool)
+    }
+
+    /// Drop the queued position migration; admin only
+    pub fn cancel_position_migration(ctx: Context<UpdatePolicy>) -> Result<()> {
+        instructions::migrate_position::cancel_handler(ctx)
+    }
+
+    /// Claim the old position's fees, close it and reopen it in the queued pool once the
+    /// timelock has elapsed; admin only, and only in builds that support position migration
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        instructions::migrate_position::handler(ctx)
+    }
+
+    /// Open a new distribution day (24h gated), committing the Merkle root of the
+    /// day's (stream, investor quote ATA) set, its expected investor/page counts and each
+    /// investor class's locked and curved weight totals as of `lock_ts`; policy authority or
+    /// operator only
+    pub fn open_day(
+        ctx: Context<OpenDay>,
+        investor_root: [u8; 32],
+        total_investors: u64,
+        total_pages: u64,
+        class_totals: Vec<DayClassTotal>,
+        lock_ts: i64,
+    ) -> Result<()> {
+        instructions::open_day::handler(ctx, investor_root, total_investors, total_pages, class_totals, lock_ts)
+    }
+
+    /// Permissionless 24h distribution crank for quote fees
+    /// Remaining accounts per investor, one per proof: vesting account, investor quote ATA,
+    /// investor record
+    pub fn crank_distribute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankDistribute<'info>>,
+        args: CrankArgsV1,
+    ) -> Result<CrankPageResult> {
+        instructions::crank::handler(ctx, args)
+    }
+
+    /// Distribution crank over registry chunk `chunk_index`, as page `chunk_index + 1`
+    /// Remaining accounts: investor registry, registry chunk, then per investor in stream
+    /// order: vesting account, investor quote ATA, investor record
+    pub fn crank_from_registry<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankDistribute<'info>>,
+        chunk_index: u32,
+    ) -> Result<CrankPageResult> {
+        instructions::crank::registry_handler(ctx, chunk_index)
+    }
+
+    /// Close the day before its final committed page once every committed investor was
+    /// processed; policy authority or creator only
+    /// Remaining accounts: the quote mint's transfer hook accounts, if any
+    pub fn force_close_day<'info>(ctx: Context<'_, '_, 'info, 'info, ForceCloseDay<'info>>) -> Result<()> {
+        instructions::force_close_day::handler(ctx)
+    }
+
+    /// Mark a day complete without distributing and route its fees to the creator, the
+    /// next day's investor pool, or leave them unclaimed; admin only
+    /// Remaining accounts: the quote mint's transfer hook accounts, if any
+    pub fn skip_day<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SkipDay<'info>>,
+        routing: SkipRouting,
+    ) -> Result<()> {
+        instructions::skip_day::handler(ctx, routing)
+    }
+
+    /// Preview a page distribution without transfers or state writes
+    /// Remaining accounts: one vesting account per investor
+    pub fn simulate_distribution<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SimulateDistribution<'info>>,
+        claimed_quote: u64,
+    ) -> Result<DistributionSimulation> {
+        instructions::simulate::handler(ctx, claimed_quote)
+    }
+
+    /// Run all vault setup validations (pool, program IDs, treasury, position custody)
+    /// without mutating state
+    pub fn preflight_validate(ctx: Context<PreflightValidate>) -> Result<()> {
+        instructions::preflight::handler(ctx)
+    }
+
+    /// Create the lifetime payout record for an investor stream vesting the vault's base mint
+    pub fn init_investor_record(ctx: Context<InitInvestorRecord>) -> Result<()> {
+        instructions::investor_record::handler(ctx)
+    }
+
+    /// Propose a timelocked (72h) recovery of treasury funds; policy authority only
+    pub fn propose_recovery(ctx: Context<ProposeRecovery>, amount: u64) -> Result<()> {
+        instructions::recovery::propose_handler(ctx, amount)
+    }
+
+    /// Execute a proposed treasury recovery once its timelock has elapsed
+    /// Remaining accounts: the quote mint's transfer hook accounts, if any
+    pub fn execute_recovery<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteRecovery<'info>>) -> Result<()> {
+        instructions::recovery::execute_handler(ctx)
+    }
+
+    /// Cancel a pending treasury recovery
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::recovery::cancel_handler(ctx)
+    }
+
+    /// Queue a timelocked change to the investor fee share and daily cap (0 = uncapped);
+    /// policy authority only
+    pub fn update_policy(
+        ctx: Context<UpdatePolicy>,
         investor_fee_share_bps: u16,
         daily_cap: u64,
-        min_payout_lamports: u64,
-        y0: u64,
     ) -> Result<()> {
-        instructions::initialize::handler(ctx, investor_fee_share_bps, daily_cap, min_payout_lamports, y0)
+        instructions::update_policy::handler(ctx, investor_fee_share_bps, daily_cap)
     }
 
-    /// Permissionless 24h distribution crank for quote fees
-    pub fn crank_distribute(
-        ctx: Context<CrankDistribute>,
-        page: u64,
-        investor_accounts: Vec<InvestorAccount>,
+    /// Turn off re-depositing the investor share as pool liquidity (turning it on fails with
+    /// `CompoundingUnsupported`); policy authority only
+    pub fn set_compounding(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+        instructions::update_policy::set_compounding_handler(ctx, enabled)
+    }
+
+    /// Grant a role (admin, operator, creator) on the policy; admin only
+    pub fn grant_role(ctx: Context<ManageRole>, role: Role, account: Pubkey) -> Result<()> {
+        instructions::roles::grant_handler(ctx, role, account)
+    }
+
+    /// Revoke a role (operator, creator) on the policy; admin only
+    pub fn revoke_role(ctx: Context<ManageRole>, role: Role) -> Result<()> {
+        instructions::roles::revoke_handler(ctx, role)
+    }
+
+    /// Sweep the treasury to the creator after `stale_after_days` without a crank
+    /// Remaining accounts: the quote mint's transfer hook accounts, if any
+    pub fn stale_vault_withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StaleVaultWithdraw<'info>>,
+    ) -> Result<()> {
+        instructions::stale_vault::withdraw_handler(ctx)
+    }
+
+    /// Set the inactivity threshold for `stale_vault_withdraw`; admin only
+    pub fn set_stale_threshold(ctx: Context<SetStaleThreshold>, stale_after_days: u64) -> Result<()> {
+        instructions::stale_vault::set_threshold_handler(ctx, stale_after_days)
+    }
+
+    /// Create the vault blacklist PDA; policy authority only
+    pub fn init_blacklist(ctx: Context<InitBlacklist>) -> Result<()> {
+        instructions::blacklist::init_handler(ctx)
+    }
+
+    /// Exclude an investor wallet from distribution; policy authority only
+    pub fn add_to_blacklist(ctx: Context<UpdateBlacklist>, wallet: Pubkey) -> Result<()> {
+        instructions::blacklist::add_handler(ctx, wallet)
+    }
+
+    /// Restore a blacklisted investor wallet; policy authority only
+    pub fn remove_from_blacklist(ctx: Context<UpdateBlacklist>, wallet: Pubkey) -> Result<()> {
+        instructions::blacklist::remove_handler(ctx, wallet)
+    }
+
+    /// Refuse fee income for a stream; signed by the stream recipient
+    pub fn opt_out(ctx: Context<SetOptOut>) -> Result<()> {
+        instructions::investor_record::set_opt_out_handler(ctx, true)
+    }
+
+    /// Resume fee income for a previously opted-out stream; signed by the stream recipient
+    pub fn opt_in(ctx: Context<SetOptOut>) -> Result<()> {
+        instructions::investor_record::set_opt_out_handler(ctx, false)
+    }
+
+    /// Create the vault cranker allowlist PDA; operator only
+    pub fn init_cranker_allowlist(ctx: Context<InitCrankerAllowlist>) -> Result<()> {
+        instructions::cranker_allowlist::init_handler(ctx)
+    }
+
+    /// Add or remove an allowed cranker; operator only
+    pub fn set_cranker(ctx: Context<UpdateCrankerAllowlist>, cranker: Pubkey, allowed: bool) -> Result<()> {
+        instructions::cranker_allowlist::set_cranker_handler(ctx, cranker, allowed)
+    }
+
+    /// Enable the cranker allowlist and accept a Switchboard function's enclave signer; operator only
+    pub fn configure_cranker_allowlist(
+        ctx: Context<UpdateCrankerAllowlist>,
+        enabled: bool,
+        switchboard_function: Pubkey,
+    ) -> Result<()> {
+        instructions::cranker_allowlist::configure_handler(ctx, enabled, switchboard_function)
+    }
+
+    /// Let holders of `mint` tokens crank while the allowlist is enabled; creator only
+    pub fn set_cranker_pass(ctx: Context<SetCrankerPass>, mint: Pubkey) -> Result<()> {
+        instructions::cranker_allowlist::set_cranker_pass_handler(ctx, mint)
+    }
+
+    /// Upgrade a vault's Policy and Progress to the current account layouts
+    pub fn migrate_accounts(ctx: Context<MigrateAccounts>) -> Result<()> {
+        instructions::migrate::handler(ctx)
+    }
+
+    /// Initialize Policy, Progress and the treasury for several vaults in one transaction;
+    /// each vault supplies `BATCH_ACCOUNTS_PER_VAULT` remaining accounts
+    pub fn batch_initialize<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchInitialize<'info>>,
+        params: Vec<VaultInitParams>,
     ) -> Result<()> {
-        instructions::crank::handler(ctx, page, investor_accounts)
+        instructions::batch_initialize::handler(ctx, params)
+    }
+
+    /// Register the vault's address lookup table for v0 crank transactions; operator only
+    pub fn set_lookup_table(ctx: Context<SetLookupTable>, lookup_table: Pubkey) -> Result<()> {
+        instructions::lookup_table::handler(ctx, lookup_table)
+    }
+
+    /// Restrict crank pages to a daily UTC window (`None` lifts it); admin only
+    pub fn set_crank_window(ctx: Context<UpdatePolicy>, crank_window: Option<CrankWindow>) -> Result<()> {
+        instructions::update_policy::set_crank_window_handler(ctx, crank_window)
+    }
+
+    /// Set the minimum delay between consecutive crank pages of a day (0 disables); admin only
+    pub fn set_page_cooldown(ctx: Context<UpdatePolicy>, page_cooldown_secs: u32) -> Result<()> {
+        instructions::update_policy::set_page_cooldown_handler(ctx, page_cooldown_secs)
+    }
+
+    /// Choose whether day close pays, donates, or burns the remainder; creator only
+    pub fn set_remainder_policy(
+        ctx: Context<SetRemainderPolicy>,
+        remainder_policy: RemainderPolicy,
+    ) -> Result<()> {
+        instructions::remainder_policy::handler(ctx, remainder_policy)
+    }
+
+    /// Correct a misconfigured `y0` once, within the correction window; admin only
+    pub fn update_y0(ctx: Context<UpdatePolicy>, new_y0: u64) -> Result<()> {
+        instructions::update_policy::update_y0_handler(ctx, new_y0)
+    }
+
+    /// Replace the policy's investor classes between distribution days; admin only
+    pub fn set_investor_classes(ctx: Context<SetInvestorClasses>, investor_classes: Vec<InvestorClass>) -> Result<()> {
+        instructions::investor_class::set_classes_handler(ctx, investor_classes)
+    }
+
+    /// Assign an investor stream to a class between distribution days; admin only
+    pub fn set_investor_class(ctx: Context<SetInvestorClass>, class: u8) -> Result<()> {
+        instructions::investor_class::set_class_handler(ctx, class)
+    }
+
+    /// Grow an investor record created before the current layout
+    pub fn migrate_investor_record(ctx: Context<MigrateInvestorRecord>) -> Result<()> {
+        instructions::migrate::investor_record_handler(ctx)
+    }
+
+    /// Switch between instant and time-weighted locked weighting; admin only
+    pub fn set_twal_weighting(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+        instructions::update_policy::set_twal_weighting_handler(ctx, enabled)
+    }
+
+    /// Sample investor locks for time-weighted weighting; permissionless
+    pub fn refresh_locks<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshLocks<'info>>) -> Result<()> {
+        instructions::refresh_locks::handler(ctx)
+    }
+
+    /// Switch the payout curve between distribution days; admin only
+    pub fn set_payout_curve(ctx: Context<SetPayoutCurve>, payout_curve: PayoutCurve) -> Result<()> {
+        instructions::update_policy::set_payout_curve_handler(ctx, payout_curve)
+    }
+
+    /// Choose how payouts below `min_payout_lamports` are handled, between distribution
+    /// days; admin only
+    pub fn set_dust_policy(ctx: Context<SetPayoutCurve>, dust_policy: DustPolicy) -> Result<()> {
+        instructions::update_policy::set_dust_policy_handler(ctx, dust_policy)
+    }
+
+    /// Fix the number of investors per crank page, 0 to disable; admin only, between days
+    pub fn set_page_size(ctx: Context<SetPayoutCurve>, page_size: u16) -> Result<()> {
+        instructions::update_policy::set_page_size_handler(ctx, page_size)
+    }
+
+    /// Distribute from the on-chain investor registry instead of keeper pages; admin only, between days
+    pub fn set_registry_mode(ctx: Context<SetPayoutCurve>, enabled: bool) -> Result<()> {
+        instructions::update_policy::set_registry_mode_handler(ctx, enabled)
+    }
+
+    /// Set the guaranteed minimum investor share while tokens remain locked; admin only
+    pub fn set_min_investor_share(ctx: Context<UpdatePolicy>, min_investor_share_bps: u16) -> Result<()> {
+        instructions::update_policy::set_min_investor_share_handler(ctx, min_investor_share_bps)
+    }
+
+    /// Claim quote fees into the treasury between distribution days; permissionless
+    pub fn harvest_fees(ctx: Context<HarvestFees>) -> Result<()> {
+        instructions::harvest::handler(ctx)
+    }
+
+    /// Choose whether a treasury reconciliation mismatch aborts day close; admin only
+    pub fn set_reconciliation_abort(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+        instructions::update_policy::set_reconciliation_abort_handler(ctx, enabled)
+    }
+
+    /// Create the vault's insurance fund token account; admin only
+    pub fn init_insurance_fund(ctx: Context<InitInsuranceFund>) -> Result<()> {
+        instructions::insurance::init_handler(ctx)
+    }
+
+    /// Set the share of claimed fees moved to the insurance fund; admin only
+    pub fn set_insurance_bps(ctx: Context<SetInsuranceBps>, insurance_bps: u16) -> Result<()> {
+        instructions::insurance::set_bps_handler(ctx, insurance_bps)
+    }
+
+    /// Require crank pages to be co-signed by a second keeper; admin only
+    pub fn set_page_attester(ctx: Context<UpdatePolicy>, page_attester: Pubkey) -> Result<()> {
+        instructions::update_policy::set_page_attester_handler(ctx, page_attester)
+    }
+
+    /// Refuse quote mints with a freeze authority when opening the honorary position; admin only
+    pub fn set_require_no_freeze_authority(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+        instructions::update_policy::set_require_no_freeze_authority_handler(ctx, enabled)
+    }
+
+    /// Require crank pages to use the investors' `refresh_locks` snapshots; admin only
+    pub fn set_require_lock_snapshot(ctx: Context<UpdatePolicy>, enabled: bool) -> Result<()> {
+        instructions::update_policy::set_require_lock_snapshot_handler(ctx, enabled)
+    }
+
+    /// Create the program-wide config; program upgrade authority only
+    pub fn init_global_config(ctx: Context<InitGlobalConfig>) -> Result<()> {
+        instructions::global_config::init_handler(ctx)
+    }
+
+    /// Allow or disallow a Token-2022 transfer hook program on quote mints; config admin only
+    pub fn set_transfer_hook_allowed(
+        ctx: Context<UpdateGlobalConfig>,
+        hook_program: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
+        instructions::global_config::set_transfer_hook_handler(ctx, hook_program, allowed)
+    }
+
+    /// Create an immutable policy template of vault parameters; config admin only
+    pub fn create_policy_template(
+        ctx: Context<CreatePolicyTemplate>,
+        template_id: u32,
+        params: VaultInitParams,
+    ) -> Result<()> {
+        instructions::policy_template::create_handler(ctx, template_id, params)
+    }
+
+    /// Create a vault's Policy, Progress and treasury like `init_policy`, with the
+    /// parameters cloned from a policy template
+    pub fn initialize_from_template(ctx: Context<InitializeFromTemplate>) -> Result<()> {
+        instructions::policy_template::initialize_handler(ctx)
+    }
+
+    /// Create the vault's metadata account (name, project and logo URIs, contact hash)
+    /// read by explorers; creator only
+    pub fn init_vault_metadata(
+        ctx: Context<InitVaultMetadata>,
+        name: String,
+        project_uri: String,
+        logo_uri: String,
+        creator_contact_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::vault_metadata::init_handler(ctx, name, project_uri, logo_uri, creator_contact_hash)
+    }
+
+    /// Replace the vault's metadata; creator only
+    pub fn set_vault_metadata(
+        ctx: Context<SetVaultMetadata>,
+        name: String,
+        project_uri: String,
+        logo_uri: String,
+        creator_contact_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::vault_metadata::set_handler(ctx, name, project_uri, logo_uri, creator_contact_hash)
+    }
+
+    /// Create the vault's on-chain investor registry; policy authority only
+    pub fn init_investor_registry(ctx: Context<InitInvestorRegistry>) -> Result<()> {
+        instructions::investor_registry::init_handler(ctx)
+    }
+
+    /// Create the next investor registry chunk once every chunk is full; permissionless
+    pub fn add_registry_chunk(ctx: Context<AddRegistryChunk>) -> Result<()> {
+        instructions::investor_registry::add_chunk_handler(ctx)
+    }
+
+    /// Add a stream to the investor registry; policy authority or stream recipient, between days
+    pub fn register_investor(ctx: Context<RegisterInvestor>, chunk_index: u32) -> Result<()> {
+        instructions::investor_registry::register_handler(ctx, chunk_index)
+    }
+
+    /// Remove a stream from the investor registry; policy authority or stream recipient, between days
+    pub fn deregister_investor(ctx: Context<DeregisterInvestor>) -> Result<()> {
+        instructions::investor_registry::deregister_handler(ctx)
+    }
+
+    /// Deposit lamports that reimburse crank callers; creator only
+    pub fn fund_crank_fee_vault(ctx: Context<FundCrankFeeVault>, amount: u64) -> Result<()> {
+        instructions::crank_fee_vault::fund_handler(ctx, amount)
+    }
+
+    /// Set the lamports reimbursed to the caller of every crank page, 0 to disable; creator only
+    pub fn set_crank_reimbursement(ctx: Context<SetCrankReimbursement>, lamports: u64) -> Result<()> {
+        instructions::crank_fee_vault::set_reimbursement_handler(ctx, lamports)
     }
 }
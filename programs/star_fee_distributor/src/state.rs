@@ -1,14 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_lang::solana_program::pubkey;
+
+use crate::errors::StarError;
+use crate::utils::DistributionMath;
 
 /// Policy configuration for fee distribution
 #[account]
 pub struct Policy {
     /// Maximum basis points (0-10000) for investor fee share
     pub investor_fee_share_bps: u16,
-    /// Optional daily maximum payout in lamports
+    /// Daily maximum investor payout in lamports; 0 means uncapped
     pub daily_cap: u64,
-    /// Minimum payout threshold in lamports (dust filter)
+    /// Minimum payout threshold in lamports (dust filter, see `dust_policy`); 0 pays
+    /// every nonzero share
     pub min_payout_lamports: u64,
     /// Total investor allocation minted at TGE (Y0)
     pub y0: u64,
@@ -20,16 +24,276 @@ pub struct Policy {
     pub created_at: i64,
     /// PDA bump seed
     pub bump: u8,
+    /// Optional schedule overriding `investor_fee_share_bps` with a time-decaying share
+    pub fee_share_decay: Option<FeeShareDecay>,
+    /// Weight multipliers by remaining lock time, ascending by `min_remaining_secs`
+    pub duration_multipliers: Vec<DurationMultiplier>,
+    /// Pre-committed parameter epochs, ascending by `start_ts`
+    pub epochs: Vec<PolicyEpoch>,
+    /// Admin for privileged instructions (keypair or multisig vault PDA)
+    pub authority: Pubkey,
+    /// Delay before a queued parameter change takes effect
+    pub update_delay_secs: i64,
+    /// Parameter change queued by `update_policy`, applied by the crank once effective
+    pub pending_update: Option<PendingPolicyUpdate>,
+    /// Operator role (pausing, cranker allowlist); `Pubkey::default()` when revoked
+    pub operator: Pubkey,
+    /// Creator role (remainder destination owner); `Pubkey::default()` leaves it unrestricted
+    pub creator: Pubkey,
+    /// Days without a crank after which the creator may sweep the treasury
+    pub stale_after_days: u64,
+    /// Re-deposit the investor share as pool liquidity instead of paying investors; no longer
+    /// supported, and the crank refuses vaults that still have it set
+    pub compound_investor_share: bool,
+    /// Formerly the treasury lending market and the automation thread; zeroed by
+    /// `migrate_accounts`
+    pub _reserved: [u8; 64],
+    /// Layout version, upgraded by `migrate_accounts`
+    pub version: u8,
+    /// Address lookup table holding the vault's crank accounts; `Pubkey::default()` when none
+    pub lookup_table: Pubkey,
+    /// Daily UTC window crank pages must land in; `None` allows cranking at any time
+    pub crank_window: Option<CrankWindow>,
+    /// Minimum seconds between consecutive crank pages of a day; 0 disables the cool-down
+    pub page_cooldown_secs: u32,
+    /// What day close does with the remainder
+    pub remainder_policy: RemainderPolicy,
+    /// Whether the one-time `update_y0` correction has been used
+    pub y0_corrected: bool,
+    /// Investor classes with their own Y0 and fee share; empty treats every investor as
+    /// one class under `y0` and the effective fee share
+    pub investor_classes: Vec<InvestorClass>,
+    /// Weigh investors by their time-weighted average locked amount since their last page,
+    /// sampled by `refresh_locks`, instead of the instant snapshot
+    pub twal_weighting: bool,
+    /// Curve applied to each investor's (weighted) locked amount before pro-rata weighting
+    pub payout_curve: PayoutCurve,
+    /// Minimum eligible investor share while any tokens remain locked (capped by the fee
+    /// share in effect); 0 disables the floor
+    pub min_investor_share_bps: u16,
+    /// Fail the final crank page instead of only emitting `ReconciliationMismatch` when the
+    /// treasury holds less than the day's books say it should
+    pub abort_on_reconciliation_mismatch: bool,
+    /// Share of each page's claimed fees moved to the insurance fund before the
+    /// investor/creator split; 0 disables the deduction
+    pub insurance_bps: u16,
+    /// Integrating platform paid a share of each day's claim at day close, out of the
+    /// creator remainder; `None` when the vault has no referrer
+    pub referral: Option<Referral>,
+    /// Second keeper that must co-sign every crank page, attesting to its investor data;
+    /// `Pubkey::default()` lets any permitted cranker submit pages alone
+    pub page_attester: Pubkey,
+    /// Refuse to open the honorary position when the quote mint has a freeze authority,
+    /// so no investor ATA can be frozen mid-distribution
+    pub require_no_freeze_authority: bool,
+    /// Honorary CP-AMM position, registered by `init_position` or `adopt_position`;
+    /// `Pubkey::default()` until then
+    pub position: Pubkey,
+    /// CP-AMM pool of the honorary position, recorded with it; the crank and harvests
+    /// must use this pool once set
+    pub pool: Pubkey,
+    /// Queued move of the honorary position to an upgraded pool
+    pub pending_position_migration: Option<PendingPositionMigration>,
+    /// Tick range of the honorary position given to `init_position`, entirely above the
+    /// pool price so it only accrues quote fees; both zero when not configured
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    /// Handling of payouts below `min_payout_lamports`
+    pub dust_policy: DustPolicy,
+    /// Investors per crank page; every page but the final one must hold exactly this
+    /// many. 0 leaves page sizes to the keeper (up to `MAX_INVESTORS_PER_PAGE`)
+    pub page_size: u16,
+    /// Distribute from the on-chain investor registry with `crank_from_registry`;
+    /// `crank_distribute` is rejected while set
+    pub registry_mode: bool,
+    /// Mint of the transferable cranker pass: holders of its tokens may crank while the
+    /// cranker allowlist is enabled; `Pubkey::default()` when unset
+    pub cranker_pass_mint: Pubkey,
+    /// Lamports paid from the crank fee vault to the caller of every crank page; 0 disables
+    pub crank_reimbursement_lamports: u64,
+    /// Crank pages only accept investors whose lock was snapshotted by `refresh_locks`
+    /// for the current day
+    pub require_lock_snapshot: bool,
+}
+
+/// Shape of the pro-rata payout weighting
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayoutCurve {
+    /// Weight proportional to the locked amount
+    Linear,
+    /// Weight proportional to the square root of the locked amount (quadratic-funding
+    /// style), favoring smaller holders
+    Sqrt,
+}
+
+impl From<PayoutCurve> for star_distribution_core::PayoutCurve {
+    fn from(curve: PayoutCurve) -> Self {
+        match curve {
+            PayoutCurve::Linear => star_distribution_core::PayoutCurve::Linear,
+            PayoutCurve::Sqrt => star_distribution_core::PayoutCurve::Sqrt,
+        }
+    }
+}
+
+/// Destination of the day-close remainder
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemainderPolicy {
+    /// Transfer it to the creator's quote ATA
+    Creator,
+    /// Roll it into the next day's investor pool via the carry-over
+    DonateToInvestors,
+    /// Burn it from the treasury (for vaults whose quote token is the project's own)
+    Burn,
+}
+
+/// Where `skip_day` sends the fees of a skipped day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipRouting {
+    /// Claim nothing; the fees stay in the position (and any harvested fees stay pending)
+    /// for the next day's first page
+    Hold,
+    /// Claim the fees and transfer them, with any harvested fees, to the creator's quote ATA
+    Creator,
+    /// Claim the fees and roll them, with any harvested fees, into the next day's
+    /// investor pool via the carry-over
+    RollOver,
+}
+
+/// What the crank does with an investor payout below `min_payout_lamports`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DustPolicy {
+    /// Keep it in the investor pool as carry-over, recorded as the investor's `accrued_dust`
+    Accumulate,
+    /// Release it to the day's remainder
+    Drop,
+    /// Pay it anyway, ignoring the threshold
+    Pay,
+}
+
+impl DustPolicy {
+    /// Book an investor share the crank could not pay, below the threshold or into a frozen
+    /// ATA: `Drop` releases it to the day's remainder through `withheld`, otherwise it stays
+    /// in the pool as carry-over, recorded in the investor's `accrued_dust`
+    pub fn route_unpaid_share(self, share: u64, withheld: &mut u64, accrued_dust: &mut u64) -> Result<()> {
+        let booked = match self {
+            DustPolicy::Drop => withheld,
+            DustPolicy::Accumulate | DustPolicy::Pay => accrued_dust,
+        };
+        *booked = booked.checked_add(share).ok_or(StarError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+impl From<DustPolicy> for star_distribution_core::DustPolicy {
+    fn from(policy: DustPolicy) -> Self {
+        match policy {
+            DustPolicy::Accumulate => star_distribution_core::DustPolicy::Accumulate,
+            DustPolicy::Drop => star_distribution_core::DustPolicy::Drop,
+            DustPolicy::Pay => star_distribution_core::DustPolicy::Pay,
+        }
+    }
+}
+
+/// Roles that can be granted on a policy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Parameter changes, recovery, and role management (stored as `authority`)
+    Admin,
+    /// Operational controls such as pausing and the cranker allowlist
+    Operator,
+    /// Owner of the remainder destination
+    Creator,
+}
+
+/// Queued change to the sensitive policy parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingPolicyUpdate {
+    /// New maximum investor fee share
+    pub investor_fee_share_bps: u16,
+    /// New daily cap (0 = uncapped)
+    pub daily_cap: u64,
+    /// Timestamp from which the change may be applied
+    pub effective_at: i64,
+}
+
+/// Queued move of the honorary position to a new pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingPositionMigration {
+    /// Pool the position moves to
+    pub new_pool: Pubkey,
+    /// Timestamp from which `migrate_position` may run
+    pub executable_at: i64,
+}
+
+/// Parameters taking effect from `start_ts` until the next epoch starts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolicyEpoch {
+    /// Unix timestamp the epoch takes effect at
+    pub start_ts: i64,
+    /// Maximum investor fee share during the epoch
+    pub investor_fee_share_bps: u16,
+    /// Daily cap during the epoch (0 = uncapped)
+    pub daily_cap: u64,
+}
+
+/// Weight multiplier applied to investors whose lock runs for at least `min_remaining_secs`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DurationMultiplier {
+    /// Minimum remaining vesting time (seconds) for this tier to apply
+    pub min_remaining_secs: i64,
+    /// Multiplier on the locked amount in basis points (10000 = 1x)
+    pub multiplier_bps: u16,
+}
+
+/// Linear schedule stepping the investor fee share from `start_bps` to `end_bps`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeShareDecay {
+    /// Investor share at (and before) `start_ts`
+    pub start_bps: u16,
+    /// Investor share once `duration` seconds have elapsed
+    pub end_bps: u16,
+    /// Unix timestamp the decay starts at
+    pub start_ts: i64,
+    /// Decay duration in seconds
+    pub duration: i64,
+}
+
+/// Investor class (e.g. seed, strategic) earning its own slice of the claimed fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvestorClass {
+    /// Total allocation of the class minted at TGE
+    pub y0: u64,
+    /// Maximum basis points of claimed fees paid to the class
+    pub investor_fee_share_bps: u16,
+}
+
+/// Referral fee owed to the platform that integrated the vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Referral {
+    /// Wallet whose quote ATA receives the fee
+    pub referrer: Pubkey,
+    /// Share of the day's claimed fees, paid out of the creator remainder
+    pub referral_bps: u16,
+}
+
+/// Time-of-day window (seconds after 00:00 UTC) in which crank pages are accepted.
+/// A window with `end_secs < start_secs` wraps past midnight.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrankWindow {
+    /// Start of the window, inclusive
+    pub start_secs: u32,
+    /// End of the window, exclusive
+    pub end_secs: u32,
 }
 
 /// Daily distribution progress tracking
-#[account]
+#[account(zero_copy)]
 pub struct Progress {
     /// Unix timestamp of last distribution day
     pub last_distribution_ts: i64,
     /// Total amount distributed today (in lamports)
     pub distributed_today: u64,
-    /// Undistributed dust carried over from previous calculations
+    /// Investor dust carried to the next page or day, held in the treasury
     pub carry_over: u64,
     /// Current pagination cursor for investor accounts
     pub pagination_cursor: u64,
@@ -37,33 +301,498 @@ pub struct Progress {
     pub current_day: i64,
     /// Total amount claimed today from honorary position
     pub claimed_today: u64,
-    /// Whether distribution is complete for current day
-    pub day_complete: bool,
+    /// Total investors in the day's set, committed at day open
+    pub total_investors: u64,
+    /// Total pages expected for the day, committed at day open
+    pub total_pages: u64,
+    /// Investors processed so far today
+    pub investors_processed: u64,
+    /// Total quote fees claimed over the vault's lifetime
+    pub lifetime_claimed: u64,
+    /// Total paid to investors over the vault's lifetime
+    pub lifetime_distributed_to_investors: u64,
+    /// Total remainder paid to the creator over the vault's lifetime
+    pub lifetime_paid_to_creator: u64,
+    /// Number of distribution days closed
+    pub days_processed: u64,
+    /// Timestamp of the last crank page processed
+    pub last_crank_ts: i64,
+    /// Investor share re-deposited as liquidity over the vault's lifetime
+    pub lifetime_compounded: u64,
+    /// Formerly the treasury lending books; zeroed by `migrate_accounts`
+    pub _reserved: [u64; 2],
+    /// Last stream pubkey processed today (stream pubkeys must strictly increase)
+    pub last_stream_pubkey: Pubkey,
+    /// Merkle root of the day's (stream, investor quote ATA) set, committed at day open
+    pub investor_root: [u8; 32],
     /// Vault this progress belongs to
     pub vault: Pubkey,
+    /// Whether distribution is complete for current day (0 or 1)
+    pub day_complete: u8,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Layout version, upgraded by `migrate_accounts`
+    pub version: u8,
+    /// Keeps the `repr(C)` layout free of implicit padding
+    pub _padding: [u8; 5],
+    /// Timestamp of the day's last crank page, for the page cool-down; 0 before the first page
+    pub last_page_ts: i64,
+    /// Current `DayPhase` of the distribution day
+    pub phase: u8,
+    /// Keeps the appended fields 8-byte aligned
+    pub _phase_padding: [u8; 7],
+    /// `carry_over` when the day was opened, reconciled against the day's flows at close
+    pub carry_over_at_open: u64,
+    /// Quote fees claimed by `harvest_fees` between days, folded into the next day's claim
+    pub harvested_pending: u64,
+    /// Quote fees moved to the insurance fund over the vault's lifetime
+    pub lifetime_insured: u64,
+    /// Total locked amount of the day's investor set, committed at day open; the
+    /// denominator of every page's investor weights
+    pub day_locked_total: u64,
+    /// Locked amount weighed by the day's pages so far, never above `day_locked_total`
+    pub day_locked_processed: u64,
+    /// The day's investor pool per investor class, fixed by the first page's claim; every
+    /// page pays its investors' weights of it
+    pub day_class_pools: [u64; MAX_INVESTOR_CLASSES],
+    /// Eligible investor share of the day's claim, summed over classes
+    pub day_eligible_share_bps: u16,
+    /// Keeps the layout 8-byte aligned
+    pub _day_padding: [u8; 6],
+    /// Timestamp the day's locks are read at, committed at day open; every page weighs its
+    /// investors as of this moment
+    pub day_lock_ts: i64,
+    /// Locked amount of each investor class, committed at day open
+    pub day_class_locked: [u64; MAX_INVESTOR_CLASSES],
+    /// Curved, multiplier-weighted total of each investor class, committed at day open; the
+    /// denominator of every investor weight of the day
+    pub day_class_weights: [u64; MAX_INVESTOR_CLASSES],
+    /// Locked amount of each class weighed by the day's pages so far
+    pub day_class_locked_processed: [u64; MAX_INVESTOR_CLASSES],
+    /// Curved weight of each class weighed by the day's pages so far
+    pub day_class_weights_processed: [u64; MAX_INVESTOR_CLASSES],
+}
+
+/// Locked amount and curved weight of one investor class, committed by `open_day`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayClassTotal {
+    /// Sum of the class's locked amounts at the day's lock timestamp
+    pub locked: u64,
+    /// Sum of the class's curved, multiplier-weighted locked amounts at that timestamp
+    pub weight: u64,
+}
+
+/// Phases of a distribution day. `open_day` enters `Idle`; the day's first crank page claims
+/// its fees (`Claimed`) and fixes the day's investor pool, then every page pays out of it
+/// (`Distributing`); the final page closes the day (`Closed`). Transitions are checked so
+/// fees are claimed exactly once per day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DayPhase {
+    Idle = 0,
+    Claimed = 1,
+    Distributing = 2,
+    Closed = 3,
+}
+
+/// Per-investor lifetime payout record, updated on every crank page
+#[account]
+pub struct InvestorRecord {
+    /// Vault this record belongs to
+    pub vault: Pubkey,
+    /// Vesting stream this record tracks
+    pub stream_pubkey: Pubkey,
+    /// Cumulative quote tokens received
+    pub cumulative_received: u64,
+    /// Day of the last payout
+    pub last_payout_day: i64,
+    /// Payouts withheld for falling below the dust threshold
+    pub accrued_dust: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Recipient refused fee income; the crank withholds their share for the creator
+    pub opted_out: bool,
+    /// Index into `policy.investor_classes` (0 when the policy has no classes)
+    pub class: u8,
+    /// Start of the current time-weighted locked period (0 before the first sample)
+    pub twal_period_start: i64,
+    /// Timestamp of the latest lock sample
+    pub twal_last_ts: i64,
+    /// Locked amount at the latest sample, held until the next one
+    pub twal_last_locked: u64,
+    /// Sum of locked amount × seconds over the period up to `twal_last_ts`
+    pub twal_accumulated: u128,
+    /// Day of the cached lock snapshot (0 before the first snapshot)
+    pub snapshot_day: i64,
+    /// Locked amount cached by `refresh_locks`, used by that day's crank page
+    pub snapshot_locked: u64,
+    /// Timestamp the snapshot was taken at
+    pub snapshot_ts: i64,
+}
+
+/// Per-vault list of investor wallets excluded from distribution
+#[account]
+pub struct Blacklist {
+    /// Vault this blacklist belongs to
+    pub vault: Pubkey,
+    /// Excluded beneficiary wallets
+    pub wallets: Vec<Pubkey>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Per-vault header of the on-chain investor registry, an alternative to keeper-supplied
+/// investor sets. Registered investors live in `InvestorRegistryChunk` PDAs indexed
+/// `0..chunk_count`.
+#[account]
+pub struct InvestorRegistry {
+    /// Vault this registry belongs to
+    pub vault: Pubkey,
+    /// Number of chunks created so far
+    pub chunk_count: u32,
+    /// Investors registered across all chunks
+    pub total_investors: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// One chunk of the investor registry, holding up to `MAX_REGISTRY_CHUNK_ENTRIES`
+/// investors in no particular order
+#[account]
+pub struct InvestorRegistryChunk {
+    /// Vault this chunk belongs to
+    pub vault: Pubkey,
+    /// Index of the chunk within the registry
+    pub index: u32,
+    /// Registered investors
+    pub entries: Vec<RegistryEntry>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// A registered investor: the vesting account and the quote ATA its payouts go to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegistryEntry {
+    pub stream_pubkey: Pubkey,
+    pub investor_quote_ata: Pubkey,
+}
+
+/// Marks a stream as registered and records its chunk, so a stream is registered at
+/// most once per vault and can be removed without scanning the registry
+#[account]
+pub struct RegisteredInvestor {
+    /// Vault the stream is registered with
+    pub vault: Pubkey,
+    /// Vesting account registered
+    pub stream_pubkey: Pubkey,
+    /// Chunk holding the entry
+    pub chunk_index: u32,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Per-vault allowlist of identities permitted to run the crank
+#[account]
+pub struct CrankerAllowlist {
+    /// Vault this allowlist belongs to
+    pub vault: Pubkey,
+    /// Whether the crank is restricted to the allowlist
+    pub enabled: bool,
+    /// Allowed cranker keys
+    pub crankers: Vec<Pubkey>,
+    /// Switchboard function whose enclave signer is an accepted cranker; `Pubkey::default()` when unset
+    pub switchboard_function: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Program-wide configuration shared by every vault
+#[account]
+pub struct GlobalConfig {
+    /// Manages the configuration; set to the program's upgrade authority at creation
+    pub admin: Pubkey,
+    /// Transfer hook programs accepted on Token-2022 quote mints
+    pub allowed_transfer_hooks: Vec<Pubkey>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Reusable distribution parameters, created by the global config admin and cloned into
+/// new vaults by `initialize_from_template`
+#[account]
+pub struct PolicyTemplate {
+    /// Identifier the template PDA is derived from
+    pub template_id: u32,
+    /// Parameters every vault created from the template starts with
+    pub params: VaultInitParams,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Optional human-readable description of a vault, for explorers and dashboards
+#[account]
+pub struct VaultMetadata {
+    /// Vault described
+    pub vault: Pubkey,
+    /// Display name, at most `MAX_VAULT_NAME_LEN` bytes
+    pub name: String,
+    /// Project website or metadata JSON, at most `MAX_VAULT_URI_LEN` bytes
+    pub project_uri: String,
+    /// Logo image, at most `MAX_VAULT_URI_LEN` bytes
+    pub logo_uri: String,
+    /// Hash of the creator's off-chain contact details; all zero when unset
+    pub creator_contact_hash: [u8; 32],
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Pending timelocked recovery of treasury funds, one per vault
+#[account]
+pub struct RecoveryProposal {
+    /// Vault the recovery targets
+    pub vault: Pubkey,
+    /// Authority that proposed the recovery
+    pub proposer: Pubkey,
+    /// Account that funded the proposal rent and receives it back on close
+    pub payer: Pubkey,
+    /// Token account receiving the recovered funds
+    pub destination: Pubkey,
+    /// Amount of quote tokens to recover
+    pub amount: u64,
+    /// Timestamp of the proposal
+    pub proposed_at: i64,
+    /// Earliest timestamp the recovery can be executed
+    pub executable_at: i64,
     /// PDA bump seed
     pub bump: u8,
+    /// Token account the funds are recovered from: the treasury or the insurance fund
+    pub source: Pubkey,
 }
 
-/// Investor account information for distribution
+/// Distribution parameters of one vault, as passed to `batch_initialize`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultInitParams {
+    pub investor_fee_share_bps: u16,
+    pub daily_cap: u64,
+    pub min_payout_lamports: u64,
+    pub y0: u64,
+    pub fee_share_decay: Option<FeeShareDecay>,
+    pub duration_multipliers: Vec<DurationMultiplier>,
+    pub epochs: Vec<PolicyEpoch>,
+    pub referral: Option<Referral>,
+}
+
+/// Arguments of `init_policy`. The leading version byte selects the layout of what
+/// follows, so later versions can append fields without breaking keepers that still
+/// send this one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InitializeArgsV1 {
+    /// Always `InitializeArgsV1::VERSION`
+    pub version: u8,
+    pub params: VaultInitParams,
+}
+
+impl InitializeArgsV1 {
+    pub const VERSION: u8 = 1;
+
+    pub fn new(params: VaultInitParams) -> Self {
+        Self { version: Self::VERSION, params }
+    }
+
+    /// Reject arguments of a version this program does not know
+    pub fn validate_version(&self) -> Result<()> {
+        require!(self.version == Self::VERSION, StarError::UnsupportedArgsVersion);
+        Ok(())
+    }
+}
+
+/// Arguments of `crank_distribute`, versioned like `InitializeArgsV1`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CrankArgsV1 {
+    /// Always `CrankArgsV1::VERSION`
+    pub version: u8,
+    /// 1-based page of the open day
+    pub page: u64,
+    /// One Merkle proof per investor entry in remaining_accounts, in page order
+    pub investor_proofs: Vec<Vec<[u8; 32]>>,
+}
+
+impl CrankArgsV1 {
+    pub const VERSION: u8 = 1;
+
+    pub fn new(page: u64, investor_proofs: Vec<Vec<[u8; 32]>>) -> Self {
+        Self { version: Self::VERSION, page, investor_proofs }
+    }
+
+    /// Reject arguments of a version this program does not know
+    pub fn validate_version(&self) -> Result<()> {
+        require!(self.version == Self::VERSION, StarError::UnsupportedArgsVersion);
+        Ok(())
+    }
+}
+
+/// An investor of a crank page. The crank builds these from its remaining_accounts
+/// entries; keepers use them to plan pages and build the investor set's Merkle tree.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct InvestorAccount {
-    /// Streamflow stream public key
+    /// Vesting account public key (Streamflow stream or Bonfida vesting schedule)
     pub stream_pubkey: Pubkey,
     /// Investor's quote token ATA
     pub investor_quote_ata: Pubkey,
-    /// Current locked amount (fetched from Streamflow)
+    /// Locked amount; the crank reads it on-chain from the vesting account
     pub locked_amount: u64,
     /// Investor's weight in this page
     pub weight: u64,
 }
 
+/// Outcome of a crank page, returned via return data for programs composing via CPI
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrankPageResult {
+    /// Paid to investors by this page
+    pub distributed: u64,
+    /// Investor dust carried to the next page or day
+    pub carry_over: u64,
+    /// Investors that received a transfer on this page
+    pub investors_paid: u64,
+    /// Whether this page closed the day
+    pub day_complete: bool,
+}
+
+/// Result of a read-only distribution simulation (returned via return data)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DistributionSimulation {
+    /// Eligible investor share in basis points
+    pub eligible_share_bps: u16,
+    /// Investor fee before the daily cap
+    pub investor_fee_quote: u64,
+    /// Investor fee after the daily cap
+    pub capped_investor_fee: u64,
+    /// Capped investor fee plus carry-over
+    pub total_to_distribute: u64,
+    /// Payout per investor, in page order
+    pub payouts: Vec<u64>,
+    /// Sum of all payouts
+    pub distributed: u64,
+    /// Dust that would be carried over
+    pub carry_over: u64,
+    /// Amount the page would leave for the creator at day close
+    pub creator_remainder: u64,
+    /// Share of the claim that would go to the insurance fund
+    pub insurance_amount: u64,
+}
+
 /// PDA seeds constants
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const POLICY_SEED: &[u8] = b"policy";
 pub const PROGRESS_SEED: &[u8] = b"progress";
 pub const INVESTOR_FEE_POS_OWNER_SEED: &[u8] = b"investor_fee_pos_owner";
 pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const INSURANCE_SEED: &[u8] = b"insurance";
+pub const INVESTOR_RECORD_SEED: &[u8] = b"investor_record";
+pub const RECOVERY_SEED: &[u8] = b"recovery";
+pub const BLACKLIST_SEED: &[u8] = b"blacklist";
+pub const CRANKER_ALLOWLIST_SEED: &[u8] = b"cranker_allowlist";
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+pub const INVESTOR_REGISTRY_SEED: &[u8] = b"investor_registry";
+pub const REGISTRY_CHUNK_SEED: &[u8] = b"registry_chunk";
+pub const REGISTERED_INVESTOR_SEED: &[u8] = b"registered_investor";
+pub const CRANK_FEE_VAULT_SEED: &[u8] = b"crank_fee_vault";
+pub const POLICY_TEMPLATE_SEED: &[u8] = b"policy_template";
+pub const VAULT_METADATA_SEED: &[u8] = b"metadata";
+
+/// Maximum number of keys in a cranker allowlist
+pub const MAX_ALLOWED_CRANKERS: usize = 16;
+
+/// Maximum number of allowlisted transfer hook programs
+pub const MAX_ALLOWED_TRANSFER_HOOKS: usize = 16;
+
+/// Maximum length in bytes of a vault's metadata name
+pub const MAX_VAULT_NAME_LEN: usize = 32;
+
+/// Maximum length in bytes of a vault's metadata URIs
+pub const MAX_VAULT_URI_LEN: usize = 200;
+
+/// Maximum number of wallets in a vault blacklist
+pub const MAX_BLACKLIST_ENTRIES: usize = 64;
+
+/// Maximum investors per registry chunk, small enough for a chunk's investor accounts
+/// (three each, four for Bonfida schedules) to fit one crank transaction
+pub const MAX_REGISTRY_CHUNK_ENTRIES: usize = 10;
+
+/// Mandatory delay between proposing and executing a treasury recovery (72h)
+pub const RECOVERY_TIMELOCK_SECS: i64 = 72 * 60 * 60;
+
+/// Mandatory delay between queuing and executing a position migration (72h)
+pub const POSITION_MIGRATION_TIMELOCK_SECS: i64 = 72 * 60 * 60;
+
+/// Whether positions can migrate: moving one needs the CP-AMM `close_position` and
+/// `create_position` CPIs, which only the mock integration provides so far
+pub const POSITION_MIGRATION_SUPPORTED: bool = cfg!(feature = "mock-integrations");
+
+/// Default delay before queued policy parameter changes take effect (24h)
+pub const DEFAULT_POLICY_UPDATE_DELAY_SECS: i64 = 24 * 60 * 60;
+
+/// Default number of crank-less days before a vault is considered stale
+pub const DEFAULT_STALE_AFTER_DAYS: u64 = 30;
+
+/// Period after policy creation during which `y0` may be corrected once (30 days)
+pub const Y0_CORRECTION_WINDOW_SECS: i64 = 30 * 86400;
+
+/// Current `Policy` layout version; bump it whenever the layout changes, so
+/// `migrate_accounts` can tell old accounts apart. v2: fields appended from `lookup_table`
+/// to `require_lock_snapshot`. v3: `lending_market` retired to `_reserved`.
+/// v4: `crank_thread` retired to `_reserved`.
+pub const CURRENT_POLICY_VERSION: u8 = 4;
+
+/// Current `Progress` layout version; bump it whenever the layout changes, so
+/// `migrate_accounts` can tell old accounts apart. v2: the day phase and day books
+/// appended from `last_page_ts` to `day_eligible_share_bps`. v3: the lending books retired
+/// to `_reserved`. v4: the day's lock timestamp and per-class totals appended from
+/// `day_lock_ts` to `day_class_weights_processed`.
+pub const CURRENT_PROGRESS_VERSION: u8 = 4;
+
+/// Meteora DAMM v2 (CP-AMM) program ID, deployed at the same address on devnet and mainnet
+#[cfg(not(feature = "mock-integrations"))]
+pub const CP_AMM_PROGRAM_ID: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+/// Local CP-AMM test double (`mock-integrations` builds)
+#[cfg(feature = "mock-integrations")]
+pub const CP_AMM_PROGRAM_ID: Pubkey = mock_cp_amm::ID;
+
+/// Streamflow vesting program ID (mainnet, also cloned into localnet)
+#[cfg(not(any(feature = "mock-integrations", feature = "devnet")))]
+pub const STREAMFLOW_PROGRAM_ID: Pubkey = pubkey!("strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m");
+
+/// Streamflow vesting program ID on devnet
+#[cfg(all(feature = "devnet", not(feature = "mock-integrations")))]
+pub const STREAMFLOW_PROGRAM_ID: Pubkey = pubkey!("HqDGZjaVRXJ9MGRQEw7qDc2rAr6iH1n1kAQdCZaCMfMZ");
+
+/// Local Streamflow test double (`mock-integrations` builds)
+#[cfg(feature = "mock-integrations")]
+pub const STREAMFLOW_PROGRAM_ID: Pubkey = mock_streamflow::ID;
+
+/// Bonfida token-vesting program ID (legacy vesting schedules)
+pub const BONFIDA_VESTING_PROGRAM_ID: Pubkey = pubkey!("CChTq6PthWU82YZkbveA3WDf7s97BWhBK4Vx9bmsT743");
+
+/// Switchboard attestation program ID (Switchboard Functions)
+pub const SWITCHBOARD_ATTESTATION_PROGRAM_ID: Pubkey = pubkey!("sbattyXrzedoNATfc4L31wC9Mhxsi1BmFhTiN8gDshx");
+
+/// Maximum number of lock-duration multiplier tiers stored in a policy
+pub const MAX_DURATION_MULTIPLIERS: usize = 8;
+
+/// Maximum number of pre-committed policy epochs
+pub const MAX_POLICY_EPOCHS: usize = 8;
+
+/// Maximum investors per crank page. `cu-trace` runs measure ~60k CU per page plus
+/// ~25k CU per paid investor, so 1.4M CU fits ~53; the cap leaves headroom for Merkle
+/// proofs, Bonfida schedule reads and the day close on the final page.
+pub const MAX_INVESTORS_PER_PAGE: usize = 40;
+
+/// Maximum number of investor classes stored in a policy
+pub const MAX_INVESTOR_CLASSES: usize = 4;
+
+/// Seconds in a UTC day, the period crank windows repeat over
+pub const SECONDS_PER_DAY: u32 = 86_400;
 
 /// PDA derivation helpers
 pub fn derive_policy_pda(vault: &Pubkey) -> (Pubkey, u8) {
@@ -88,6 +817,66 @@ pub fn derive_treasury_pda(vault: &Pubkey, quote_mint: &Pubkey) -> (Pubkey, u8)
     )
 }
 
+pub fn derive_insurance_pda(vault: &Pubkey, quote_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VAULT_SEED, vault.as_ref(), INSURANCE_SEED, quote_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn derive_investor_record_pda(vault: &Pubkey, stream_pubkey: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VAULT_SEED, vault.as_ref(), INVESTOR_RECORD_SEED, stream_pubkey.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn derive_cranker_allowlist_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), CRANKER_ALLOWLIST_SEED], &crate::ID)
+}
+
+pub fn derive_global_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &crate::ID)
+}
+
+pub fn derive_blacklist_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), BLACKLIST_SEED], &crate::ID)
+}
+
+pub fn derive_investor_registry_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), INVESTOR_REGISTRY_SEED], &crate::ID)
+}
+
+pub fn derive_registry_chunk_pda(vault: &Pubkey, chunk_index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VAULT_SEED, vault.as_ref(), REGISTRY_CHUNK_SEED, &chunk_index.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn derive_registered_investor_pda(vault: &Pubkey, stream_pubkey: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VAULT_SEED, vault.as_ref(), REGISTERED_INVESTOR_SEED, stream_pubkey.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn derive_crank_fee_vault_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), CRANK_FEE_VAULT_SEED], &crate::ID)
+}
+
+pub fn derive_policy_template_pda(template_id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POLICY_TEMPLATE_SEED, &template_id.to_le_bytes()], &crate::ID)
+}
+
+pub fn derive_vault_metadata_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), VAULT_METADATA_SEED], &crate::ID)
+}
+
+pub fn derive_recovery_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), RECOVERY_SEED], &crate::ID)
+}
+
 impl Policy {
     pub const SIZE: usize = 8 + // discriminator
         2 + // investor_fee_share_bps
@@ -97,8 +886,46 @@ impl Policy {
         32 + // quote_mint
         32 + // vault
         8 + // created_at
-        1; // bump
+        1 + // bump
+        1 + FeeShareDecay::SIZE + // fee_share_decay
+        4 + MAX_DURATION_MULTIPLIERS * DurationMultiplier::SIZE + // duration_multipliers
+        4 + MAX_POLICY_EPOCHS * PolicyEpoch::SIZE + // epochs
+        32 + // authority
+        8 + // update_delay_secs
+        1 + PendingPolicyUpdate::SIZE + // pending_update
+        32 + // operator
+        32 + // creator
+        8 + // stale_after_days
+        1 + // compound_investor_share
+        64 + // _reserved
+        1 + // version
+        32 + // lookup_table
+        1 + CrankWindow::SIZE + // crank_window
+        4 + // page_cooldown_secs
+        1 + // remainder_policy
+        1 + // y0_corrected
+        4 + MAX_INVESTOR_CLASSES * InvestorClass::SIZE + // investor_classes
+        1 + // twal_weighting
+        1 + // payout_curve
+        2 + // min_investor_share_bps
+        1 + // abort_on_reconciliation_mismatch
+        2 + // insurance_bps
+        1 + Referral::SIZE + // referral
+        32 + // page_attester
+        1 + // require_no_freeze_authority
+        32 + // position
+        32 + // pool
+        1 + PendingPositionMigration::SIZE + // pending_position_migration
+        4 + // tick_lower
+        4 + // tick_upper
+        1 + // dust_policy
+        2 + // page_size
+        1 + // registry_mode
+        32 + // cranker_pass_mint
+        8 + // crank_reimbursement_lamports
+        1; // require_lock_snapshot
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         investor_fee_share_bps: u16,
         daily_cap: u64,
@@ -107,6 +934,10 @@ impl Policy {
         quote_mint: Pubkey,
         vault: Pubkey,
         bump: u8,
+        fee_share_decay: Option<FeeShareDecay>,
+        duration_multipliers: Vec<DurationMultiplier>,
+        epochs: Vec<PolicyEpoch>,
+        authority: Pubkey,
     ) -> Self {
         Self {
             investor_fee_share_bps,
@@ -117,19 +948,278 @@ impl Policy {
             vault,
             created_at: Clock::get().unwrap().unix_timestamp,
             bump,
+            fee_share_decay,
+            duration_multipliers,
+            epochs,
+            authority,
+            update_delay_secs: DEFAULT_POLICY_UPDATE_DELAY_SECS,
+            pending_update: None,
+            operator: authority,
+            creator: Pubkey::default(),
+            stale_after_days: DEFAULT_STALE_AFTER_DAYS,
+            compound_investor_share: false,
+            _reserved: [0u8; 64],
+            version: CURRENT_POLICY_VERSION,
+            lookup_table: Pubkey::default(),
+            crank_window: None,
+            page_cooldown_secs: 0,
+            remainder_policy: RemainderPolicy::Creator,
+            y0_corrected: false,
+            investor_classes: Vec::new(),
+            twal_weighting: false,
+            payout_curve: PayoutCurve::Linear,
+            min_investor_share_bps: 0,
+            abort_on_reconciliation_mismatch: false,
+            insurance_bps: 0,
+            referral: None,
+            page_attester: Pubkey::default(),
+            require_no_freeze_authority: false,
+            position: Pubkey::default(),
+            pool: Pubkey::default(),
+            pending_position_migration: None,
+            tick_lower: 0,
+            tick_upper: 0,
+            dust_policy: DustPolicy::Accumulate,
+            page_size: 0,
+            registry_mode: false,
+            cranker_pass_mint: Pubkey::default(),
+            crank_reimbursement_lamports: 0,
+            require_lock_snapshot: false,
         }
     }
 
     pub fn validate(&self) -> Result<()> {
-        require!(self.investor_fee_share_bps <= 10000, crate::StarError::InvalidFeeShareBps);
-        require!(self.daily_cap > 0, crate::StarError::InvalidDailyCap);
-        require!(self.min_payout_lamports > 0, crate::StarError::InvalidMinPayout);
-        require!(self.y0 > 0, crate::StarError::InvalidY0);
+        require!(self.investor_fee_share_bps <= 10000, StarError::InvalidFeeShareBps);
+        require!(self.y0 > 0, StarError::InvalidY0);
+        if let Some(decay) = &self.fee_share_decay {
+            decay.validate()?;
+        }
+
+        require!(
+            self.duration_multipliers.len() <= MAX_DURATION_MULTIPLIERS,
+            StarError::InvalidDurationMultipliers
+        );
+        let mut previous_min = -1i64;
+        for tier in &self.duration_multipliers {
+            require!(
+                tier.multiplier_bps > 0 && tier.min_remaining_secs > previous_min,
+                StarError::InvalidDurationMultipliers
+            );
+            previous_min = tier.min_remaining_secs;
+        }
+
+        require!(
+            self.epochs.len() <= MAX_POLICY_EPOCHS
+                && (self.epochs.is_empty() || self.fee_share_decay.is_none()),
+            StarError::InvalidPolicyEpochs
+        );
+        for (i, epoch) in self.epochs.iter().enumerate() {
+            require!(
+                epoch.investor_fee_share_bps <= 10000
+                    && (i == 0 || epoch.start_ts > self.epochs[i - 1].start_ts),
+                StarError::InvalidPolicyEpochs
+            );
+        }
+        if let Some(referral) = &self.referral {
+            referral.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Validate a replacement class list: bounded, non-zero allocations, and a combined
+    /// fee share of at most 100%
+    pub fn validate_investor_classes(classes: &[InvestorClass]) -> Result<()> {
+        require!(classes.len() <= MAX_INVESTOR_CLASSES, StarError::InvalidInvestorClasses);
+        let mut total_share_bps = 0u32;
+        for class in classes {
+            require!(class.y0 > 0, StarError::InvalidInvestorClasses);
+            total_share_bps += class.investor_fee_share_bps as u32;
+        }
+        require!(total_share_bps <= 10000, StarError::InvalidInvestorClasses);
+        Ok(())
+    }
+
+    /// Number of classes investors are grouped into (1 when no classes are configured)
+    pub fn investor_class_count(&self) -> usize {
+        self.investor_classes.len().max(1)
+    }
+
+    /// `(y0, max fee share bps)` for `class` at `current_ts`. Without classes this is the
+    /// policy's own Y0 and effective share; configured classes use their fixed terms.
+    pub fn investor_class_terms(&self, class: usize, current_ts: i64) -> (u64, u16) {
+        match self.investor_classes.get(class) {
+            Some(terms) => (terms.y0, terms.investor_fee_share_bps),
+            None => (self.y0, self.effective_investor_fee_share_bps(current_ts)),
+        }
+    }
+
+    /// Account currently holding `role`
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Admin => self.authority,
+            Role::Operator => self.operator,
+            Role::Creator => self.creator,
+        }
+    }
+
+    /// Whether `key` holds `role` (revoked roles match nobody)
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        let holder = self.role_holder(role);
+        holder != Pubkey::default() && holder == *key
+    }
+
+    /// Apply the queued parameter change if its delay has elapsed
+    /// Returns the applied update so the caller can emit it
+    pub fn apply_pending_update(&mut self, current_ts: i64) -> Option<PendingPolicyUpdate> {
+        let update = self.pending_update.filter(|update| current_ts >= update.effective_at)?;
+
+        self.investor_fee_share_bps = update.investor_fee_share_bps;
+        self.daily_cap = update.daily_cap;
+        self.pending_update = None;
+
+        Some(update)
+    }
+
+    /// Epoch in effect at `current_ts`, if any has started
+    pub fn active_epoch(&self, current_ts: i64) -> Option<&PolicyEpoch> {
+        self.epochs
+            .iter()
+            .rev()
+            .find(|epoch| current_ts >= epoch.start_ts)
+    }
+
+    /// Payout threshold the crank applies: none when the dust policy pays everything
+    pub fn effective_min_payout(&self) -> u64 {
+        match self.dust_policy {
+            DustPolicy::Pay => 0,
+            DustPolicy::Accumulate | DustPolicy::Drop => self.min_payout_lamports,
+        }
+    }
+
+    /// Daily cap in effect at `current_ts`
+    pub fn effective_daily_cap(&self, current_ts: i64) -> u64 {
+        self.active_epoch(current_ts)
+            .map_or(self.daily_cap, |epoch| epoch.daily_cap)
+    }
+
+    /// Multiplier for a lock with `remaining_secs` of vesting left: the highest tier
+    /// whose threshold is met, or 1x when no tier applies
+    pub fn duration_multiplier_bps(&self, remaining_secs: i64) -> u16 {
+        self.duration_multipliers
+            .iter()
+            .rev()
+            .find(|tier| remaining_secs >= tier.min_remaining_secs)
+            .map_or(10000, |tier| tier.multiplier_bps)
+    }
+
+    /// Investor fee share in effect at `current_ts`, applying the decay schedule or
+    /// the active epoch if set
+    pub fn effective_investor_fee_share_bps(&self, current_ts: i64) -> u16 {
+        if let Some(decay) = &self.fee_share_decay {
+            return decay.share_bps_at(current_ts);
+        }
+
+        self.active_epoch(current_ts)
+            .map_or(self.investor_fee_share_bps, |epoch| epoch.investor_fee_share_bps)
+    }
+}
+
+impl PendingPolicyUpdate {
+    pub const SIZE: usize = 2 + // investor_fee_share_bps
+        8 + // daily_cap
+        8; // effective_at
+}
+
+impl PendingPositionMigration {
+    pub const SIZE: usize = 32 + // new_pool
+        8; // executable_at
+}
+
+impl PolicyEpoch {
+    pub const SIZE: usize = 8 + // start_ts
+        2 + // investor_fee_share_bps
+        8; // daily_cap
+}
+
+impl DurationMultiplier {
+    pub const SIZE: usize = 8 + // min_remaining_secs
+        2; // multiplier_bps
+}
+
+impl FeeShareDecay {
+    pub const SIZE: usize = 2 + // start_bps
+        2 + // end_bps
+        8 + // start_ts
+        8; // duration
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.start_bps <= 10000 && self.end_bps <= 10000 && self.duration > 0,
+            StarError::InvalidFeeShareDecay
+        );
+        Ok(())
+    }
+
+    /// Linearly interpolated share at `current_ts`, clamped to the schedule endpoints
+    pub fn share_bps_at(&self, current_ts: i64) -> u16 {
+        if current_ts <= self.start_ts {
+            return self.start_bps;
+        }
+
+        let elapsed = (current_ts as i128 - self.start_ts as i128).min(self.duration as i128);
+        let delta = self.end_bps as i128 - self.start_bps as i128;
+        let share = self.start_bps as i128 + delta * elapsed / self.duration as i128;
+
+        share as u16
+    }
+}
+
+impl InvestorClass {
+    pub const SIZE: usize = 8 + // y0
+        2; // investor_fee_share_bps
+}
+
+impl CrankWindow {
+    pub const SIZE: usize = 4 + // start_secs
+        4; // end_secs
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.start_secs < SECONDS_PER_DAY
+                && self.end_secs < SECONDS_PER_DAY
+                && self.start_secs != self.end_secs,
+            StarError::InvalidCrankWindow
+        );
+        Ok(())
+    }
+
+    /// Whether `current_ts` falls inside the window on its UTC day
+    pub fn contains(&self, current_ts: i64) -> bool {
+        let time_of_day = current_ts.rem_euclid(SECONDS_PER_DAY as i64) as u32;
+        if self.start_secs < self.end_secs {
+            time_of_day >= self.start_secs && time_of_day < self.end_secs
+        } else {
+            time_of_day >= self.start_secs || time_of_day < self.end_secs
+        }
+    }
+}
+
+impl Referral {
+    pub const SIZE: usize = 32 + // referrer
+        2; // referral_bps
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.referrer != Pubkey::default() && self.referral_bps <= 10000,
+            StarError::InvalidReferral
+        );
         Ok(())
     }
 }
 
 impl Progress {
+    /// Zero-copy layout: fields are ordered by alignment so `repr(C)` adds no padding;
+    /// fields added later are appended after `_padding`, keeping 8-byte alignment
     pub const SIZE: usize = 8 + // discriminator
         8 + // last_distribution_ts
         8 + // distributed_today
@@ -137,9 +1227,42 @@ impl Progress {
         8 + // pagination_cursor
         8 + // current_day
         8 + // claimed_today
-        1 + // day_complete
+        8 + // total_investors
+        8 + // total_pages
+        8 + // investors_processed
+        8 + // lifetime_claimed
+        8 + // lifetime_distributed_to_investors
+        8 + // lifetime_paid_to_creator
+        8 + // days_processed
+        8 + // last_crank_ts
+        8 + // lifetime_compounded
+        16 + // _reserved
+        32 + // last_stream_pubkey
+        32 + // investor_root
         32 + // vault
-        1; // bump
+        1 + // day_complete
+        1 + // bump
+        1 + // version
+        5 + // _padding
+        8 + // last_page_ts
+        1 + // phase
+        7 + // _phase_padding
+        8 + // carry_over_at_open
+        8 + // harvested_pending
+        8 + // lifetime_insured
+        8 + // day_locked_total
+        8 + // day_locked_processed
+        8 * MAX_INVESTOR_CLASSES + // day_class_pools
+        2 + // day_eligible_share_bps
+        6 + // _day_padding
+        8 + // day_lock_ts
+        8 * MAX_INVESTOR_CLASSES + // day_class_locked
+        8 * MAX_INVESTOR_CLASSES + // day_class_weights
+        8 * MAX_INVESTOR_CLASSES + // day_class_locked_processed
+        8 * MAX_INVESTOR_CLASSES; // day_class_weights_processed
+
+    /// Size of the first zero-copy layout; Borsh-era accounts are always shorter
+    pub const FIRST_ZERO_COPY_SIZE: usize = 248;
 
     pub fn new(vault: Pubkey, bump: u8) -> Self {
         Self {
@@ -149,12 +1272,191 @@ impl Progress {
             pagination_cursor: 0,
             current_day: 0,
             claimed_today: 0,
-            day_complete: false,
+            total_investors: 0,
+            total_pages: 0,
+            investors_processed: 0,
+            lifetime_claimed: 0,
+            lifetime_distributed_to_investors: 0,
+            lifetime_paid_to_creator: 0,
+            days_processed: 0,
+            last_crank_ts: 0,
+            lifetime_compounded: 0,
+            _reserved: [0u64; 2],
+            last_stream_pubkey: Pubkey::default(),
+            investor_root: [0u8; 32],
             vault,
+            day_complete: 0,
             bump,
+            version: CURRENT_PROGRESS_VERSION,
+            _padding: [0u8; 5],
+            last_page_ts: 0,
+            phase: DayPhase::Idle as u8,
+            _phase_padding: [0u8; 7],
+            carry_over_at_open: 0,
+            harvested_pending: 0,
+            lifetime_insured: 0,
+            day_locked_total: 0,
+            day_locked_processed: 0,
+            day_class_pools: [0u64; MAX_INVESTOR_CLASSES],
+            day_eligible_share_bps: 0,
+            _day_padding: [0u8; 6],
+            day_lock_ts: 0,
+            day_class_locked: [0u64; MAX_INVESTOR_CLASSES],
+            day_class_weights: [0u64; MAX_INVESTOR_CLASSES],
+            day_class_locked_processed: [0u64; MAX_INVESTOR_CLASSES],
+            day_class_weights_processed: [0u64; MAX_INVESTOR_CLASSES],
         }
     }
 
+    pub fn phase(&self) -> Result<DayPhase> {
+        match self.phase {
+            0 => Ok(DayPhase::Idle),
+            1 => Ok(DayPhase::Claimed),
+            2 => Ok(DayPhase::Distributing),
+            3 => Ok(DayPhase::Closed),
+            _ => err!(StarError::InvalidPhaseTransition),
+        }
+    }
+
+    /// Move to `to`, failing unless the day is currently in one of `from`
+    pub fn advance_phase(&mut self, from: &[DayPhase], to: DayPhase) -> Result<()> {
+        require!(from.contains(&self.phase()?), StarError::InvalidPhaseTransition);
+        self.phase = to as u8;
+        Ok(())
+    }
+
+    /// Record the day's fee claim, made once by its first page
+    pub fn record_claim(&mut self, claimed: u64) -> Result<()> {
+        self.advance_phase(&[DayPhase::Idle], DayPhase::Claimed)?;
+        self.claimed_today = self.claimed_today
+            .checked_add(claimed)
+            .ok_or(StarError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Fix the day's investor pool after its claim: `total_to_distribute` (the capped
+    /// investor fee plus the carry-over) becomes the pool left to pay in `carry_over`, and
+    /// `class_pools` its split between investor classes
+    pub fn fix_day_pool(
+        &mut self,
+        total_to_distribute: u64,
+        class_pools: &[u64],
+        eligible_share_bps: u16,
+    ) -> Result<()> {
+        require!(class_pools.len() <= MAX_INVESTOR_CLASSES, StarError::InvalidInvestorClass);
+        self.advance_phase(&[DayPhase::Claimed], DayPhase::Distributing)?;
+        self.carry_over = total_to_distribute;
+        self.day_class_pools = [0u64; MAX_INVESTOR_CLASSES];
+        self.day_class_pools[..class_pools.len()].copy_from_slice(class_pools);
+        self.day_eligible_share_bps = eligible_share_bps;
+        Ok(())
+    }
+
+    /// Book a page's payouts against the pool left in `carry_over`: what the page paid and
+    /// withheld leaves it, rounding and dust stay. Returns the new carry-over.
+    pub fn record_page_payouts(&mut self, distributed: u64, withheld: u64) -> Result<u64> {
+        self.carry_over = DistributionMath::calculate_page_carry_over(self.carry_over, distributed, withheld)?;
+        self.distributed_today = self.distributed_today
+            .checked_add(distributed)
+            .ok_or(StarError::MathOverflow)?;
+        Ok(self.carry_over)
+    }
+
+    /// Commit the day's per-class locked and weight totals, read at `lock_ts`. Every page
+    /// divides by these totals, so an investor's payout does not depend on its page.
+    pub fn commit_day_totals(&mut self, class_totals: &[DayClassTotal], lock_ts: i64) -> Result<()> {
+        require!(
+            !class_totals.is_empty() && class_totals.len() <= MAX_INVESTOR_CLASSES,
+            StarError::InvalidInvestorClass
+        );
+        self.day_lock_ts = lock_ts;
+        self.day_locked_total = 0;
+        for (class, total) in class_totals.iter().enumerate() {
+            self.day_class_locked[class] = total.locked;
+            self.day_class_weights[class] = total.weight;
+            self.day_locked_total = self.day_locked_total
+                .checked_add(total.locked)
+                .ok_or(StarError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Book a page's per-class locked amounts and weights; the day's pages may never weigh
+    /// more than the totals committed at open
+    pub fn record_page_totals(&mut self, class_locked: &[u64], class_weights: &[u64]) -> Result<()> {
+        require!(
+            class_locked.len() <= MAX_INVESTOR_CLASSES && class_weights.len() == class_locked.len(),
+            StarError::InvalidInvestorClass
+        );
+        for class in 0..class_locked.len() {
+            let locked = self.day_class_locked_processed[class]
+                .checked_add(class_locked[class])
+                .ok_or(StarError::MathOverflow)?;
+            let weight = self.day_class_weights_processed[class]
+                .checked_add(class_weights[class])
+                .ok_or(StarError::MathOverflow)?;
+            require!(
+                locked <= self.day_class_locked[class] && weight <= self.day_class_weights[class],
+                StarError::DayLockedTotalExceeded
+            );
+            self.day_class_locked_processed[class] = locked;
+            self.day_class_weights_processed[class] = weight;
+            self.day_locked_processed = self.day_locked_processed
+                .checked_add(class_locked[class])
+                .ok_or(StarError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Require the day's pages to have weighed exactly the totals committed at open, so an
+    /// inflated commitment cannot shrink every investor's weight and keep the difference
+    pub fn check_day_totals_reached(&self) -> Result<()> {
+        require!(
+            self.day_class_locked_processed == self.day_class_locked
+                && self.day_class_weights_processed == self.day_class_weights,
+            StarError::DayTotalsMismatch
+        );
+        Ok(())
+    }
+
+    pub fn is_day_complete(&self) -> bool {
+        self.day_complete != 0
+    }
+
+    /// Whether a day has been opened and not yet closed
+    pub fn is_day_in_progress(&self) -> bool {
+        self.last_distribution_ts > 0 && !self.is_day_complete()
+    }
+
+    /// Phase of a day recorded before `phase` existed: closed once complete, distributing
+    /// once a page ran (so it is never claimed twice), idle otherwise
+    pub fn phase_from_day_state(&self) -> DayPhase {
+        if self.is_day_complete() {
+            DayPhase::Closed
+        } else if self.pagination_cursor > 0 {
+            DayPhase::Distributing
+        } else {
+            DayPhase::Idle
+        }
+    }
+
+    /// Reject a clock earlier than the last day open or crank page, so validator clock
+    /// quirks or replays cannot rewind day bookkeeping
+    pub fn check_clock(&self, current_ts: i64) -> Result<()> {
+        require!(
+            current_ts >= self.last_distribution_ts
+                && current_ts >= self.last_crank_ts
+                && current_ts >= self.last_page_ts,
+            StarError::ClockRollback
+        );
+        Ok(())
+    }
+
+    /// Day number a day opened at `current_ts` gets
+    pub fn day_number(current_ts: i64) -> i64 {
+        current_ts / 86400
+    }
+
     pub fn is_new_day(&self, current_ts: i64) -> bool {
         current_ts >= self.last_distribution_ts + 86400 // 24 hours
     }
@@ -164,8 +1466,337 @@ impl Progress {
         self.distributed_today = 0;
         self.claimed_today = 0;
         self.pagination_cursor = 0;
-        self.current_day = current_ts / 86400; // Day number
-        self.day_complete = false;
-        // carry_over persists across days
+        self.current_day = Self::day_number(current_ts);
+        self.day_complete = 0;
+        self.last_stream_pubkey = Pubkey::default();
+        self.investor_root = [0u8; 32];
+        self.total_investors = 0;
+        self.total_pages = 0;
+        self.investors_processed = 0;
+        self.last_page_ts = 0;
+        self.phase = DayPhase::Idle as u8;
+        self.day_locked_total = 0;
+        self.day_locked_processed = 0;
+        self.day_class_pools = [0u64; MAX_INVESTOR_CLASSES];
+        self.day_eligible_share_bps = 0;
+        self.day_lock_ts = 0;
+        self.day_class_locked = [0u64; MAX_INVESTOR_CLASSES];
+        self.day_class_weights = [0u64; MAX_INVESTOR_CLASSES];
+        self.day_class_locked_processed = [0u64; MAX_INVESTOR_CLASSES];
+        self.day_class_weights_processed = [0u64; MAX_INVESTOR_CLASSES];
+        // carry_over persists across days and is reconciled at close; harvested_pending
+        // waits for the day's first page
+        self.carry_over_at_open = self.carry_over;
+    }
+}
+
+impl InvestorRecord {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        32 + // stream_pubkey
+        8 + // cumulative_received
+        8 + // last_payout_day
+        8 + // accrued_dust
+        1 + // bump
+        1 + // opted_out
+        1 + // class
+        8 + // twal_period_start
+        8 + // twal_last_ts
+        8 + // twal_last_locked
+        16 + // twal_accumulated
+        8 + // snapshot_day
+        8 + // snapshot_locked
+        8; // snapshot_ts
+
+    pub fn new(vault: Pubkey, stream_pubkey: Pubkey, bump: u8) -> Self {
+        Self {
+            vault,
+            stream_pubkey,
+            cumulative_received: 0,
+            last_payout_day: 0,
+            accrued_dust: 0,
+            bump,
+            opted_out: false,
+            class: 0,
+            twal_period_start: 0,
+            twal_last_ts: 0,
+            twal_last_locked: 0,
+            twal_accumulated: 0,
+            snapshot_day: 0,
+            snapshot_locked: 0,
+            snapshot_ts: 0,
+        }
+    }
+
+    /// Fold the lock held since the previous sample into the accumulator and record
+    /// `locked_amount` as the lock from `current_ts` on
+    pub fn sample_lock(&mut self, locked_amount: u64, current_ts: i64) -> Result<()> {
+        if self.twal_period_start == 0 {
+            self.twal_period_start = current_ts;
+        } else {
+            let elapsed = current_ts.saturating_sub(self.twal_last_ts).max(0) as u128;
+            self.twal_accumulated = (self.twal_last_locked as u128)
+                .checked_mul(elapsed)
+                .and_then(|weighted| self.twal_accumulated.checked_add(weighted))
+                .ok_or(StarError::MathOverflow)?;
+        }
+        self.twal_last_ts = current_ts;
+        self.twal_last_locked = locked_amount;
+        Ok(())
+    }
+
+    /// Locked amount snapshotted for `day`, if any
+    pub fn snapshot_for(&self, day: i64) -> Option<u64> {
+        (self.snapshot_day == day && self.snapshot_ts > 0).then_some(self.snapshot_locked)
+    }
+
+    /// Cache `locked_amount` as the snapshot for `day` unless one was already taken for it.
+    /// Returns whether the snapshot was taken.
+    pub fn snapshot_lock(&mut self, day: i64, locked_amount: u64, current_ts: i64) -> bool {
+        if self.snapshot_for(day).is_some() {
+            return false;
+        }
+        self.snapshot_day = day;
+        self.snapshot_locked = locked_amount;
+        self.snapshot_ts = current_ts;
+        true
+    }
+
+    /// Time-weighted average locked amount from the period start to `current_ts`, holding
+    /// the latest sample until then. `None` before the first sample or for an empty period.
+    pub fn time_weighted_locked(&self, current_ts: i64) -> Result<Option<u64>> {
+        if self.twal_period_start == 0 || current_ts <= self.twal_period_start {
+            return Ok(None);
+        }
+
+        let tail_secs = current_ts.saturating_sub(self.twal_last_ts).max(0) as u128;
+        let total = (self.twal_last_locked as u128)
+            .checked_mul(tail_secs)
+            .and_then(|tail| self.twal_accumulated.checked_add(tail))
+            .ok_or(StarError::MathOverflow)?;
+        let average = total / (current_ts - self.twal_period_start) as u128;
+
+        u64::try_from(average)
+            .map(Some)
+            .map_err(|_| StarError::MathOverflow.into())
+    }
+
+    /// Start a new period at `current_ts` from `locked_amount`
+    pub fn restart_twal(&mut self, locked_amount: u64, current_ts: i64) {
+        self.twal_period_start = current_ts;
+        self.twal_last_ts = current_ts;
+        self.twal_last_locked = locked_amount;
+        self.twal_accumulated = 0;
+    }
+}
+
+impl RecoveryProposal {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        32 + // proposer
+        32 + // payer
+        32 + // destination
+        8 + // amount
+        8 + // proposed_at
+        8 + // executable_at
+        1 + // bump
+        32; // source
+}
+
+impl InvestorRegistry {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        4 + // chunk_count
+        8 + // total_investors
+        1; // bump
+
+    pub fn new(vault: Pubkey, bump: u8) -> Self {
+        Self {
+            vault,
+            chunk_count: 0,
+            total_investors: 0,
+            bump,
+        }
+    }
+
+    /// Whether every chunk is full, the only time a new chunk may be created
+    pub fn is_full(&self) -> bool {
+        self.total_investors >= self.chunk_count as u64 * MAX_REGISTRY_CHUNK_ENTRIES as u64
+    }
+}
+
+impl InvestorRegistryChunk {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        4 + // index
+        4 + MAX_REGISTRY_CHUNK_ENTRIES * RegistryEntry::SIZE + // entries
+        1; // bump
+
+    pub fn new(vault: Pubkey, index: u32, bump: u8) -> Self {
+        Self {
+            vault,
+            index,
+            entries: Vec::new(),
+            bump,
+        }
+    }
+}
+
+impl RegistryEntry {
+    pub const SIZE: usize = 32 + // stream_pubkey
+        32; // investor_quote_ata
+}
+
+impl RegisteredInvestor {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        32 + // stream_pubkey
+        4 + // chunk_index
+        1; // bump
+}
+
+impl Blacklist {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        4 + MAX_BLACKLIST_ENTRIES * 32 + // wallets
+        1; // bump
+
+    pub fn new(vault: Pubkey, bump: u8) -> Self {
+        Self {
+            vault,
+            wallets: Vec::new(),
+            bump,
+        }
+    }
+
+    pub fn contains(&self, wallet: &Pubkey) -> bool {
+        self.wallets.contains(wallet)
+    }
+
+    /// Read the blacklisted wallets from the vault's blacklist PDA.
+    /// Vaults that never created a blacklist exclude nobody.
+    pub fn load_wallets(blacklist_info: &AccountInfo) -> Result<Vec<Pubkey>> {
+        if blacklist_info.data_is_empty() || *blacklist_info.owner != crate::ID {
+            return Ok(Vec::new());
+        }
+
+        let blacklist = Blacklist::try_deserialize(&mut &blacklist_info.try_borrow_data()?[..])?;
+        Ok(blacklist.wallets)
+    }
+}
+
+impl CrankerAllowlist {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        1 + // enabled
+        4 + MAX_ALLOWED_CRANKERS * 32 + // crankers
+        32 + // switchboard_function
+        1; // bump
+
+    pub fn new(vault: Pubkey, bump: u8) -> Self {
+        Self {
+            vault,
+            enabled: false,
+            crankers: Vec::new(),
+            switchboard_function: Pubkey::default(),
+            bump,
+        }
+    }
+
+    /// Read the vault's allowlist; vaults that never created one stay permissionless
+    pub fn load(allowlist_info: &AccountInfo) -> Result<Option<CrankerAllowlist>> {
+        if allowlist_info.data_is_empty() || *allowlist_info.owner != crate::ID {
+            return Ok(None);
+        }
+
+        let allowlist = CrankerAllowlist::try_deserialize(&mut &allowlist_info.try_borrow_data()?[..])?;
+        Ok(Some(allowlist))
+    }
+}
+
+impl GlobalConfig {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // admin
+        4 + MAX_ALLOWED_TRANSFER_HOOKS * 32 + // allowed_transfer_hooks
+        1; // bump
+
+    pub fn new(admin: Pubkey, bump: u8) -> Self {
+        Self {
+            admin,
+            allowed_transfer_hooks: Vec::new(),
+            bump,
+        }
+    }
+
+    /// Read the global config; until it is created no transfer hook is allowed
+    pub fn load(config_info: &AccountInfo) -> Result<Option<GlobalConfig>> {
+        if config_info.data_is_empty() || *config_info.owner != crate::ID {
+            return Ok(None);
+        }
+
+        let config = GlobalConfig::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+        Ok(Some(config))
+    }
+
+    /// Whether `hook_program` may run on quote mint transfers
+    pub fn is_transfer_hook_allowed(&self, hook_program: &Pubkey) -> bool {
+        self.allowed_transfer_hooks.contains(hook_program)
+    }
+}
+
+impl PolicyTemplate {
+    pub const SIZE: usize = 8 + // discriminator
+        4 + // template_id
+        2 + // params.investor_fee_share_bps
+        8 + // params.daily_cap
+        8 + // params.min_payout_lamports
+        8 + // params.y0
+        1 + FeeShareDecay::SIZE + // params.fee_share_decay
+        4 + MAX_DURATION_MULTIPLIERS * DurationMultiplier::SIZE + // params.duration_multipliers
+        4 + MAX_POLICY_EPOCHS * PolicyEpoch::SIZE + // params.epochs
+        1 + Referral::SIZE + // params.referral
+        1; // bump
+}
+
+impl VaultMetadata {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        4 + MAX_VAULT_NAME_LEN + // name
+        4 + MAX_VAULT_URI_LEN + // project_uri
+        4 + MAX_VAULT_URI_LEN + // logo_uri
+        32 + // creator_contact_hash
+        1; // bump
+
+    pub fn new(vault: Pubkey, bump: u8) -> Self {
+        Self {
+            vault,
+            name: String::new(),
+            project_uri: String::new(),
+            logo_uri: String::new(),
+            creator_contact_hash: [0; 32],
+            bump,
+        }
+    }
+
+    /// Replace the description, rejecting fields over their length limits
+    pub fn set(
+        &mut self,
+        name: String,
+        project_uri: String,
+        logo_uri: String,
+        creator_contact_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            name.len() <= MAX_VAULT_NAME_LEN
+                && project_uri.len() <= MAX_VAULT_URI_LEN
+                && logo_uri.len() <= MAX_VAULT_URI_LEN,
+            StarError::VaultMetadataTooLong
+        );
+
+        self.name = name;
+        self.project_uri = project_uri;
+        self.logo_uri = logo_uri;
+        self.creator_contact_hash = creator_contact_hash;
+        Ok(())
     }
 }
@@ -1,10 +1,39 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{self, Token, Transfer};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TransferChecked};
+use spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use star_distribution_core::{DayPool, DistributionMath as CoreMath, PagePayout, PayoutRules};
 
 use crate::errors::StarError;
-use crate::state::{InvestorAccount, Policy, Progress};
+use crate::events::SkipReason;
+use crate::state::{
+    CrankerAllowlist, GlobalConfig, InvestorAccount, PayoutCurve, Policy,
+    BONFIDA_VESTING_PROGRAM_ID, STREAMFLOW_PROGRAM_ID, SWITCHBOARD_ATTESTATION_PROGRAM_ID,
+};
 
-/// Mathematical utilities for fee distribution calculations
+/// Log the remaining compute units at a named checkpoint. Compiled out unless the
+/// program is built with the `cu-trace` feature.
+#[macro_export]
+macro_rules! cu_checkpoint {
+    ($($label:tt)+) => {
+        #[cfg(feature = "cu-trace")]
+        {
+            ::anchor_lang::prelude::msg!("CU {}", format_args!($($label)+));
+            ::anchor_lang::solana_program::log::sol_log_compute_units();
+        }
+    };
+}
+
+/// Mathematical utilities for fee distribution calculations. The arithmetic lives in the
+/// dependency-free `star-distribution-core` crate (where its invariants are property-tested);
+/// this wrapper takes the program's policy types and reports failures as `StarError`.
 pub struct DistributionMath;
 
 impl DistributionMath {
@@ -15,21 +44,18 @@ impl DistributionMath {
         y0: u64,
         max_investor_fee_share_bps: u16,
     ) -> Result<u16> {
-        if y0 == 0 {
-            return Ok(0);
-        }
-
-        // f_locked(t) = locked_total(t) / Y0
-        let f_locked = (locked_total as u128)
-            .checked_mul(10000)
-            .ok_or(StarError::MathOverflow)?
-            .checked_div(y0 as u128)
-            .ok_or(StarError::MathOverflow)?;
-
-        // eligible_investor_share_bps = min(investor_fee_share_bps, floor(f_locked(t) * 10000))
-        let eligible_share = f_locked.min(max_investor_fee_share_bps as u128) as u16;
+        math_result(CoreMath::calculate_eligible_share_bps(locked_total, y0, max_investor_fee_share_bps))
+    }
 
-        Ok(eligible_share)
+    /// Raise the eligible share to the policy floor while any tokens remain locked. The floor
+    /// never exceeds the fee share cap, and fully vested pages keep a zero share.
+    pub fn apply_share_floor(
+        eligible_share_bps: u16,
+        locked_total: u64,
+        min_investor_share_bps: u16,
+        max_investor_fee_share_bps: u16,
+    ) -> u16 {
+        CoreMath::apply_share_floor(eligible_share_bps, locked_total, min_investor_share_bps, max_investor_fee_share_bps)
     }
 
     /// Calculate investor fee amount in quote tokens
@@ -37,73 +63,139 @@ impl DistributionMath {
         claimed_quote: u64,
         eligible_share_bps: u16,
     ) -> Result<u64> {
-        if eligible_share_bps == 0 {
-            return Ok(0);
-        }
-
-        let investor_fee = (claimed_quote as u128)
-            .checked_mul(eligible_share_bps as u128)
-            .ok_or(StarError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(StarError::MathOverflow)?;
-
-        Ok(investor_fee as u64)
+        math_result(CoreMath::calculate_investor_fee_quote(claimed_quote, eligible_share_bps))
     }
 
-    /// Apply daily cap to the distribution amount
+    /// Apply daily cap to the distribution amount; a `daily_cap` of 0 means uncapped. Saturates
+    /// once the day is past its cap (carry-over is paid on top of it).
     pub fn apply_daily_cap(
         requested_amount: u64,
         daily_cap: u64,
         already_distributed: u64,
     ) -> Result<u64> {
-        let remaining_cap = daily_cap
-            .checked_sub(already_distributed)
-            .unwrap_or(0);
+        math_result(CoreMath::apply_daily_cap(requested_amount, daily_cap, already_distributed))
+    }
+
+    /// Investor pool fixed by a day's first page: the investors' share of the claim, capped
+    /// by the room left under `daily_cap`, plus the carry-over the day opened with
+    pub fn calculate_day_pool(
+        claimed_quote: u64,
+        eligible_share_bps: u16,
+        daily_cap: u64,
+        already_distributed: u64,
+        carry_in: u64,
+    ) -> Result<DayPool> {
+        math_result(CoreMath::calculate_day_pool(
+            claimed_quote,
+            eligible_share_bps,
+            daily_cap,
+            already_distributed,
+            carry_in,
+        ))
+    }
 
-        Ok(requested_amount.min(remaining_cap))
+    /// Apply a payout curve to a (weighted) locked amount; `Sqrt` is in 16.16 fixed point
+    pub fn apply_payout_curve(locked: u64, curve: PayoutCurve) -> u64 {
+        CoreMath::apply_payout_curve(locked, curve.into())
     }
 
-    /// Calculate pro-rata weight for an investor
+    /// Sum of the curved amounts of a day's (or class's) investors, the denominator of
+    /// `calculate_investor_weight`
+    pub fn calculate_curved_total(locked: &[u64], curve: PayoutCurve) -> Result<u64> {
+        math_result(CoreMath::calculate_curved_total(locked, curve.into()))
+    }
+
+    /// Calculate pro-rata weight (basis points) for an investor under the payout curve
     pub fn calculate_investor_weight(
         investor_locked: u64,
-        total_locked: u64,
+        total_curved: u64,
+        curve: PayoutCurve,
     ) -> Result<u64> {
-        if total_locked == 0 {
-            return Ok(0);
-        }
-
-        // weight_i(t) = locked_i(t) / locked_total(t)
-        // Return as basis points (0-10000)
-        let weight = (investor_locked as u128)
-            .checked_mul(10000)
-            .ok_or(StarError::MathOverflow)?
-            .checked_div(total_locked as u128)
-            .ok_or(StarError::MathOverflow)?;
+        math_result(CoreMath::calculate_investor_weight(investor_locked, total_curved, curve.into()))
+    }
 
-        Ok(weight as u64)
+    /// Scale a locked amount by a lock-duration multiplier (basis points, 10000 = 1x)
+    pub fn calculate_weighted_locked(
+        locked_amount: u64,
+        multiplier_bps: u16,
+    ) -> Result<u64> {
+        math_result(CoreMath::calculate_weighted_locked(locked_amount, multiplier_bps))
     }
 
-    /// Calculate individual investor payout
+    /// Calculate individual investor payout; payouts below `min_payout_lamports` are 0
     pub fn calculate_investor_payout(
         total_investor_fee_quote: u64,
         investor_weight_bps: u64,
         min_payout_lamports: u64,
     ) -> Result<u64> {
-        let payout = (total_investor_fee_quote as u128)
-            .checked_mul(investor_weight_bps as u128)
-            .ok_or(StarError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(StarError::MathOverflow)?;
+        math_result(CoreMath::calculate_investor_payout(
+            total_investor_fee_quote,
+            investor_weight_bps,
+            min_payout_lamports,
+        ))
+    }
 
-        let payout_amount = payout as u64;
+    /// Pay a page of investors their day-wide weights of `day_pool` under the policy's curve
+    /// and dust policy. `pool_remaining` is what earlier pages left of the pool.
+    pub fn distribute_page(
+        day_pool: u64,
+        pool_remaining: u64,
+        weighted_locked: &[u64],
+        total_curved: u64,
+        policy: &Policy,
+    ) -> Result<(Vec<u64>, PagePayout)> {
+        let rules = PayoutRules {
+            curve: policy.payout_curve.into(),
+            min_payout_lamports: policy.min_payout_lamports,
+            dust_policy: policy.dust_policy.into(),
+        };
+        let mut payouts = vec![0u64; weighted_locked.len()];
+        let page = math_result(CoreMath::distribute_page(
+            day_pool,
+            pool_remaining,
+            weighted_locked,
+            total_curved,
+            rules,
+            &mut payouts,
+        ))?;
+        Ok((payouts, page))
+    }
 
-        // Apply minimum payout threshold
-        if payout_amount < min_payout_lamports {
-            Ok(0)
-        } else {
-            Ok(payout_amount)
-        }
+    /// Share of `amount` proportional to `part / total`, rounded down (0 when `total` is 0)
+    pub fn split_pro_rata(amount: u64, part: u64, total: u64) -> Result<u64> {
+        math_result(CoreMath::split_pro_rata(amount, part, total))
+    }
+
+    /// Carry-over after a page: the investor pool minus what was paid and what was withheld
+    /// for the creator. Paying out more than the pool fails with `MathUnderflow`.
+    pub fn calculate_page_carry_over(
+        total_to_distribute: u64,
+        distributed: u64,
+        withheld: u64,
+    ) -> Result<u64> {
+        math_result(CoreMath::calculate_page_carry_over(total_to_distribute, distributed, withheld))
     }
+
+    /// Creator remainder at day close, so `claimed + carry_in = distributed + remainder +
+    /// carry_out`. Payouts and carry-over beyond the inflow fail with `MathUnderflow`.
+    pub fn calculate_day_remainder(
+        claimed_today: u64,
+        carry_over_at_open: u64,
+        distributed_today: u64,
+        carry_over: u64,
+    ) -> Result<u64> {
+        math_result(CoreMath::calculate_day_remainder(
+            claimed_today,
+            carry_over_at_open,
+            distributed_today,
+            carry_over,
+        ))
+    }
+}
+
+/// Report a core math failure as the matching `StarError`
+fn math_result<T>(result: star_distribution_core::Result<T>) -> Result<T> {
+    result.map_err(|error| StarError::from(error).into())
 }
 
 /// Pagination utilities for processing investor accounts in batches
@@ -135,6 +227,57 @@ impl PaginationUtils {
     }
 }
 
+/// Merkle utilities for the per-day investor set commitment
+/// Leaves are `sha256(0x00 || stream || investor_quote_ata)`; inner nodes hash the
+/// sorted child pair as `sha256(0x01 || min || max)`, so proofs need no indices.
+/// Page hashes are `sha256(0x02 || (stream || investor_quote_ata || locked_amount_le)*)`.
+/// Payout IDs are `sha256(0x03 || vault || day_le || stream)`.
+pub struct MerkleUtils;
+
+impl MerkleUtils {
+    const LEAF_PREFIX: &'static [u8] = &[0];
+    const NODE_PREFIX: &'static [u8] = &[1];
+    const PAGE_PREFIX: &'static [u8] = &[2];
+    const PAYOUT_PREFIX: &'static [u8] = &[3];
+
+    /// Compute the leaf for an investor entry
+    pub fn investor_leaf(stream_pubkey: &Pubkey, investor_quote_ata: &Pubkey) -> [u8; 32] {
+        hashv(&[Self::LEAF_PREFIX, stream_pubkey.as_ref(), investor_quote_ata.as_ref()]).to_bytes()
+    }
+
+    /// Hash two sibling nodes in sorted order
+    pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[Self::NODE_PREFIX, left.as_ref(), right.as_ref()]).to_bytes()
+    }
+
+    /// Verify that `leaf` belongs to the tree with the given `root`
+    pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+        let computed = proof
+            .iter()
+            .fold(leaf, |node, sibling| Self::hash_pair(&node, sibling));
+        computed == *root
+    }
+
+    /// Hash a page's (stream, investor quote ATA, locked amount) tuples in page order
+    pub fn page_hash(entries: &[(Pubkey, Pubkey, u64)]) -> [u8; 32] {
+        let mut data = Vec::with_capacity(Self::PAGE_PREFIX.len() + entries.len() * 72);
+        data.extend_from_slice(Self::PAGE_PREFIX);
+        for (stream_pubkey, investor_quote_ata, locked_amount) in entries {
+            data.extend_from_slice(stream_pubkey.as_ref());
+            data.extend_from_slice(investor_quote_ata.as_ref());
+            data.extend_from_slice(&locked_amount.to_le_bytes());
+        }
+        hashv(&[data.as_slice()]).to_bytes()
+    }
+
+    /// Identifier of a stream's payout for a day. A stream is paid at most once per day,
+    /// so the ID is the same however the day is paged, retried or re-indexed.
+    pub fn payout_id(vault: &Pubkey, day: i64, stream_pubkey: &Pubkey) -> [u8; 32] {
+        hashv(&[Self::PAYOUT_PREFIX, vault.as_ref(), &day.to_le_bytes(), stream_pubkey.as_ref()]).to_bytes()
+    }
+}
+
 /// Validation utilities for pool configuration and fee detection
 pub struct ValidationUtils;
 
@@ -151,9 +294,191 @@ impl ValidationUtils {
             StarError::InvalidPoolTokenOrder
         );
 
-        // Additional validation can be added here based on CP-AMM specific requirements
-        // For example, checking tick ranges, liquidity concentration, etc.
-        
+        Ok(())
+    }
+
+    /// Validate that a vesting account locks the pool's base token (token A)
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn validate_stream_mint(vesting_account: &AccountInfo, pool: &CpAmmPool) -> Result<()> {
+        require_keys_eq!(
+            VestingUtils::get_mint(vesting_account)?,
+            pool.token_a_mint,
+            StarError::StreamMintMismatch
+        );
+        Ok(())
+    }
+
+    /// Mock pools record no base token, so any mock stream is accepted
+    #[cfg(feature = "mock-integrations")]
+    pub fn validate_stream_mint(vesting_account: &AccountInfo, _pool: &CpAmmPool) -> Result<()> {
+        VestingUtils::get_mint(vesting_account).map(|_| ())
+    }
+
+    /// Re-validate the live pool state before a crank page: the quote mint must still be
+    /// token B, and the pool must collect fees only in token B unless the position was
+    /// opened over a quote-only tick range
+    pub fn validate_pool_state(pool: &CpAmmPool, policy: &Policy) -> Result<()> {
+        require_keys_eq!(pool.token_b_mint, policy.quote_mint, StarError::InvalidPoolTokenOrder);
+
+        let has_quote_only_range = policy.tick_lower != policy.tick_upper;
+        require!(
+            pool.collect_fee_mode == CollectFeeMode::OnlyB
+                || (pool.collect_fee_mode == CollectFeeMode::BothToken && has_quote_only_range),
+            StarError::InvalidQuoteOnlyConfig
+        );
+        Ok(())
+    }
+
+    /// Validate that the position's tick range lies entirely above the pool's current
+    /// price. With the quote mint as token B, the position then holds only the base token
+    /// and swaps that enter its range pay their fees in quote.
+    pub fn validate_quote_only_range(pool_config: &PoolConfig, current_sqrt_price: u128) -> Result<()> {
+        require!(
+            pool_config.tick_lower < pool_config.tick_upper,
+            StarError::InvalidTickRange
+        );
+
+        let lower_sqrt_price = CpAmmUtils::sqrt_price_at_tick(pool_config.tick_lower)?;
+        CpAmmUtils::sqrt_price_at_tick(pool_config.tick_upper)?;
+
+        require!(
+            lower_sqrt_price > current_sqrt_price,
+            StarError::InvalidQuoteOnlyConfig
+        );
+        Ok(())
+    }
+
+    /// Validate that the honorary position can only ever earn the pool's quote fees: it
+    /// holds no liquidity, or its liquidity sits in a configured tick range entirely above
+    /// the pool's current price (see `validate_quote_only_range`)
+    pub fn validate_fee_only_position(
+        position: &AccountInfo,
+        pool_config: &PoolConfig,
+        current_sqrt_price: u128,
+    ) -> Result<()> {
+        if CpAmmUtils::position_liquidity(position)? == 0 {
+            return Ok(());
+        }
+
+        require!(
+            pool_config.tick_lower < pool_config.tick_upper,
+            StarError::InvalidQuoteOnlyConfig
+        );
+        Self::validate_quote_only_range(pool_config, current_sqrt_price)
+    }
+
+    /// Validate that stream pubkeys strictly increase within the page and after the
+    /// last stream processed today, so an investor can never be paid twice in a day and
+    /// every keeper must submit the set in the same order. A repeat of the previous key
+    /// fails with `DuplicateInvestor`, any other decrease with `InvestorsOutOfOrder`.
+    /// Returns the last stream pubkey of the page.
+    pub fn validate_investor_order(
+        investor_accounts: &[InvestorAccount],
+        last_stream_pubkey: &Pubkey,
+    ) -> Result<Pubkey> {
+        let mut previous = *last_stream_pubkey;
+        for investor in investor_accounts {
+            require!(investor.stream_pubkey != previous, StarError::DuplicateInvestor);
+            require!(investor.stream_pubkey > previous, StarError::InvestorsOutOfOrder);
+            previous = investor.stream_pubkey;
+        }
+        Ok(previous)
+    }
+
+    /// Check that `cranker` may run the crank under the vault's allowlist.
+    /// Allowed when no allowlist is enabled, when the key is listed, when it holds a
+    /// token of the vault's `cranker_pass_mint`, or when it is the attested enclave signer
+    /// of the allowlisted Switchboard function.
+    pub fn validate_cranker(
+        allowlist: Option<&CrankerAllowlist>,
+        cranker: &Pubkey,
+        cranker_pass_mint: &Pubkey,
+        cranker_pass: Option<&TokenAccount>,
+        switchboard_function: Option<&AccountInfo>,
+    ) -> Result<()> {
+        let allowlist = match allowlist {
+            Some(allowlist) if allowlist.enabled => allowlist,
+            _ => return Ok(()),
+        };
+
+        if allowlist.crankers.contains(cranker) {
+            return Ok(());
+        }
+
+        // The pass is a plain token, so cranking rights move with it
+        if let Some(pass) = cranker_pass {
+            if *cranker_pass_mint != Pubkey::default()
+                && pass.mint == *cranker_pass_mint
+                && pass.owner == *cranker
+                && pass.amount > 0
+            {
+                return Ok(());
+            }
+        }
+
+        if let Some(function_account) = switchboard_function {
+            require_keys_eq!(
+                function_account.key(),
+                allowlist.switchboard_function,
+                StarError::InvalidSwitchboardFunction
+            );
+            if SwitchboardUtils::is_enclave_signer(function_account, cranker)? {
+                return Ok(());
+            }
+        }
+
+        err!(StarError::CrankerNotAllowed)
+    }
+
+    /// Check that only the position owner PDA can move or close the treasury: no
+    /// delegate, and a close authority (if any) equal to the position owner
+    pub fn validate_treasury_account(treasury: &TokenAccount, position_owner: &Pubkey) -> Result<()> {
+        require_keys_eq!(treasury.owner, *position_owner, StarError::InvalidTreasuryAta);
+        require!(treasury.delegate.is_none(), StarError::TreasuryDelegateSet);
+        let close_authority = Option::<Pubkey>::from(treasury.close_authority);
+        require!(
+            close_authority.is_none() || close_authority == Some(*position_owner),
+            StarError::InvalidTreasuryCloseAuthority
+        );
+        Ok(())
+    }
+
+    /// Reject quote mints whose freeze authority could freeze investor ATAs mid-distribution,
+    /// when the policy opts into it
+    pub fn validate_quote_mint_freeze_authority(quote_mint: &Mint, policy: &Policy) -> Result<()> {
+        if policy.require_no_freeze_authority {
+            require!(quote_mint.freeze_authority.is_none(), StarError::QuoteMintFreezeAuthoritySet);
+        }
+        Ok(())
+    }
+
+    /// Reject Token-2022 quote mints whose extensions let a third party move treasury
+    /// funds (permanent delegate) or run code on every transfer (transfer hook), unless
+    /// the hook program is on the global allowlist. Classic SPL Token mints carry no
+    /// extensions.
+    pub fn validate_quote_mint_extensions(
+        quote_mint: &AccountInfo,
+        global_config: Option<&GlobalConfig>,
+    ) -> Result<()> {
+        if *quote_mint.owner != spl_token_2022::ID {
+            return Ok(());
+        }
+
+        let data = quote_mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        let permanent_delegate = mint
+            .get_extension::<PermanentDelegate>()
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.delegate));
+        require!(permanent_delegate.is_none(), StarError::UnsupportedMintExtension);
+        drop(data);
+
+        if let Some(hook_program) = TokenTransferUtils::transfer_hook_program(quote_mint)? {
+            require!(
+                global_config.is_some_and(|config| config.is_transfer_hook_allowed(&hook_program)),
+                StarError::TransferHookNotAllowed
+            );
+        }
         Ok(())
     }
 
@@ -174,6 +499,41 @@ pub struct PoolConfig {
     pub tick_upper: i32,
 }
 
+/// How a CP-AMM pool charges its trading fees (`Pool.collect_fee_mode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectFeeMode {
+    /// Fees are taken in whichever token is swapped in
+    BothToken,
+    /// Fees are always taken in token B
+    OnlyB,
+    /// A mode this program does not know
+    Unknown(u8),
+}
+
+impl From<u8> for CollectFeeMode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => CollectFeeMode::BothToken,
+            1 => CollectFeeMode::OnlyB,
+            other => CollectFeeMode::Unknown(other),
+        }
+    }
+}
+
+/// Fields of a CP-AMM `Pool` account read by the distributor
+#[derive(Debug, Clone)]
+pub struct CpAmmPool {
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    /// Lower bound of the pool's price range, as a Q64.64 sqrt price
+    pub sqrt_min_price: u128,
+    /// Upper bound of the pool's price range, as a Q64.64 sqrt price
+    pub sqrt_max_price: u128,
+    /// Current sqrt price in Q64.64
+    pub sqrt_price: u128,
+    pub collect_fee_mode: CollectFeeMode,
+}
+
 /// Claim result structure for fee validation
 #[derive(Debug, Clone)]
 pub struct ClaimResult {
@@ -181,70 +541,989 @@ pub struct ClaimResult {
     pub quote_amount: u64,
 }
 
+/// Quote mint and extra accounts for a Token-2022 quote transfer: `transfer_checked` needs
+/// the mint and its decimals, and a transfer hook the accounts it reads on every transfer
+#[derive(Clone)]
+pub struct CheckedTransferAccounts<'a, 'info> {
+    pub mint: AccountInfo<'info>,
+    pub decimals: u8,
+    /// Hook program, its extra account metas PDA and the accounts those metas list; empty
+    /// for mints without a transfer hook
+    pub extra_accounts: &'a [AccountInfo<'info>],
+}
+
 /// Token transfer utilities
 pub struct TokenTransferUtils;
 
 impl TokenTransferUtils {
     /// Transfer tokens from source to destination
     pub fn transfer_tokens<'info>(
-        source: Account<'info, TokenAccount>,
-        destination: Account<'info, TokenAccount>,
+        source: Account<'info, token::TokenAccount>,
+        destination: Account<'info, token::TokenAccount>,
         amount: u64,
         authority: &Signer<'info>,
         token_program: Program<'info, Token>,
     ) -> Result<()> {
         let cpi_accounts = Transfer {
-            from: source,
-            to: destination,
+            from: source.to_account_info(),
+            to: destination.to_account_info(),
             authority: authority.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(token_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
         token::transfer(cpi_ctx, amount)
     }
 
     /// Transfer tokens using PDA as authority
     pub fn transfer_tokens_with_pda<'info>(
-        source: Account<'info, TokenAccount>,
-        destination: Account<'info, TokenAccount>,
+        source: Account<'info, token::TokenAccount>,
+        destination: Account<'info, token::TokenAccount>,
         amount: u64,
         authority_pda: &AccountInfo<'info>,
         seeds: &[&[u8]],
         token_program: Program<'info, Token>,
     ) -> Result<()> {
         let cpi_accounts = Transfer {
-            from: source,
-            to: destination,
+            from: source.to_account_info(),
+            to: destination.to_account_info(),
             authority: authority_pda.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, &[seeds]);
+        let signer = &[seeds];
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
         token::transfer(cpi_ctx, amount)
     }
+
+    /// Transfer `amount` out of a token account owned by a PDA (signing with
+    /// `signer_seeds`). Token-2022 quote mints go through `transfer_checked`, with any
+    /// transfer hook's extra accounts appended; Token-2022 rejects a plain transfer of a
+    /// mint with a transfer fee.
+    pub fn transfer_signed<'info>(
+        token_program: &AccountInfo<'info>,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        signer_seeds: &[&[u8]],
+        amount: u64,
+        checked: Option<&CheckedTransferAccounts<'_, 'info>>,
+    ) -> Result<()> {
+        let signer = &[signer_seeds];
+        match checked {
+            Some(checked) => {
+                let cpi_accounts = TransferChecked {
+                    from: from.clone(),
+                    mint: checked.mint.clone(),
+                    to: to.clone(),
+                    authority: authority.clone(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer)
+                    .with_remaining_accounts(checked.extra_accounts.to_vec());
+                token_interface::transfer_checked(cpi_ctx, amount, checked.decimals)
+            }
+            None => {
+                let cpi_accounts = Transfer {
+                    from: from.clone(),
+                    to: to.clone(),
+                    authority: authority.clone(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer);
+                token::transfer(cpi_ctx, amount)
+            }
+        }
+    }
+
+    /// Transfer hook program set on a Token-2022 quote mint, if any
+    pub fn transfer_hook_program(quote_mint: &AccountInfo) -> Result<Option<Pubkey>> {
+        if *quote_mint.owner != spl_token_2022::ID {
+            return Ok(None);
+        }
+
+        let data = quote_mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        Ok(mint
+            .get_extension::<TransferHook>()
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.program_id)))
+    }
+
+    /// Size of a token account for `mint` under its owning token program: Token-2022 mints
+    /// may require account extensions (e.g. the withheld transfer fee amount)
+    pub fn token_account_len(mint: &AccountInfo) -> Result<usize> {
+        if *mint.owner != spl_token_2022::ID {
+            return Ok(spl_token_2022::state::Account::LEN);
+        }
+
+        let data = mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        let extensions = ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+        Ok(ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&extensions)?)
+    }
+
+    /// Fee withheld from a transfer of `amount` by the quote mint's Token-2022 transfer-fee
+    /// extension at the current epoch; zero for mints without the extension
+    pub fn transfer_fee(quote_mint: &AccountInfo, amount: u64) -> Result<u64> {
+        if *quote_mint.owner != spl_token_2022::ID {
+            return Ok(0);
+        }
+
+        let data = quote_mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        match mint.get_extension::<TransferFeeConfig>() {
+            Ok(config) => config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or_else(|| StarError::MathOverflow.into()),
+            Err(_) => Ok(0),
+        }
+    }
+}
+
+/// CP-AMM (DAMM v2) integration utilities
+pub struct CpAmmUtils;
+
+impl CpAmmUtils {
+    /// Create the honorary position in `pool` over `[tick_lower, tick_upper]`, with its
+    /// position NFT held by `position_owner` (signing via `position_owner_seeds`)
+    #[cfg(not(feature = "mock-integrations"))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_position<'info>(
+        _cp_amm_program: &AccountInfo<'info>,
+        _pool: &AccountInfo<'info>,
+        _position: &AccountInfo<'info>,
+        _position_owner: &AccountInfo<'info>,
+        _position_owner_seeds: &[&[u8]],
+        _payer: &AccountInfo<'info>,
+        _tick_lower: i32,
+        _tick_upper: i32,
+    ) -> Result<()> {
+        // Call CP-AMM `create_position` for the pool and tick range, minting the
+        // position NFT to the position owner PDA
+
+        Ok(())
+    }
+
+    /// Open the honorary position in the mock CP-AMM pool, which has no tick ranges
+    #[cfg(feature = "mock-integrations")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_position<'info>(
+        cp_amm_program: &AccountInfo<'info>,
+        pool: &AccountInfo<'info>,
+        _position: &AccountInfo<'info>,
+        position_owner: &AccountInfo<'info>,
+        position_owner_seeds: &[&[u8]],
+        _payer: &AccountInfo<'info>,
+        _tick_lower: i32,
+        _tick_upper: i32,
+    ) -> Result<()> {
+        mock_cp_amm::cpi::create_position(CpiContext::new_with_signer(
+            cp_amm_program.clone(),
+            mock_cp_amm::cpi::accounts::CreatePosition {
+                pool: pool.clone(),
+                position_owner: position_owner.clone(),
+            },
+            &[position_owner_seeds],
+        ))
+    }
+
+    /// Close the honorary position in `pool`, signed by `position_owner` (via
+    /// `position_owner_seeds`); its fees must have been claimed first. The CP-AMM
+    /// `close_position` CPI is not wired up, so this always fails outside the mock build
+    /// (see `POSITION_MIGRATION_SUPPORTED`).
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn close_position<'info>(
+        _cp_amm_program: &AccountInfo<'info>,
+        _pool: &AccountInfo<'info>,
+        _position: &AccountInfo<'info>,
+        _position_owner: &AccountInfo<'info>,
+        _position_owner_seeds: &[&[u8]],
+        _rent_receiver: &AccountInfo<'info>,
+    ) -> Result<()> {
+        err!(StarError::PositionMigrationUnsupported)
+    }
+
+    /// Close the honorary position in the mock CP-AMM pool
+    #[cfg(feature = "mock-integrations")]
+    pub fn close_position<'info>(
+        cp_amm_program: &AccountInfo<'info>,
+        pool: &AccountInfo<'info>,
+        _position: &AccountInfo<'info>,
+        position_owner: &AccountInfo<'info>,
+        position_owner_seeds: &[&[u8]],
+        _rent_receiver: &AccountInfo<'info>,
+    ) -> Result<()> {
+        mock_cp_amm::cpi::close_position(CpiContext::new_with_signer(
+            cp_amm_program.clone(),
+            mock_cp_amm::cpi::accounts::ClosePosition {
+                pool: pool.clone(),
+                position_owner: position_owner.clone(),
+            },
+            &[position_owner_seeds],
+        ))
+    }
+
+    /// Lowest tick a position range may start at
+    pub const MIN_TICK: i32 = -443_636;
+    /// Highest tick a position range may end at
+    pub const MAX_TICK: i32 = 443_636;
+    /// `sqrt(1.0001)` in Q64.64, the sqrt price ratio between adjacent ticks
+    pub const SQRT_TICK_BASE_X64: u128 = 18_447_666_387_855_959_851;
+    /// Byte offset of `Pool.token_a_mint`
+    pub const POOL_TOKEN_A_MINT_OFFSET: usize = 168;
+    /// Byte offset of `Pool.token_b_mint`
+    pub const POOL_TOKEN_B_MINT_OFFSET: usize = 200;
+    /// Byte offset of `Pool.sqrt_min_price` (Q64.64)
+    pub const POOL_SQRT_MIN_PRICE_OFFSET: usize = 424;
+    /// Byte offset of `Pool.sqrt_max_price` (Q64.64)
+    pub const POOL_SQRT_MAX_PRICE_OFFSET: usize = 440;
+    /// Byte offset of `Pool.sqrt_price` (Q64.64)
+    pub const POOL_SQRT_PRICE_OFFSET: usize = 456;
+    /// Byte offset of `Pool.collect_fee_mode`
+    pub const POOL_COLLECT_FEE_MODE_OFFSET: usize = 484;
+    /// Minimum `Pool` account size covering every field we read
+    pub const POOL_MIN_SIZE: usize = 488;
+
+    /// Sqrt price at `tick`, `sqrt(1.0001^tick)` in Q64.64
+    pub fn sqrt_price_at_tick(tick: i32) -> Result<u128> {
+        require!(
+            (Self::MIN_TICK..=Self::MAX_TICK).contains(&tick),
+            StarError::InvalidTickRange
+        );
+
+        // Square-and-multiply over |tick|, then invert for negative ticks
+        let mut sqrt_price = 1u128 << 64;
+        let mut base = Self::SQRT_TICK_BASE_X64;
+        let mut exponent = tick.unsigned_abs();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                sqrt_price = mul_x64(sqrt_price, base).ok_or(StarError::MathOverflow)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = mul_x64(base, base).ok_or(StarError::MathOverflow)?;
+            }
+        }
+
+        if tick < 0 {
+            sqrt_price = u128::MAX / sqrt_price;
+        }
+
+        Ok(sqrt_price)
+    }
+
+    /// Greatest tick whose sqrt price does not exceed `sqrt_price` (Q64.64), clamped to
+    /// `[MIN_TICK, MAX_TICK]`
+    pub fn tick_at_sqrt_price(sqrt_price: u128) -> Result<i32> {
+        let (mut low, mut high) = (Self::MIN_TICK, Self::MAX_TICK);
+        if sqrt_price < Self::sqrt_price_at_tick(low)? {
+            return Ok(low);
+        }
+
+        // Binary search: the sqrt price at `low` never exceeds `sqrt_price`
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if Self::sqrt_price_at_tick(mid)? <= sqrt_price {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        Ok(low)
+    }
+
+    /// Tick range of a position in `pool`: DAMM v2 positions provide liquidity over their
+    /// pool's whole price range. Both bounds round down, so the range never starts above
+    /// the pool's actual lower bound.
+    pub fn position_tick_range(pool: &CpAmmPool) -> Result<(i32, i32)> {
+        Ok((
+            Self::tick_at_sqrt_price(pool.sqrt_min_price)?,
+            Self::tick_at_sqrt_price(pool.sqrt_max_price)?,
+        ))
+    }
+
+    /// Deserialize the fields of a CP-AMM pool used by the distributor
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn load_pool(pool: &AccountInfo) -> Result<CpAmmPool> {
+        require_keys_eq!(
+            *pool.owner,
+            crate::state::CP_AMM_PROGRAM_ID,
+            StarError::InvalidCpAmmConfig
+        );
+        require!(pool.data_len() >= Self::POOL_MIN_SIZE, StarError::InvalidCpAmmConfig);
+
+        let data = pool.try_borrow_data()?;
+        Ok(CpAmmPool {
+            token_a_mint: read_pubkey(&data, Self::POOL_TOKEN_A_MINT_OFFSET),
+            token_b_mint: read_pubkey(&data, Self::POOL_TOKEN_B_MINT_OFFSET),
+            sqrt_min_price: read_u128(&data, Self::POOL_SQRT_MIN_PRICE_OFFSET),
+            sqrt_max_price: read_u128(&data, Self::POOL_SQRT_MAX_PRICE_OFFSET),
+            sqrt_price: read_u128(&data, Self::POOL_SQRT_PRICE_OFFSET),
+            collect_fee_mode: data[Self::POOL_COLLECT_FEE_MODE_OFFSET].into(),
+        })
+    }
+
+    /// Fields of a mock CP-AMM pool: a quote token account that always trades at 1
+    /// (tick 0) and only ever collects quote fees
+    #[cfg(feature = "mock-integrations")]
+    pub fn load_pool(pool: &AccountInfo) -> Result<CpAmmPool> {
+        let data = pool.try_borrow_data()?;
+        require!(data.len() >= 32, StarError::InvalidCpAmmConfig);
+
+        Ok(CpAmmPool {
+            token_a_mint: Pubkey::default(),
+            token_b_mint: read_pubkey(&data, 0),
+            sqrt_min_price: 1u128 << 64,
+            sqrt_max_price: 1u128 << 64,
+            sqrt_price: 1u128 << 64,
+            collect_fee_mode: CollectFeeMode::OnlyB,
+        })
+    }
+
+    /// Current sqrt price (Q64.64) of a CP-AMM pool
+    pub fn pool_sqrt_price(pool: &AccountInfo) -> Result<u128> {
+        Ok(Self::load_pool(pool)?.sqrt_price)
+    }
+
+    /// Byte offset of `Position.pool`
+    pub const POSITION_POOL_OFFSET: usize = 8;
+    /// Byte offset of `Position.nft_mint`
+    pub const POSITION_NFT_MINT_OFFSET: usize = 40;
+    /// Byte offset of `Position.unlocked_liquidity`
+    pub const UNLOCKED_LIQUIDITY_OFFSET: usize = 152;
+    /// Byte offset of `Position.vested_liquidity`
+    pub const VESTED_LIQUIDITY_OFFSET: usize = 168;
+    /// Byte offset of `Position.permanent_locked_liquidity`
+    pub const PERMANENT_LOCKED_LIQUIDITY_OFFSET: usize = 184;
+    /// Minimum `Position` account size covering every field we read
+    pub const POSITION_MIN_SIZE: usize = 200;
+
+    /// Total liquidity held by `position`: unlocked, vesting and permanently locked
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn position_liquidity(position: &AccountInfo) -> Result<u128> {
+        require_keys_eq!(
+            *position.owner,
+            crate::state::CP_AMM_PROGRAM_ID,
+            StarError::InvalidHonoraryPosition
+        );
+        require!(
+            position.data_len() >= Self::POSITION_MIN_SIZE,
+            StarError::InvalidHonoraryPosition
+        );
+
+        let data = position.try_borrow_data()?;
+        let liquidity = [
+            Self::UNLOCKED_LIQUIDITY_OFFSET,
+            Self::VESTED_LIQUIDITY_OFFSET,
+            Self::PERMANENT_LOCKED_LIQUIDITY_OFFSET,
+        ]
+        .iter()
+        .try_fold(0u128, |total, offset| total.checked_add(read_u128(&data, *offset)))
+        .ok_or(StarError::MathOverflow)?;
+
+        Ok(liquidity)
+    }
+
+    /// Liquidity of a mock CP-AMM position; the mock keeps no position state, so none
+    #[cfg(feature = "mock-integrations")]
+    pub fn position_liquidity(_position: &AccountInfo) -> Result<u128> {
+        Ok(0)
+    }
+
+    /// Mint of the NFT representing `position`
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn position_nft_mint(position: &AccountInfo) -> Result<Pubkey> {
+        require_keys_eq!(
+            *position.owner,
+            crate::state::CP_AMM_PROGRAM_ID,
+            StarError::InvalidHonoraryPosition
+        );
+        require!(
+            position.data_len() >= Self::POSITION_MIN_SIZE,
+            StarError::InvalidHonoraryPosition
+        );
+
+        let data = position.try_borrow_data()?;
+        Ok(read_pubkey(&data, Self::POSITION_NFT_MINT_OFFSET))
+    }
+
+    /// NFT mint of a mock CP-AMM position; the mock keeps no position state, so none
+    #[cfg(feature = "mock-integrations")]
+    pub fn position_nft_mint(_position: &AccountInfo) -> Result<Pubkey> {
+        Ok(Pubkey::default())
+    }
+
+    /// Verify that `position` belongs to `pool` and that its NFT sits in
+    /// `position_nft_account`, held by `position_owner`
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn verify_position_custody(
+        position: &AccountInfo,
+        pool: &Pubkey,
+        position_nft_account: &AccountInfo,
+        position_owner: &Pubkey,
+    ) -> Result<()> {
+        require_keys_eq!(
+            *position.owner,
+            crate::state::CP_AMM_PROGRAM_ID,
+            StarError::InvalidHonoraryPosition
+        );
+        require!(
+            position.data_len() >= Self::POSITION_MIN_SIZE,
+            StarError::InvalidHonoraryPosition
+        );
+
+        let data = position.try_borrow_data()?;
+        require_keys_eq!(
+            read_pubkey(&data, Self::POSITION_POOL_OFFSET),
+            *pool,
+            StarError::InvalidHonoraryPosition
+        );
+        let nft_mint = read_pubkey(&data, Self::POSITION_NFT_MINT_OFFSET);
+
+        // Whoever holds the position NFT controls the position
+        require!(
+            *position_nft_account.owner == spl_token_2022::ID || *position_nft_account.owner == token::ID,
+            StarError::InvalidPositionNft
+        );
+        let nft_data = position_nft_account.try_borrow_data()?;
+        let nft_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&nft_data)?.base;
+        require_keys_eq!(nft_account.mint, nft_mint, StarError::InvalidPositionNft);
+        require!(nft_account.amount == 1, StarError::InvalidPositionNft);
+        require_keys_eq!(nft_account.owner, *position_owner, StarError::InvalidPositionOwner);
+
+        Ok(())
+    }
+
+    /// Custody of a mock CP-AMM position; the mock keeps no position state or NFT, so
+    /// there is nothing to verify
+    #[cfg(feature = "mock-integrations")]
+    pub fn verify_position_custody(
+        _position: &AccountInfo,
+        _pool: &Pubkey,
+        _position_nft_account: &AccountInfo,
+        _position_owner: &Pubkey,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Claim the honorary position's accrued fees in `pool` into `treasury`, signed by
+    /// `position_owner` (via `position_owner_seeds`)
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn claim_position_fees<'info>(
+        _cp_amm_program: &AccountInfo<'info>,
+        _pool: &AccountInfo<'info>,
+        _position_owner: &AccountInfo<'info>,
+        _position_owner_seeds: &[&[u8]],
+        _treasury: &AccountInfo<'info>,
+        _quote_mint: Option<&AccountInfo<'info>>,
+        _token_program: &AccountInfo<'info>,
+    ) -> Result<ClaimResult> {
+        // Call CP-AMM `claim_position_fee` for the position, sending the quote fees
+        // to the treasury ATA; return the claimed amounts
+
+        Ok(ClaimResult {
+            base_amount: 0,
+            quote_amount: 1000000,
+        })
+    }
+
+    /// Sweep the fees accrued in the mock CP-AMM pool into `treasury`; a Token-2022 quote
+    /// mint is needed to move a transfer-fee mint
+    #[cfg(feature = "mock-integrations")]
+    pub fn claim_position_fees<'info>(
+        cp_amm_program: &AccountInfo<'info>,
+        pool: &AccountInfo<'info>,
+        position_owner: &AccountInfo<'info>,
+        position_owner_seeds: &[&[u8]],
+        treasury: &AccountInfo<'info>,
+        quote_mint: Option<&AccountInfo<'info>>,
+        token_program: &AccountInfo<'info>,
+    ) -> Result<ClaimResult> {
+        let claimed = mock_cp_amm::cpi::claim_position_fee(CpiContext::new_with_signer(
+            cp_amm_program.clone(),
+            mock_cp_amm::cpi::accounts::ClaimPositionFee {
+                pool: pool.clone(),
+                position_owner: position_owner.clone(),
+                destination: treasury.clone(),
+                quote_mint: quote_mint.cloned(),
+                token_program: token_program.clone(),
+            },
+            &[position_owner_seeds],
+        ))?
+        .get();
+
+        Ok(ClaimResult {
+            base_amount: claimed.base_amount,
+            quote_amount: claimed.quote_amount,
+        })
+    }
+}
+
+/// Switchboard Functions integration utilities
+pub struct SwitchboardUtils;
+
+impl SwitchboardUtils {
+    /// Anchor discriminator of `FunctionAccountData`, `sha256("account:FunctionAccountData")[..8]`
+    pub const FUNCTION_DISCRIMINATOR: [u8; 8] = [76, 139, 47, 44, 240, 182, 148, 200];
+    /// Byte offset of `FunctionAccountData.enclave.enclave_signer`
+    pub const FUNCTION_ENCLAVE_SIGNER_OFFSET: usize = 392;
+    /// Minimum `FunctionAccountData` account size covering every field we read
+    pub const FUNCTION_MIN_SIZE: usize = 424;
+
+    /// Get the enclave signer currently attested for a Switchboard function
+    pub fn get_enclave_signer(function_account: &AccountInfo) -> Result<Pubkey> {
+        require_keys_eq!(
+            *function_account.owner,
+            SWITCHBOARD_ATTESTATION_PROGRAM_ID,
+            StarError::InvalidSwitchboardFunction
+        );
+        require!(
+            function_account.data_len() >= Self::FUNCTION_MIN_SIZE,
+            StarError::InvalidSwitchboardFunction
+        );
+
+        let data = function_account.try_borrow_data()?;
+        require!(
+            data[..8] == Self::FUNCTION_DISCRIMINATOR,
+            StarError::InvalidSwitchboardFunction
+        );
+
+        // Only set once the function's enclave has passed attestation
+        let enclave_signer = read_pubkey(&data, Self::FUNCTION_ENCLAVE_SIGNER_OFFSET);
+        require_keys_neq!(enclave_signer, Pubkey::default(), StarError::EnclaveSignerUnset);
+        Ok(enclave_signer)
+    }
+
+    /// Whether `signer` is the attested enclave signer of `function_account`
+    pub fn is_enclave_signer(function_account: &AccountInfo, signer: &Pubkey) -> Result<bool> {
+        Ok(Self::get_enclave_signer(function_account)? == *signer)
+    }
+}
+
+/// Fields of a Streamflow `Contract` account read by the distributor
+#[derive(Debug, Clone)]
+pub struct StreamflowContract {
+    /// Cancellation timestamp (0 while the stream is live)
+    pub canceled_at: u64,
+    /// Wallet entitled to the stream's tokens
+    pub recipient: Pubkey,
+    /// Whether the stream has been closed
+    pub closed: bool,
+    /// Timestamp at which the stream is fully vested
+    pub end_time: u64,
+}
+
+/// Vesting terms of a Streamflow `Contract`, from which its locked amount is computed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamflowSchedule {
+    /// Stream start timestamp
+    pub start_time: u64,
+    /// Cliff timestamp (0 or `start_time` when the stream has no cliff)
+    pub cliff: u64,
+    /// Amount unlocked at the cliff
+    pub cliff_amount: u64,
+    /// Seconds between unlocks
+    pub period: u64,
+    /// Amount unlocked every period after the cliff
+    pub amount_per_period: u64,
+    /// Net amount deposited, including top-ups
+    pub net_amount_deposited: u64,
+    /// Amount the recipient has withdrawn so far
+    pub amount_withdrawn: u64,
+    /// Timestamp at which the stream is fully vested
+    pub end_time: u64,
+    /// Start of the ongoing pause (0 while the stream is not paused)
+    pub current_pause_start: u64,
+    /// Total seconds of completed pauses
+    pub pause_cumulative: u64,
+}
+
+impl StreamflowSchedule {
+    /// Amount vested at `now`, withdrawn or not. Unlocks begin at the cliff with
+    /// `cliff_amount`, then add `amount_per_period` per full period; paused time does not
+    /// count, and an ongoing pause freezes the schedule at its start. Everything is
+    /// vested from `end_time` on, which Streamflow pushes back when a pause ends.
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let now = if self.current_pause_start > 0 {
+            self.current_pause_start
+        } else {
+            now.max(0) as u64
+        };
+        let start = self.start_time.max(self.cliff);
+
+        if now < start {
+            return 0;
+        }
+        if now >= self.end_time {
+            return self.net_amount_deposited;
+        }
+
+        // Saturates on purpose: time spent paused does not vest, however long the pause
+        let elapsed = (now - start).saturating_sub(self.pause_cumulative);
+        let periods = elapsed.checked_div(self.period).unwrap_or(0);
+        self.cliff_amount
+            .saturating_add(periods.saturating_mul(self.amount_per_period))
+            .min(self.net_amount_deposited)
+    }
+
+    /// Amount still locked at `now`: deposits and top-ups, less the withdrawn amount and
+    /// the vested amount still available to withdraw. Withdrawals can only come out of
+    /// vested tokens, so this is what the recipient cannot yet claim.
+    pub fn locked_amount(&self, now: i64) -> u64 {
+        let vested = self.vested_amount(now);
+        // Saturates on purpose: a stream whose vested amount is all withdrawn has nothing
+        // left to withdraw, and one with more withdrawn than deposited has nothing locked
+        let available = vested.saturating_sub(self.amount_withdrawn);
+
+        self.net_amount_deposited
+            .saturating_sub(self.amount_withdrawn)
+            .saturating_sub(available)
+    }
 }
 
 /// Streamflow integration utilities
 pub struct StreamflowUtils;
 
 impl StreamflowUtils {
+    /// Byte offset of `Contract.amount_withdrawn`
+    pub const AMOUNT_WITHDRAWN_OFFSET: usize = 17;
+    /// Byte offset of `Contract.canceled_at`
+    pub const CANCELED_AT_OFFSET: usize = 25;
+    /// Byte offset of `Contract.end_time`
+    pub const END_TIME_OFFSET: usize = 33;
+    /// Byte offset of `Contract.sender`
+    pub const SENDER_OFFSET: usize = 49;
+    /// Byte offset of `Contract.recipient`
+    pub const RECIPIENT_OFFSET: usize = 113;
+    /// Byte offset of `Contract.mint`
+    pub const MINT_OFFSET: usize = 177;
+    /// Byte offset of `Contract.ix.start_time`
+    pub const START_TIME_OFFSET: usize = 409;
+    /// Byte offset of `Contract.ix.net_amount_deposited`
+    pub const NET_AMOUNT_DEPOSITED_OFFSET: usize = 417;
+    /// Byte offset of `Contract.ix.period`
+    pub const PERIOD_OFFSET: usize = 425;
+    /// Byte offset of `Contract.ix.amount_per_period`
+    pub const AMOUNT_PER_PERIOD_OFFSET: usize = 433;
+    /// Byte offset of `Contract.ix.cliff`
+    pub const CLIFF_OFFSET: usize = 441;
+    /// Byte offset of `Contract.ix.cliff_amount`
+    pub const CLIFF_AMOUNT_OFFSET: usize = 449;
+    /// Byte offset of `Contract.closed`
+    pub const CLOSED_OFFSET: usize = 667;
+    /// Byte offset of `Contract.current_pause_start`
+    pub const CURRENT_PAUSE_START_OFFSET: usize = 668;
+    /// Byte offset of `Contract.pause_cumulative`
+    pub const PAUSE_CUMULATIVE_OFFSET: usize = 676;
+    /// Minimum `Contract` account size covering every field we read
+    pub const CONTRACT_MIN_SIZE: usize = 700;
+
     /// Validate a Streamflow stream account
     pub fn validate_stream_account(stream_account: &AccountInfo) -> Result<()> {
-        // Basic validation - in a real implementation, this would:
-        // 1. Deserialize the stream account
-        // 2. Validate the stream is active
-        // 3. Check the stream hasn't been cancelled
-        // 4. Verify the token mint matches expected quote mint
-        
-        require!(stream_account.data_is_empty() == false, StarError::InvalidStreamAccount);
+        require_keys_eq!(
+            *stream_account.owner,
+            STREAMFLOW_PROGRAM_ID,
+            StarError::InvalidStreamAccount
+        );
+        require!(
+            stream_account.data_len() >= Self::CONTRACT_MIN_SIZE,
+            StarError::InvalidStreamAccount
+        );
         Ok(())
     }
 
+    /// Deserialize the fields of a Streamflow contract used by the distributor
+    pub fn load_contract(stream_account: &AccountInfo) -> Result<StreamflowContract> {
+        Self::validate_stream_account(stream_account)?;
+
+        let data = stream_account.try_borrow_data()?;
+        Ok(StreamflowContract {
+            canceled_at: read_u64(&data, Self::CANCELED_AT_OFFSET),
+            recipient: read_pubkey(&data, Self::RECIPIENT_OFFSET),
+            closed: data[Self::CLOSED_OFFSET] != 0,
+            end_time: read_u64(&data, Self::END_TIME_OFFSET),
+        })
+    }
+
+    /// Read the vesting terms of a Streamflow contract's data; `data` must hold at least
+    /// `CONTRACT_MIN_SIZE` bytes
+    pub fn read_schedule(data: &[u8]) -> Result<StreamflowSchedule> {
+        require!(data.len() >= Self::CONTRACT_MIN_SIZE, StarError::InvalidStreamAccount);
+
+        Ok(StreamflowSchedule {
+            start_time: read_u64(data, Self::START_TIME_OFFSET),
+            cliff: read_u64(data, Self::CLIFF_OFFSET),
+            cliff_amount: read_u64(data, Self::CLIFF_AMOUNT_OFFSET),
+            period: read_u64(data, Self::PERIOD_OFFSET),
+            amount_per_period: read_u64(data, Self::AMOUNT_PER_PERIOD_OFFSET),
+            net_amount_deposited: read_u64(data, Self::NET_AMOUNT_DEPOSITED_OFFSET),
+            amount_withdrawn: read_u64(data, Self::AMOUNT_WITHDRAWN_OFFSET),
+            end_time: read_u64(data, Self::END_TIME_OFFSET),
+            current_pause_start: read_u64(data, Self::CURRENT_PAUSE_START_OFFSET),
+            pause_cumulative: read_u64(data, Self::PAUSE_CUMULATIVE_OFFSET),
+        })
+    }
+
+    /// Detect a cancelled or closed stream that must be skipped
+    pub fn get_skip_reason(stream_account: &AccountInfo) -> Result<Option<SkipReason>> {
+        let contract = Self::load_contract(stream_account)?;
+
+        if contract.closed {
+            Ok(Some(SkipReason::StreamClosed))
+        } else if contract.canceled_at > 0 {
+            Ok(Some(SkipReason::StreamCancelled))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get the mint a Streamflow stream vests
+    pub fn get_mint(stream_account: &AccountInfo) -> Result<Pubkey> {
+        Self::validate_stream_account(stream_account)?;
+
+        let data = stream_account.try_borrow_data()?;
+        Ok(read_pubkey(&data, Self::MINT_OFFSET))
+    }
+
     /// Get the current locked amount from a Streamflow stream
-    pub fn get_locked_amount(_stream_account: &AccountInfo, _current_timestamp: i64) -> Result<u64> {
-        // Deserialize the Streamflow stream account
-        // Calculate locked amount based on vesting schedule
-        // Return the current locked amount at timestamp
-        
-        Ok(1000) // Locked amount
+    #[cfg(not(feature = "mock-integrations"))]
+    pub fn get_locked_amount(stream_account: &AccountInfo, current_timestamp: i64) -> Result<u64> {
+        Self::validate_stream_account(stream_account)?;
+
+        let data = stream_account.try_borrow_data()?;
+        Ok(Self::read_schedule(&data)?.locked_amount(current_timestamp))
+    }
+
+    /// Locked amount of a mock Streamflow stream, vesting linearly from `start_time`
+    /// to `end_time`
+    #[cfg(feature = "mock-integrations")]
+    pub fn get_locked_amount(stream_account: &AccountInfo, current_timestamp: i64) -> Result<u64> {
+        let data = stream_account.try_borrow_data()?;
+        let start_time = read_u64(&data, Self::START_TIME_OFFSET) as i64;
+        let end_time = read_u64(&data, Self::END_TIME_OFFSET) as i64;
+        let deposited = read_u64(&data, Self::NET_AMOUNT_DEPOSITED_OFFSET);
+
+        if current_timestamp <= start_time {
+            return Ok(deposited);
+        }
+        if current_timestamp >= end_time {
+            return Ok(0);
+        }
+
+        let vested = DistributionMath::split_pro_rata(
+            deposited,
+            (current_timestamp - start_time) as u64,
+            (end_time - start_time) as u64,
+        )?;
+        Ok(deposited - vested)
+    }
+}
+
+/// Read a little-endian u64 at `offset`
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// Multiply two Q64.64 numbers, `None` on overflow
+fn mul_x64(a: u128, b: u128) -> Option<u128> {
+    let (a_hi, a_lo) = (a >> 64, a & u64::MAX as u128);
+    let (b_hi, b_lo) = (b >> 64, b & u64::MAX as u128);
+
+    let hi = a_hi.checked_mul(b_hi)?;
+    if hi > u64::MAX as u128 {
+        return None;
+    }
+
+    (hi << 64)
+        .checked_add(a_hi.checked_mul(b_lo)?)?
+        .checked_add(a_lo.checked_mul(b_hi)?)?
+        .checked_add((a_lo * b_lo) >> 64)
+}
+
+/// Read a little-endian u128 at `offset`
+#[cfg(not(feature = "mock-integrations"))]
+fn read_u128(data: &[u8], offset: usize) -> u128 {
+    u128::from_le_bytes(data[offset..offset + 16].try_into().unwrap())
+}
+
+/// Read a pubkey at `offset`
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap())
+}
+
+/// Bonfida token-vesting integration utilities
+pub struct BonfidaVestingUtils;
+
+impl BonfidaVestingUtils {
+    /// `VestingScheduleHeader` size: destination (32) + mint (32) + is_initialized (1)
+    pub const HEADER_SIZE: usize = 32 + 32 + 1;
+    /// `VestingSchedule` size: release_time (8) + amount (8)
+    pub const SCHEDULE_SIZE: usize = 8 + 8;
+
+    /// Validate a Bonfida vesting account
+    pub fn validate_vesting_account(vesting_account: &AccountInfo) -> Result<()> {
+        require_keys_eq!(
+            *vesting_account.owner,
+            BONFIDA_VESTING_PROGRAM_ID,
+            StarError::InvalidStreamAccount
+        );
+
+        let data = vesting_account.try_borrow_data()?;
+        require!(data.len() >= Self::HEADER_SIZE, StarError::InvalidStreamAccount);
+        require!(data[Self::HEADER_SIZE - 1] == 1, StarError::InvalidStreamAccount);
+        Ok(())
+    }
+
+    /// Get the destination token account recorded in the vesting header
+    pub fn get_destination(vesting_account: &AccountInfo) -> Result<Pubkey> {
+        Self::validate_vesting_account(vesting_account)?;
+
+        let data = vesting_account.try_borrow_data()?;
+        Ok(read_pubkey(&data, 0))
+    }
+
+    /// Get the mint recorded in the vesting header
+    pub fn get_mint(vesting_account: &AccountInfo) -> Result<Pubkey> {
+        Self::validate_vesting_account(vesting_account)?;
+
+        let data = vesting_account.try_borrow_data()?;
+        Ok(read_pubkey(&data, 32))
+    }
+
+    /// Get the amount still locked in a Bonfida vesting account
+    /// Sums every schedule entry whose release time is still in the future
+    pub fn get_locked_amount(vesting_account: &AccountInfo, current_timestamp: i64) -> Result<u64> {
+        Self::validate_vesting_account(vesting_account)?;
+
+        let data = vesting_account.try_borrow_data()?;
+        let mut locked = 0u64;
+
+        for schedule in data[Self::HEADER_SIZE..].chunks_exact(Self::SCHEDULE_SIZE) {
+            let release_time = u64::from_le_bytes(schedule[0..8].try_into().unwrap());
+            let amount = u64::from_le_bytes(schedule[8..16].try_into().unwrap());
+
+            // Released schedules are zeroed by the vesting program on unlock,
+            // but we also skip any entry whose release time has passed
+            if release_time > current_timestamp.max(0) as u64 {
+                locked = locked.checked_add(amount).ok_or(StarError::MathOverflow)?;
+            }
+        }
+
+        Ok(locked)
+    }
+
+    /// Get the release time of the last schedule entry still locked (0 when fully released)
+    pub fn get_unlock_end(vesting_account: &AccountInfo, current_timestamp: i64) -> Result<i64> {
+        Self::validate_vesting_account(vesting_account)?;
+
+        let data = vesting_account.try_borrow_data()?;
+        let unlock_end = data[Self::HEADER_SIZE..]
+            .chunks_exact(Self::SCHEDULE_SIZE)
+            .map(|schedule| u64::from_le_bytes(schedule[0..8].try_into().unwrap()))
+            .filter(|release_time| *release_time > current_timestamp.max(0) as u64)
+            .max()
+            .unwrap_or(0);
+
+        Ok(unlock_end.min(i64::MAX as u64) as i64)
+    }
+}
+
+/// Beneficiary recorded by a vesting program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VestingRecipient {
+    /// Recipient wallet (Streamflow)
+    Wallet(Pubkey),
+    /// Destination token account whose owner is the recipient (Bonfida)
+    TokenAccount(Pubkey),
+}
+
+/// Locked amount read from a vesting account
+#[derive(Debug, Clone, Copy)]
+pub struct VestingLock {
+    /// Locked amount at the crank timestamp (zero when skipped)
+    pub locked_amount: u64,
+    /// Beneficiary payouts must be bound to (unset when skipped)
+    pub recipient: Option<VestingRecipient>,
+    /// Set when the investor must be skipped for this distribution
+    pub skip_reason: Option<SkipReason>,
+    /// Timestamp at which the lock fully vests (zero when skipped)
+    pub unlock_end_ts: i64,
+}
+
+impl VestingLock {
+    pub fn active(locked_amount: u64, recipient: VestingRecipient, unlock_end_ts: i64) -> Self {
+        Self { locked_amount, recipient: Some(recipient), skip_reason: None, unlock_end_ts }
+    }
+
+    pub fn skipped(reason: SkipReason) -> Self {
+        Self { locked_amount: 0, recipient: None, skip_reason: Some(reason), unlock_end_ts: 0 }
+    }
+
+    /// Seconds of vesting left at `current_timestamp`
+    pub fn remaining_secs(&self, current_timestamp: i64) -> i64 {
+        self.unlock_end_ts.saturating_sub(current_timestamp).max(0)
+    }
+
+    /// Locked amount scaled by the policy's lock-duration multiplier
+    pub fn weighted_locked(&self, policy: &Policy, current_timestamp: i64) -> Result<u64> {
+        let multiplier_bps = policy.duration_multiplier_bps(self.remaining_secs(current_timestamp));
+        DistributionMath::calculate_weighted_locked(self.locked_amount, multiplier_bps)
+    }
+}
+
+/// Vesting adapter that reads locked amounts from any supported vesting program
+pub struct VestingUtils;
+
+impl VestingUtils {
+    /// Read the current lock, dispatching on the vesting account's owner
+    /// Cancelled or closed streams resolve to a zero lock with a skip reason
+    pub fn read_lock(vesting_account: &AccountInfo, current_timestamp: i64) -> Result<VestingLock> {
+        // Closed vesting accounts are drained and handed back to the system program
+        if vesting_account.data_is_empty() && *vesting_account.owner == system_program::ID {
+            return Ok(VestingLock::skipped(SkipReason::StreamClosed));
+        }
+
+        if *vesting_account.owner == BONFIDA_VESTING_PROGRAM_ID {
+            let locked = BonfidaVestingUtils::get_locked_amount(vesting_account, current_timestamp)?;
+            let destination = BonfidaVestingUtils::get_destination(vesting_account)?;
+            let unlock_end = BonfidaVestingUtils::get_unlock_end(vesting_account, current_timestamp)?;
+            return Ok(VestingLock::active(
+                locked,
+                VestingRecipient::TokenAccount(destination),
+                unlock_end,
+            ));
+        }
+
+        if let Some(reason) = StreamflowUtils::get_skip_reason(vesting_account)? {
+            return Ok(VestingLock::skipped(reason));
+        }
+
+        let contract = StreamflowUtils::load_contract(vesting_account)?;
+        let locked = StreamflowUtils::get_locked_amount(vesting_account, current_timestamp)?;
+        let unlock_end = contract.end_time.min(i64::MAX as u64) as i64;
+        Ok(VestingLock::active(locked, VestingRecipient::Wallet(contract.recipient), unlock_end))
+    }
+
+    /// Mint locked by a vesting account, dispatching on its owner
+    pub fn get_mint(vesting_account: &AccountInfo) -> Result<Pubkey> {
+        if *vesting_account.owner == BONFIDA_VESTING_PROGRAM_ID {
+            BonfidaVestingUtils::get_mint(vesting_account)
+        } else {
+            StreamflowUtils::get_mint(vesting_account)
+        }
+    }
+
+    /// Resolve the wallet a live vesting account pays out to. Bonfida schedules record a
+    /// destination token account, which must be supplied to read its owner.
+    pub fn resolve_recipient(
+        lock: &VestingLock,
+        destination_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    ) -> Result<Pubkey> {
+        match lock.recipient.ok_or(StarError::InvalidStreamAccount)? {
+            VestingRecipient::Wallet(wallet) => Ok(wallet),
+            VestingRecipient::TokenAccount(destination) => {
+                let destination_account = destination_token_account.ok_or(StarError::InvalidStreamAccount)?;
+                require_keys_eq!(destination_account.key(), destination, StarError::InvalidStreamAccount);
+                Ok(destination_account.owner)
+            }
+        }
     }
 }
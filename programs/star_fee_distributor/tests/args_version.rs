@@ -0,0 +1,48 @@
+//! Versioned instruction arguments: the version byte leads the serialized arguments and
+//! unknown versions are rejected.
+
+use anchor_lang::AnchorSerialize;
+use star_fee_distributor::state::{CrankArgsV1, InitializeArgsV1, VaultInitParams};
+
+fn params() -> VaultInitParams {
+    VaultInitParams {
+        investor_fee_share_bps: 5_000,
+        daily_cap: 0,
+        min_payout_lamports: 1_000,
+        y0: 10_000_000_000,
+        fee_share_decay: None,
+        duration_multipliers: vec![],
+        epochs: vec![],
+        referral: None,
+    }
+}
+
+#[test]
+fn constructors_use_the_current_version() {
+    assert_eq!(CrankArgsV1::new(1, vec![]).version, 1);
+    assert_eq!(InitializeArgsV1::new(params()).version, 1);
+    assert!(CrankArgsV1::new(1, vec![]).validate_version().is_ok());
+    assert!(InitializeArgsV1::new(params()).validate_version().is_ok());
+}
+
+#[test]
+fn version_byte_leads_the_arguments() {
+    let crank = CrankArgsV1::new(3, vec![vec![[7u8; 32]]]).try_to_vec().unwrap();
+    assert_eq!(crank[0], CrankArgsV1::VERSION);
+    assert_eq!(crank[1..9], 3u64.to_le_bytes());
+
+    let initialize = InitializeArgsV1::new(params()).try_to_vec().unwrap();
+    assert_eq!(initialize[0], InitializeArgsV1::VERSION);
+    assert_eq!(initialize[1..], params().try_to_vec().unwrap()[..]);
+}
+
+#[test]
+fn unknown_versions_are_rejected() {
+    for version in [0, 2, u8::MAX] {
+        let crank = CrankArgsV1 { version, ..CrankArgsV1::new(1, vec![]) };
+        assert!(crank.validate_version().is_err(), "crank version {}", version);
+
+        let initialize = InitializeArgsV1 { version, ..InitializeArgsV1::new(params()) };
+        assert!(initialize.validate_version().is_err(), "initialize version {}", version);
+    }
+}
@@ -0,0 +1,254 @@
+//! Replays multi-page, multi-day distributions through the same carry-over accounting as
+//! `crank_distribute` and checks every claimed token is paid out, sent to the creator, or
+//! still carried over.
+
+use star_fee_distributor::state::{DustPolicy, PayoutCurve};
+use star_fee_distributor::utils::DistributionMath;
+
+struct Page {
+    claimed: u64,
+    locked: Vec<u64>,
+    /// Indices of blacklisted or opted-out investors whose share is withheld
+    withheld: Vec<usize>,
+}
+
+struct Policy {
+    investor_fee_share_bps: u16,
+    daily_cap: u64,
+    min_payout_lamports: u64,
+    dust_policy: DustPolicy,
+    y0: u64,
+}
+
+#[derive(Default)]
+struct Ledger {
+    carry_over: u64,
+    total_claimed: u64,
+    total_distributed: u64,
+    total_to_creator: u64,
+}
+
+impl Ledger {
+    /// Run one day page by page, then close it like `close_day`
+    fn run_day(&mut self, policy: &Policy, pages: &[Page]) {
+        let carry_over_at_open = self.carry_over;
+        let mut claimed_today = 0u64;
+        let mut distributed_today = 0u64;
+
+        for page in pages {
+            claimed_today += page.claimed;
+
+            let total_locked: u64 = page.locked.iter().sum();
+            let eligible_share_bps = DistributionMath::calculate_eligible_share_bps(
+                total_locked,
+                policy.y0,
+                policy.investor_fee_share_bps,
+            )
+            .unwrap();
+            let investor_fee =
+                DistributionMath::calculate_investor_fee_quote(page.claimed, eligible_share_bps)
+                    .unwrap();
+            let capped = DistributionMath::apply_daily_cap(
+                investor_fee,
+                policy.daily_cap,
+                distributed_today,
+            )
+            .unwrap();
+            let total_to_distribute = capped + self.carry_over;
+
+            let mut distributed = 0u64;
+            let mut withheld = 0u64;
+            for (i, locked) in page.locked.iter().enumerate() {
+                let weight_bps = DistributionMath::calculate_investor_weight(
+                    *locked,
+                    total_locked,
+                    PayoutCurve::Linear,
+                )
+                .unwrap();
+                if page.withheld.contains(&i) {
+                    withheld += DistributionMath::calculate_investor_payout(
+                        total_to_distribute,
+                        weight_bps,
+                        0,
+                    )
+                    .unwrap();
+                    continue;
+                }
+                let min_payout = match policy.dust_policy {
+                    DustPolicy::Pay => 0,
+                    DustPolicy::Accumulate | DustPolicy::Drop => policy.min_payout_lamports,
+                };
+                let payout = DistributionMath::calculate_investor_payout(
+                    total_to_distribute,
+                    weight_bps,
+                    min_payout,
+                )
+                .unwrap();
+                if payout == 0 && policy.dust_policy == DustPolicy::Drop {
+                    withheld += DistributionMath::calculate_investor_payout(
+                        total_to_distribute,
+                        weight_bps,
+                        0,
+                    )
+                    .unwrap();
+                }
+                distributed += payout;
+            }
+
+            self.carry_over = DistributionMath::calculate_page_carry_over(
+                total_to_distribute,
+                distributed,
+                withheld,
+            )
+            .unwrap();
+            distributed_today += distributed;
+        }
+
+        let remainder = DistributionMath::calculate_day_remainder(
+            claimed_today,
+            carry_over_at_open,
+            distributed_today,
+            self.carry_over,
+        )
+        .unwrap();
+
+        self.total_claimed += claimed_today;
+        self.total_distributed += distributed_today;
+        self.total_to_creator += remainder;
+    }
+
+    fn assert_conserved(&self) {
+        assert_eq!(
+            self.total_distributed + self.total_to_creator + self.carry_over,
+            self.total_claimed
+        );
+    }
+}
+
+fn policy(min_payout_lamports: u64, daily_cap: u64) -> Policy {
+    Policy {
+        investor_fee_share_bps: 5000,
+        daily_cap,
+        min_payout_lamports,
+        dust_policy: DustPolicy::Accumulate,
+        y0: 10_000_000_000,
+    }
+}
+
+fn page(claimed: u64, locked: &[u64]) -> Page {
+    Page {
+        claimed,
+        locked: locked.to_vec(),
+        withheld: Vec::new(),
+    }
+}
+
+#[test]
+fn dust_from_earlier_pages_reaches_later_pages() {
+    let policy = policy(1_000, 1_000_000_000);
+    let mut ledger = Ledger::default();
+
+    // The first page's payouts all fall below the threshold, so its pool is carried
+    ledger.run_day(
+        &policy,
+        &[
+            page(1_500, &[3_000_000_000, 2_000_000_000]),
+            page(1_000_000, &[5_000_000_000]),
+        ],
+    );
+
+    assert_eq!(ledger.total_distributed, 500_750);
+    assert_eq!(ledger.carry_over, 0);
+    ledger.assert_conserved();
+}
+
+#[test]
+fn carry_over_survives_day_close() {
+    let policy = policy(10_000, 1_000_000_000);
+    let mut ledger = Ledger::default();
+
+    ledger.run_day(&policy, &[page(12_000, &[4_000_000_000, 4_000_000_000])]);
+    assert_eq!(ledger.total_distributed, 0);
+    assert_eq!(ledger.carry_over, 6_000);
+    assert_eq!(ledger.total_to_creator, 6_000);
+    ledger.assert_conserved();
+
+    // The next day's pool includes the carried dust, pushing payouts over the threshold
+    ledger.run_day(&policy, &[page(30_000, &[4_000_000_000, 4_000_000_000])]);
+    assert_eq!(ledger.total_distributed, 21_000);
+    assert_eq!(ledger.carry_over, 0);
+    ledger.assert_conserved();
+}
+
+#[test]
+fn dropped_dust_goes_to_the_creator() {
+    let policy = Policy {
+        dust_policy: DustPolicy::Drop,
+        ..policy(10_000, 1_000_000_000)
+    };
+    let mut ledger = Ledger::default();
+
+    ledger.run_day(&policy, &[page(12_000, &[4_000_000_000, 4_000_000_000])]);
+    assert_eq!(ledger.total_distributed, 0);
+    assert_eq!(ledger.carry_over, 0);
+    assert_eq!(ledger.total_to_creator, 12_000);
+    ledger.assert_conserved();
+}
+
+#[test]
+fn paid_dust_ignores_the_threshold() {
+    let policy = Policy {
+        dust_policy: DustPolicy::Pay,
+        ..policy(10_000, 1_000_000_000)
+    };
+    let mut ledger = Ledger::default();
+
+    ledger.run_day(&policy, &[page(12_000, &[4_000_000_000, 4_000_000_000])]);
+    assert_eq!(ledger.total_distributed, 6_000);
+    assert_eq!(ledger.carry_over, 0);
+    ledger.assert_conserved();
+}
+
+#[test]
+fn totals_equal_claimed_across_mixed_days() {
+    let mut ledger = Ledger::default();
+    let capped = policy(1_000, 250_000);
+    let uncapped = policy(777, 1_000_000_000);
+
+    let mut withheld_page = page(333_333, &[1_000_000_000, 2_000_000_000, 3_000_000_001]);
+    withheld_page.withheld = vec![1];
+
+    let days: Vec<(&Policy, Vec<Page>)> = vec![
+        (
+            &uncapped,
+            vec![
+                page(777_777, &[1_000_000_000, 1_000_000_000, 1_000_000_001]),
+                page(1_999, &[7, 11, 13]),
+                withheld_page,
+            ],
+        ),
+        (
+            &capped,
+            vec![
+                page(1_000_000, &[6_000_000_000]),
+                page(1_000_000, &[2_500_000_000]),
+            ],
+        ),
+        (
+            &uncapped,
+            vec![page(0, &[1_000_000_000]), page(5, &[9_999_999_999])],
+        ),
+        (
+            &capped,
+            vec![
+                page(123_456_789, &[4_000_000_000, 1]),
+                page(987, &[1, 1, 1]),
+            ],
+        ),
+    ];
+
+    for (policy, pages) in &days {
+        ledger.run_day(policy, pages);
+        ledger.assert_conserved();
+    }
+}
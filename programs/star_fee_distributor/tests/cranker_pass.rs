@@ -0,0 +1,96 @@
+//! Cranker allowlist checks, including callers authorized by holding a cranker pass.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token::state::{Account, AccountState};
+use anchor_spl::token_interface::TokenAccount;
+use solana_program::program_pack::Pack;
+use star_fee_distributor::state::CrankerAllowlist;
+use star_fee_distributor::utils::ValidationUtils;
+
+fn enabled_allowlist(crankers: Vec<Pubkey>) -> CrankerAllowlist {
+    let mut allowlist = CrankerAllowlist::new(Pubkey::new_unique(), 255);
+    allowlist.enabled = true;
+    allowlist.crankers = crankers;
+    allowlist
+}
+
+fn pass_account(mint: Pubkey, owner: Pubkey, amount: u64) -> TokenAccount {
+    let mut data = vec![0u8; Account::LEN];
+    Account::pack(
+        Account {
+            mint,
+            owner,
+            amount,
+            state: AccountState::Initialized,
+            ..Account::default()
+        },
+        &mut data,
+    )
+    .unwrap();
+    TokenAccount::try_deserialize_unchecked(&mut data.as_slice()).unwrap()
+}
+
+fn allowed(
+    allowlist: Option<&CrankerAllowlist>,
+    caller: &Pubkey,
+    pass_mint: &Pubkey,
+    pass: Option<&TokenAccount>,
+) -> bool {
+    ValidationUtils::validate_cranker(allowlist, caller, pass_mint, pass, None).is_ok()
+}
+
+#[test]
+fn pass_holder_may_crank() {
+    let mint = Pubkey::new_unique();
+    let holder = Pubkey::new_unique();
+    let allowlist = enabled_allowlist(vec![]);
+    let pass = pass_account(mint, holder, 1);
+
+    assert!(allowed(Some(&allowlist), &holder, &mint, Some(&pass)));
+}
+
+#[test]
+fn pass_moves_with_the_token() {
+    let mint = Pubkey::new_unique();
+    let seller = Pubkey::new_unique();
+    let buyer = Pubkey::new_unique();
+    let allowlist = enabled_allowlist(vec![]);
+
+    // After the transfer the seller's account is empty and the buyer's holds the pass
+    let seller_pass = pass_account(mint, seller, 0);
+    let buyer_pass = pass_account(mint, buyer, 1);
+    assert!(!allowed(Some(&allowlist), &seller, &mint, Some(&seller_pass)));
+    assert!(allowed(Some(&allowlist), &buyer, &mint, Some(&buyer_pass)));
+}
+
+#[test]
+fn pass_of_another_owner_or_mint_is_rejected() {
+    let mint = Pubkey::new_unique();
+    let caller = Pubkey::new_unique();
+    let allowlist = enabled_allowlist(vec![]);
+
+    let borrowed = pass_account(mint, Pubkey::new_unique(), 1);
+    assert!(!allowed(Some(&allowlist), &caller, &mint, Some(&borrowed)));
+
+    let other_mint = pass_account(Pubkey::new_unique(), caller, 1);
+    assert!(!allowed(Some(&allowlist), &caller, &mint, Some(&other_mint)));
+}
+
+#[test]
+fn unset_pass_mint_accepts_no_pass() {
+    let caller = Pubkey::new_unique();
+    let allowlist = enabled_allowlist(vec![]);
+    let pass = pass_account(Pubkey::default(), caller, 1);
+
+    assert!(!allowed(Some(&allowlist), &caller, &Pubkey::default(), Some(&pass)));
+}
+
+#[test]
+fn listed_crankers_need_no_pass() {
+    let listed = Pubkey::new_unique();
+    let allowlist = enabled_allowlist(vec![listed]);
+
+    assert!(allowed(Some(&allowlist), &listed, &Pubkey::new_unique(), None));
+    assert!(allowed(None, &Pubkey::new_unique(), &Pubkey::default(), None));
+}
@@ -0,0 +1,157 @@
+//! Fees are claimed once per day, by its first page: later pages pay out of the pool that
+//! claim fixed, so splitting the day into more pages neither re-counts the claim nor
+//! changes what investors receive.
+
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::state::{DayClassTotal, DayPhase, PayoutCurve, Progress};
+use star_fee_distributor::utils::DistributionMath;
+
+const T0: i64 = 1_700_000_000;
+const CLAIMED: u64 = 987_654_321;
+const CARRY_IN: u64 = 1_234;
+const Y0: u64 = 40_000_000;
+const FEE_SHARE_BPS: u16 = 7_000;
+
+/// Deterministic locked amounts (xorshift)
+fn locked_amounts(count: usize) -> Vec<u64> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            1 + (state >> 8) % 10_000_000
+        })
+        .collect()
+}
+
+struct Day {
+    progress: Progress,
+    payouts: Vec<u64>,
+    remainder: u64,
+}
+
+/// Run one day split into `pages` pages the way `crank_distribute` books it
+fn run_day(locked: &[u64], pages: usize) -> Day {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.carry_over = CARRY_IN;
+    progress.reset_for_new_day(T0);
+    let day_total = DistributionMath::calculate_curved_total(locked, PayoutCurve::Linear).unwrap();
+    progress
+        .commit_day_totals(&[DayClassTotal { locked: locked.iter().sum(), weight: day_total }], T0)
+        .unwrap();
+
+    let page_size = locked.len().div_ceil(pages);
+    let mut payouts = Vec::with_capacity(locked.len());
+    for (index, page) in locked.chunks(page_size).enumerate() {
+        if index == 0 {
+            progress.record_claim(CLAIMED).unwrap();
+
+            let eligible_share_bps =
+                DistributionMath::calculate_eligible_share_bps(progress.day_locked_total, Y0, FEE_SHARE_BPS)
+                    .unwrap();
+            let investor_fee =
+                DistributionMath::calculate_investor_fee_quote(progress.claimed_today, eligible_share_bps).unwrap();
+            let total_to_distribute = investor_fee + progress.carry_over;
+            progress
+                .fix_day_pool(total_to_distribute, &[total_to_distribute], eligible_share_bps)
+                .unwrap();
+        } else {
+            assert_eq!(progress.phase().unwrap(), DayPhase::Distributing);
+        }
+
+        let page_curved = DistributionMath::calculate_curved_total(page, PayoutCurve::Linear).unwrap();
+        progress.record_page_totals(&[page.iter().sum()], &[page_curved]).unwrap();
+
+        let mut distributed = 0u64;
+        for locked in page {
+            let weight = DistributionMath::calculate_investor_weight(*locked, day_total, PayoutCurve::Linear).unwrap();
+            let payout =
+                DistributionMath::calculate_investor_payout(progress.day_class_pools[0], weight, 0).unwrap();
+            distributed += payout;
+            payouts.push(payout);
+        }
+        progress.record_page_payouts(distributed, 0).unwrap();
+    }
+
+    progress.check_day_totals_reached().unwrap();
+
+    let remainder = DistributionMath::calculate_day_remainder(
+        progress.claimed_today,
+        progress.carry_over_at_open,
+        progress.distributed_today,
+        progress.carry_over,
+    )
+    .unwrap();
+
+    Day { progress, payouts, remainder }
+}
+
+#[test]
+fn claim_is_counted_once_whatever_the_page_count() {
+    let locked = locked_amounts(30);
+    for pages in [1, 2, 10] {
+        let day = run_day(&locked, pages);
+        assert_eq!(day.progress.claimed_today, CLAIMED, "{} pages", pages);
+    }
+}
+
+#[test]
+fn day_is_conserved_whatever_the_page_count() {
+    let locked = locked_amounts(30);
+    for pages in [1, 2, 10] {
+        let day = run_day(&locked, pages);
+        assert_eq!(day.payouts.iter().sum::<u64>(), day.progress.distributed_today);
+        assert_eq!(
+            day.progress.distributed_today + day.progress.carry_over + day.remainder,
+            CLAIMED + CARRY_IN,
+            "{} pages",
+            pages
+        );
+    }
+}
+
+#[test]
+fn payouts_do_not_depend_on_the_page_count() {
+    let locked = locked_amounts(30);
+    let single_page = run_day(&locked, 1);
+    for pages in [2, 10] {
+        let day = run_day(&locked, pages);
+        assert_eq!(day.payouts, single_page.payouts, "{} pages", pages);
+        assert_eq!(day.remainder, single_page.remainder, "{} pages", pages);
+        assert_eq!(day.progress.carry_over, single_page.progress.carry_over, "{} pages", pages);
+    }
+}
+
+#[test]
+fn second_claim_is_rejected() {
+    let mut day = run_day(&locked_amounts(10), 2);
+    assert!(day.progress.record_claim(1).is_err());
+    assert_eq!(day.progress.claimed_today, CLAIMED);
+}
+
+#[test]
+fn pool_is_fixed_only_after_the_claim() {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.reset_for_new_day(T0);
+    assert!(progress.fix_day_pool(100, &[100], 5_000).is_err());
+
+    progress.record_claim(200).unwrap();
+    progress.fix_day_pool(100, &[60, 40], 5_000).unwrap();
+    assert!(progress.fix_day_pool(100, &[100], 5_000).is_err());
+    assert_eq!(progress.carry_over, 100);
+    assert_eq!(progress.day_class_pools, [60, 40, 0, 0]);
+}
+
+#[test]
+fn page_payouts_draw_down_the_pool() {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.reset_for_new_day(T0);
+    progress.record_claim(1_000).unwrap();
+    progress.fix_day_pool(700, &[700], 7_000).unwrap();
+
+    assert_eq!(progress.record_page_payouts(300, 50).unwrap(), 350);
+    assert_eq!(progress.record_page_payouts(340, 0).unwrap(), 10);
+    assert_eq!(progress.distributed_today, 640);
+    assert_eq!(progress.claimed_today, 1_000);
+}
@@ -0,0 +1,144 @@
+//! Day-wide weight denominators: every page divides by the class weight committed at day
+//! open, so an investor's weight does not depend on which other investors share their page,
+//! and the day's pages must weigh exactly the committed totals.
+
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::state::{DayClassTotal, PayoutCurve, Progress};
+use star_fee_distributor::utils::DistributionMath;
+
+const T0: i64 = 1_700_000_000;
+const POOL: u64 = 1_000_000_000;
+
+/// Deterministic pseudo-random locked amounts (xorshift)
+fn locked_amounts(seed: u64, count: usize) -> Vec<u64> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            1 + (state >> 8) % 1_000_000_000_000
+        })
+        .collect()
+}
+
+/// Single-class totals of the whole day, as the keeper commits them at open
+fn day_totals(locked: &[u64], curve: PayoutCurve) -> DayClassTotal {
+    DayClassTotal {
+        locked: locked.iter().sum(),
+        weight: DistributionMath::calculate_curved_total(locked, curve).unwrap(),
+    }
+}
+
+fn opened_day(totals: &[DayClassTotal]) -> Progress {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.reset_for_new_day(T0);
+    progress.commit_day_totals(totals, T0).unwrap();
+    progress
+}
+
+/// Weights of the whole day when it is split into pages of `page_size`, booking every page
+/// against the committed totals the way the crank does
+fn day_weights(locked: &[u64], page_size: usize, curve: PayoutCurve) -> (Vec<u64>, Progress) {
+    let mut progress = opened_day(&[day_totals(locked, curve)]);
+    let mut weights = Vec::with_capacity(locked.len());
+    for page in locked.chunks(page_size) {
+        let page_totals = day_totals(page, curve);
+        progress.record_page_totals(&[page_totals.locked], &[page_totals.weight]).unwrap();
+        weights.extend(page.iter().map(|locked| {
+            DistributionMath::calculate_investor_weight(*locked, progress.day_class_weights[0], curve).unwrap()
+        }));
+    }
+    (weights, progress)
+}
+
+#[test]
+fn weight_is_independent_of_page_split() {
+    for curve in [PayoutCurve::Linear, PayoutCurve::Sqrt] {
+        let locked = locked_amounts(7, 24);
+        let (single_page, _) = day_weights(&locked, locked.len(), curve);
+
+        for page_size in [1, 2, 5, 7, 10] {
+            let (weights, progress) = day_weights(&locked, page_size, curve);
+            assert_eq!(weights, single_page, "{:?} page size {}", curve, page_size);
+            progress.check_day_totals_reached().unwrap();
+        }
+    }
+}
+
+#[test]
+fn linear_weight_is_share_of_day_total() {
+    // 5000 of 10000 locked on a page holding only 6000 would weigh 8333 bps per page
+    let progress = opened_day(&[DayClassTotal { locked: 10_000, weight: 10_000 }]);
+    let weight =
+        DistributionMath::calculate_investor_weight(5_000, progress.day_class_weights[0], PayoutCurve::Linear);
+    assert_eq!(weight.unwrap(), 5_000);
+}
+
+#[test]
+fn day_weights_never_sum_past_the_pool() {
+    for curve in [PayoutCurve::Linear, PayoutCurve::Sqrt] {
+        for seed in 1..20 {
+            let locked = locked_amounts(seed, 30);
+            for page_size in [1, 3, 8, 30] {
+                let (weights, _) = day_weights(&locked, page_size, curve);
+                assert!(weights.iter().sum::<u64>() <= 10_000, "{:?} seed {} page size {}", curve, seed, page_size);
+
+                let paid: u64 = weights
+                    .iter()
+                    .map(|weight| DistributionMath::calculate_investor_payout(POOL, *weight, 0).unwrap())
+                    .sum();
+                assert!(paid <= POOL);
+            }
+        }
+    }
+}
+
+#[test]
+fn pages_cannot_weigh_more_than_committed() {
+    let mut progress = opened_day(&[DayClassTotal { locked: 1_000, weight: 1_000 }]);
+    progress.record_page_totals(&[600], &[600]).unwrap();
+    assert!(progress.record_page_totals(&[500], &[400]).is_err());
+    assert!(progress.record_page_totals(&[400], &[500]).is_err());
+    assert_eq!(progress.day_class_locked_processed[0], 600);
+    assert_eq!(progress.day_locked_processed, 600);
+}
+
+#[test]
+fn inflated_commitment_cannot_close() {
+    // Committing twice the real weight would halve every investor's payout
+    let mut progress = opened_day(&[DayClassTotal { locked: 2_000, weight: 2_000 }]);
+    progress.record_page_totals(&[1_000], &[1_000]).unwrap();
+    assert!(progress.check_day_totals_reached().is_err());
+}
+
+#[test]
+fn classes_are_checked_separately() {
+    let totals = [DayClassTotal { locked: 100, weight: 100 }, DayClassTotal { locked: 50, weight: 70 }];
+    let mut progress = opened_day(&totals);
+    assert_eq!(progress.day_locked_total, 150);
+
+    // Class 0 is still 10 short of its committed weight
+    progress.record_page_totals(&[100, 50], &[90, 70]).unwrap();
+    assert!(progress.check_day_totals_reached().is_err());
+    progress.record_page_totals(&[0, 0], &[10, 0]).unwrap();
+    progress.check_day_totals_reached().unwrap();
+}
+
+#[test]
+fn commitment_needs_one_to_four_classes() {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.reset_for_new_day(T0);
+    assert!(progress.commit_day_totals(&[], T0).is_err());
+    assert!(progress.commit_day_totals(&[DayClassTotal::default(); 5], T0).is_err());
+}
+
+#[test]
+fn new_day_clears_the_commitment() {
+    let mut progress = opened_day(&[DayClassTotal { locked: 10, weight: 10 }]);
+    progress.record_page_totals(&[10], &[10]).unwrap();
+    progress.reset_for_new_day(T0 + 86_400);
+    assert_eq!(progress.day_lock_ts, 0);
+    assert_eq!(progress.day_class_weights, [0; 4]);
+    assert_eq!(progress.day_class_weights_processed, [0; 4]);
+}
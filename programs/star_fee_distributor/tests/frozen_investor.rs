@@ -0,0 +1,78 @@
+//! A frozen investor ATA cannot receive its payout: the share is routed by the dust policy,
+//! released to the day's remainder under `Drop` and carried over otherwise.
+
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::state::{DayClassTotal, DustPolicy, PayoutCurve, Progress};
+use star_fee_distributor::utils::DistributionMath;
+
+const T0: i64 = 1_700_000_000;
+const CLAIMED: u64 = 1_000_000;
+const LOCKED: [u64; 3] = [3_000, 5_000, 2_000];
+const FROZEN: usize = 1;
+
+struct Day {
+    progress: Progress,
+    payouts: Vec<u64>,
+    accrued_dust: Vec<u64>,
+    remainder: u64,
+}
+
+/// Pay a one-page day of `LOCKED` out of the whole claim, with investor `FROZEN`'s ATA frozen
+fn run_day(dust_policy: DustPolicy) -> Day {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.reset_for_new_day(T0);
+    let total_curved = DistributionMath::calculate_curved_total(&LOCKED, PayoutCurve::Linear).unwrap();
+    progress
+        .commit_day_totals(&[DayClassTotal { locked: LOCKED.iter().sum(), weight: total_curved }], T0)
+        .unwrap();
+    progress.record_claim(CLAIMED).unwrap();
+    progress.fix_day_pool(CLAIMED, &[CLAIMED], 10_000).unwrap();
+
+    let mut payouts = vec![0u64; LOCKED.len()];
+    let mut accrued_dust = vec![0u64; LOCKED.len()];
+    let mut distributed = 0u64;
+    let mut withheld = 0u64;
+    for (i, locked) in LOCKED.iter().enumerate() {
+        let weight = DistributionMath::calculate_investor_weight(*locked, total_curved, PayoutCurve::Linear).unwrap();
+        let share = DistributionMath::calculate_investor_payout(CLAIMED, weight, 0).unwrap();
+        if i == FROZEN {
+            dust_policy.route_unpaid_share(share, &mut withheld, &mut accrued_dust[i]).unwrap();
+        } else {
+            payouts[i] = share;
+            distributed += share;
+        }
+    }
+    progress.record_page_payouts(distributed, withheld).unwrap();
+
+    let remainder = DistributionMath::calculate_day_remainder(
+        progress.claimed_today,
+        progress.carry_over_at_open,
+        progress.distributed_today,
+        progress.carry_over,
+    )
+    .unwrap();
+
+    Day { progress, payouts, accrued_dust, remainder }
+}
+
+#[test]
+fn frozen_share_goes_to_the_remainder_under_drop() {
+    let day = run_day(DustPolicy::Drop);
+
+    assert_eq!(day.payouts, vec![300_000, 0, 200_000]);
+    assert_eq!(day.accrued_dust[FROZEN], 0);
+    assert_eq!(day.progress.carry_over, 0);
+    assert_eq!(day.remainder, 500_000);
+}
+
+#[test]
+fn frozen_share_is_carried_over_otherwise() {
+    for dust_policy in [DustPolicy::Accumulate, DustPolicy::Pay] {
+        let day = run_day(dust_policy);
+
+        assert_eq!(day.payouts, vec![300_000, 0, 200_000], "{:?}", dust_policy);
+        assert_eq!(day.accrued_dust[FROZEN], 500_000, "{:?}", dust_policy);
+        assert_eq!(day.progress.carry_over, 500_000, "{:?}", dust_policy);
+        assert_eq!(day.remainder, 0, "{:?}", dust_policy);
+    }
+}
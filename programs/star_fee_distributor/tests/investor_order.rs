@@ -0,0 +1,66 @@
+//! Stream pubkey ordering within a page and across the day's pages.
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::errors::StarError;
+use star_fee_distributor::state::InvestorAccount;
+use star_fee_distributor::utils::ValidationUtils;
+
+fn stream(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+fn page(bytes: &[u8]) -> Vec<InvestorAccount> {
+    bytes
+        .iter()
+        .map(|byte| InvestorAccount {
+            stream_pubkey: stream(*byte),
+            investor_quote_ata: Pubkey::new_unique(),
+            locked_amount: 0,
+            weight: 0,
+        })
+        .collect()
+}
+
+fn assert_error(result: anchor_lang::Result<Pubkey>, expected: StarError) {
+    match result {
+        Err(Error::AnchorError(error)) => assert_eq!(error.error_code_number, u32::from(expected)),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+#[test]
+fn sorted_pages_chain_across_the_day() {
+    let last = ValidationUtils::validate_investor_order(&page(&[1, 2, 5]), &Pubkey::default()).unwrap();
+    assert_eq!(last, stream(5));
+    let last = ValidationUtils::validate_investor_order(&page(&[6, 9]), &last).unwrap();
+    assert_eq!(last, stream(9));
+}
+
+#[test]
+fn unsorted_page_is_rejected() {
+    assert_error(
+        ValidationUtils::validate_investor_order(&page(&[1, 3, 2]), &Pubkey::default()),
+        StarError::InvestorsOutOfOrder,
+    );
+}
+
+#[test]
+fn page_starting_below_the_previous_page_is_rejected() {
+    assert_error(
+        ValidationUtils::validate_investor_order(&page(&[4, 7]), &stream(5)),
+        StarError::InvestorsOutOfOrder,
+    );
+}
+
+#[test]
+fn repeated_stream_is_a_duplicate() {
+    assert_error(
+        ValidationUtils::validate_investor_order(&page(&[1, 2, 2]), &Pubkey::default()),
+        StarError::DuplicateInvestor,
+    );
+    assert_error(
+        ValidationUtils::validate_investor_order(&page(&[5, 6]), &stream(5)),
+        StarError::DuplicateInvestor,
+    );
+}
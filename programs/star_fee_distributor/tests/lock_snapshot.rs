@@ -0,0 +1,58 @@
+//! Per-day lock snapshots on investor records, as taken by `refresh_locks` between days
+//! and read by `crank_distribute`.
+
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::state::InvestorRecord;
+
+const T0: i64 = 1_700_000_000;
+const DAY: i64 = 19_675;
+
+fn record() -> InvestorRecord {
+    InvestorRecord::new(Pubkey::default(), Pubkey::default(), 255)
+}
+
+#[test]
+fn fresh_record_has_no_snapshot() {
+    // A zeroed record (new or migrated) must not read as a snapshot for day 0
+    assert_eq!(record().snapshot_for(0), None);
+    assert_eq!(record().snapshot_for(DAY), None);
+}
+
+#[test]
+fn snapshot_applies_to_its_day_only() {
+    let mut record = record();
+    assert!(record.snapshot_lock(DAY, 1_000, T0));
+
+    assert_eq!(record.snapshot_for(DAY), Some(1_000));
+    assert_eq!(record.snapshot_for(DAY - 1), None);
+    assert_eq!(record.snapshot_for(DAY + 1), None);
+}
+
+#[test]
+fn snapshot_is_taken_once_per_day() {
+    let mut record = record();
+    assert!(record.snapshot_lock(DAY, 1_000, T0));
+
+    // A top-up after the snapshot cannot replace it
+    assert!(!record.snapshot_lock(DAY, 5_000, T0 + 60));
+    assert_eq!(record.snapshot_for(DAY), Some(1_000));
+    assert_eq!(record.snapshot_ts, T0);
+}
+
+#[test]
+fn next_day_replaces_the_snapshot() {
+    let mut record = record();
+    assert!(record.snapshot_lock(DAY, 1_000, T0));
+    assert!(record.snapshot_lock(DAY + 1, 900, T0 + 86_400));
+
+    assert_eq!(record.snapshot_for(DAY), None);
+    assert_eq!(record.snapshot_for(DAY + 1), Some(900));
+}
+
+#[test]
+fn zero_lock_snapshot_is_still_a_snapshot() {
+    let mut record = record();
+    assert!(record.snapshot_lock(DAY, 0, T0));
+
+    assert_eq!(record.snapshot_for(DAY), Some(0));
+}
@@ -0,0 +1,38 @@
+//! Account migration: a day recorded before `Progress.phase` existed gets the phase its
+//! state implies, so a day left mid-way is never claimed twice.
+
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::state::{DayPhase, Progress, CURRENT_PROGRESS_VERSION};
+
+const T0: i64 = 1_700_000_000;
+
+fn opened_day() -> Progress {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.reset_for_new_day(T0);
+    progress
+}
+
+#[test]
+fn new_accounts_start_at_the_current_version() {
+    assert_eq!(Progress::new(Pubkey::default(), 255).version, CURRENT_PROGRESS_VERSION);
+}
+
+#[test]
+fn unstarted_day_stays_idle() {
+    assert_eq!(opened_day().phase_from_day_state(), DayPhase::Idle);
+}
+
+#[test]
+fn day_with_pages_is_distributing() {
+    let mut progress = opened_day();
+    progress.pagination_cursor = 2;
+    assert_eq!(progress.phase_from_day_state(), DayPhase::Distributing);
+}
+
+#[test]
+fn completed_day_is_closed() {
+    let mut progress = opened_day();
+    progress.pagination_cursor = 3;
+    progress.day_complete = 1;
+    assert_eq!(progress.phase_from_day_state(), DayPhase::Closed);
+}
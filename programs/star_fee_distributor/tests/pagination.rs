@@ -0,0 +1,39 @@
+//! Page math shared by the crank's fixed page size check and the client planner.
+
+use star_fee_distributor::utils::PaginationUtils;
+
+#[test]
+fn pages_cover_the_set_exactly_once() {
+    let page_size = 8;
+    for total in [1u64, 7, 8, 9, 40, 41] {
+        let mut covered = 0;
+        let mut page = 1;
+        loop {
+            let (start, _) = PaginationUtils::get_page_bounds(page, page_size);
+            assert_eq!(start, covered, "page {} of {} starts at {}", page, total, start);
+            covered += PaginationUtils::get_page_size(page, page_size, total);
+            if PaginationUtils::is_last_page(page, page_size, total) {
+                break;
+            }
+            assert_eq!(PaginationUtils::get_page_size(page, page_size, total), page_size);
+            page += 1;
+        }
+        assert_eq!(covered, total);
+        assert_eq!(page, total.div_ceil(page_size));
+    }
+}
+
+#[test]
+fn only_the_final_page_is_short() {
+    assert_eq!(PaginationUtils::get_page_size(1, 8, 20), 8);
+    assert_eq!(PaginationUtils::get_page_size(2, 8, 20), 8);
+    assert_eq!(PaginationUtils::get_page_size(3, 8, 20), 4);
+    assert!(!PaginationUtils::is_last_page(2, 8, 20));
+    assert!(PaginationUtils::is_last_page(3, 8, 20));
+}
+
+#[test]
+fn pages_past_the_end_are_empty() {
+    assert_eq!(PaginationUtils::get_page_size(4, 8, 20), 0);
+    assert_eq!(PaginationUtils::get_page_size(2, 8, 8), 0);
+}
@@ -0,0 +1,146 @@
+//! Payout curves: exact integer square roots, small-holder weighting, and conservation of
+//! the investor pool under every curve.
+
+use star_fee_distributor::state::PayoutCurve;
+use star_fee_distributor::utils::DistributionMath;
+
+const CURVES: [PayoutCurve; 2] = [PayoutCurve::Linear, PayoutCurve::Sqrt];
+
+/// Deterministic pseudo-random locked amounts (xorshift), spanning dust to whale sizes
+fn locked_amounts(seed: u64, count: usize) -> Vec<u64> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let magnitude = state % 40;
+            1 + (state >> 8) % (1u64 << magnitude)
+        })
+        .collect()
+}
+
+/// Per-investor payouts of `pool` under `curve`, as the crank computes them
+fn payouts(pool: u64, locked: &[u64], curve: PayoutCurve) -> Vec<u64> {
+    let total_curved = DistributionMath::calculate_curved_total(locked, curve).unwrap();
+    locked
+        .iter()
+        .map(|amount| {
+            let weight_bps =
+                DistributionMath::calculate_investor_weight(*amount, total_curved, curve).unwrap();
+            DistributionMath::calculate_investor_payout(pool, weight_bps, 0).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn sqrt_curve_is_exact_on_perfect_squares() {
+    for root in [0u64, 1, 2, 3, 1_000, 65_535, 4_294_967_295] {
+        assert_eq!(
+            DistributionMath::apply_payout_curve(root * root, PayoutCurve::Sqrt),
+            root << 16
+        );
+    }
+}
+
+#[test]
+fn sqrt_curve_rounds_down() {
+    for value in [2u64, 3, 5, 99, 1_000_001, u64::MAX] {
+        let root = DistributionMath::apply_payout_curve(value, PayoutCurve::Sqrt) as u128;
+        let scaled = (value as u128) << 32;
+        assert!(root * root <= scaled);
+        assert!((root + 1) * (root + 1) > scaled);
+    }
+}
+
+#[test]
+fn linear_curve_is_identity() {
+    for value in [0u64, 1, 12_345, u64::MAX] {
+        assert_eq!(
+            DistributionMath::apply_payout_curve(value, PayoutCurve::Linear),
+            value
+        );
+    }
+}
+
+#[test]
+fn sqrt_curve_favors_smaller_holders() {
+    let locked = [100u64, 10_000];
+
+    let linear_total =
+        DistributionMath::calculate_curved_total(&locked, PayoutCurve::Linear).unwrap();
+    let sqrt_total = DistributionMath::calculate_curved_total(&locked, PayoutCurve::Sqrt).unwrap();
+
+    assert_eq!(
+        DistributionMath::calculate_investor_weight(locked[0], linear_total, PayoutCurve::Linear)
+            .unwrap(),
+        99
+    );
+    // sqrt(100) / (sqrt(100) + sqrt(10000)) = 10 / 110
+    assert_eq!(
+        DistributionMath::calculate_investor_weight(locked[0], sqrt_total, PayoutCurve::Sqrt)
+            .unwrap(),
+        909
+    );
+}
+
+#[test]
+fn payouts_never_exceed_the_pool() {
+    for seed in 1..200u64 {
+        let count = 1 + (seed % 40) as usize;
+        let locked = locked_amounts(seed, count);
+        let pool = seed * 7_919_993;
+
+        for curve in CURVES {
+            let total_curved = DistributionMath::calculate_curved_total(&locked, curve).unwrap();
+            let total_weight_bps: u64 = locked
+                .iter()
+                .map(|amount| {
+                    DistributionMath::calculate_investor_weight(*amount, total_curved, curve)
+                        .unwrap()
+                })
+                .sum();
+            assert!(total_weight_bps <= 10_000);
+
+            let distributed: u64 = payouts(pool, &locked, curve).iter().sum();
+            assert!(distributed <= pool, "{curve:?} seed {seed}");
+
+            // Flooring the weight loses at most one basis point of the pool per investor,
+            // flooring the payout at most one more token
+            let rounding_bound = count as u64 * (pool / 10_000 + 2);
+            assert!(
+                pool - distributed <= rounding_bound,
+                "{curve:?} seed {seed}"
+            );
+        }
+    }
+}
+
+#[test]
+fn claimed_fees_are_conserved_under_every_curve() {
+    for curve in CURVES {
+        let claimed = 1_000_000u64;
+        let carry_in = 777u64;
+        let locked = locked_amounts(42, 25);
+
+        let total_locked: u64 = locked.iter().sum();
+        let eligible_share_bps =
+            DistributionMath::calculate_eligible_share_bps(total_locked, total_locked * 2, 7_000)
+                .unwrap();
+        let investor_fee =
+            DistributionMath::calculate_investor_fee_quote(claimed, eligible_share_bps).unwrap();
+        let pool = investor_fee + carry_in;
+
+        let distributed: u64 = payouts(pool, &locked, curve).iter().sum();
+        let carry_out = DistributionMath::calculate_page_carry_over(pool, distributed, 0).unwrap();
+        let remainder =
+            DistributionMath::calculate_day_remainder(claimed, carry_in, distributed, carry_out)
+                .unwrap();
+
+        assert_eq!(
+            claimed + carry_in,
+            distributed + remainder + carry_out,
+            "{curve:?}"
+        );
+    }
+}
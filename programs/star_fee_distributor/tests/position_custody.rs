@@ -0,0 +1,116 @@
+//! Honorary position custody: the CP-AMM position must belong to the vault's pool, and its
+//! NFT must sit in a token account held by the position owner PDA.
+//!
+//! Mock positions keep no state, so `mock-integrations` builds skip these checks.
+#![cfg(not(feature = "mock-integrations"))]
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::state::{Account, AccountState};
+use star_fee_distributor::errors::StarError;
+use star_fee_distributor::state::CP_AMM_PROGRAM_ID;
+use star_fee_distributor::utils::CpAmmUtils;
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: StarError) {
+    match result {
+        Err(Error::AnchorError(error)) => assert_eq!(error.error_code_number, u32::from(expected)),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+/// CP-AMM `Position` data for `pool` with NFT `nft_mint`
+fn position_data(pool: Pubkey, nft_mint: Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; CpAmmUtils::POSITION_MIN_SIZE];
+    let offset = CpAmmUtils::POSITION_POOL_OFFSET;
+    data[offset..offset + 32].copy_from_slice(pool.as_ref());
+    let offset = CpAmmUtils::POSITION_NFT_MINT_OFFSET;
+    data[offset..offset + 32].copy_from_slice(nft_mint.as_ref());
+    data
+}
+
+/// Token account of `mint` held by `owner`
+fn nft_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0u8; Account::LEN];
+    Account { mint, owner, amount, state: AccountState::Initialized, ..Account::default() }.pack_into_slice(&mut data);
+    data
+}
+
+struct Custody {
+    pool: Pubkey,
+    nft_mint: Pubkey,
+    position_owner: Pubkey,
+}
+
+impl Custody {
+    fn new() -> Self {
+        Self { pool: Pubkey::new_unique(), nft_mint: Pubkey::new_unique(), position_owner: Pubkey::new_unique() }
+    }
+
+    fn verify(&self, position: &mut [u8], nft_account: &mut [u8], nft_program: &Pubkey) -> anchor_lang::Result<()> {
+        let (position_key, nft_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut position_lamports, mut nft_lamports) = (1_000_000, 1_000_000);
+        let position = AccountInfo::new(
+            &position_key,
+            false,
+            false,
+            &mut position_lamports,
+            position,
+            &CP_AMM_PROGRAM_ID,
+            false,
+            0,
+        );
+        let nft_account =
+            AccountInfo::new(&nft_key, false, false, &mut nft_lamports, nft_account, nft_program, false, 0);
+        CpAmmUtils::verify_position_custody(&position, &self.pool, &nft_account, &self.position_owner)
+    }
+}
+
+#[test]
+fn position_held_by_the_owner_pda_is_accepted() {
+    let custody = Custody::new();
+    let mut position = position_data(custody.pool, custody.nft_mint);
+
+    for program in [spl_token_2022::ID, anchor_spl::token::ID] {
+        let mut nft_account = nft_account_data(custody.nft_mint, custody.position_owner, 1);
+        custody.verify(&mut position, &mut nft_account, &program).unwrap();
+    }
+}
+
+#[test]
+fn position_of_another_pool_is_rejected() {
+    let custody = Custody::new();
+    let mut position = position_data(Pubkey::new_unique(), custody.nft_mint);
+    let mut nft_account = nft_account_data(custody.nft_mint, custody.position_owner, 1);
+
+    assert_error(
+        custody.verify(&mut position, &mut nft_account, &spl_token_2022::ID),
+        StarError::InvalidHonoraryPosition,
+    );
+}
+
+#[test]
+fn nft_held_elsewhere_is_rejected() {
+    let custody = Custody::new();
+    let mut position = position_data(custody.pool, custody.nft_mint);
+
+    let mut other_owner = nft_account_data(custody.nft_mint, Pubkey::new_unique(), 1);
+    assert_error(
+        custody.verify(&mut position, &mut other_owner, &spl_token_2022::ID),
+        StarError::InvalidPositionOwner,
+    );
+
+    let mut other_mint = nft_account_data(Pubkey::new_unique(), custody.position_owner, 1);
+    assert_error(
+        custody.verify(&mut position, &mut other_mint, &spl_token_2022::ID),
+        StarError::InvalidPositionNft,
+    );
+
+    let mut empty = nft_account_data(custody.nft_mint, custody.position_owner, 0);
+    assert_error(custody.verify(&mut position, &mut empty, &spl_token_2022::ID), StarError::InvalidPositionNft);
+
+    // Same data, but not a token program's account
+    let mut forged = nft_account_data(custody.nft_mint, custody.position_owner, 1);
+    assert_error(custody.verify(&mut position, &mut forged, &Pubkey::new_unique()), StarError::InvalidPositionNft);
+}
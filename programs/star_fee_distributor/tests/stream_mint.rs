@@ -0,0 +1,90 @@
+//! Investor records are only created for vesting accounts that lock the vault's base mint,
+//! token A of the honorary position's pool.
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use star_fee_distributor::errors::StarError;
+use star_fee_distributor::state::{BONFIDA_VESTING_PROGRAM_ID, STREAMFLOW_PROGRAM_ID};
+use star_fee_distributor::utils::{
+    BonfidaVestingUtils, CollectFeeMode, CpAmmPool, StreamflowUtils, ValidationUtils,
+};
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: StarError) {
+    match result {
+        Err(Error::AnchorError(error)) => assert_eq!(error.error_code_number, u32::from(expected)),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+fn pool(base_mint: Pubkey) -> CpAmmPool {
+    CpAmmPool {
+        token_a_mint: base_mint,
+        token_b_mint: Pubkey::new_unique(),
+        sqrt_min_price: 1u128 << 64,
+        sqrt_max_price: 1u128 << 64,
+        sqrt_price: 1u128 << 64,
+        collect_fee_mode: CollectFeeMode::OnlyB,
+    }
+}
+
+/// Streamflow `Contract` data vesting `mint`
+fn stream_data(mint: Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; StreamflowUtils::CONTRACT_MIN_SIZE];
+    let offset = StreamflowUtils::MINT_OFFSET;
+    data[offset..offset + 32].copy_from_slice(mint.as_ref());
+    data
+}
+
+/// Initialized Bonfida vesting header vesting `mint`, with no schedule entries
+fn bonfida_data(mint: Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; BonfidaVestingUtils::HEADER_SIZE];
+    data[32..64].copy_from_slice(mint.as_ref());
+    data[BonfidaVestingUtils::HEADER_SIZE - 1] = 1;
+    data
+}
+
+fn validate(data: &mut [u8], owner: &Pubkey, pool: &CpAmmPool) -> anchor_lang::Result<()> {
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let account = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+    ValidationUtils::validate_stream_mint(&account, pool)
+}
+
+#[test]
+fn streams_of_the_base_mint_are_accepted() {
+    let base_mint = Pubkey::new_unique();
+
+    validate(&mut stream_data(base_mint), &STREAMFLOW_PROGRAM_ID, &pool(base_mint)).unwrap();
+    validate(&mut bonfida_data(base_mint), &BONFIDA_VESTING_PROGRAM_ID, &pool(base_mint)).unwrap();
+}
+
+// Mock pools have no base mint to compare against
+#[cfg(not(feature = "mock-integrations"))]
+#[test]
+fn streams_of_another_mint_are_rejected() {
+    let pool = pool(Pubkey::new_unique());
+
+    assert_error(
+        validate(&mut stream_data(Pubkey::new_unique()), &STREAMFLOW_PROGRAM_ID, &pool),
+        StarError::StreamMintMismatch,
+    );
+    assert_error(
+        validate(&mut bonfida_data(Pubkey::new_unique()), &BONFIDA_VESTING_PROGRAM_ID, &pool),
+        StarError::StreamMintMismatch,
+    );
+    // Vesting a quote-token stream earns nothing either
+    assert_error(
+        validate(&mut stream_data(pool.token_b_mint), &STREAMFLOW_PROGRAM_ID, &pool),
+        StarError::StreamMintMismatch,
+    );
+}
+
+#[test]
+fn accounts_of_other_programs_are_rejected() {
+    let base_mint = Pubkey::new_unique();
+
+    assert_error(
+        validate(&mut stream_data(base_mint), &Pubkey::new_unique(), &pool(base_mint)),
+        StarError::InvalidStreamAccount,
+    );
+}
@@ -0,0 +1,188 @@
+//! Streamflow locked amounts: cliff, periodic unlocks, withdrawals, top-ups and pauses,
+//! read from contract data laid out as Streamflow's `Contract` account.
+
+use star_fee_distributor::utils::{StreamflowSchedule, StreamflowUtils};
+
+const START: u64 = 1_700_000_000;
+const DAY: u64 = 86_400;
+
+/// Size of a Streamflow `Contract` account
+const CONTRACT_SIZE: usize = 1104;
+
+/// Contract data with each field at its offset in Streamflow's `Contract` layout
+fn contract_data(schedule: &StreamflowSchedule) -> Vec<u8> {
+    let mut data = vec![0u8; CONTRACT_SIZE];
+    let mut write = |offset: usize, value: u64| data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    write(17, schedule.amount_withdrawn);
+    write(33, schedule.end_time);
+    write(409, schedule.start_time); // ix.start_time
+    write(417, schedule.net_amount_deposited); // ix.net_amount_deposited
+    write(425, schedule.period); // ix.period
+    write(433, schedule.amount_per_period); // ix.amount_per_period
+    write(441, schedule.cliff); // ix.cliff
+    write(449, schedule.cliff_amount); // ix.cliff_amount
+    write(668, schedule.current_pause_start);
+    write(676, schedule.pause_cumulative);
+    data
+}
+
+/// 1000 tokens unlocking 100 a day over 10 days, no cliff
+fn linear() -> StreamflowSchedule {
+    StreamflowSchedule {
+        start_time: START,
+        cliff: START,
+        cliff_amount: 0,
+        period: DAY,
+        amount_per_period: 100,
+        net_amount_deposited: 1000,
+        amount_withdrawn: 0,
+        end_time: START + 10 * DAY,
+        current_pause_start: 0,
+        pause_cumulative: 0,
+    }
+}
+
+/// 250 tokens at a 30-day cliff, then 25 a day for 30 days
+fn cliff() -> StreamflowSchedule {
+    StreamflowSchedule {
+        cliff: START + 30 * DAY,
+        cliff_amount: 250,
+        amount_per_period: 25,
+        end_time: START + 60 * DAY,
+        ..linear()
+    }
+}
+
+struct Case {
+    name: &'static str,
+    schedule: StreamflowSchedule,
+    now: u64,
+    locked: u64,
+}
+
+fn cases() -> Vec<Case> {
+    vec![
+        Case { name: "before start", schedule: linear(), now: START - 1, locked: 1000 },
+        Case { name: "at start", schedule: linear(), now: START, locked: 1000 },
+        Case { name: "within first period", schedule: linear(), now: START + DAY - 1, locked: 1000 },
+        Case { name: "first period", schedule: linear(), now: START + DAY, locked: 900 },
+        Case { name: "mid stream", schedule: linear(), now: START + 5 * DAY + DAY / 2, locked: 500 },
+        Case { name: "at end", schedule: linear(), now: START + 10 * DAY, locked: 0 },
+        Case { name: "after end", schedule: linear(), now: START + 20 * DAY, locked: 0 },
+        Case {
+            name: "unset cliff counts from start",
+            schedule: StreamflowSchedule { cliff: 0, ..linear() },
+            now: START + 3 * DAY,
+            locked: 700,
+        },
+        Case { name: "before cliff", schedule: cliff(), now: START + 30 * DAY - 1, locked: 1000 },
+        Case { name: "at cliff", schedule: cliff(), now: START + 30 * DAY, locked: 750 },
+        Case { name: "after cliff", schedule: cliff(), now: START + 40 * DAY, locked: 500 },
+        Case { name: "cliff stream end", schedule: cliff(), now: START + 60 * DAY, locked: 0 },
+        Case {
+            name: "partial withdrawal",
+            schedule: StreamflowSchedule { amount_withdrawn: 300, ..linear() },
+            now: START + 5 * DAY,
+            locked: 500,
+        },
+        Case {
+            name: "everything vested withdrawn",
+            schedule: StreamflowSchedule { amount_withdrawn: 500, ..linear() },
+            now: START + 5 * DAY,
+            locked: 500,
+        },
+        Case {
+            name: "withdrawn after end",
+            schedule: StreamflowSchedule { amount_withdrawn: 400, ..linear() },
+            now: START + 10 * DAY,
+            locked: 0,
+        },
+        Case {
+            name: "top-up extends the stream",
+            schedule: StreamflowSchedule {
+                net_amount_deposited: 1500,
+                end_time: START + 15 * DAY,
+                ..linear()
+            },
+            now: START + 5 * DAY,
+            locked: 1000,
+        },
+        Case {
+            name: "ongoing pause freezes the schedule",
+            schedule: StreamflowSchedule { current_pause_start: START + 3 * DAY + 100, ..linear() },
+            now: START + 8 * DAY,
+            locked: 700,
+        },
+        Case {
+            name: "ongoing pause past end",
+            schedule: StreamflowSchedule { current_pause_start: START + 3 * DAY, ..linear() },
+            now: START + 20 * DAY,
+            locked: 700,
+        },
+        Case {
+            name: "completed pause delays unlocks",
+            schedule: StreamflowSchedule {
+                pause_cumulative: 2 * DAY,
+                end_time: START + 12 * DAY,
+                ..linear()
+            },
+            now: START + 5 * DAY,
+            locked: 700,
+        },
+        Case {
+            name: "completed pause pushes back the end",
+            schedule: StreamflowSchedule {
+                pause_cumulative: 2 * DAY,
+                end_time: START + 12 * DAY,
+                ..linear()
+            },
+            now: START + 12 * DAY,
+            locked: 0,
+        },
+        Case {
+            name: "rounding remainder unlocks at end",
+            schedule: StreamflowSchedule { net_amount_deposited: 1001, ..linear() },
+            now: START + 10 * DAY - 1,
+            locked: 101,
+        },
+    ]
+}
+
+#[test]
+fn locked_amount_matches_fixtures() {
+    for case in cases() {
+        let schedule = StreamflowUtils::read_schedule(&contract_data(&case.schedule)).unwrap();
+        assert_eq!(schedule, case.schedule, "{}: decoded schedule", case.name);
+        assert_eq!(schedule.locked_amount(case.now as i64), case.locked, "{}", case.name);
+    }
+}
+
+#[test]
+fn locked_never_exceeds_deposits_less_withdrawals() {
+    for case in cases() {
+        let schedule = case.schedule;
+        for now in (START - DAY..START + 70 * DAY).step_by((DAY / 4) as usize) {
+            let locked = schedule.locked_amount(now as i64);
+            assert!(
+                locked <= schedule.net_amount_deposited - schedule.amount_withdrawn,
+                "{} at {}",
+                case.name,
+                now
+            );
+        }
+    }
+}
+
+#[test]
+fn zero_period_unlocks_only_cliff_until_end() {
+    let schedule = StreamflowSchedule { period: 0, ..cliff() };
+
+    assert_eq!(schedule.locked_amount((START + 45 * DAY) as i64), 750);
+    assert_eq!(schedule.locked_amount((START + 60 * DAY) as i64), 0);
+}
+
+#[test]
+fn truncated_contract_is_rejected() {
+    let data = contract_data(&linear());
+    assert!(StreamflowUtils::read_schedule(&data[..StreamflowUtils::CONTRACT_MIN_SIZE - 1]).is_err());
+}
@@ -0,0 +1,70 @@
+//! Switchboard function accounts: the crank trusts the enclave signer recorded by the
+//! attestation program, and only once the function's enclave has been attested.
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use star_fee_distributor::errors::StarError;
+use star_fee_distributor::state::SWITCHBOARD_ATTESTATION_PROGRAM_ID;
+use star_fee_distributor::utils::SwitchboardUtils;
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: StarError) {
+    match result {
+        Err(Error::AnchorError(error)) => assert_eq!(error.error_code_number, u32::from(expected)),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+/// `FunctionAccountData` of a function whose enclave attested with `enclave_signer`
+/// (`Pubkey::default()` before attestation)
+fn function_data(enclave_signer: Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; SwitchboardUtils::FUNCTION_MIN_SIZE];
+    data[..8].copy_from_slice(&SwitchboardUtils::FUNCTION_DISCRIMINATOR);
+    let offset = SwitchboardUtils::FUNCTION_ENCLAVE_SIGNER_OFFSET;
+    data[offset..offset + 32].copy_from_slice(enclave_signer.as_ref());
+    data
+}
+
+fn enclave_signer(data: &mut [u8], owner: &Pubkey) -> anchor_lang::Result<Pubkey> {
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let account = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+    SwitchboardUtils::get_enclave_signer(&account)
+}
+
+#[test]
+fn attested_signer_is_read_from_the_function() {
+    let signer = Pubkey::new_unique();
+    let mut data = function_data(signer);
+
+    assert_eq!(enclave_signer(&mut data, &SWITCHBOARD_ATTESTATION_PROGRAM_ID).unwrap(), signer);
+}
+
+#[test]
+fn unattested_function_has_no_signer() {
+    let mut data = function_data(Pubkey::default());
+
+    assert_error(enclave_signer(&mut data, &SWITCHBOARD_ATTESTATION_PROGRAM_ID), StarError::EnclaveSignerUnset);
+}
+
+#[test]
+fn foreign_or_malformed_accounts_are_rejected() {
+    let signer = Pubkey::new_unique();
+
+    let mut foreign = function_data(signer);
+    assert_error(enclave_signer(&mut foreign, &Pubkey::new_unique()), StarError::InvalidSwitchboardFunction);
+
+    // Another attestation program account type
+    let mut other_type = function_data(signer);
+    other_type[..8].copy_from_slice(&[0; 8]);
+    assert_error(
+        enclave_signer(&mut other_type, &SWITCHBOARD_ATTESTATION_PROGRAM_ID),
+        StarError::InvalidSwitchboardFunction,
+    );
+
+    let mut truncated = function_data(signer);
+    truncated.truncate(SwitchboardUtils::FUNCTION_MIN_SIZE - 1);
+    assert_error(
+        enclave_signer(&mut truncated, &SWITCHBOARD_ATTESTATION_PROGRAM_ID),
+        StarError::InvalidSwitchboardFunction,
+    );
+}
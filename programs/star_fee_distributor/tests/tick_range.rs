@@ -0,0 +1,157 @@
+//! Tick math for the honorary position range: Q64.64 sqrt prices at ticks, the
+//! quote-only range check against the pool's current price, and the liquidity the
+//! honorary position may hold in that range.
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use star_fee_distributor::state::CP_AMM_PROGRAM_ID;
+use star_fee_distributor::utils::{CpAmmUtils, PoolConfig, ValidationUtils};
+
+const ONE_X64: u128 = 1 << 64;
+
+fn pool_config(tick_lower: i32, tick_upper: i32) -> PoolConfig {
+    PoolConfig {
+        token_a: Pubkey::new_unique(),
+        token_b: Pubkey::new_unique(),
+        pool_id: Pubkey::new_unique(),
+        tick_lower,
+        tick_upper,
+    }
+}
+
+/// Run `validate_fee_only_position` on a CP-AMM position holding `liquidity` unlocked
+fn validate_position(liquidity: u128, pool_config: &PoolConfig, current_sqrt_price: u128) -> anchor_lang::Result<()> {
+    let mut data = vec![0u8; CpAmmUtils::POSITION_MIN_SIZE];
+    let offset = CpAmmUtils::UNLOCKED_LIQUIDITY_OFFSET;
+    data[offset..offset + 16].copy_from_slice(&liquidity.to_le_bytes());
+
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let position = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &CP_AMM_PROGRAM_ID, false, 0);
+    ValidationUtils::validate_fee_only_position(&position, pool_config, current_sqrt_price)
+}
+
+/// `|actual - expected| / expected` within `1e-12`
+fn assert_close(actual: u128, expected: u128) {
+    let diff = actual.abs_diff(expected);
+    assert!(
+        diff <= expected / 1_000_000_000_000,
+        "{} is not within 1e-12 of {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn sqrt_price_matches_reference_values() {
+    assert_eq!(CpAmmUtils::sqrt_price_at_tick(0).unwrap(), ONE_X64);
+    assert_eq!(
+        CpAmmUtils::sqrt_price_at_tick(1).unwrap(),
+        CpAmmUtils::SQRT_TICK_BASE_X64
+    );
+
+    // sqrt(1.0001^tick) * 2^64, computed with 60-digit decimals
+    assert_close(CpAmmUtils::sqrt_price_at_tick(2).unwrap(), 18_448_588_748_116_922_571);
+    assert_close(CpAmmUtils::sqrt_price_at_tick(-1).unwrap(), 18_445_821_805_675_392_311);
+    assert_close(
+        CpAmmUtils::sqrt_price_at_tick(CpAmmUtils::MAX_TICK).unwrap(),
+        79_226_673_515_401_279_992_447_579_061,
+    );
+    assert_close(
+        CpAmmUtils::sqrt_price_at_tick(CpAmmUtils::MIN_TICK).unwrap(),
+        4_295_048_016,
+    );
+}
+
+#[test]
+fn sqrt_price_increases_with_tick() {
+    let mut previous = CpAmmUtils::sqrt_price_at_tick(-200_000).unwrap();
+    for tick in (-199_000..=200_000).step_by(1_000) {
+        let sqrt_price = CpAmmUtils::sqrt_price_at_tick(tick).unwrap();
+        assert!(sqrt_price > previous, "not increasing at tick {}", tick);
+        previous = sqrt_price;
+    }
+}
+
+#[test]
+fn ticks_outside_bounds_are_rejected() {
+    assert!(CpAmmUtils::sqrt_price_at_tick(CpAmmUtils::MAX_TICK + 1).is_err());
+    assert!(CpAmmUtils::sqrt_price_at_tick(CpAmmUtils::MIN_TICK - 1).is_err());
+}
+
+#[test]
+fn range_above_the_price_is_quote_only() {
+    let current = CpAmmUtils::sqrt_price_at_tick(0).unwrap();
+    assert!(ValidationUtils::validate_quote_only_range(&pool_config(1, 100), current).is_ok());
+    assert!(ValidationUtils::validate_quote_only_range(&pool_config(100, 200), current).is_ok());
+}
+
+#[test]
+fn range_at_or_below_the_price_is_rejected() {
+    let current = CpAmmUtils::sqrt_price_at_tick(0).unwrap();
+    // Touching the current price, straddling it, and entirely below it
+    for (lower, upper) in [(0, 100), (-100, 100), (-200, -100)] {
+        assert!(
+            ValidationUtils::validate_quote_only_range(&pool_config(lower, upper), current).is_err(),
+            "[{}, {}] accepted",
+            lower,
+            upper
+        );
+    }
+}
+
+#[test]
+fn empty_and_inverted_ranges_are_rejected() {
+    let current = CpAmmUtils::sqrt_price_at_tick(0).unwrap();
+    assert!(ValidationUtils::validate_quote_only_range(&pool_config(100, 100), current).is_err());
+    assert!(ValidationUtils::validate_quote_only_range(&pool_config(200, 100), current).is_err());
+    assert!(ValidationUtils::validate_quote_only_range(
+        &pool_config(100, CpAmmUtils::MAX_TICK + 1),
+        current
+    )
+    .is_err());
+}
+
+#[test]
+fn empty_position_needs_no_range() {
+    let current = CpAmmUtils::sqrt_price_at_tick(0).unwrap();
+    assert!(validate_position(0, &pool_config(0, 0), current).is_ok());
+    assert!(validate_position(0, &pool_config(-100, 100), current).is_ok());
+}
+
+// Mock positions hold no liquidity to read
+#[cfg(not(feature = "mock-integrations"))]
+#[test]
+fn liquidity_is_accepted_only_in_a_quote_only_range() {
+    let current = CpAmmUtils::sqrt_price_at_tick(0).unwrap();
+    assert!(validate_position(1_000, &pool_config(1, 100), current).is_ok());
+
+    // No configured range, straddling the price, and below it
+    for (lower, upper) in [(0, 0), (-100, 100), (-200, -100)] {
+        assert!(
+            validate_position(1_000, &pool_config(lower, upper), current).is_err(),
+            "liquidity in [{}, {}] accepted",
+            lower,
+            upper
+        );
+    }
+
+    // A range once above the price no longer qualifies after the price moves into it
+    let moved = CpAmmUtils::sqrt_price_at_tick(50).unwrap();
+    assert!(validate_position(1_000, &pool_config(1, 100), moved).is_err());
+}
+
+#[test]
+fn tick_at_sqrt_price_inverts_sqrt_price_at_tick() {
+    for tick in [CpAmmUtils::MIN_TICK, -200_000, -1, 0, 1, 100, 200_000, CpAmmUtils::MAX_TICK] {
+        let sqrt_price = CpAmmUtils::sqrt_price_at_tick(tick).unwrap();
+        assert_eq!(CpAmmUtils::tick_at_sqrt_price(sqrt_price).unwrap(), tick);
+        if tick < CpAmmUtils::MAX_TICK {
+            // Prices between two ticks round down
+            assert_eq!(CpAmmUtils::tick_at_sqrt_price(sqrt_price + 1).unwrap(), tick);
+        }
+    }
+
+    // Prices past the tick bounds clamp to them
+    assert_eq!(CpAmmUtils::tick_at_sqrt_price(1).unwrap(), CpAmmUtils::MIN_TICK);
+    assert_eq!(CpAmmUtils::tick_at_sqrt_price(u128::MAX).unwrap(), CpAmmUtils::MAX_TICK);
+}
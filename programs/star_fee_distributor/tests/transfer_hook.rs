@@ -0,0 +1,94 @@
+//! Token-2022 quote mints with a transfer hook: the hook program must be on the global
+//! config's allowlist, and is read back from the mint's `TransferHook` extension.
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut};
+use spl_token_2022::state::Mint;
+use star_fee_distributor::errors::StarError;
+use star_fee_distributor::state::GlobalConfig;
+use star_fee_distributor::utils::{TokenTransferUtils, ValidationUtils};
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: StarError) {
+    match result {
+        Err(Error::AnchorError(error)) => assert_eq!(error.error_code_number, u32::from(expected)),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+/// Mint data as Token-2022 lays it out, with a `TransferHook` extension when `hook` is set
+fn mint_data(hook: Option<Pubkey>) -> Vec<u8> {
+    let extensions: &[ExtensionType] = if hook.is_some() { &[ExtensionType::TransferHook] } else { &[] };
+    let mut data = vec![0u8; ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap()];
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    if let Some(hook) = hook {
+        let extension = mint.init_extension::<TransferHook>(true).unwrap();
+        extension.program_id = Some(hook).try_into().unwrap();
+    }
+    mint.base = Mint { decimals: 9, is_initialized: true, ..Mint::default() };
+    mint.pack_base();
+    mint.init_account_type().unwrap();
+    data
+}
+
+fn global_config(allowed_transfer_hooks: Vec<Pubkey>) -> GlobalConfig {
+    GlobalConfig { admin: Pubkey::new_unique(), allowed_transfer_hooks, bump: 255 }
+}
+
+/// Run `check` against `data` as a mint account owned by `owner`
+fn with_mint<R>(data: &mut [u8], owner: &Pubkey, check: impl FnOnce(&AccountInfo) -> R) -> R {
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let account = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+    check(&account)
+}
+
+#[test]
+fn hook_program_is_read_from_the_mint() {
+    let hook = Pubkey::new_unique();
+    let mut hooked = mint_data(Some(hook));
+    let mut plain = mint_data(None);
+
+    let read = with_mint(&mut hooked, &spl_token_2022::ID, TokenTransferUtils::transfer_hook_program);
+    assert_eq!(read.unwrap(), Some(hook));
+    let read = with_mint(&mut plain, &spl_token_2022::ID, TokenTransferUtils::transfer_hook_program);
+    assert_eq!(read.unwrap(), None);
+}
+
+#[test]
+fn allowlisted_hook_is_accepted() {
+    let hook = Pubkey::new_unique();
+    let config = global_config(vec![Pubkey::new_unique(), hook]);
+    let mut data = mint_data(Some(hook));
+
+    with_mint(&mut data, &spl_token_2022::ID, |mint| {
+        ValidationUtils::validate_quote_mint_extensions(mint, Some(&config)).unwrap()
+    });
+}
+
+#[test]
+fn hook_off_the_allowlist_is_rejected() {
+    let hook = Pubkey::new_unique();
+    let config = global_config(vec![Pubkey::new_unique()]);
+    let mut data = mint_data(Some(hook));
+
+    with_mint(&mut data, &spl_token_2022::ID, |mint| {
+        assert_error(
+            ValidationUtils::validate_quote_mint_extensions(mint, Some(&config)),
+            StarError::TransferHookNotAllowed,
+        );
+        // Without a global config no hook is allowed
+        assert_error(ValidationUtils::validate_quote_mint_extensions(mint, None), StarError::TransferHookNotAllowed);
+    });
+}
+
+#[test]
+fn mint_without_a_hook_needs_no_allowlist() {
+    let mut data = mint_data(None);
+
+    with_mint(&mut data, &spl_token_2022::ID, |mint| {
+        ValidationUtils::validate_quote_mint_extensions(mint, None).unwrap()
+    });
+}
@@ -0,0 +1,79 @@
+//! Time-weighted locked (TWAL) accumulation on investor records, as sampled by
+//! `refresh_locks` and consumed by `crank_distribute`.
+
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::state::InvestorRecord;
+
+const DAY: i64 = 86_400;
+/// Start of the period under test (0 marks a never-sampled record)
+const T0: i64 = 1_700_000_000;
+
+/// Record whose period was restarted by a crank page at `start` with `locked`
+fn record_from(locked: u64, start: i64) -> InvestorRecord {
+    let mut record = InvestorRecord::new(Pubkey::default(), Pubkey::default(), 255);
+    record.restart_twal(locked, start);
+    record
+}
+
+#[test]
+fn never_sampled_record_has_no_twal() {
+    let record = InvestorRecord::new(Pubkey::default(), Pubkey::default(), 255);
+    assert_eq!(record.time_weighted_locked(T0 + DAY).unwrap(), None);
+}
+
+#[test]
+fn first_sample_starts_the_period() {
+    let mut record = InvestorRecord::new(Pubkey::default(), Pubkey::default(), 255);
+    record.sample_lock(1_000, T0).unwrap();
+
+    assert_eq!(record.time_weighted_locked(T0).unwrap(), None);
+    assert_eq!(record.time_weighted_locked(T0 + DAY).unwrap(), Some(1_000));
+}
+
+#[test]
+fn constant_lock_averages_to_itself() {
+    let mut record = record_from(500, T0);
+    record.sample_lock(500, T0 + 40_000).unwrap();
+
+    assert_eq!(record.time_weighted_locked(T0 + DAY).unwrap(), Some(500));
+}
+
+#[test]
+fn vesting_lock_averages_over_the_period() {
+    let mut record = record_from(1_000, T0);
+    record.sample_lock(800, T0 + DAY / 2).unwrap();
+
+    // (1000 * 43200 + 800 * 43200) / 86400
+    assert_eq!(record.time_weighted_locked(T0 + DAY).unwrap(), Some(900));
+}
+
+#[test]
+fn unsampled_top_up_does_not_count() {
+    // The latest sample holds until the crank; a top-up after it is invisible
+    let record = record_from(1_000, T0);
+    assert_eq!(record.time_weighted_locked(T0 + DAY).unwrap(), Some(1_000));
+}
+
+#[test]
+fn last_minute_top_up_carries_little_weight() {
+    let mut record = record_from(1_000, T0);
+    record.sample_lock(1_000, T0 + 23 * 3_600).unwrap();
+    // Topped up 100x and sampled 100 seconds before the crank
+    record.sample_lock(100_000, T0 + DAY - 100).unwrap();
+
+    // (1000 * 86300 + 100000 * 100) / 86400 = 1114
+    assert_eq!(record.time_weighted_locked(T0 + DAY).unwrap(), Some(1_114));
+}
+
+#[test]
+fn restart_clears_the_accumulator() {
+    let mut record = record_from(1_000, T0);
+    record.sample_lock(100_000, T0 + DAY - 100).unwrap();
+    record.restart_twal(100_000, T0 + DAY);
+
+    assert_eq!(record.twal_accumulated, 0);
+    assert_eq!(
+        record.time_weighted_locked(T0 + 2 * DAY).unwrap(),
+        Some(100_000)
+    );
+}
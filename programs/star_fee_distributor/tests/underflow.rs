@@ -0,0 +1,88 @@
+//! Subtractions in the distribution books: accounting that would go negative fails with
+//! `MathUnderflow`, while the deliberately saturating paths floor at zero.
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+use star_fee_distributor::errors::StarError;
+use star_fee_distributor::state::Progress;
+use star_fee_distributor::utils::{DistributionMath, StreamflowSchedule};
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: StarError) {
+    match result {
+        Err(Error::AnchorError(error)) => assert_eq!(error.error_code_number, u32::from(expected)),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+#[test]
+fn daily_cap_leaves_the_remaining_room() {
+    assert_eq!(DistributionMath::apply_daily_cap(500, 0, 10_000).unwrap(), 500);
+    assert_eq!(DistributionMath::apply_daily_cap(500, 1_000, 200).unwrap(), 500);
+    assert_eq!(DistributionMath::apply_daily_cap(900, 1_000, 200).unwrap(), 800);
+}
+
+#[test]
+fn daily_cap_saturates_once_reached() {
+    assert_eq!(DistributionMath::apply_daily_cap(500, 1_000, 1_000).unwrap(), 0);
+    // Carry-over paid on top of the cap can take the day past it
+    assert_eq!(DistributionMath::apply_daily_cap(500, 1_000, 1_500).unwrap(), 0);
+}
+
+#[test]
+fn page_carry_over_keeps_what_was_not_paid() {
+    assert_eq!(DistributionMath::calculate_page_carry_over(1_000, 600, 300).unwrap(), 100);
+    assert_eq!(DistributionMath::calculate_page_carry_over(1_000, 1_000, 0).unwrap(), 0);
+}
+
+#[test]
+fn page_paying_past_its_pool_underflows() {
+    assert_error(DistributionMath::calculate_page_carry_over(1_000, 1_001, 0), StarError::MathUnderflow);
+    assert_error(DistributionMath::calculate_page_carry_over(1_000, 600, 401), StarError::MathUnderflow);
+}
+
+#[test]
+fn day_remainder_balances_the_books() {
+    assert_eq!(DistributionMath::calculate_day_remainder(1_000, 50, 700, 100).unwrap(), 250);
+    assert_eq!(DistributionMath::calculate_day_remainder(1_000, 50, 950, 100).unwrap(), 0);
+}
+
+#[test]
+fn day_paying_past_its_inflow_underflows() {
+    assert_error(DistributionMath::calculate_day_remainder(1_000, 50, 1_051, 0), StarError::MathUnderflow);
+    assert_error(DistributionMath::calculate_day_remainder(1_000, 50, 1_000, 51), StarError::MathUnderflow);
+}
+
+#[test]
+fn day_inflow_overflow_is_not_an_underflow() {
+    assert_error(DistributionMath::calculate_day_remainder(u64::MAX, 1, 0, 0), StarError::MathOverflow);
+}
+
+#[test]
+fn page_payouts_past_the_day_pool_underflow() {
+    let mut progress = Progress::new(Pubkey::default(), 255);
+    progress.reset_for_new_day(1_700_000_000);
+    progress.record_claim(1_000).unwrap();
+    progress.fix_day_pool(500, &[500], 5_000).unwrap();
+
+    assert_error(progress.record_page_payouts(400, 101), StarError::MathUnderflow);
+    assert_eq!(progress.carry_over, 500);
+    assert_eq!(progress.distributed_today, 0);
+}
+
+#[test]
+fn overdrawn_stream_has_nothing_locked() {
+    let schedule = StreamflowSchedule {
+        start_time: 1_000,
+        cliff: 1_000,
+        cliff_amount: 0,
+        period: 10,
+        amount_per_period: 10,
+        net_amount_deposited: 100,
+        amount_withdrawn: 150,
+        end_time: 1_100,
+        current_pause_start: 0,
+        pause_cumulative: 0,
+    };
+
+    assert_eq!(schedule.locked_amount(1_050), 0);
+}
@@ -243,7 +243,7 @@ class RequirementsValidator {
 
     // Check independent position (A6)
     const initializeContent = fs.readFileSync('programs/star_fee_distributor/src/instructions/initialize.rs', 'utf8');
-    if (initializeContent.includes('InitializeHonoraryPosition') && 
+    if (initializeContent.includes('InitPosition') && 
         initializeContent.includes('position_owner_pda')) {
       this.markRequirement('A6', 'PASS', 'Independent position with no dependency on creator position implemented');
     } else {
@@ -0,0 +1,51 @@
+// Investor set Merkle tree matching MerkleUtils in programs/star_fee_distributor/src/utils.rs
+// Leaves: sha256(0x00 || stream || investor_quote_ata); nodes: sha256(0x01 || min || max)
+
+import { createHash } from "crypto";
+import { InvestorAccount } from "./types";
+
+const sha256 = (...parts: Buffer[]): Buffer =>
+  createHash("sha256").update(Buffer.concat(parts)).digest();
+
+export const investorLeaf = (investor: InvestorAccount): Buffer =>
+  sha256(Buffer.from([0]), investor.streamPubkey.toBuffer(), investor.investorQuoteAta.toBuffer());
+
+const hashPair = (a: Buffer, b: Buffer): Buffer =>
+  Buffer.compare(a, b) <= 0
+    ? sha256(Buffer.from([1]), a, b)
+    : sha256(Buffer.from([1]), b, a);
+
+export interface InvestorTree {
+  root: number[];
+  proofs: number[][][];
+}
+
+/// Build the day's investor tree, returning the root and one proof per investor
+export const buildInvestorTree = (investors: InvestorAccount[]): InvestorTree => {
+  let level = investors.map(investorLeaf);
+  const positions = investors.map((_, i) => i);
+  const proofs: Buffer[][] = investors.map(() => []);
+
+  while (level.length > 1) {
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      // A lone trailing node is promoted unchanged
+      next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i]);
+    }
+
+    positions.forEach((position, investor) => {
+      const sibling = position ^ 1;
+      if (sibling < level.length) {
+        proofs[investor].push(level[sibling]);
+      }
+      positions[investor] = position >> 1;
+    });
+
+    level = next;
+  }
+
+  return {
+    root: Array.from(level[0]),
+    proofs: proofs.map((proof) => proof.map((node) => Array.from(node))),
+  };
+};
@@ -1,15 +1,25 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { StarFeeDistributor, InvestorAccount } from "./types";
+import { buildInvestorTree } from "./merkle";
 import { 
   PublicKey, 
   Keypair, 
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
-  SYSVAR_CLOCK_PUBKEY
+  SYSVAR_CLOCK_PUBKEY,
+  Transaction,
+  sendAndConfirmTransaction
 } from "@solana/web3.js";
 import { 
   TOKEN_PROGRAM_ID, 
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
+  getMintLen,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeMintInstruction,
+  createAssociatedTokenAccountIdempotent,
+  getTransferFeeAmount,
   createMint, 
   createAccount, 
   mintTo,
@@ -20,6 +30,19 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 
+/// Day's class totals to commit with `openDay`: a single class under the linear curve and
+/// no duration multipliers, so its weight is its locked amount
+function dayClassTotals(investorAccounts: InvestorAccount[]): { locked: anchor.BN; weight: anchor.BN }[] {
+  const locked = investorAccounts.reduce((sum, investor) => sum.add(investor.lockedAmount), new anchor.BN(0));
+  return [{ locked, weight: locked }];
+}
+
+/// Lock timestamp to commit with `openDay`: the cluster's current time
+async function lockTimestamp(connection: anchor.web3.Connection): Promise<anchor.BN> {
+  const blockTime = await connection.getBlockTime(await connection.getSlot());
+  return new anchor.BN(blockTime ?? Math.floor(Date.now() / 1000));
+}
+
 describe("star_fee_distributor", () => {
   // Configure the client to use the local cluster
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -33,10 +56,12 @@ describe("star_fee_distributor", () => {
   let baseMint: PublicKey;
   let cpAmmPool: Keypair;
   let cpAmmProgram: Keypair;
+  let honoraryPosition: Keypair;
+  // The mock CP-AMM mints no position NFT, so custody is not checked against this account
+  let positionNftAccount: Keypair;
   let streamflowProgram: Keypair;
   let creator: Keypair;
   let creatorQuoteAta: PublicKey;
-  let programTreasury: Keypair;
 
   // Investor accounts
   let investor1: Keypair;
@@ -51,14 +76,96 @@ describe("star_fee_distributor", () => {
   let stream2: Keypair;
   let stream3: Keypair;
 
+  // CP-AMM (DAMM v2) program enforced by init_position
+  const CP_AMM_PROGRAM_ID = new PublicKey("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+  // Event CPI authority used by emit_cpi! in the crank
+  const [eventAuthorityPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    program.programId
+  );
+
+  const investorRecordPda = (vaultKey: PublicKey, streamKey: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultKey.toBuffer(), Buffer.from("investor_record"), streamKey.toBuffer()],
+      program.programId
+    )[0];
+
+  const blacklistPda = (vaultKey: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultKey.toBuffer(), Buffer.from("blacklist")],
+      program.programId
+    )[0];
+
+  const crankerAllowlistPda = (vaultKey: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultKey.toBuffer(), Buffer.from("cranker_allowlist")],
+      program.programId
+    )[0];
+
+  const positionOwnerPdaFor = (vaultKey: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultKey.toBuffer(), Buffer.from("investor_fee_pos_owner")],
+      program.programId
+    )[0];
+
+  // Treasury PDA token account created by init_policy
+  const treasuryPda = (vaultKey: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultKey.toBuffer(), Buffer.from("treasury"), quoteMint.toBuffer()],
+      program.programId
+    )[0];
+
+  // Stand in for claimed fees: mint quote tokens straight into the vault's treasury
+  const fundTreasury = async (vaultKey: PublicKey) =>
+    mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      quoteMint,
+      treasuryPda(vaultKey),
+      provider.wallet.payer,
+      1000000000 // 1B tokens
+    );
+
+  // Crank remaining accounts: (vesting account, investor quote ATA, investor record) per investor
+  const crankRemainingAccounts = (vaultKey: PublicKey, investorAccounts: InvestorAccount[]) =>
+    investorAccounts.flatMap((investor) => [
+      { pubkey: investor.streamPubkey, isWritable: false, isSigner: false },
+      { pubkey: investor.investorQuoteAta, isWritable: true, isSigner: false },
+      { pubkey: investorRecordPda(vaultKey, investor.streamPubkey), isWritable: true, isSigner: false },
+    ]);
+
+  const initInvestorRecords = async (vaultKey: PublicKey, investorAccounts: InvestorAccount[]) => {
+    const [policyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultKey.toBuffer(), Buffer.from("policy")],
+      program.programId
+    );
+
+    for (const investor of investorAccounts) {
+      await program.methods
+        .initInvestorRecord()
+        .accounts({
+          payer: provider.wallet.publicKey,
+          vault: vaultKey,
+          policy: policyPda,
+          cpAmmPool: cpAmmPool.publicKey,
+          stream: investor.streamPubkey,
+          investorRecord: investorRecordPda(vaultKey, investor.streamPubkey),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+  };
+
   before(async () => {
     // Initialize test accounts
     vault = Keypair.generate();
     cpAmmPool = Keypair.generate();
     cpAmmProgram = Keypair.generate();
+    honoraryPosition = Keypair.generate();
+    positionNftAccount = Keypair.generate();
     streamflowProgram = Keypair.generate();
     creator = Keypair.generate();
-    programTreasury = Keypair.generate();
 
     investor1 = Keypair.generate();
     investor2 = Keypair.generate();
@@ -90,29 +197,6 @@ describe("star_fee_distributor", () => {
     investor1QuoteAta = await getAssociatedTokenAddress(quoteMint, investor1.publicKey);
     investor2QuoteAta = await getAssociatedTokenAddress(quoteMint, investor2.publicKey);
     investor3QuoteAta = await getAssociatedTokenAddress(quoteMint, investor3.publicKey);
-
-    // Create program treasury ATA
-    await provider.connection.requestAirdrop(programTreasury.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
-    
-    const createTreasuryIx = await createAssociatedTokenAccountInstruction(
-      provider.wallet.publicKey,
-      programTreasury.publicKey,
-      quoteMint,
-      provider.wallet.publicKey
-    );
-
-    const tx = new anchor.web3.Transaction().add(createTreasuryIx);
-    await provider.sendAndConfirm(tx);
-
-    // Mint tokens to program treasury for testing
-    await mintTo(
-      provider.connection,
-      provider.wallet.payer,
-      quoteMint,
-      programTreasury.publicKey,
-      provider.wallet.payer,
-      1000000000 // 1B tokens
-    );
   });
 
   it("Initializes honorary position with valid configuration", async () => {
@@ -134,22 +218,30 @@ describe("star_fee_distributor", () => {
 
     try {
       const tx = await program.methods
-        .initializeHonoraryPosition(
-          new anchor.BN(5000), // 50% investor fee share
-          new anchor.BN(1000000000), // 1B daily cap
-          new anchor.BN(1000), // 1000 minimum payout
-          new anchor.BN(10000000000) // 10B total allocation
-        )
+        .initPolicy({
+          version: 1,
+          params: {
+            investorFeeShareBps: 5000, // 50% investor fee share
+            dailyCap: new anchor.BN(1000000000), // 1B daily cap
+            minPayoutLamports: new anchor.BN(1000), // 1000 minimum payout
+            y0: new anchor.BN(10000000000), // 10B total allocation
+            feeShareDecay: null,
+            durationMultipliers: [],
+            epochs: [],
+            referral: null,
+          },
+        })
         .accounts({
           payer: provider.wallet.publicKey,
+          authority: provider.wallet.publicKey,
           vault: vault.publicKey,
           cpAmmPool: cpAmmPool.publicKey,
           quoteMint: quoteMint,
           baseMint: baseMint,
-          cpAmmProgram: cpAmmProgram.publicKey,
           policy: policyPda,
           progress: progressPda,
-          programTreasury: programTreasury.publicKey,
+          positionOwnerPda: positionOwnerPdaFor(vault.publicKey),
+          programTreasury: treasuryPda(vault.publicKey),
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -157,6 +249,29 @@ describe("star_fee_distributor", () => {
         .rpc();
 
       console.log("Initialize transaction signature:", tx);
+      await fundTreasury(vault.publicKey);
+
+      // Open the honorary position in a separate transaction
+      const positionTx = await program.methods
+        .initPosition(100, 200) // Range above the mock pool's price (tick 0)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          cpAmmPool: cpAmmPool.publicKey,
+          quoteMint: quoteMint,
+          baseMint: baseMint,
+          positionOwnerPda: positionOwnerPda,
+          position: honoraryPosition.publicKey,
+          positionNftAccount: positionNftAccount.publicKey,
+          cpAmmProgram: CP_AMM_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      console.log("Init position transaction signature:", positionTx);
 
       // Verify policy was created
       const policyAccount = await program.account.policy.fetch(policyPda);
@@ -194,22 +309,30 @@ describe("star_fee_distributor", () => {
     // Test with invalid fee share (> 100%)
     try {
       await program.methods
-        .initializeHonoraryPosition(
-          new anchor.BN(10001), // Invalid: > 100%
-          new anchor.BN(1000000000),
-          new anchor.BN(1000),
-          new anchor.BN(10000000000)
-        )
+        .initPolicy({
+          version: 1,
+          params: {
+            investorFeeShareBps: 10001, // Invalid: > 100%
+            dailyCap: new anchor.BN(1000000000),
+            minPayoutLamports: new anchor.BN(1000),
+            y0: new anchor.BN(10000000000),
+            feeShareDecay: null,
+            durationMultipliers: [],
+            epochs: [],
+            referral: null,
+          },
+        })
         .accounts({
           payer: provider.wallet.publicKey,
+          authority: provider.wallet.publicKey,
           vault: vault.publicKey,
           cpAmmPool: cpAmmPool.publicKey,
           quoteMint: quoteMint,
           baseMint: baseMint,
-          cpAmmProgram: cpAmmProgram.publicKey,
           policy: policyPda,
           progress: progressPda,
-          programTreasury: programTreasury.publicKey,
+          positionOwnerPda: positionOwnerPdaFor(vault.publicKey),
+          programTreasury: treasuryPda(vault.publicKey),
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -253,28 +376,59 @@ describe("star_fee_distributor", () => {
         lockedAmount: new anchor.BN(3000000000), // 3B locked (30% of total)
         weight: new anchor.BN(0),
       },
-    ];
+    ].sort((a, b) => a.streamPubkey.toBuffer().compare(b.streamPubkey.toBuffer()));
+
+    await initInvestorRecords(vault.publicKey, investorAccounts);
+
+    // Open the day, committing the investor set
+    const investorTree = buildInvestorTree(investorAccounts);
+    await program.methods
+      .openDay(
+        investorTree.root,
+        new anchor.BN(investorAccounts.length), // Total investors
+        new anchor.BN(1), // Single page
+        dayClassTotals(investorAccounts), // Day's class totals
+        await lockTimestamp(provider.connection) // Locks read now
+      )
+      .accounts({
+        caller: provider.wallet.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .rpc();
 
     try {
       const tx = await program.methods
-        .crankDistribute(
-          new anchor.BN(1), // First page
-          investorAccounts
-        )
+        .crankDistribute({
+          version: 1,
+          page: new anchor.BN(1), // First page
+          investorProofs: investorTree.proofs,
+        })
         .accounts({
           crankCaller: provider.wallet.publicKey,
           vault: vault.publicKey,
           positionOwnerPda: positionOwnerPda,
-          programTreasury: programTreasury.publicKey,
+          programTreasury: treasuryPda(vault.publicKey),
           creatorQuoteAta: creatorQuoteAta,
           policy: policyPda,
           progress: progressPda,
+          blacklist: blacklistPda(vault.publicKey),
+          crankerAllowlist: crankerAllowlistPda(vault.publicKey),
+          switchboardFunction: null,
+          crankerPass: null,
+          memoProgram: null,
+          quoteMint: null,
           cpAmmProgram: cpAmmProgram.publicKey,
           cpAmmPool: cpAmmPool.publicKey,
+          honoraryPosition: honoraryPosition.publicKey,
           streamflowProgram: streamflowProgram.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          eventAuthority: eventAuthorityPda,
+          program: program.programId,
         })
+        .remainingAccounts(crankRemainingAccounts(vault.publicKey, investorAccounts))
         .rpc();
 
       console.log("Crank transaction signature:", tx);
@@ -285,28 +439,29 @@ describe("star_fee_distributor", () => {
       expect(progressAccount.paginationCursor.toNumber()).to.equal(1);
       expect(progressAccount.distributedToday.toNumber()).to.be.greaterThan(0);
 
+      // Verify investor records were updated
+      const investorRecord = await program.account.investorRecord.fetch(
+        investorRecordPda(vault.publicKey, investorAccounts[0].streamPubkey)
+      );
+      expect(investorRecord.cumulativeReceived.toNumber()).to.be.greaterThan(0);
+      expect(investorRecord.lastPayoutDay.toNumber()).to.equal(progressAccount.currentDay.toNumber());
+
     } catch (error) {
       console.error("Crank failed:", error);
       throw error;
     }
   });
 
-  it("Fails crank when called too early", async () => {
+  it("Fails to open a new day when called too early", async () => {
     const [policyPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("vault"), vault.publicKey.toBuffer(), Buffer.from("policy")],
       program.programId
     );
-
     const [progressPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("vault"), vault.publicKey.toBuffer(), Buffer.from("progress")],
       program.programId
     );
 
-    const [positionOwnerPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), vault.publicKey.toBuffer(), Buffer.from("investor_fee_pos_owner")],
-      program.programId
-    );
-
     const investorAccounts: InvestorAccount[] = [
       {
         streamPubkey: stream1.publicKey,
@@ -315,35 +470,29 @@ describe("star_fee_distributor", () => {
         weight: new anchor.BN(0),
       },
     ];
+    const investorTree = buildInvestorTree(investorAccounts);
 
-    // Try to call crank again immediately (should fail due to 24h gate)
+    // Try to open another day immediately (should fail due to 24h gate)
     try {
       await program.methods
-        .crankDistribute(
-          new anchor.BN(2), // Second page
-          investorAccounts
-        )
+        .openDay(
+        investorTree.root,
+        new anchor.BN(investorAccounts.length), // Total investors
+        new anchor.BN(1), // Single page
+        dayClassTotals(investorAccounts), // Day's class totals
+        await lockTimestamp(provider.connection) // Locks read now
+      )
         .accounts({
-          crankCaller: provider.wallet.publicKey,
+          caller: provider.wallet.publicKey,
           vault: vault.publicKey,
-          positionOwnerPda: positionOwnerPda,
-          programTreasury: programTreasury.publicKey,
-          creatorQuoteAta: creatorQuoteAta,
           policy: policyPda,
           progress: progressPda,
-          cpAmmProgram: cpAmmProgram.publicKey,
-          cpAmmPool: cpAmmPool.publicKey,
-          streamflowProgram: streamflowProgram.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
       expect.fail("Should have failed due to 24h gate");
     } catch (error) {
-      // This should fail because we're calling the same day
-      // In a real test, we'd mock the clock to advance 24 hours
-      console.log("Expected failure due to timing:", error.message);
+      expect(error.message).to.include("DistributionTooEarly");
     }
   });
 
@@ -364,22 +513,30 @@ describe("star_fee_distributor", () => {
     // Initialize with a very low daily cap
     try {
       await program.methods
-        .initializeHonoraryPosition(
-          new anchor.BN(10000), // 100% investor fee share
-          new anchor.BN(100000), // Very low daily cap
-          new anchor.BN(1000),
-          new anchor.BN(10000000000)
-        )
+        .initPolicy({
+          version: 1,
+          params: {
+            investorFeeShareBps: 10000, // 100% investor fee share
+            dailyCap: new anchor.BN(100000), // Very low daily cap
+            minPayoutLamports: new anchor.BN(1000),
+            y0: new anchor.BN(10000000000),
+            feeShareDecay: null,
+            durationMultipliers: [],
+            epochs: [],
+            referral: null,
+          },
+        })
         .accounts({
           payer: provider.wallet.publicKey,
+          authority: provider.wallet.publicKey,
           vault: newVault.publicKey,
           cpAmmPool: cpAmmPool.publicKey,
           quoteMint: quoteMint,
           baseMint: baseMint,
-          cpAmmProgram: cpAmmProgram.publicKey,
           policy: policyPda,
           progress: progressPda,
-          programTreasury: programTreasury.publicKey,
+          positionOwnerPda: positionOwnerPdaFor(newVault.publicKey),
+          programTreasury: treasuryPda(newVault.publicKey),
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -419,27 +576,36 @@ describe("star_fee_distributor", () => {
 
     // Initialize
     await program.methods
-      .initializeHonoraryPosition(
-        new anchor.BN(5000), // 50% investor fee share
-        new anchor.BN(1000000000),
-        new anchor.BN(1000),
-        new anchor.BN(10000000000)
-      )
+      .initPolicy({
+        version: 1,
+        params: {
+          investorFeeShareBps: 5000, // 50% investor fee share
+          dailyCap: new anchor.BN(1000000000),
+          minPayoutLamports: new anchor.BN(1000),
+          y0: new anchor.BN(10000000000),
+          feeShareDecay: null,
+          durationMultipliers: [],
+          epochs: [],
+          referral: null,
+        },
+      })
       .accounts({
         payer: provider.wallet.publicKey,
+        authority: provider.wallet.publicKey,
         vault: newVault.publicKey,
         cpAmmPool: cpAmmPool.publicKey,
         quoteMint: quoteMint,
         baseMint: baseMint,
-        cpAmmProgram: cpAmmProgram.publicKey,
         policy: policyPda,
         progress: progressPda,
-        programTreasury: programTreasury.publicKey,
+        positionOwnerPda: positionOwnerPdaFor(newVault.publicKey),
+        programTreasury: treasuryPda(newVault.publicKey),
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([newVault])
       .rpc();
+    await fundTreasury(newVault.publicKey);
 
     // Create investor accounts with zero locked amounts (all unlocked)
     const investorAccounts: InvestorAccount[] = [
@@ -451,33 +617,64 @@ describe("star_fee_distributor", () => {
       },
     ];
 
+    await initInvestorRecords(newVault.publicKey, investorAccounts);
+
+    // Open the day, committing the investor set
+    const investorTree = buildInvestorTree(investorAccounts);
+    await program.methods
+      .openDay(
+        investorTree.root,
+        new anchor.BN(investorAccounts.length), // Total investors
+        new anchor.BN(1), // Single page
+        dayClassTotals(investorAccounts), // Day's class totals
+        await lockTimestamp(provider.connection) // Locks read now
+      )
+      .accounts({
+        caller: provider.wallet.publicKey,
+        vault: newVault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .rpc();
+
     try {
       const tx = await program.methods
-        .crankDistribute(
-          new anchor.BN(1),
-          investorAccounts
-        )
+        .crankDistribute({
+          version: 1,
+          page: new anchor.BN(1),
+          investorProofs: investorTree.proofs,
+        })
         .accounts({
           crankCaller: provider.wallet.publicKey,
           vault: newVault.publicKey,
           positionOwnerPda: positionOwnerPda,
-          programTreasury: programTreasury.publicKey,
+          programTreasury: treasuryPda(newVault.publicKey),
           creatorQuoteAta: creatorQuoteAta,
           policy: policyPda,
           progress: progressPda,
+          blacklist: blacklistPda(newVault.publicKey),
+          crankerAllowlist: crankerAllowlistPda(newVault.publicKey),
+          switchboardFunction: null,
+          crankerPass: null,
+          memoProgram: null,
+          quoteMint: null,
           cpAmmProgram: cpAmmProgram.publicKey,
           cpAmmPool: cpAmmPool.publicKey,
+          honoraryPosition: honoraryPosition.publicKey,
           streamflowProgram: streamflowProgram.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          eventAuthority: eventAuthorityPda,
+          program: program.programId,
         })
+        .remainingAccounts(crankRemainingAccounts(newVault.publicKey, investorAccounts))
         .rpc();
 
       console.log("All unlocked distribution transaction:", tx);
 
       // Verify that day was marked complete and creator received remainder
       const progressAccount = await program.account.progress.fetch(progressPda);
-      expect(progressAccount.dayComplete).to.be.true;
+      expect(progressAccount.dayComplete).to.equal(1);
 
     } catch (error) {
       console.error("All unlocked distribution failed:", error);
@@ -507,27 +704,36 @@ describe("star_fee_distributor", () => {
 
     // Initialize with high dust threshold
     await program.methods
-      .initializeHonoraryPosition(
-        new anchor.BN(5000), // 50% investor fee share
-        new anchor.BN(1000000000),
-        new anchor.BN(1000000), // High dust threshold (1M)
-        new anchor.BN(10000000000)
-      )
+      .initPolicy({
+        version: 1,
+        params: {
+          investorFeeShareBps: 5000, // 50% investor fee share
+          dailyCap: new anchor.BN(1000000000),
+          minPayoutLamports: new anchor.BN(1000000), // High dust threshold (1M)
+          y0: new anchor.BN(10000000000),
+          feeShareDecay: null,
+          durationMultipliers: [],
+          epochs: [],
+          referral: null,
+        },
+      })
       .accounts({
         payer: provider.wallet.publicKey,
+        authority: provider.wallet.publicKey,
         vault: newVault.publicKey,
         cpAmmPool: cpAmmPool.publicKey,
         quoteMint: quoteMint,
         baseMint: baseMint,
-        cpAmmProgram: cpAmmProgram.publicKey,
         policy: policyPda,
         progress: progressPda,
-        programTreasury: programTreasury.publicKey,
+        positionOwnerPda: positionOwnerPdaFor(newVault.publicKey),
+        programTreasury: treasuryPda(newVault.publicKey),
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([newVault])
       .rpc();
+    await fundTreasury(newVault.publicKey);
 
     // Create investor accounts with small locked amounts that would result in dust
     const investorAccounts: InvestorAccount[] = [
@@ -539,26 +745,57 @@ describe("star_fee_distributor", () => {
       },
     ];
 
+    await initInvestorRecords(newVault.publicKey, investorAccounts);
+
+    // Open the day, committing the investor set
+    const investorTree = buildInvestorTree(investorAccounts);
+    await program.methods
+      .openDay(
+        investorTree.root,
+        new anchor.BN(investorAccounts.length), // Total investors
+        new anchor.BN(1), // Single page
+        dayClassTotals(investorAccounts), // Day's class totals
+        await lockTimestamp(provider.connection) // Locks read now
+      )
+      .accounts({
+        caller: provider.wallet.publicKey,
+        vault: newVault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .rpc();
+
     try {
       const tx = await program.methods
-        .crankDistribute(
-          new anchor.BN(1),
-          investorAccounts
-        )
+        .crankDistribute({
+          version: 1,
+          page: new anchor.BN(1),
+          investorProofs: investorTree.proofs,
+        })
         .accounts({
           crankCaller: provider.wallet.publicKey,
           vault: newVault.publicKey,
           positionOwnerPda: positionOwnerPda,
-          programTreasury: programTreasury.publicKey,
+          programTreasury: treasuryPda(newVault.publicKey),
           creatorQuoteAta: creatorQuoteAta,
           policy: policyPda,
           progress: progressPda,
+          blacklist: blacklistPda(newVault.publicKey),
+          crankerAllowlist: crankerAllowlistPda(newVault.publicKey),
+          switchboardFunction: null,
+          crankerPass: null,
+          memoProgram: null,
+          quoteMint: null,
           cpAmmProgram: cpAmmProgram.publicKey,
           cpAmmPool: cpAmmPool.publicKey,
+          honoraryPosition: honoraryPosition.publicKey,
           streamflowProgram: streamflowProgram.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          eventAuthority: eventAuthorityPda,
+          program: program.programId,
         })
+        .remainingAccounts(crankRemainingAccounts(newVault.publicKey, investorAccounts))
         .rpc();
 
       console.log("Dust threshold test transaction:", tx);
@@ -572,4 +809,318 @@ describe("star_fee_distributor", () => {
       throw error;
     }
   });
+
+  it("Accepts a policy authority distinct from the payer (multisig-style)", async () => {
+    // A Squads vault PDA signs privileged instructions through invoke_signed, so on
+    // our side it is just a signer that never pays rent. A separate keypair stands in
+    // for it here while the provider wallet pays for everything.
+    const multisigVault = Keypair.generate();
+    const newVault = Keypair.generate();
+
+    const [policyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), newVault.publicKey.toBuffer(), Buffer.from("policy")],
+      program.programId
+    );
+
+    const [progressPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), newVault.publicKey.toBuffer(), Buffer.from("progress")],
+      program.programId
+    );
+
+    await program.methods
+      .initPolicy({
+        version: 1,
+        params: {
+          investorFeeShareBps: 5000,
+          dailyCap: new anchor.BN(1000000000),
+          minPayoutLamports: new anchor.BN(1000),
+          y0: new anchor.BN(10000000000),
+          feeShareDecay: null,
+          durationMultipliers: [],
+          epochs: [],
+          referral: null,
+        },
+      })
+      .accounts({
+        payer: provider.wallet.publicKey,
+        authority: multisigVault.publicKey,
+        vault: newVault.publicKey,
+        cpAmmPool: cpAmmPool.publicKey,
+        quoteMint: quoteMint,
+        baseMint: baseMint,
+        policy: policyPda,
+        progress: progressPda,
+        positionOwnerPda: positionOwnerPdaFor(newVault.publicKey),
+        programTreasury: treasuryPda(newVault.publicKey),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([newVault])
+      .rpc();
+
+    const policyAccount = await program.account.policy.fetch(policyPda);
+    expect(policyAccount.authority.toString()).to.equal(multisigVault.publicKey.toString());
+
+    // The payer alone cannot act as the authority
+    try {
+      await program.methods
+        .updatePolicy(4000, new anchor.BN(500000000))
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: newVault.publicKey,
+          policy: policyPda,
+        })
+        .rpc();
+      expect.fail("Should have failed with Unauthorized");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    // The authority signs with zero lamports; the provider wallet pays the fee
+    await program.methods
+      .updatePolicy(4000, new anchor.BN(500000000))
+      .accounts({
+        authority: multisigVault.publicKey,
+        vault: newVault.publicKey,
+        policy: policyPda,
+      })
+      .signers([multisigVault])
+      .rpc();
+
+    const updatedPolicy = await program.account.policy.fetch(policyPda);
+    expect(updatedPolicy.pendingUpdate.investorFeeShareBps).to.equal(4000);
+    expect(updatedPolicy.pendingUpdate.dailyCap.toNumber()).to.equal(500000000);
+  });
+
+  it("Refuses to turn on auto-compounding", async () => {
+    const [policyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vault.publicKey.toBuffer(), Buffer.from("policy")],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .setCompounding(true)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+        })
+        .rpc();
+      expect.fail("Should have failed with CompoundingUnsupported");
+    } catch (error) {
+      expect(error.message).to.include("CompoundingUnsupported");
+    }
+
+    // Opting out is always allowed
+    await program.methods
+      .setCompounding(false)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+      })
+      .rpc();
+
+    const policyAccount = await program.account.policy.fetch(policyPda);
+    expect(policyAccount.compoundInvestorShare).to.equal(false);
+  });
+
+  it("Pays investors of a Token-2022 transfer-fee quote mint net of the fee", async () => {
+    const feeVault = Keypair.generate();
+    const feeMint = Keypair.generate();
+    const transferFeeBps = 100; // 1%
+
+    // Token-2022 quote mint with a 1% transfer fee and no fee cap
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: provider.wallet.publicKey,
+          newAccountPubkey: feeMint.publicKey,
+          space: mintLen,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMint.publicKey,
+          provider.wallet.publicKey,
+          provider.wallet.publicKey,
+          transferFeeBps,
+          BigInt("18446744073709551615"),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(feeMint.publicKey, 9, provider.wallet.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [provider.wallet.payer, feeMint]
+    );
+
+    const ata2022 = (owner: PublicKey) =>
+      createAssociatedTokenAccountIdempotent(
+        provider.connection,
+        provider.wallet.payer,
+        feeMint.publicKey,
+        owner,
+        {},
+        TOKEN_2022_PROGRAM_ID
+      );
+    const feeCreatorAta = await ata2022(creator.publicKey);
+    const feeInvestor1Ata = await ata2022(investor1.publicKey);
+    const feeInvestor2Ata = await ata2022(investor2.publicKey);
+
+    const [policyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), feeVault.publicKey.toBuffer(), Buffer.from("policy")],
+      program.programId
+    );
+
+    const [progressPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), feeVault.publicKey.toBuffer(), Buffer.from("progress")],
+      program.programId
+    );
+
+    const [feeTreasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), feeVault.publicKey.toBuffer(), Buffer.from("treasury"), feeMint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // The treasury is created under Token-2022, sized for the mint's transfer-fee account extension
+    await program.methods
+      .initPolicy({
+        version: 1,
+        params: {
+          investorFeeShareBps: 5000, // 50% investor fee share
+          dailyCap: new anchor.BN(1000000000),
+          minPayoutLamports: new anchor.BN(1000),
+          y0: new anchor.BN(10000000000),
+          feeShareDecay: null,
+          durationMultipliers: [],
+          epochs: [],
+          referral: null,
+        },
+      })
+      .accounts({
+        payer: provider.wallet.publicKey,
+        authority: provider.wallet.publicKey,
+        vault: feeVault.publicKey,
+        cpAmmPool: cpAmmPool.publicKey,
+        quoteMint: feeMint.publicKey,
+        baseMint: baseMint,
+        policy: policyPda,
+        progress: progressPda,
+        positionOwnerPda: positionOwnerPdaFor(feeVault.publicKey),
+        programTreasury: feeTreasury,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([feeVault])
+      .rpc();
+
+    // Minting charges no transfer fee, so the treasury holds the full 1B
+    await mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      feeMint.publicKey,
+      feeTreasury,
+      provider.wallet.payer,
+      1000000000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const investorAccounts: InvestorAccount[] = [
+      {
+        streamPubkey: stream1.publicKey,
+        investorQuoteAta: feeInvestor1Ata,
+        lockedAmount: new anchor.BN(5000000000), // 5B locked
+        weight: new anchor.BN(0),
+      },
+      {
+        streamPubkey: stream2.publicKey,
+        investorQuoteAta: feeInvestor2Ata,
+        lockedAmount: new anchor.BN(3000000000), // 3B locked
+        weight: new anchor.BN(0),
+      },
+    ].sort((a, b) => a.streamPubkey.toBuffer().compare(b.streamPubkey.toBuffer()));
+
+    await initInvestorRecords(feeVault.publicKey, investorAccounts);
+
+    const investorTree = buildInvestorTree(investorAccounts);
+    await program.methods
+      .openDay(
+        investorTree.root,
+        new anchor.BN(investorAccounts.length), // Total investors
+        new anchor.BN(1), // Single page
+        dayClassTotals(investorAccounts), // Day's class totals
+        await lockTimestamp(provider.connection) // Locks read now
+      )
+      .accounts({
+        caller: provider.wallet.publicKey,
+        vault: feeVault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .rpc();
+
+    const crank = (mint: PublicKey | null) =>
+      program.methods
+        .crankDistribute({
+          version: 1,
+          page: new anchor.BN(1),
+          investorProofs: investorTree.proofs,
+        })
+        .accounts({
+          crankCaller: provider.wallet.publicKey,
+          vault: feeVault.publicKey,
+          positionOwnerPda: positionOwnerPdaFor(feeVault.publicKey),
+          programTreasury: feeTreasury,
+          creatorQuoteAta: feeCreatorAta,
+          policy: policyPda,
+          progress: progressPda,
+          blacklist: blacklistPda(feeVault.publicKey),
+          crankerAllowlist: crankerAllowlistPda(feeVault.publicKey),
+          switchboardFunction: null,
+          crankerPass: null,
+          memoProgram: null,
+          quoteMint: mint,
+          cpAmmProgram: cpAmmProgram.publicKey,
+          cpAmmPool: cpAmmPool.publicKey,
+          honoraryPosition: honoraryPosition.publicKey,
+          streamflowProgram: streamflowProgram.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          eventAuthority: eventAuthorityPda,
+          program: program.programId,
+        })
+        .remainingAccounts(crankRemainingAccounts(feeVault.publicKey, investorAccounts))
+        .rpc();
+
+    // transfer_checked needs the mint, so a Token-2022 treasury cannot be cranked without it
+    try {
+      await crank(null);
+      expect.fail("Should have failed with MissingQuoteMint");
+    } catch (error) {
+      expect(error.message).to.include("MissingQuoteMint");
+    }
+
+    await crank(feeMint.publicKey);
+
+    // Each investor holds their share net of the 1% fee, which stays withheld in their ATA
+    for (const investor of investorAccounts) {
+      const ata = await getAccount(provider.connection, investor.investorQuoteAta, undefined, TOKEN_2022_PROGRAM_ID);
+      const withheld = getTransferFeeAmount(ata).withheldAmount;
+      const gross = ata.amount + withheld;
+      expect(withheld > BigInt(0)).to.equal(true);
+      expect(withheld).to.equal((gross * BigInt(transferFeeBps) + BigInt(9999)) / BigInt(10000));
+
+      const investorRecord = await program.account.investorRecord.fetch(
+        investorRecordPda(feeVault.publicKey, investor.streamPubkey)
+      );
+      expect(investorRecord.cumulativeReceived.toString()).to.equal(ata.amount.toString());
+    }
+
+    const progressAccount = await program.account.progress.fetch(progressPda);
+    expect(progressAccount.dayComplete).to.equal(1);
+  });
 });
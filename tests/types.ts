@@ -10,6 +10,85 @@ export interface InvestorAccount {
   weight: anchor.BN;
 }
 
+export interface FeeShareDecay {
+  startBps: number;
+  endBps: number;
+  startTs: anchor.BN;
+  duration: anchor.BN;
+}
+
+export interface DurationMultiplier {
+  minRemainingSecs: anchor.BN;
+  multiplierBps: number;
+}
+
+export interface PolicyEpoch {
+  startTs: anchor.BN;
+  investorFeeShareBps: number;
+  dailyCap: anchor.BN;
+}
+
+export interface CrankWindow {
+  startSecs: number;
+  endSecs: number;
+}
+
+export interface Referral {
+  referrer: anchor.web3.PublicKey;
+  referralBps: number;
+}
+
+export interface VaultInitParams {
+  investorFeeShareBps: number;
+  dailyCap: anchor.BN;
+  minPayoutLamports: anchor.BN;
+  y0: anchor.BN;
+  feeShareDecay: FeeShareDecay | null;
+  durationMultipliers: DurationMultiplier[];
+  epochs: PolicyEpoch[];
+  referral: Referral | null;
+}
+
+export interface InitializeArgsV1 {
+  version: number;
+  params: VaultInitParams;
+}
+
+export interface CrankArgsV1 {
+  version: number;
+  page: anchor.BN;
+  investorProofs: number[][][];
+}
+
+export type Role = { admin: {} } | { operator: {} } | { creator: {} };
+
+export type RemainderPolicy =
+  | { creator: {} }
+  | { donateToInvestors: {} }
+  | { burn: {} };
+
+export interface InvestorClass {
+  y0: anchor.BN;
+  investorFeeShareBps: number;
+}
+
+export type PayoutCurve = { linear: {} } | { sqrt: {} };
+
+export type DustPolicy = { accumulate: {} } | { drop: {} } | { pay: {} };
+
+export type SkipRouting = { hold: {} } | { creator: {} } | { rollOver: {} };
+
+export interface PendingPolicyUpdate {
+  investorFeeShareBps: number;
+  dailyCap: anchor.BN;
+  effectiveAt: anchor.BN;
+}
+
+export interface PendingPositionMigration {
+  newPool: anchor.web3.PublicKey;
+  executableAt: anchor.BN;
+}
+
 export interface PolicyAccount {
   investorFeeShareBps: anchor.BN;
   dailyCap: anchor.BN;
@@ -19,6 +98,42 @@ export interface PolicyAccount {
   vault: anchor.web3.PublicKey;
   createdAt: anchor.BN;
   bump: number;
+  feeShareDecay: FeeShareDecay | null;
+  durationMultipliers: DurationMultiplier[];
+  epochs: PolicyEpoch[];
+  authority: anchor.web3.PublicKey;
+  updateDelaySecs: anchor.BN;
+  pendingUpdate: PendingPolicyUpdate | null;
+  operator: anchor.web3.PublicKey;
+  creator: anchor.web3.PublicKey;
+  staleAfterDays: anchor.BN;
+  compoundInvestorShare: boolean;
+  version: number;
+  lookupTable: anchor.web3.PublicKey;
+  crankWindow: CrankWindow | null;
+  pageCooldownSecs: number;
+  remainderPolicy: RemainderPolicy;
+  y0Corrected: boolean;
+  investorClasses: InvestorClass[];
+  twalWeighting: boolean;
+  payoutCurve: PayoutCurve;
+  minInvestorShareBps: number;
+  abortOnReconciliationMismatch: boolean;
+  insuranceBps: number;
+  referral: Referral | null;
+  pageAttester: anchor.web3.PublicKey;
+  requireNoFreezeAuthority: boolean;
+  position: anchor.web3.PublicKey;
+  pool: anchor.web3.PublicKey;
+  pendingPositionMigration: PendingPositionMigration | null;
+  tickLower: number;
+  tickUpper: number;
+  dustPolicy: DustPolicy;
+  pageSize: number;
+  registryMode: boolean;
+  crankerPassMint: anchor.web3.PublicKey;
+  crankReimbursementLamports: anchor.BN;
+  requireLockSnapshot: boolean;
 }
 
 export interface ProgressAccount {
@@ -28,26 +143,342 @@ export interface ProgressAccount {
   paginationCursor: anchor.BN;
   currentDay: anchor.BN;
   claimedToday: anchor.BN;
-  dayComplete: boolean;
+  dayComplete: number;
+  lastStreamPubkey: anchor.web3.PublicKey;
+  investorRoot: number[];
+  totalInvestors: anchor.BN;
+  totalPages: anchor.BN;
+  investorsProcessed: anchor.BN;
+  vault: anchor.web3.PublicKey;
+  bump: number;
+  lifetimeClaimed: anchor.BN;
+  lifetimeDistributedToInvestors: anchor.BN;
+  lifetimePaidToCreator: anchor.BN;
+  daysProcessed: anchor.BN;
+  lastCrankTs: anchor.BN;
+  lifetimeCompounded: anchor.BN;
+  lastPageTs: anchor.BN;
+  phase: number;
+  carryOverAtOpen: anchor.BN;
+  harvestedPending: anchor.BN;
+  lifetimeInsured: anchor.BN;
+  dayLockedTotal: anchor.BN;
+  dayLockedProcessed: anchor.BN;
+  dayClassPools: anchor.BN[];
+  dayEligibleShareBps: number;
+  dayLockTs: anchor.BN;
+  dayClassLocked: anchor.BN[];
+  dayClassWeights: anchor.BN[];
+  dayClassLockedProcessed: anchor.BN[];
+  dayClassWeightsProcessed: anchor.BN[];
+}
+
+export interface InvestorRecordAccount {
   vault: anchor.web3.PublicKey;
+  streamPubkey: anchor.web3.PublicKey;
+  cumulativeReceived: anchor.BN;
+  lastPayoutDay: anchor.BN;
+  accruedDust: anchor.BN;
+  bump: number;
+  optedOut: boolean;
+  class: number;
+  twalPeriodStart: anchor.BN;
+  twalLastTs: anchor.BN;
+  twalLastLocked: anchor.BN;
+  twalAccumulated: anchor.BN;
+  snapshotDay: anchor.BN;
+  snapshotLocked: anchor.BN;
+  snapshotTs: anchor.BN;
+}
+
+export interface PolicyTemplateAccount {
+  templateId: number;
+  params: VaultInitParams;
+  bump: number;
+}
+
+export interface VaultMetadataAccount {
+  vault: anchor.web3.PublicKey;
+  name: string;
+  projectUri: string;
+  logoUri: string;
+  creatorContactHash: number[];
   bump: number;
 }
 
 export interface StarFeeDistributor {
   methods: {
-    initializeHonoraryPosition(
-      investorFeeShareBps: anchor.BN,
-      dailyCap: anchor.BN,
-      minPayoutLamports: anchor.BN,
-      y0: anchor.BN
+    initPolicy(args: InitializeArgsV1): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    initPosition(tickLower: number, tickUpper: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    batchInitialize(params: VaultInitParams[]): {
+      accounts: (accounts: any) => any;
+      remainingAccounts: (accounts: anchor.web3.AccountMeta[]) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    initInvestorRecord(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    openDay(
+      investorRoot: number[],
+      totalInvestors: anchor.BN,
+      totalPages: anchor.BN,
+      classTotals: { locked: anchor.BN; weight: anchor.BN }[],
+      lockTs: anchor.BN
+    ): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    crankDistribute(args: CrankArgsV1): {
+      accounts: (accounts: any) => any;
+      remainingAccounts: (accounts: anchor.web3.AccountMeta[]) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    updatePolicy(
+      investorFeeShareBps: number,
+      dailyCap: anchor.BN
+    ): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    grantRole(
+      role: Role,
+      account: anchor.web3.PublicKey
+    ): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    revokeRole(role: Role): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setCrankWindow(crankWindow: CrankWindow | null): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setPageCooldown(pageCooldownSecs: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setRemainderPolicy(remainderPolicy: RemainderPolicy): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    updateY0(newY0: anchor.BN): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setInvestorClasses(investorClasses: InvestorClass[]): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setInvestorClass(class_: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setTwalWeighting(enabled: boolean): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    refreshLocks(): {
+      accounts: (accounts: any) => any;
+      remainingAccounts: (accounts: anchor.web3.AccountMeta[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setPayoutCurve(payoutCurve: PayoutCurve): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setDustPolicy(dustPolicy: DustPolicy): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setPageSize(pageSize: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setRegistryMode(enabled: boolean): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setCrankerPass(mint: anchor.web3.PublicKey): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    fundCrankFeeVault(amount: anchor.BN): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setCrankReimbursement(lamports: anchor.BN): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setMinInvestorShare(minInvestorShareBps: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    harvestFees(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setReconciliationAbort(enabled: boolean): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    initInsuranceFund(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setInsuranceBps(insuranceBps: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setPageAttester(pageAttester: anchor.web3.PublicKey): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setRequireNoFreezeAuthority(enabled: boolean): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setRequireLockSnapshot(enabled: boolean): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    adoptPosition(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    proposePositionMigration(newPool: anchor.web3.PublicKey): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    cancelPositionMigration(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    migratePosition(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    initGlobalConfig(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    setTransferHookAllowed(hookProgram: anchor.web3.PublicKey, allowed: boolean): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    migrateInvestorRecord(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    initInvestorRegistry(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    addRegistryChunk(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    registerInvestor(chunkIndex: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    deregisterInvestor(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    crankFromRegistry(chunkIndex: number): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    forceCloseDay(): {
+      accounts: (accounts: any) => any;
+      remainingAccounts: (accounts: anchor.web3.AccountMeta[]) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    skipDay(routing: SkipRouting): {
+      accounts: (accounts: any) => any;
+      remainingAccounts: (accounts: anchor.web3.AccountMeta[]) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    createPolicyTemplate(templateId: number, params: VaultInitParams): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    initializeFromTemplate(): {
+      accounts: (accounts: any) => any;
+      signers: (signers: any[]) => any;
+      rpc: () => Promise<string>;
+    };
+    initVaultMetadata(
+      name: string,
+      projectUri: string,
+      logoUri: string,
+      creatorContactHash: number[]
     ): {
       accounts: (accounts: any) => any;
       signers: (signers: any[]) => any;
       rpc: () => Promise<string>;
     };
-    crankDistribute(
-      page: anchor.BN,
-      investorAccounts: InvestorAccount[]
+    setVaultMetadata(
+      name: string,
+      projectUri: string,
+      logoUri: string,
+      creatorContactHash: number[]
     ): {
       accounts: (accounts: any) => any;
       signers: (signers: any[]) => any;
@@ -61,6 +492,15 @@ export interface StarFeeDistributor {
     progress: {
       fetch: (pubkey: anchor.web3.PublicKey) => Promise<ProgressAccount>;
     };
+    investorRecord: {
+      fetch: (pubkey: anchor.web3.PublicKey) => Promise<InvestorRecordAccount>;
+    };
+    policyTemplate: {
+      fetch: (pubkey: anchor.web3.PublicKey) => Promise<PolicyTemplateAccount>;
+    };
+    vaultMetadata: {
+      fetch: (pubkey: anchor.web3.PublicKey) => Promise<VaultMetadataAccount>;
+    };
   };
   programId: anchor.web3.PublicKey;
 }